@@ -854,6 +854,20 @@ impl App {
         self
     }
 
+    /// Adds the free function `function` to the type registry [`Resource`] under the given name.
+    #[cfg(feature = "bevy_reflect")]
+    pub fn register_function<Marker>(
+        &mut self,
+        name: impl Into<std::borrow::Cow<'static, str>>,
+        function: impl bevy_reflect::IntoFunction<Marker>,
+    ) -> &mut Self {
+        {
+            let registry = self.world.resource_mut::<bevy_reflect::TypeRegistryArc>();
+            registry.write().register_function(name, function);
+        }
+        self
+    }
+
     /// Adds an [`App`] as a child of the current one.
     ///
     /// The provided function `f` is called by the [`update`](Self::update) method. The [`World`]
@@ -1,17 +1,23 @@
 use crate::{
+    get_meta_path,
     path::{AssetPath, AssetPathId, SourcePathId},
-    Asset, AssetIo, AssetIoError, AssetLifecycle, AssetLifecycleChannel, AssetLifecycleEvent,
-    AssetLoader, Assets, Handle, HandleId, HandleUntyped, LabelId, LoadContext, LoadState,
-    RefChange, RefChangeChannel, SourceInfo, SourceMeta,
+    Asset, AssetEvent, AssetIo, AssetIoError, AssetLifecycle, AssetLifecycleChannel,
+    AssetLifecycleEvent, AssetLoadFailedEvent, AssetLoader, AssetLoaderSettings, AssetSaver,
+    Assets, ErasedAssetSaver, Handle, HandleId, HandleUntyped, LabelId, LoadContext, LoadState,
+    RecursiveDependencyLoadState, RefChange, RefChangeChannel, SaveContext, SaveRequest,
+    SourceInfo, SourceMeta,
 };
 use anyhow::Result;
 use bevy_ecs::system::{Res, ResMut};
 use bevy_log::warn;
 use bevy_tasks::IoTaskPool;
-use bevy_utils::{Entry, HashMap, Uuid};
+use bevy_utils::{Entry, HashMap, HashSet, Uuid};
 use crossbeam_channel::TryRecvError;
 use parking_lot::{Mutex, RwLock};
-use std::{path::Path, sync::Arc};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 use thiserror::Error;
 
 /// Errors that occur while loading assets with an `AssetServer`
@@ -23,10 +29,22 @@ pub enum AssetServerError {
     MissingAssetLoader { extensions: Vec<String> },
     #[error("the given type does not match the type of the loaded asset")]
     IncorrectHandleType,
+    #[error("the given settings do not match the settings type of the asset loader")]
+    IncorrectSettingsType,
+    #[error("encountered an error while reading asset settings from {}: {error}", path.display())]
+    AssetSettingsError { path: PathBuf, error: ron::Error },
     #[error("encountered an error while loading an asset: {0}")]
     AssetLoaderError(anyhow::Error),
     #[error("encountered an error while reading an asset: {0}")]
     AssetIoError(#[from] AssetIoError),
+    #[error("no asset source named `{0}`")]
+    MissingAssetSource(String),
+    #[error("no `AssetSaver` found{}", format_missing_asset_ext(.extensions))]
+    MissingAssetSaver { extensions: Vec<String> },
+    #[error("the loaded asset has no asset with the given label")]
+    MissingLabeledAsset,
+    #[error("cycle of assets loaded with `load_direct`: `{0}` is already being loaded")]
+    LoadDirectCycle(AssetPath<'static>),
 }
 
 fn format_missing_asset_ext(exts: &[String]) -> String {
@@ -49,13 +67,32 @@ pub(crate) struct AssetRefCounter {
 }
 
 pub struct AssetServerInternal {
-    pub(crate) asset_io: Box<dyn AssetIo>,
+    pub(crate) asset_io: Arc<dyn AssetIo>,
+    pub(crate) named_asset_io: RwLock<HashMap<String, Arc<dyn AssetIo>>>,
     pub(crate) asset_ref_counter: AssetRefCounter,
     pub(crate) asset_sources: Arc<RwLock<HashMap<SourcePathId, SourceInfo>>>,
     pub(crate) asset_lifecycles: Arc<RwLock<HashMap<Uuid, Box<dyn AssetLifecycle>>>>,
     loaders: RwLock<Vec<Arc<dyn AssetLoader>>>,
     extension_to_loader_index: RwLock<HashMap<String, usize>>,
     handle_to_path: Arc<RwLock<HashMap<HandleId, AssetPath<'static>>>>,
+    /// The handles of the assets saved by [`AssetServer::save`], so that loading their path
+    /// returns the asset that was saved.
+    saved_handles: RwLock<HashMap<AssetPathId, HandleId>>,
+    asset_settings: RwLock<HashMap<SourcePathId, Arc<dyn AssetLoaderSettings>>>,
+    savers: RwLock<Vec<Arc<dyn ErasedAssetSaver>>>,
+    save_requests: Mutex<Vec<SaveRequest>>,
+    /// Hashes of the files written by [`AssetServer::save`], so that writing them doesn't
+    /// trigger a hot reload.
+    pub(crate) saved_hashes: RwLock<HashMap<(Option<String>, PathBuf), u64>>,
+    /// The assets depending on each source file, either through the dependencies of their
+    /// [`LoadedAsset`](crate::LoadedAsset)s or through [`LoadContext::load_direct`], so that
+    /// they can be reloaded when it changes.
+    dependents: RwLock<HashMap<SourcePathId, HashMap<SourcePathId, AssetPath<'static>>>>,
+    /// The errors of the assets that failed to load, until they are loaded again.
+    pub(crate) load_errors: RwLock<HashMap<SourcePathId, Arc<AssetServerError>>>,
+    /// The asset types each source file was requested as, to send [`AssetLoadFailedEvent`]s
+    /// for them even if they never loaded.
+    requested_types: RwLock<HashMap<SourcePathId, HashSet<(LabelId, Uuid)>>>,
 }
 
 /// Loads assets from the filesystem on background threads
@@ -77,16 +114,60 @@ impl AssetServer {
                 asset_sources: Default::default(),
                 asset_ref_counter: Default::default(),
                 handle_to_path: Default::default(),
+                saved_handles: Default::default(),
                 asset_lifecycles: Default::default(),
-                asset_io,
+                asset_settings: Default::default(),
+                named_asset_io: Default::default(),
+                savers: Default::default(),
+                save_requests: Default::default(),
+                saved_hashes: Default::default(),
+                dependents: Default::default(),
+                load_errors: Default::default(),
+                requested_types: Default::default(),
+                asset_io: asset_io.into(),
             }),
         }
     }
 
+    /// The [`AssetIo`] of the default asset source.
     pub fn asset_io(&self) -> &dyn AssetIo {
         &*self.server.asset_io
     }
 
+    /// Adds an asset source named `name`, replacing any source with the same name.
+    ///
+    /// Assets are loaded from this source with paths such as `"name://path/to/asset.png"`.
+    pub fn add_source<T: AssetIo>(&self, name: impl Into<String>, asset_io: T) {
+        self.add_boxed_source(name, Box::new(asset_io));
+    }
+
+    pub fn add_boxed_source(&self, name: impl Into<String>, asset_io: Box<dyn AssetIo>) {
+        self.server
+            .named_asset_io
+            .write()
+            .insert(name.into(), asset_io.into());
+    }
+
+    /// Returns the [`AssetIo`] of the asset source named `source`, or of the default source if
+    /// `source` is `None`.
+    pub fn get_source(&self, source: Option<&str>) -> Result<Arc<dyn AssetIo>, AssetServerError> {
+        match source {
+            Some(name) => self
+                .server
+                .named_asset_io
+                .read()
+                .get(name)
+                .cloned()
+                .ok_or_else(|| AssetServerError::MissingAssetSource(name.to_string())),
+            None => Ok(self.server.asset_io.clone()),
+        }
+    }
+
+    /// Returns the names of the asset sources, besides the default one.
+    pub fn source_names(&self) -> Vec<String> {
+        self.server.named_asset_io.read().keys().cloned().collect()
+    }
+
     pub(crate) fn register_asset_type<T: Asset>(&self) -> Assets<T> {
         if self
             .server
@@ -119,10 +200,20 @@ impl AssetServer {
         loaders.push(Arc::new(loader));
     }
 
+    pub fn add_saver<T>(&self, saver: T)
+    where
+        T: AssetSaver,
+    {
+        self.server.savers.write().push(Arc::new(saver));
+    }
+
     /// Enable watching of the filesystem for changes, if support is available, starting from after
     /// the point of calling this function.
     pub fn watch_for_changes(&self) -> Result<(), AssetServerError> {
         self.asset_io().watch_for_changes()?;
+        for asset_io in self.server.named_asset_io.read().values() {
+            asset_io.watch_for_changes()?;
+        }
         Ok(())
     }
 
@@ -149,7 +240,7 @@ impl AssetServer {
             })
     }
 
-    fn get_path_asset_loader<P: AsRef<Path>>(
+    pub(crate) fn get_path_asset_loader<P: AsRef<Path>>(
         &self,
         path: P,
     ) -> Result<Arc<dyn AssetLoader>, AssetServerError> {
@@ -179,6 +270,136 @@ impl AssetServer {
         })
     }
 
+    fn get_path_asset_saver(
+        &self,
+        type_uuid: Uuid,
+        path: &Path,
+    ) -> Result<Arc<dyn ErasedAssetSaver>, AssetServerError> {
+        let file_name = path
+            .file_name()
+            .and_then(|file_name| file_name.to_str())
+            .map(|file_name| file_name.to_lowercase())
+            .unwrap_or_default();
+
+        let savers = self.server.savers.read();
+        let mut exts = Vec::new();
+        let mut ext = file_name.as_str();
+        while let Some(idx) = ext.find('.') {
+            ext = &ext[idx + 1..];
+            exts.push(ext);
+            // the last saver added for an extension takes precedence, as with loaders
+            if let Some(saver) = savers.iter().rev().find(|saver| {
+                saver.asset_type_uuid() == type_uuid && saver.extensions().contains(&ext)
+            }) {
+                return Ok(saver.clone());
+            }
+        }
+        Err(AssetServerError::MissingAssetSaver {
+            extensions: exts.into_iter().map(String::from).collect(),
+        })
+    }
+
+    /// Queue the asset of the given handle for saving at the provided path.
+    ///
+    /// The asset is turned into bytes by the [`AssetSaver`] of its type matching the extension
+    /// of the path, when the asset events of this frame are sent, and written on a background
+    /// thread. The handle is then associated with the path, as returned by
+    /// [`AssetServer::get_handle_path`], and loading the path returns the same handle instead of
+    /// loading the file again. Writing the file doesn't trigger a hot reload of the asset.
+    pub fn save<'a, T: Asset, P: Into<AssetPath<'a>>>(
+        &self,
+        handle: &Handle<T>,
+        path: P,
+    ) -> Result<(), AssetServerError> {
+        let path = path.into();
+        self.get_path_asset_saver(T::TYPE_UUID, path.path())?;
+        self.get_source(path.source())?;
+
+        let path_id = path.get_id();
+        let mut saved_handles = self.server.saved_handles.write();
+        if let Some(previous_path) = self
+            .server
+            .handle_to_path
+            .write()
+            .insert(handle.id, path.to_owned())
+        {
+            saved_handles.remove(&previous_path.get_id());
+        }
+        // an asset loaded from the path keeps its own handle
+        if HandleId::from(path_id) != handle.id {
+            saved_handles.insert(path_id, handle.id);
+        }
+        drop(saved_handles);
+        self.server.save_requests.lock().push(SaveRequest {
+            handle_id: handle.id,
+            type_uuid: T::TYPE_UUID,
+            path: path.to_owned(),
+        });
+        Ok(())
+    }
+
+    pub(crate) fn save_queued_assets<T: Asset>(&self, assets: &Assets<T>) {
+        let requests = {
+            let mut save_requests = self.server.save_requests.lock();
+            if save_requests.is_empty() {
+                return;
+            }
+            let (requests, others) = save_requests
+                .drain(..)
+                .partition(|request| request.type_uuid == T::TYPE_UUID);
+            *save_requests = others;
+            requests
+        };
+
+        for request in requests {
+            if let Err(err) = self.save_asset(assets, &request) {
+                warn!("{}", err);
+            }
+        }
+    }
+
+    fn save_asset<T: Asset>(
+        &self,
+        assets: &Assets<T>,
+        request: &SaveRequest,
+    ) -> Result<(), anyhow::Error> {
+        let asset = assets
+            .get(&Handle::weak(request.handle_id))
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "could not save {:?}, the asset doesn't exist",
+                    request.path.path()
+                )
+            })?;
+        let saver = self.get_path_asset_saver(T::TYPE_UUID, request.path.path())?;
+        let asset_io = self.get_source(request.path.source())?;
+        let save_context = SaveContext {
+            path: &request.path,
+            asset_server: self,
+        };
+        let bytes = saver.save(asset, &save_context)?;
+
+        let mut hasher = crate::path::get_hasher();
+        std::hash::Hash::hash(&bytes, &mut hasher);
+        self.server.saved_hashes.write().insert(
+            (
+                request.path.source().map(String::from),
+                request.path.path().to_owned(),
+            ),
+            std::hash::Hasher::finish(&hasher),
+        );
+
+        let path = request.path.to_owned();
+        IoTaskPool::get()
+            .spawn(async move {
+                if let Err(err) = asset_io.save_path(path.path(), &bytes).await {
+                    warn!("could not save {:?}: {}", path.path(), err);
+                }
+            })
+            .detach();
+        Ok(())
+    }
+
     pub fn get_handle_path<H: Into<HandleId>>(&self, handle: H) -> Option<AssetPath<'_>> {
         self.server
             .handle_to_path
@@ -199,6 +420,20 @@ impl AssetServer {
         }
     }
 
+    /// Returns the error of the asset of the given handle if it failed to load, until it is
+    /// loaded again.
+    pub fn get_load_error<H: Into<HandleId>>(&self, handle: H) -> Option<Arc<AssetServerError>> {
+        match handle.into() {
+            HandleId::AssetPathId(id) => self
+                .server
+                .load_errors
+                .read()
+                .get(&id.source_path_id())
+                .cloned(),
+            HandleId::Id(_, _) => None,
+        }
+    }
+
     pub fn get_group_load_state(&self, handles: impl IntoIterator<Item = HandleId>) -> LoadState {
         let mut load_state = LoadState::Loaded;
         for handle_id in handles {
@@ -219,6 +454,120 @@ impl AssetServer {
         load_state
     }
 
+    /// Returns the load state of the asset of the given handle and of all of its dependencies,
+    /// recursively.
+    ///
+    /// Unlike [`get_load_state`](AssetServer::get_load_state), a glTF file for example is only
+    /// [`Loaded`](RecursiveDependencyLoadState::Loaded) once its textures have loaded as well.
+    pub fn get_recursive_dependency_load_state<H: Into<HandleId>>(
+        &self,
+        handle: H,
+    ) -> RecursiveDependencyLoadState {
+        let id = match handle.into() {
+            HandleId::AssetPathId(id) => id,
+            HandleId::Id(_, _) => return RecursiveDependencyLoadState::NotLoaded,
+        };
+        let asset_sources = self.server.asset_sources.read();
+        let mut load_state = RecursiveDependencyLoadState::Loaded;
+        let mut visited = HashSet::default();
+        let mut stack = vec![id];
+        while let Some(id) = stack.pop() {
+            if !visited.insert(id) {
+                continue;
+            }
+            let source_info = match asset_sources.get(&id.source_path_id()) {
+                Some(source_info) => source_info,
+                None => return RecursiveDependencyLoadState::NotLoaded,
+            };
+            match source_info.load_state {
+                LoadState::Loaded => {}
+                LoadState::Loading => {
+                    load_state = RecursiveDependencyLoadState::Loading;
+                    continue;
+                }
+                LoadState::Failed => return RecursiveDependencyLoadState::Failed,
+                LoadState::NotLoaded | LoadState::Unloaded => {
+                    return RecursiveDependencyLoadState::NotLoaded
+                }
+            }
+            if let Some(dependencies) = source_info.get_dependencies(id.label_id()) {
+                stack.extend(dependencies.iter().map(AssetPath::get_id));
+            }
+        }
+
+        load_state
+    }
+
+    /// Returns the path and load state of the source file of the asset of the given handle, and
+    /// of those of its dependencies, recursively.
+    ///
+    /// The dependencies of an asset are only known once it has loaded, so more entries can be
+    /// returned as loading progresses.
+    pub fn get_dependency_load_states<H: Into<HandleId>>(
+        &self,
+        handle: H,
+    ) -> Vec<(AssetPath<'static>, LoadState)> {
+        let handle_id = handle.into();
+        let root = match self.get_handle_path(handle_id) {
+            Some(root) => root.to_owned(),
+            None => return Vec::new(),
+        };
+        let asset_sources = self.server.asset_sources.read();
+        let mut load_states = Vec::new();
+        let mut visited = HashSet::default();
+        let mut stack = vec![root];
+        while let Some(asset_path) = stack.pop() {
+            let id = asset_path.get_id();
+            if !visited.insert(id.source_path_id()) {
+                continue;
+            }
+            let source_info = asset_sources.get(&id.source_path_id());
+            let load_state = source_info.map_or(LoadState::NotLoaded, |info| info.load_state);
+            let source_path = AssetPath::new_ref(asset_path.path(), None)
+                .with_source_ref(asset_path.source())
+                .to_owned();
+            load_states.push((source_path, load_state));
+            if let Some(dependencies) =
+                source_info.and_then(|info| info.get_dependencies(id.label_id()))
+            {
+                stack.extend(dependencies.iter().cloned());
+            }
+        }
+
+        load_states
+    }
+
+    /// Returns the error of the source file that failed to load at `asset_path`.
+    pub(crate) fn get_source_load_error(
+        &self,
+        asset_path: &AssetPath,
+    ) -> Option<Arc<AssetServerError>> {
+        self.server
+            .load_errors
+            .read()
+            .get(&asset_path.get_id().source_path_id())
+            .cloned()
+    }
+
+    /// Returns the combined [`RecursiveDependencyLoadState`] of the given handles.
+    pub fn get_group_recursive_dependency_load_state(
+        &self,
+        handles: impl IntoIterator<Item = HandleId>,
+    ) -> RecursiveDependencyLoadState {
+        let mut load_state = RecursiveDependencyLoadState::Loaded;
+        for handle_id in handles {
+            match self.get_recursive_dependency_load_state(handle_id) {
+                RecursiveDependencyLoadState::Loaded => continue,
+                RecursiveDependencyLoadState::Loading => {
+                    load_state = RecursiveDependencyLoadState::Loading;
+                }
+                state => return state,
+            }
+        }
+
+        load_state
+    }
+
     /// Queue an [`Asset`] at the provided relative path for asynchronous loading.
     ///
     /// The absolute Path to the asset is `"ROOT/ASSET_FOLDER_NAME/path"`.
@@ -238,14 +587,97 @@ impl AssetServer {
     /// effectively loaded and available in the [`Assets`] collection.
     #[must_use = "not using the returned strong handle may result in the unexpected release of the asset"]
     pub fn load<'a, T: Asset, P: Into<AssetPath<'a>>>(&self, path: P) -> Handle<T> {
-        self.load_untyped(path).typed()
+        let asset_path = path.into();
+        self.add_requested_type::<T>(&asset_path);
+        self.load_untyped(asset_path).typed()
+    }
+
+    /// Queue an [`Asset`] at the provided relative path for asynchronous loading, using the given
+    /// settings for its [`AssetLoader`].
+    ///
+    /// The settings take precedence over the asset's `.meta` file, and are kept for later hot
+    /// reloads. If the asset was already loaded, it is loaded again with the new settings.
+    /// Loading fails with [`AssetServerError::IncorrectSettingsType`] if `S` is not the settings
+    /// type of the asset's loader.
+    #[must_use = "not using the returned strong handle may result in the unexpected release of the asset"]
+    pub fn load_with_settings<'a, T: Asset, S: AssetLoaderSettings, P: Into<AssetPath<'a>>>(
+        &self,
+        path: P,
+        settings: S,
+    ) -> Handle<T> {
+        let asset_path = path.into();
+        self.add_requested_type::<T>(&asset_path);
+        self.server
+            .asset_settings
+            .write()
+            .insert(asset_path.get_id().source_path_id(), Arc::new(settings));
+        let handle_id = self.load_untracked(asset_path, true);
+        self.get_handle(handle_id)
+    }
+
+    fn add_requested_type<T: Asset>(&self, asset_path: &AssetPath) {
+        let id = asset_path.get_id();
+        self.server
+            .requested_types
+            .write()
+            .entry(id.source_path_id())
+            .or_default()
+            .insert((id.label_id(), T::TYPE_UUID));
+    }
+
+    /// Resolves the settings to load `asset_path` with: those given to
+    /// [`AssetServer::load_with_settings`], then those of its `.meta` file, then the defaults of
+    /// the loader.
+    pub(crate) async fn load_asset_settings(
+        &self,
+        asset_loader: &dyn AssetLoader,
+        asset_io: &dyn AssetIo,
+        asset_path: &AssetPath<'_>,
+    ) -> Result<Option<Arc<dyn AssetLoaderSettings>>, AssetServerError> {
+        let explicit_settings = self
+            .server
+            .asset_settings
+            .read()
+            .get(&asset_path.get_id().source_path_id())
+            .cloned();
+        let default_settings = asset_loader.default_settings();
+
+        let default_settings = match (explicit_settings, default_settings) {
+            (Some(settings), Some(default_settings)) => {
+                return if settings.type_name() == default_settings.type_name() {
+                    Ok(Some(settings))
+                } else {
+                    Err(AssetServerError::IncorrectSettingsType)
+                };
+            }
+            (Some(_), None) => return Err(AssetServerError::IncorrectSettingsType),
+            (None, Some(default_settings)) => default_settings,
+            (None, None) => return Ok(None),
+        };
+
+        let meta_path = get_meta_path(asset_path.path());
+        // watched even if it doesn't exist, so that creating it reloads the asset
+        asset_io.watch_path_for_changes(&meta_path)?;
+        match asset_io.load_path(&meta_path).await {
+            Ok(bytes) => {
+                let settings = default_settings.deserialize_meta(&bytes).map_err(|error| {
+                    AssetServerError::AssetSettingsError {
+                        path: meta_path.clone(),
+                        error,
+                    }
+                })?;
+                Ok(Some(settings.into()))
+            }
+            Err(AssetIoError::NotFound(_)) => Ok(Some(default_settings.into())),
+            Err(err) => Err(err.into()),
+        }
     }
 
     async fn load_async(
         &self,
         asset_path: AssetPath<'_>,
         force: bool,
-    ) -> Result<AssetPathId, AssetServerError> {
+    ) -> Result<AssetPathId, Arc<AssetServerError>> {
         let asset_path_id: AssetPathId = asset_path.get_id();
 
         // load metadata and update source info. this is done in a scope to ensure we release the
@@ -275,44 +707,102 @@ impl AssetServer {
             }
 
             source_info.load_state = LoadState::Loading;
+            self.server
+                .load_errors
+                .write()
+                .remove(&asset_path_id.source_path_id());
             source_info.committed_assets.clear();
             source_info.version += 1;
             source_info.meta = None;
             source_info.version
         };
 
-        let set_asset_failed = || {
-            let mut asset_sources = self.server.asset_sources.write();
-            let source_info = asset_sources
-                .get_mut(&asset_path_id.source_path_id())
-                .expect("`AssetSource` should exist at this point.");
-            source_info.load_state = LoadState::Failed;
+        let set_asset_failed = |err: AssetServerError| {
+            let err = Arc::new(err);
+            let mut failed_assets = self
+                .server
+                .requested_types
+                .read()
+                .get(&asset_path_id.source_path_id())
+                .cloned()
+                .unwrap_or_default();
+            {
+                let mut asset_sources = self.server.asset_sources.write();
+                let source_info = asset_sources
+                    .get_mut(&asset_path_id.source_path_id())
+                    .expect("`AssetSource` should exist at this point.");
+                source_info.load_state = LoadState::Failed;
+                self.server
+                    .load_errors
+                    .write()
+                    .insert(asset_path_id.source_path_id(), err.clone());
+                // the assets of a previous load may have been requested untyped
+                failed_assets.extend(
+                    source_info
+                        .asset_types
+                        .iter()
+                        .map(|(label_id, type_uuid)| (*label_id, *type_uuid)),
+                );
+            }
+
+            let handle_to_path = self.server.handle_to_path.read();
+            let asset_lifecycles = self.server.asset_lifecycles.read();
+            for (label_id, type_uuid) in failed_assets {
+                let id = AssetPathId::new(asset_path_id.source_path_id(), label_id);
+                let path = handle_to_path
+                    .get(&id.into())
+                    .cloned()
+                    .unwrap_or_else(|| asset_path.to_owned());
+                if let Some(asset_lifecycle) = asset_lifecycles.get(&type_uuid) {
+                    asset_lifecycle.fail_asset(id.into(), path, err.clone());
+                }
+            }
+            err
         };
 
         // get the according asset loader
         let asset_loader = match self.get_path_asset_loader(asset_path.path()) {
             Ok(loader) => loader,
             Err(err) => {
-                set_asset_failed();
-                return Err(err);
+                return Err(set_asset_failed(err));
+            }
+        };
+
+        // get the source of the asset
+        let asset_io = match self.get_source(asset_path.source()) {
+            Ok(asset_io) => asset_io,
+            Err(err) => {
+                return Err(set_asset_failed(err));
+            }
+        };
+
+        // resolve the settings of the asset loader
+        let settings = match self
+            .load_asset_settings(&*asset_loader, &*asset_io, &asset_path)
+            .await
+        {
+            Ok(settings) => settings,
+            Err(err) => {
+                return Err(set_asset_failed(err));
             }
         };
 
         // load the asset bytes
-        let bytes = match self.asset_io().load_path(asset_path.path()).await {
+        let bytes = match asset_io.load_path(asset_path.path()).await {
             Ok(bytes) => bytes,
             Err(err) => {
-                set_asset_failed();
-                return Err(AssetServerError::AssetIoError(err));
+                return Err(set_asset_failed(AssetServerError::AssetIoError(err)));
             }
         };
 
         // load the asset source using the corresponding AssetLoader
         let mut load_context = LoadContext::new(
             asset_path.path(),
-            &self.server.asset_ref_counter.channel,
-            self.asset_io(),
+            asset_path.source(),
+            self,
+            &*asset_io,
             version,
+            settings.as_deref(),
         );
 
         if let Err(err) = asset_loader
@@ -320,8 +810,14 @@ impl AssetServer {
             .await
             .map_err(AssetServerError::AssetLoaderError)
         {
-            set_asset_failed();
-            return Err(err);
+            return Err(set_asset_failed(err));
+        }
+
+        // dependencies without a source are in the source of the asset depending on them
+        for loaded_asset in load_context.labeled_assets.values_mut() {
+            for dependency in &mut loaded_asset.dependencies {
+                *dependency = dependency.clone().or_source(asset_path.source());
+            }
         }
 
         // if version has changed since we loaded and grabbed a lock, return. theres is a newer
@@ -349,6 +845,11 @@ impl AssetServer {
         });
 
         // load asset dependencies and prepare asset type hashmap
+        let mut dependencies = load_context.direct_dependencies.clone();
+        for dependency in &load_context.nested_dependencies {
+            self.load_untracked(dependency.clone(), false);
+        }
+        dependencies.extend(load_context.nested_dependencies.iter().cloned());
         for (label, loaded_asset) in &mut load_context.labeled_assets {
             let label_id = LabelId::from(label.as_ref().map(|label| label.as_str()));
             let type_uuid = loaded_asset.value.as_ref().unwrap().type_uuid();
@@ -356,18 +857,86 @@ impl AssetServer {
             for dependency in &loaded_asset.dependencies {
                 self.load_untracked(dependency.clone(), false);
             }
+            dependencies.extend(loaded_asset.dependencies.iter().cloned());
         }
 
-        self.asset_io()
-            .watch_path_for_changes(asset_path.path())
-            .unwrap();
+        asset_io.watch_path_for_changes(asset_path.path()).unwrap();
+        drop(asset_sources);
+        self.register_dependencies(&asset_path, &dependencies);
         self.create_assets_in_load_context(&mut load_context);
         Ok(asset_path_id)
     }
 
+    /// Records that `asset_path` depends on the files at `dependencies` and watches them, so
+    /// that the asset is reloaded when one of them changes.
+    fn register_dependencies(
+        &self,
+        asset_path: &AssetPath<'_>,
+        dependencies: &[AssetPath<'static>],
+    ) {
+        let dependent = AssetPath::new_ref(asset_path.path(), None)
+            .with_source_ref(asset_path.source())
+            .to_owned();
+        let dependent_id = asset_path.get_id().source_path_id();
+        let mut dependents = self.server.dependents.write();
+        for dependency in dependencies {
+            if let Ok(asset_io) = self.get_source(dependency.source()) {
+                let _ = asset_io.watch_path_for_changes(dependency.path());
+            }
+            dependents
+                .entry(dependency.get_id().source_path_id())
+                .or_default()
+                .insert(dependent_id, dependent.clone());
+        }
+    }
+
+    /// Reloads the asset at `asset_path` after its source file changed, along with the assets
+    /// depending on it, recursively.
+    #[cfg(any(
+        all(
+            feature = "filesystem_watcher",
+            all(not(target_arch = "wasm32"), not(target_os = "android"))
+        ),
+        all(feature = "http_asset_io", not(target_arch = "wasm32"))
+    ))]
+    pub(crate) fn reload_changed_path(&self, asset_path: AssetPath<'_>) {
+        let source_path_id = asset_path.get_id().source_path_id();
+        let all_dependents = self.server.dependents.read();
+        let is_loaded = self
+            .server
+            .asset_sources
+            .read()
+            .contains_key(&source_path_id);
+        // a file that is only loaded directly by other assets isn't reloaded on its own
+        if is_loaded || !all_dependents.contains_key(&source_path_id) {
+            self.load_untracked(asset_path, true);
+        }
+
+        let mut visited = HashSet::default();
+        visited.insert(source_path_id);
+        let mut stack = vec![source_path_id];
+        while let Some(source_path_id) = stack.pop() {
+            for (dependent_id, dependent) in
+                all_dependents.get(&source_path_id).into_iter().flatten()
+            {
+                if visited.insert(*dependent_id) {
+                    self.load_untracked(dependent.clone(), true);
+                    stack.push(*dependent_id);
+                }
+            }
+        }
+    }
+
     #[must_use = "not using the returned strong handle may result in the unexpected release of the asset"]
     pub fn load_untyped<'a, P: Into<AssetPath<'a>>>(&self, path: P) -> HandleUntyped {
-        let handle_id = self.load_untracked(path.into(), false);
+        let asset_path = path.into();
+        let saved_handle = self
+            .server
+            .saved_handles
+            .read()
+            .get(&asset_path.get_id())
+            .copied();
+        let handle_id = saved_handle.unwrap_or_else(|| self.load_untracked(asset_path, false));
         self.get_handle_untyped(handle_id)
     }
 
@@ -393,27 +962,28 @@ impl AssetServer {
     }
 
     #[must_use = "not using the returned strong handles may result in the unexpected release of the assets"]
-    pub fn load_folder<P: AsRef<Path>>(
+    pub fn load_folder<'a, P: Into<AssetPath<'a>>>(
         &self,
         path: P,
     ) -> Result<Vec<HandleUntyped>, AssetServerError> {
-        let path = path.as_ref();
-        if !self.asset_io().is_dir(path) {
+        let path = path.into();
+        let asset_io = self.get_source(path.source())?;
+        if !asset_io.is_dir(path.path()) {
             return Err(AssetServerError::AssetFolderNotADirectory(
-                path.to_str().unwrap().to_string(),
+                path.path().to_str().unwrap().to_string(),
             ));
         }
 
         let mut handles = Vec::new();
-        for child_path in self.asset_io().read_directory(path.as_ref())? {
-            if self.asset_io().is_dir(&child_path) {
-                handles.extend(self.load_folder(&child_path)?);
+        for child_path in asset_io.read_directory(path.path())? {
+            let child_path = AssetPath::from(child_path).with_source_ref(path.source());
+            if asset_io.is_dir(child_path.path()) {
+                handles.extend(self.load_folder(child_path)?);
             } else {
-                if self.get_path_asset_loader(&child_path).is_err() {
+                if self.get_path_asset_loader(child_path.path()).is_err() {
                     continue;
                 }
-                let handle =
-                    self.load_untyped(child_path.to_str().expect("Path should be a valid string."));
+                let handle = self.load_untyped(child_path);
                 handles.push(handle);
             }
         }
@@ -442,6 +1012,31 @@ impl AssetServer {
                             asset_lifecycle.free_asset(potential_free);
                         }
                     }
+
+                    match potential_free {
+                        // a freed asset that was saved can't be returned when loading its path
+                        HandleId::Id(..) => {
+                            if let Some(path) =
+                                self.server.handle_to_path.write().remove(&potential_free)
+                            {
+                                self.server.saved_handles.write().remove(&path.get_id());
+                            }
+                        }
+                        // nothing waits for the freed asset to load anymore
+                        HandleId::AssetPathId(id) => {
+                            let mut requested_types = self.server.requested_types.write();
+                            if let Entry::Occupied(mut entry) =
+                                requested_types.entry(id.source_path_id())
+                            {
+                                entry
+                                    .get_mut()
+                                    .retain(|(label_id, _)| *label_id != id.label_id());
+                                if entry.get().is_empty() {
+                                    entry.remove();
+                                }
+                            }
+                        }
+                    }
                 }
             }
         }
@@ -476,20 +1071,31 @@ impl AssetServer {
 
     fn create_assets_in_load_context(&self, load_context: &mut LoadContext) {
         let asset_lifecycles = self.server.asset_lifecycles.read();
-        for (label, asset) in &mut load_context.labeled_assets {
+        let labeled_assets = load_context
+            .labeled_assets
+            .iter_mut()
+            .map(|(label, asset)| {
+                let asset_path =
+                    AssetPath::new_ref(load_context.path, label.as_ref().map(|l| l.as_str()))
+                        .with_source_ref(load_context.source);
+                (asset_path, asset)
+            });
+        let nested_assets = load_context
+            .nested_assets
+            .iter_mut()
+            .map(|(asset_path, asset)| (asset_path.clone(), asset));
+        for (asset_path, asset) in labeled_assets.chain(nested_assets) {
             let asset_value = asset
                 .value
                 .take()
                 .expect("Asset should exist at this point.");
             if let Some(asset_lifecycle) = asset_lifecycles.get(&asset_value.type_uuid()) {
-                let asset_path =
-                    AssetPath::new_ref(load_context.path, label.as_ref().map(|l| l.as_str()));
                 asset_lifecycle.create_asset(asset_path.into(), asset_value, load_context.version);
             } else {
                 panic!(
                     "Failed to find AssetLifecycle for label '{:?}', which has an asset type {} (UUID {:?}). \
                         Are you sure this asset type has been added to your app builder?",
-                    label,
+                    asset_path.label(),
                     asset_value.type_name(),
                     asset_value.type_uuid(),
                 );
@@ -520,6 +1126,7 @@ impl AssetServer {
                                 if source_info.is_loaded() {
                                     source_info.load_state = LoadState::Loaded;
                                 }
+                                assets.waiting_for_dependencies.insert(result.id);
                             }
                         }
                     }
@@ -535,14 +1142,42 @@ impl AssetServer {
                             source_info.load_state = LoadState::Unloaded;
                         }
                     }
+                    assets.waiting_for_dependencies.remove(&handle_id);
                     assets.remove(handle_id);
                 }
+                Ok(AssetLifecycleEvent::Fail { id, path, error }) => {
+                    assets.send_load_failed_event(AssetLoadFailedEvent {
+                        handle: Handle::weak(id),
+                        path,
+                        error,
+                    });
+                }
                 Err(TryRecvError::Empty) => {
                     break;
                 }
                 Err(TryRecvError::Disconnected) => panic!("AssetChannel disconnected."),
             }
         }
+        drop(asset_sources_guard);
+
+        // send `LoadedWithDependencies` for the assets whose whole dependency tree has loaded
+        if !assets.waiting_for_dependencies.is_empty() {
+            let waiting_for_dependencies = std::mem::take(&mut assets.waiting_for_dependencies);
+            for handle_id in waiting_for_dependencies {
+                match self.get_recursive_dependency_load_state(handle_id) {
+                    RecursiveDependencyLoadState::Loaded => {
+                        assets.send_event(AssetEvent::LoadedWithDependencies {
+                            handle: Handle::weak(handle_id),
+                        });
+                    }
+                    RecursiveDependencyLoadState::Failed => {}
+                    RecursiveDependencyLoadState::Loading
+                    | RecursiveDependencyLoadState::NotLoaded => {
+                        assets.waiting_for_dependencies.insert(handle_id);
+                    }
+                }
+            }
+        }
     }
 }
 
@@ -561,8 +1196,9 @@ mod test {
     use crate::{loader::LoadedAsset, update_asset_storage_system};
     use bevy_app::App;
     use bevy_ecs::prelude::*;
-    use bevy_reflect::TypeUuid;
+    use bevy_reflect::{Reflect, TypeUuid};
     use bevy_utils::BoxedFuture;
+    use serde::{Deserialize, Serialize};
 
     #[derive(Debug, TypeUuid)]
     #[uuid = "a5189b72-0572-4290-a2e0-96f73a491c44"]
@@ -614,6 +1250,118 @@ mod test {
         }
     }
 
+    #[derive(Debug, TypeUuid)]
+    #[uuid = "3c2cbd5e-2ce7-4aa2-b5a5-4c2aa1c6f3a9"]
+    struct TextAsset(String);
+
+    #[derive(Default, Reflect, Serialize, Deserialize)]
+    struct TextSettings {
+        uppercase: bool,
+    }
+
+    struct TextLoader;
+    impl AssetLoader for TextLoader {
+        fn load<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            ctx: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            Box::pin(async move {
+                let mut text = String::from_utf8(bytes.to_vec())?;
+                if ctx.settings::<TextSettings>().unwrap().uppercase {
+                    text = text.to_uppercase();
+                }
+                ctx.set_default_asset(LoadedAsset::new(TextAsset(text)));
+                Ok(())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["txt"]
+        }
+
+        fn default_settings(&self) -> Option<Box<dyn AssetLoaderSettings>> {
+            Some(Box::new(TextSettings::default()))
+        }
+    }
+
+    /// Loads a [`TextAsset`] joining the text assets at the paths listed in the file.
+    struct IncludeLoader;
+    impl AssetLoader for IncludeLoader {
+        fn load<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            ctx: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            Box::pin(async move {
+                let mut text = String::new();
+                for path in std::str::from_utf8(bytes)?.lines() {
+                    text += &ctx.load_direct::<TextAsset>(path).await?.0;
+                }
+                ctx.set_default_asset(LoadedAsset::new(TextAsset(text)));
+                Ok(())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["include"]
+        }
+    }
+
+    /// Loads each line of the file after the first as a [`TextAsset`] labeled with its index,
+    /// depending on the asset at the path given by the first line.
+    struct SplitLoader;
+    impl AssetLoader for SplitLoader {
+        fn load<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            ctx: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            Box::pin(async move {
+                let mut lines = std::str::from_utf8(bytes)?.lines();
+                let dependency = AssetPath::from(lines.next().unwrap_or_default()).to_owned();
+                for (index, line) in lines.enumerate() {
+                    ctx.set_labeled_asset(
+                        &index.to_string(),
+                        LoadedAsset::new(TextAsset(line.to_string()))
+                            .with_dependency(dependency.clone()),
+                    );
+                }
+                Ok(())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["split"]
+        }
+    }
+
+    /// Loads a [`TextAsset`] depending on the assets at the paths listed in the file.
+    struct DependencyLoader;
+    impl AssetLoader for DependencyLoader {
+        fn load<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            ctx: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            Box::pin(async move {
+                let text = std::str::from_utf8(bytes)?;
+                let dependencies = text
+                    .lines()
+                    .map(|path| AssetPath::from(path).to_owned())
+                    .collect();
+                ctx.set_default_asset(
+                    LoadedAsset::new(TextAsset(text.to_string())).with_dependencies(dependencies),
+                );
+                Ok(())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["deps"]
+        }
+    }
+
     fn setup(asset_path: impl AsRef<Path>) -> AssetServer {
         use crate::FileAssetIo;
         IoTaskPool::init(Default::default);
@@ -714,9 +1462,9 @@ mod test {
 
         let err = futures_lite::future::block_on(asset_server.load_async(path.clone(), true))
             .unwrap_err();
-        assert!(match err {
+        assert!(match &*err {
             AssetServerError::MissingAssetLoader { extensions } => {
-                extensions == ["not-a-real-extension"]
+                *extensions == ["not-a-real-extension"]
             }
             _ => false,
         });
@@ -734,7 +1482,7 @@ mod test {
 
         let err = futures_lite::future::block_on(asset_server.load_async(path.clone(), true))
             .unwrap_err();
-        assert!(matches!(err, AssetServerError::AssetIoError(_)));
+        assert!(matches!(*err, AssetServerError::AssetIoError(_)));
 
         assert_eq!(asset_server.get_load_state(handle), LoadState::Failed);
     }
@@ -750,7 +1498,7 @@ mod test {
 
         let err = futures_lite::future::block_on(asset_server.load_async(path.clone(), true))
             .unwrap_err();
-        assert!(matches!(err, AssetServerError::AssetLoaderError(_)));
+        assert!(matches!(*err, AssetServerError::AssetLoaderError(_)));
 
         assert_eq!(asset_server.get_load_state(handle), LoadState::Failed);
     }
@@ -832,6 +1580,542 @@ mod test {
         assert!(get_asset(&handle, &app.world).is_some());
     }
 
+    #[test]
+    fn test_loader_settings() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("default.txt"), "text").unwrap();
+        std::fs::write(dir.path().join("meta.txt"), "text").unwrap();
+        std::fs::write(dir.path().join("meta.txt.meta"), "(uppercase: true)").unwrap();
+        std::fs::write(dir.path().join("invalid.txt"), "text").unwrap();
+        std::fs::write(dir.path().join("invalid.txt.meta"), "(uppercase: 1)").unwrap();
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(TextLoader);
+        let assets = asset_server.register_asset_type::<TextAsset>();
+
+        let mut app = App::new();
+        app.insert_resource(assets);
+        app.insert_resource(asset_server.clone());
+        app.add_system(update_asset_storage_system::<TextAsset>);
+
+        let load = |path: &str| {
+            let id =
+                futures_lite::future::block_on(asset_server.load_async(path.into(), true)).unwrap();
+            asset_server.get_handle::<TextAsset, _>(id)
+        };
+        let default = load("default.txt");
+        let meta = load("meta.txt");
+
+        let err =
+            futures_lite::future::block_on(asset_server.load_async("invalid.txt".into(), true))
+                .unwrap_err();
+        assert!(matches!(*err, AssetServerError::AssetSettingsError { .. }));
+
+        // explicit settings take precedence over the defaults and are kept for reloads
+        let _: Handle<TextAsset> =
+            asset_server.load_with_settings("default.txt", TextSettings { uppercase: true });
+        let explicit = load("default.txt");
+
+        app.update();
+        let assets = app.world.resource::<Assets<TextAsset>>();
+        assert_eq!(assets.get(&meta).unwrap().0, "TEXT");
+        assert_eq!(assets.get(&explicit).unwrap().0, "TEXT");
+        assert_eq!(default, explicit);
+
+        // settings of the wrong type are rejected
+        let _: Handle<TextAsset> = asset_server.load_with_settings("meta.txt", 42u32);
+        let err = futures_lite::future::block_on(asset_server.load_async("meta.txt".into(), true))
+            .unwrap_err();
+        assert!(matches!(*err, AssetServerError::IncorrectSettingsType));
+    }
+
+    #[test]
+    fn test_named_sources() {
+        let default_dir = tempfile::tempdir().unwrap();
+        let mods_dir = tempfile::tempdir().unwrap();
+        std::fs::write(default_dir.path().join("a.txt"), "default").unwrap();
+        std::fs::write(mods_dir.path().join("a.txt"), "mods").unwrap();
+        let asset_server = setup(default_dir.path());
+        asset_server.add_source("mods", crate::FileAssetIo::new(mods_dir.path(), false));
+        asset_server.add_loader(TextLoader);
+        let assets = asset_server.register_asset_type::<TextAsset>();
+
+        let mut app = App::new();
+        app.insert_resource(assets);
+        app.add_system(update_asset_storage_system::<TextAsset>);
+        app.insert_resource(asset_server.clone());
+
+        let load = |path: &str| {
+            let id =
+                futures_lite::future::block_on(asset_server.load_async(path.into(), true)).unwrap();
+            asset_server.get_handle::<TextAsset, _>(id)
+        };
+        let default = load("a.txt");
+        let mods = load("mods://a.txt");
+        assert_ne!(default, mods);
+
+        app.update();
+        let assets = app.world.resource::<Assets<TextAsset>>();
+        assert_eq!(assets.get(&default).unwrap().0, "default");
+        assert_eq!(assets.get(&mods).unwrap().0, "mods");
+
+        let err =
+            futures_lite::future::block_on(asset_server.load_async("dlc://a.txt".into(), true))
+                .unwrap_err();
+        assert!(matches!(&*err, AssetServerError::MissingAssetSource(source) if source == "dlc"));
+    }
+
+    #[test]
+    fn test_relative_paths_inherit_source() {
+        let default_dir = tempfile::tempdir().unwrap();
+        let mods_dir = tempfile::tempdir().unwrap();
+        std::fs::write(default_dir.path().join("a.txt"), "default").unwrap();
+        std::fs::write(mods_dir.path().join("a.txt"), "mods").unwrap();
+        std::fs::write(mods_dir.path().join("root.deps"), "a.txt").unwrap();
+        std::fs::write(mods_dir.path().join("root.include"), "a.txt").unwrap();
+        let asset_server = setup(default_dir.path());
+        asset_server.add_source("mods", crate::FileAssetIo::new(mods_dir.path(), false));
+        asset_server.add_loader(TextLoader);
+        asset_server.add_loader(DependencyLoader);
+        asset_server.add_loader(IncludeLoader);
+        let assets = asset_server.register_asset_type::<TextAsset>();
+
+        let mut app = App::new();
+        app.insert_resource(assets);
+        app.add_system(update_asset_storage_system::<TextAsset>);
+        app.insert_resource(asset_server.clone());
+
+        let load = |path: &str| {
+            let id =
+                futures_lite::future::block_on(asset_server.load_async(path.into(), true)).unwrap();
+            asset_server.get_handle::<TextAsset, _>(id)
+        };
+        let deps = load("mods://root.deps");
+        let included = load("mods://root.include");
+
+        // the dependency is loaded from the `mods` source, not the default one
+        let dependency = asset_server.get_handle::<TextAsset, _>("mods://a.txt");
+        let default = asset_server.get_handle::<TextAsset, _>("a.txt");
+        for _ in 0..100 {
+            app.update();
+            if asset_server.get_recursive_dependency_load_state(&deps)
+                == RecursiveDependencyLoadState::Loaded
+            {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert_eq!(
+            asset_server.get_recursive_dependency_load_state(&deps),
+            RecursiveDependencyLoadState::Loaded
+        );
+        assert_eq!(asset_server.get_load_state(&default), LoadState::NotLoaded);
+        let assets = app.world.resource::<Assets<TextAsset>>();
+        assert_eq!(assets.get(&dependency).unwrap().0, "mods");
+        assert_eq!(assets.get(&included).unwrap().0, "mods");
+    }
+
+    #[test]
+    fn test_load_direct() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.txt"), "a").unwrap();
+        std::fs::write(dir.path().join("b.txt"), "b").unwrap();
+        std::fs::write(dir.path().join("b.txt.meta"), "(uppercase: true)").unwrap();
+        std::fs::write(dir.path().join("ab.include"), "a.txt\nb.txt").unwrap();
+        std::fs::write(dir.path().join("nested.include"), "ab.include\na.txt").unwrap();
+        std::fs::write(dir.path().join("missing.include"), "c.txt").unwrap();
+        std::fs::write(dir.path().join("wrong.include"), "a.png").unwrap();
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(TextLoader);
+        asset_server.add_loader(IncludeLoader);
+        asset_server.add_loader(FakePngLoader);
+        let assets = asset_server.register_asset_type::<TextAsset>();
+
+        let mut app = App::new();
+        app.insert_resource(assets);
+        app.insert_resource(asset_server.clone());
+        app.add_system(update_asset_storage_system::<TextAsset>);
+
+        let load = |path: &str| {
+            futures_lite::future::block_on(asset_server.load_async(path.into(), true))
+                .map(|id| asset_server.get_handle::<TextAsset, _>(id))
+        };
+        let ab = load("ab.include").unwrap();
+        let nested = load("nested.include").unwrap();
+        assert!(matches!(
+            *load("missing.include").unwrap_err(),
+            AssetServerError::AssetLoaderError(_)
+        ));
+        assert!(matches!(
+            *load("wrong.include").unwrap_err(),
+            AssetServerError::AssetLoaderError(_)
+        ));
+
+        app.update();
+        let assets = app.world.resource::<Assets<TextAsset>>();
+        assert_eq!(assets.get(&ab).unwrap().0, "aB");
+        assert_eq!(assets.get(&nested).unwrap().0, "aBa");
+        // directly loaded assets are not added to the asset storage
+        assert_eq!(assets.len(), 2);
+
+        // both assets depend on `b.txt`, even through a nested `load_direct`
+        let dependents = asset_server.server.dependents.read();
+        let b_dependents = &dependents[&AssetPath::from("b.txt").get_id().source_path_id()];
+        assert_eq!(b_dependents.len(), 2);
+        assert!(b_dependents.contains_key(&AssetPath::from("ab.include").get_id().source_path_id()));
+    }
+
+    #[test]
+    fn test_load_direct_cycles() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("self.include"), "self.include").unwrap();
+        std::fs::write(dir.path().join("a.include"), "b.include").unwrap();
+        std::fs::write(dir.path().join("b.include"), "a.include").unwrap();
+        std::fs::write(dir.path().join("c.txt"), "c").unwrap();
+        std::fs::write(dir.path().join("twice.include"), "c.txt\nc.txt").unwrap();
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(TextLoader);
+        asset_server.add_loader(IncludeLoader);
+        let _assets = asset_server.register_asset_type::<TextAsset>();
+
+        let load =
+            |path: &str| futures_lite::future::block_on(asset_server.load_async(path.into(), true));
+        for (path, cycle) in [("self.include", "self.include"), ("a.include", "a.include")] {
+            let err = load(path).unwrap_err();
+            assert!(matches!(*err, AssetServerError::AssetLoaderError(_)));
+            assert!(
+                err.to_string().contains(
+                    &AssetServerError::LoadDirectCycle(AssetPath::from(cycle).to_owned())
+                        .to_string()
+                ),
+                "{}",
+                err
+            );
+        }
+
+        // loading the same file twice, one after the other, is not a cycle
+        load("twice.include").unwrap();
+    }
+
+    #[test]
+    fn test_load_direct_labeled_assets() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.txt"), "a").unwrap();
+        std::fs::write(dir.path().join("lines.split"), "a.txt\nfirst\nsecond").unwrap();
+        std::fs::write(dir.path().join("first.include"), "lines.split#0").unwrap();
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(TextLoader);
+        asset_server.add_loader(IncludeLoader);
+        asset_server.add_loader(SplitLoader);
+        let assets = asset_server.register_asset_type::<TextAsset>();
+
+        let mut app = App::new();
+        app.insert_resource(assets);
+        app.insert_resource(asset_server.clone());
+        app.add_system(update_asset_storage_system::<TextAsset>);
+
+        let id =
+            futures_lite::future::block_on(asset_server.load_async("first.include".into(), true))
+                .unwrap();
+        let first = asset_server.get_handle::<TextAsset, _>(id);
+        let second = asset_server.get_handle::<TextAsset, _>("lines.split#1");
+        let dependency = asset_server.get_handle::<TextAsset, _>("a.txt");
+        for _ in 0..100 {
+            app.update();
+            if asset_server.get_load_state(&dependency) == LoadState::Loaded {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+
+        // the requested asset is returned, the other asset of the file is created at its path
+        // and the dependencies of both are loaded
+        app.update();
+        let assets = app.world.resource::<Assets<TextAsset>>();
+        assert_eq!(assets.get(&first).unwrap().0, "first");
+        assert_eq!(assets.get(&second).unwrap().0, "second");
+        assert_eq!(assets.get(&dependency).unwrap().0, "a");
+        assert!(assets
+            .get(&asset_server.get_handle::<TextAsset, _>("lines.split#0"))
+            .is_none());
+    }
+
+    #[test]
+    fn test_recursive_dependency_load_state() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.txt"), "a").unwrap();
+        std::fs::write(dir.path().join("child.deps"), "a.txt").unwrap();
+        std::fs::write(dir.path().join("root.deps"), "child.deps").unwrap();
+        std::fs::write(dir.path().join("broken.deps"), "child.deps\nmissing.txt").unwrap();
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(TextLoader);
+        asset_server.add_loader(DependencyLoader);
+        let assets = asset_server.register_asset_type::<TextAsset>();
+
+        let mut app = App::new();
+        app.insert_resource(assets);
+        app.insert_resource(asset_server.clone());
+        app.add_event::<AssetEvent<TextAsset>>();
+        app.add_system(update_asset_storage_system::<TextAsset>);
+        app.add_system(Assets::<TextAsset>::asset_event_system);
+
+        let root: Handle<TextAsset> = asset_server.load("root.deps");
+        let broken: Handle<TextAsset> = asset_server.load("broken.deps");
+        assert_eq!(
+            asset_server.get_recursive_dependency_load_state(&root),
+            RecursiveDependencyLoadState::Loading
+        );
+
+        let mut reader = app
+            .world
+            .resource::<bevy_ecs::event::Events<AssetEvent<TextAsset>>>()
+            .get_reader();
+        let mut loaded_with_dependencies = Vec::new();
+        for _ in 0..200 {
+            app.update();
+            let events = app
+                .world
+                .resource::<bevy_ecs::event::Events<AssetEvent<TextAsset>>>();
+            for event in reader.iter(events) {
+                if let AssetEvent::LoadedWithDependencies { handle } = event {
+                    loaded_with_dependencies.push(handle.clone_weak());
+                }
+            }
+            let state =
+                asset_server.get_group_recursive_dependency_load_state([root.id, broken.id]);
+            if state == RecursiveDependencyLoadState::Failed
+                && asset_server.get_recursive_dependency_load_state(&root)
+                    == RecursiveDependencyLoadState::Loaded
+                && loaded_with_dependencies.contains(&root)
+            {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+
+        // `broken.deps` itself loaded, but one of its dependencies failed
+        assert_eq!(asset_server.get_load_state(&broken), LoadState::Loaded);
+        assert_eq!(
+            asset_server.get_recursive_dependency_load_state(&broken),
+            RecursiveDependencyLoadState::Failed
+        );
+        assert!(loaded_with_dependencies.contains(&root));
+        assert!(!loaded_with_dependencies.contains(&broken));
+        assert!(loaded_with_dependencies.contains(&asset_server.get_handle("child.deps")));
+
+        // a change to `a.txt` reloads both `child.deps` and `root.deps`
+        let dependents = asset_server.server.dependents.read();
+        let a_id = AssetPath::from("a.txt").get_id().source_path_id();
+        let child_id = AssetPath::from("child.deps").get_id().source_path_id();
+        let root_id = AssetPath::from("root.deps").get_id().source_path_id();
+        assert!(dependents[&a_id].contains_key(&child_id));
+        assert!(dependents[&child_id].contains_key(&root_id));
+    }
+
+    #[test]
+    fn test_asset_load_failed_event() {
+        use crate::AssetLoadFailedEvent;
+        use bevy_ecs::event::Events;
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.txt"), "a").unwrap();
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(TextLoader);
+        let assets = asset_server.register_asset_type::<TextAsset>();
+
+        let mut app = App::new();
+        app.insert_resource(assets);
+        app.insert_resource(asset_server.clone());
+        app.add_event::<AssetLoadFailedEvent<TextAsset>>();
+        app.add_system(update_asset_storage_system::<TextAsset>);
+        app.add_system(Assets::<TextAsset>::asset_load_failed_event_system);
+
+        let a: Handle<TextAsset> = asset_server.load("a.txt");
+        let missing: Handle<TextAsset> = asset_server.load("missing.txt");
+        let missing_untyped = asset_server.load_untyped("missing_untyped.txt");
+
+        let mut reader = app
+            .world
+            .resource::<Events<AssetLoadFailedEvent<TextAsset>>>()
+            .get_reader();
+        let mut failed = Vec::new();
+        for _ in 0..200 {
+            app.update();
+            let events = app
+                .world
+                .resource::<Events<AssetLoadFailedEvent<TextAsset>>>();
+            failed.extend(reader.iter(events).cloned());
+            if !failed.is_empty()
+                && asset_server.get_load_state(&a) == LoadState::Loaded
+                && asset_server.get_load_state(&missing_untyped) == LoadState::Failed
+            {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+
+        // only the asset requested as a `TextAsset` is reported
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].handle, missing);
+        assert_eq!(failed[0].path.path(), Path::new("missing.txt"));
+        assert!(matches!(
+            *failed[0].error,
+            AssetServerError::AssetIoError(AssetIoError::NotFound(_))
+        ));
+
+        assert!(asset_server.get_load_error(&a).is_none());
+        assert!(Arc::ptr_eq(
+            &asset_server.get_load_error(&missing).unwrap(),
+            &failed[0].error
+        ));
+        assert!(asset_server.get_load_error(&missing_untyped).is_some());
+
+        // the requested types are forgotten once the assets are freed
+        assert_eq!(asset_server.server.requested_types.read().len(), 2);
+        drop((a, missing, missing_untyped, failed));
+        asset_server.mark_unused_assets();
+        asset_server.free_unused_assets();
+        assert!(asset_server.server.requested_types.read().is_empty());
+    }
+
+    #[test]
+    fn test_loading_tracker() {
+        use crate::{transition_when_loaded, update_loading_tracker_system, LoadingTracker};
+
+        #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+        enum AppState {
+            Loading,
+            Ready,
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.txt"), "a").unwrap();
+        std::fs::write(dir.path().join("child.deps"), "a.txt").unwrap();
+        std::fs::write(dir.path().join("root.deps"), "child.deps\na.txt").unwrap();
+        std::fs::write(dir.path().join("broken.deps"), "a.txt\nmissing.txt").unwrap();
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(TextLoader);
+        asset_server.add_loader(DependencyLoader);
+        let assets = asset_server.register_asset_type::<TextAsset>();
+
+        let mut app = App::new();
+        app.insert_resource(assets);
+        app.insert_resource(asset_server.clone());
+        app.init_resource::<LoadingTracker>();
+        app.add_state(AppState::Loading);
+        app.add_system(update_asset_storage_system::<TextAsset>);
+        app.add_system(update_loading_tracker_system);
+        app.add_system(transition_when_loaded("level", AppState::Ready));
+
+        let mut tracker = app.world.resource_mut::<LoadingTracker>();
+        tracker.track("level", &asset_server.load::<TextAsset, _>("root.deps"));
+        tracker.track("broken", &asset_server.load::<TextAsset, _>("broken.deps"));
+        assert_eq!(tracker.progress("level").total, 1);
+        assert!(!tracker.is_finished("level"));
+        assert!(tracker.is_finished("unknown"));
+
+        for _ in 0..200 {
+            app.update();
+            let tracker = app.world.resource::<LoadingTracker>();
+            if tracker.is_finished("level")
+                && tracker.is_finished("broken")
+                && *app.world.resource::<State<AppState>>().current() == AppState::Ready
+            {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+
+        // `root.deps`, `child.deps` and `a.txt`
+        let tracker = app.world.resource::<LoadingTracker>();
+        let progress = tracker.progress("level");
+        assert_eq!(
+            (progress.loaded, progress.failed, progress.total),
+            (3, 0, 3)
+        );
+        assert!(tracker.is_loaded("level"));
+        assert_eq!(
+            *app.world.resource::<State<AppState>>().current(),
+            AppState::Ready
+        );
+
+        let progress = tracker.progress("broken");
+        assert_eq!(
+            (progress.loaded, progress.failed, progress.total),
+            (2, 1, 3)
+        );
+        assert!(!tracker.is_loaded("broken"));
+        let failed = tracker.failed("broken");
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].path.path(), Path::new("missing.txt"));
+        assert!(matches!(
+            *failed[0].error,
+            AssetServerError::AssetIoError(AssetIoError::NotFound(_))
+        ));
+    }
+
+    struct TextSaver;
+    impl AssetSaver for TextSaver {
+        type Asset = TextAsset;
+
+        fn save(&self, asset: &TextAsset, _: &SaveContext) -> Result<Vec<u8>> {
+            Ok(asset.0.clone().into_bytes())
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["txt"]
+        }
+    }
+
+    #[test]
+    fn test_save_asset() {
+        let dir = tempfile::tempdir().unwrap();
+        let asset_server = setup(dir.path());
+        asset_server.add_saver(TextSaver);
+        let mut assets = asset_server.register_asset_type::<TextAsset>();
+        let handle = assets.add(TextAsset("saved".to_string()));
+
+        let mut app = App::new();
+        app.insert_resource(assets);
+        app.insert_resource(asset_server.clone());
+        app.add_system(crate::save_assets_system::<TextAsset>);
+
+        assert!(matches!(
+            asset_server.save(&handle, "text.png"),
+            Err(AssetServerError::MissingAssetSaver { .. })
+        ));
+        asset_server.save(&handle, "saved/text.txt").unwrap();
+        assert_eq!(
+            asset_server.get_handle_path(&handle).unwrap().path(),
+            Path::new("saved/text.txt")
+        );
+        // loading the saved path returns the saved asset instead of loading it again
+        let loaded: Handle<TextAsset> = asset_server.load("saved/text.txt");
+        assert_eq!(loaded, handle);
+        assert_eq!(
+            asset_server.get_load_state(AssetPath::from("saved/text.txt")),
+            LoadState::NotLoaded
+        );
+
+        app.update();
+        let path = dir.path().join("saved/text.txt");
+        let mut saved = false;
+        for _ in 0..100 {
+            if std::fs::read_to_string(&path).map_or(false, |text| text == "saved") {
+                saved = true;
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert!(saved, "the asset was not saved");
+
+        // once the saved asset is freed, its path is loaded from the file
+        drop((handle, loaded));
+        asset_server.mark_unused_assets();
+        asset_server.free_unused_assets();
+        let loaded: Handle<TextAsset> = asset_server.load("saved/text.txt");
+        assert_eq!(loaded.id, AssetPath::from("saved/text.txt").get_id().into());
+    }
+
     #[test]
     fn test_get_handle_path() {
         const PATH: &str = "path/file.png";
@@ -1,6 +1,7 @@
 use crate::{
-    update_asset_storage_system, Asset, AssetLoader, AssetServer, AssetStage, Handle, HandleId,
-    RefChange,
+    save_assets_system, update_asset_storage_system, Asset, AssetLoader, AssetPath, AssetPipeline,
+    AssetProcessor, AssetSaver, AssetServer, AssetServerError, AssetStage, Handle, HandleId,
+    RefChange, ReflectAsset,
 };
 use bevy_app::App;
 use bevy_ecs::{
@@ -8,17 +9,29 @@ use bevy_ecs::{
     system::ResMut,
     world::FromWorld,
 };
-use bevy_utils::HashMap;
+use bevy_reflect::{FromReflect, FromType, GetTypeRegistration, TypeRegistryArc};
+use bevy_utils::{HashMap, HashSet};
 use crossbeam_channel::Sender;
-use std::fmt::Debug;
+use std::{any::TypeId, fmt::Debug, sync::Arc};
 
 /// Events that happen on assets of type `T`
 ///
 /// Events sent via the [Assets] struct will always be sent with a _Weak_ handle
 pub enum AssetEvent<T: Asset> {
-    Created { handle: Handle<T> },
-    Modified { handle: Handle<T> },
-    Removed { handle: Handle<T> },
+    Created {
+        handle: Handle<T>,
+    },
+    Modified {
+        handle: Handle<T>,
+    },
+    Removed {
+        handle: Handle<T>,
+    },
+    /// Sent once an asset loaded by the [`AssetServer`](crate::AssetServer) and all of its
+    /// dependencies, recursively, have loaded. It is sent again when the asset is reloaded.
+    LoadedWithDependencies {
+        handle: Handle<T>,
+    },
 }
 
 impl<T: Asset> Debug for AssetEvent<T> {
@@ -45,10 +58,52 @@ impl<T: Asset> Debug for AssetEvent<T> {
                 ))
                 .field("handle", &handle.id)
                 .finish(),
+            AssetEvent::LoadedWithDependencies { handle } => f
+                .debug_struct(&format!(
+                    "AssetEvent<{}>::LoadedWithDependencies",
+                    std::any::type_name::<T>()
+                ))
+                .field("handle", &handle.id)
+                .finish(),
         }
     }
 }
 
+/// Sent when an asset of type `T` requested from the [`AssetServer`](crate::AssetServer) fails to
+/// load
+///
+/// It is sent for every asset type the failed asset was requested as, for example with
+/// [`AssetServer::load`](crate::AssetServer::load), or previously loaded as.
+pub struct AssetLoadFailedEvent<T: Asset> {
+    /// A weak handle to the asset.
+    pub handle: Handle<T>,
+    pub path: AssetPath<'static>,
+    pub error: Arc<AssetServerError>,
+}
+
+impl<T: Asset> Clone for AssetLoadFailedEvent<T> {
+    fn clone(&self) -> Self {
+        Self {
+            handle: self.handle.clone_weak(),
+            path: self.path.clone(),
+            error: self.error.clone(),
+        }
+    }
+}
+
+impl<T: Asset> Debug for AssetLoadFailedEvent<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct(&format!(
+            "AssetLoadFailedEvent<{}>",
+            std::any::type_name::<T>()
+        ))
+        .field("handle", &self.handle.id)
+        .field("path", &self.path)
+        .field("error", &self.error)
+        .finish()
+    }
+}
+
 /// Stores Assets of a given type and tracks changes to them.
 ///
 /// Each asset is mapped by a unique [`HandleId`], allowing any [`Handle`] with the same
@@ -66,7 +121,11 @@ impl<T: Asset> Debug for AssetEvent<T> {
 pub struct Assets<T: Asset> {
     assets: HashMap<HandleId, T>,
     events: Events<AssetEvent<T>>,
+    load_failed_events: Events<AssetLoadFailedEvent<T>>,
     pub(crate) ref_change_sender: Sender<RefChange>,
+    /// Loaded assets whose dependencies are still loading, waiting to send
+    /// [`AssetEvent::LoadedWithDependencies`].
+    pub(crate) waiting_for_dependencies: HashSet<HandleId>,
 }
 
 impl<T: Asset> Assets<T> {
@@ -74,7 +133,9 @@ impl<T: Asset> Assets<T> {
         Assets {
             assets: HashMap::default(),
             events: Events::default(),
+            load_failed_events: Events::default(),
             ref_change_sender,
+            waiting_for_dependencies: HashSet::default(),
         }
     }
 
@@ -238,6 +299,14 @@ impl<T: Asset> Assets<T> {
         self.assets.shrink_to_fit();
     }
 
+    pub(crate) fn send_event(&mut self, event: AssetEvent<T>) {
+        self.events.send(event);
+    }
+
+    pub(crate) fn send_load_failed_event(&mut self, event: AssetLoadFailedEvent<T>) {
+        self.load_failed_events.send(event);
+    }
+
     pub fn asset_event_system(
         mut events: EventWriter<AssetEvent<T>>,
         mut assets: ResMut<Assets<T>>,
@@ -249,6 +318,15 @@ impl<T: Asset> Assets<T> {
         }
     }
 
+    pub fn asset_load_failed_event_system(
+        mut events: EventWriter<AssetLoadFailedEvent<T>>,
+        mut assets: ResMut<Assets<T>>,
+    ) {
+        if !assets.load_failed_events.is_empty() {
+            events.send_batch(assets.load_failed_events.drain());
+        }
+    }
+
     /// Gets the number of assets in the collection
     pub fn len(&self) -> usize {
         self.assets.len()
@@ -268,6 +346,9 @@ pub trait AddAsset {
     fn add_debug_asset<T: Clone>(&mut self) -> &mut Self
     where
         T: Asset;
+    fn register_asset_reflect<T>(&mut self) -> &mut Self
+    where
+        T: Asset + FromReflect + GetTypeRegistration;
     fn init_asset_loader<T>(&mut self) -> &mut Self
     where
         T: AssetLoader + FromWorld;
@@ -277,6 +358,15 @@ pub trait AddAsset {
     fn add_asset_loader<T>(&mut self, loader: T) -> &mut Self
     where
         T: AssetLoader;
+    fn add_asset_processor<T>(&mut self, processor: T) -> &mut Self
+    where
+        T: AssetProcessor;
+    fn init_asset_saver<T>(&mut self) -> &mut Self
+    where
+        T: AssetSaver + FromWorld;
+    fn add_asset_saver<T>(&mut self, saver: T) -> &mut Self
+    where
+        T: AssetSaver;
 }
 
 impl AddAsset for App {
@@ -297,9 +387,15 @@ impl AddAsset for App {
 
         self.insert_resource(assets)
             .add_system_to_stage(AssetStage::AssetEvents, Assets::<T>::asset_event_system)
+            .add_system_to_stage(
+                AssetStage::AssetEvents,
+                Assets::<T>::asset_load_failed_event_system,
+            )
             .add_system_to_stage(AssetStage::LoadAssets, update_asset_storage_system::<T>)
+            .add_system_to_stage(AssetStage::AssetEvents, save_assets_system::<T>)
             .register_type::<Handle<T>>()
             .add_event::<AssetEvent<T>>()
+            .add_event::<AssetLoadFailedEvent<T>>()
     }
 
     fn add_debug_asset<T: Clone>(&mut self) -> &mut Self
@@ -318,6 +414,24 @@ impl AddAsset for App {
         self
     }
 
+    /// Registers the [`Asset`] type `T` for reflection, with its [`ReflectAsset`] type data.
+    ///
+    /// The asset type must already have been added with [`AddAsset::add_asset`].
+    fn register_asset_reflect<T>(&mut self) -> &mut Self
+    where
+        T: Asset + FromReflect + GetTypeRegistration,
+    {
+        let type_registry = self.world.resource::<TypeRegistryArc>();
+        let mut type_registry = type_registry.write();
+        type_registry.register::<T>();
+        type_registry
+            .get_mut(TypeId::of::<T>())
+            .unwrap()
+            .insert(<ReflectAsset as FromType<T>>::from_type());
+        drop(type_registry);
+        self
+    }
+
     fn init_asset_loader<T>(&mut self) -> &mut Self
     where
         T: AssetLoader + FromWorld,
@@ -347,6 +461,35 @@ impl AddAsset for App {
         self.world.resource_mut::<AssetServer>().add_loader(loader);
         self
     }
+
+    /// Add an [`AssetProcessor`] to the [`AssetPipeline`].
+    ///
+    /// Does nothing if the [`AssetProcessorPlugin`](crate::AssetProcessorPlugin) wasn't added.
+    fn add_asset_processor<T>(&mut self, processor: T) -> &mut Self
+    where
+        T: AssetProcessor,
+    {
+        if let Some(mut pipeline) = self.world.get_resource_mut::<AssetPipeline>() {
+            pipeline.add_processor(processor);
+        }
+        self
+    }
+
+    fn init_asset_saver<T>(&mut self) -> &mut Self
+    where
+        T: AssetSaver + FromWorld,
+    {
+        let result = T::from_world(&mut self.world);
+        self.add_asset_saver(result)
+    }
+
+    fn add_asset_saver<T>(&mut self, saver: T) -> &mut Self
+    where
+        T: AssetSaver,
+    {
+        self.world.resource::<AssetServer>().add_saver(saver);
+        self
+    }
 }
 
 #[cfg(feature = "debug_asset_server")]
@@ -379,11 +522,35 @@ macro_rules! load_internal_asset {
     }};
 }
 
+/// Embeds a file into the binary and registers it as an asset of the
+/// [`EMBEDDED_ASSET_SOURCE`](crate::EMBEDDED_ASSET_SOURCE), so that it can be loaded like any
+/// other asset with [`AssetServer::load`].
+///
+/// The file path is relative to the current file, as with [`include_bytes!`]. The asset path is
+/// the file path unless it is given before it:
+///
+/// ```ignore
+/// // loaded with `asset_server.load("embedded://icon.png")`
+/// embedded_asset!(app, "icon.png");
+/// // loaded with `asset_server.load("embedded://my_tool/shader.wgsl")`
+/// embedded_asset!(app, "my_tool/shader.wgsl", "../assets/shader.wgsl");
+/// ```
+#[macro_export]
+macro_rules! embedded_asset {
+    ($app: ident, $path_str: expr) => {{
+        $crate::embed_asset(&$app.world, $path_str, include_bytes!($path_str));
+    }};
+    ($app: ident, $asset_path: expr, $path_str: expr) => {{
+        $crate::embed_asset(&$app.world, $asset_path, include_bytes!($path_str));
+    }};
+}
+
 #[cfg(test)]
 mod tests {
     use bevy_app::App;
 
-    use crate::{AddAsset, Assets};
+    use crate::{AddAsset, AssetServer, Assets, EMBEDDED_ASSET_SOURCE};
+    use std::path::Path;
 
     #[test]
     fn asset_overwriting() {
@@ -400,4 +567,21 @@ mod tests {
         let assets_after = app.world.resource_mut::<Assets<MyAsset>>();
         assert!(assets_after.get(&handle).is_some());
     }
+
+    #[test]
+    fn embedded_asset() {
+        let mut app = App::new();
+        app.add_plugin(bevy_core::CorePlugin)
+            .add_plugin(crate::AssetPlugin);
+        embedded_asset!(app, "embedded/data.txt", "assets.rs");
+
+        let asset_server = app.world.resource::<AssetServer>();
+        let asset_io = asset_server
+            .get_source(Some(EMBEDDED_ASSET_SOURCE))
+            .unwrap();
+        let bytes =
+            futures_lite::future::block_on(asset_io.load_path(Path::new("embedded/data.txt")))
+                .unwrap();
+        assert_eq!(bytes, include_bytes!("assets.rs"));
+    }
 }
@@ -94,7 +94,7 @@ pub(crate) fn sync_debug_assets<T: Asset + Clone>(
     for changed in changed_shaders.iter_current_update_events() {
         let debug_handle = match changed {
             AssetEvent::Created { handle } | AssetEvent::Modified { handle } => handle,
-            AssetEvent::Removed { .. } => continue,
+            AssetEvent::Removed { .. } | AssetEvent::LoadedWithDependencies { .. } => continue,
         };
         if let Some(handle) = handle_map.handles.get(debug_handle) {
             if let Some(debug_asset) = debug_assets.get(debug_handle) {
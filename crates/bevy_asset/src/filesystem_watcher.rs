@@ -1,12 +1,15 @@
+use bevy_utils::HashSet;
 use crossbeam_channel::Receiver;
 use notify::{Event, RecommendedWatcher, RecursiveMode, Result, Watcher};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Watches for changes to assets on the filesystem. This is used by the `AssetServer` to reload
 /// them
 pub struct FilesystemWatcher {
     pub watcher: RecommendedWatcher,
     pub receiver: Receiver<Result<Event>>,
+    /// The paths given to [`FilesystemWatcher::watch_file`]
+    pub watched_files: HashSet<PathBuf>,
 }
 
 impl Default for FilesystemWatcher {
@@ -16,7 +19,11 @@ impl Default for FilesystemWatcher {
             sender.send(res).expect("Watch event send failure.");
         })
         .expect("Failed to create filesystem watcher.");
-        FilesystemWatcher { watcher, receiver }
+        FilesystemWatcher {
+            watcher,
+            receiver,
+            watched_files: HashSet::default(),
+        }
     }
 }
 
@@ -24,4 +31,20 @@ impl FilesystemWatcher {
     pub fn watch<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
         self.watcher.watch(path.as_ref(), RecursiveMode::Recursive)
     }
+
+    /// Watches a single file. A file that doesn't exist yet is watched through its directory,
+    /// so its creation is reported too.
+    ///
+    /// As the events of the other files of that directory are reported as well, the paths of
+    /// events should be checked against [`watched_files`](Self::watched_files).
+    pub fn watch_file<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        if path.exists() {
+            self.watcher.watch(path, RecursiveMode::NonRecursive)?;
+        } else if let Some(parent) = path.parent() {
+            self.watcher.watch(parent, RecursiveMode::NonRecursive)?;
+        }
+        self.watched_files.insert(path.to_owned());
+        Ok(())
+    }
 }
@@ -7,10 +7,12 @@ use std::{
 
 use crate::{
     path::{AssetPath, AssetPathId},
-    Asset, Assets,
+    Asset, Assets, ReflectHandle,
 };
 use bevy_ecs::{component::Component, reflect::ReflectComponent};
 use bevy_reflect::{FromReflect, Reflect, ReflectDeserialize, ReflectSerialize};
+#[cfg(feature = "json_schema")]
+use bevy_reflect::{ReflectValueSchema, ValueSchema};
 use bevy_utils::Uuid;
 use crossbeam_channel::{Receiver, Sender};
 use serde::{Deserialize, Serialize};
@@ -30,12 +32,58 @@ use serde::{Deserialize, Serialize};
     Reflect,
     FromReflect,
 )]
-#[reflect_value(Serialize, Deserialize, PartialEq, Hash)]
+#[cfg_attr(
+    feature = "json_schema",
+    reflect_value(Serialize, Deserialize, PartialEq, Hash, ValueSchema)
+)]
+#[cfg_attr(
+    not(feature = "json_schema"),
+    reflect_value(Serialize, Deserialize, PartialEq, Hash)
+)]
 pub enum HandleId {
     Id(Uuid, u64),
     AssetPathId(AssetPathId),
 }
 
+#[cfg(feature = "json_schema")]
+impl ValueSchema for HandleId {
+    fn value_schema() -> serde_json::Value {
+        let id = serde_json::json!({ "type": "integer", "minimum": 0, "maximum": u64::MAX });
+        serde_json::json!({
+            "oneOf": [
+                {
+                    "type": "object",
+                    "properties": {
+                        "Id": {
+                            "type": "array",
+                            "prefixItems": [{ "type": "string", "format": "uuid" }, id],
+                            "items": false,
+                            "minItems": 2,
+                            "maxItems": 2,
+                        },
+                    },
+                    "required": ["Id"],
+                    "additionalProperties": false,
+                },
+                {
+                    "type": "object",
+                    "properties": {
+                        "AssetPathId": {
+                            "type": "array",
+                            "prefixItems": [id, id],
+                            "items": false,
+                            "minItems": 2,
+                            "maxItems": 2,
+                        },
+                    },
+                    "required": ["AssetPathId"],
+                    "additionalProperties": false,
+                },
+            ],
+        })
+    }
+}
+
 impl From<AssetPathId> for HandleId {
     fn from(value: AssetPathId) -> Self {
         HandleId::AssetPathId(value)
@@ -95,7 +143,7 @@ impl HandleId {
 /// collisions no longer being detected for that entity.
 ///
 #[derive(Component, Reflect, FromReflect)]
-#[reflect(Component)]
+#[reflect(Component, Handle)]
 pub struct Handle<T>
 where
     T: Asset,
@@ -36,6 +36,16 @@ impl SourceInfo {
     pub fn get_asset_type(&self, label_id: LabelId) -> Option<Uuid> {
         self.asset_types.get(&label_id).cloned()
     }
+
+    /// Returns the dependencies of the asset with the given label, once the source has loaded.
+    pub fn get_dependencies(&self, label_id: LabelId) -> Option<&[AssetPath<'static>]> {
+        self.meta
+            .as_ref()?
+            .assets
+            .iter()
+            .find(|asset| LabelId::from(asset.label.as_deref()) == label_id)
+            .map(|asset| asset.dependencies.as_slice())
+    }
 }
 
 /// The load state of an asset
@@ -53,3 +63,16 @@ pub enum LoadState {
     /// the asset was removed from the [`Assets`](crate::Assets) collection.
     Unloaded,
 }
+
+/// The load state of an asset and of all of its dependencies, recursively
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub enum RecursiveDependencyLoadState {
+    /// The asset, or one of its dependencies, has not been loaded.
+    NotLoaded,
+    /// The asset, or one of its dependencies, is still loading.
+    Loading,
+    /// The asset and all of its dependencies have loaded.
+    Loaded,
+    /// The asset, or one of its dependencies, failed to load.
+    Failed,
+}
@@ -1,13 +1,11 @@
 #[cfg(feature = "filesystem_watcher")]
-use crate::{filesystem_watcher::FilesystemWatcher, AssetServer};
+use crate::{filesystem_watcher::FilesystemWatcher, AssetPath, AssetServer};
 use crate::{AssetIo, AssetIoError, Metadata};
 use anyhow::Result;
 #[cfg(feature = "filesystem_watcher")]
 use bevy_ecs::system::Res;
 use bevy_utils::BoxedFuture;
 #[cfg(feature = "filesystem_watcher")]
-use bevy_utils::HashSet;
-#[cfg(feature = "filesystem_watcher")]
 use crossbeam_channel::TryRecvError;
 use fs::File;
 #[cfg(feature = "filesystem_watcher")]
@@ -103,6 +101,21 @@ impl AssetIo for FileAssetIo {
         )))
     }
 
+    fn save_path<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move {
+            let full_path = self.root_path.join(path);
+            if let Some(parent) = full_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(full_path, bytes)?;
+            Ok(())
+        })
+    }
+
     fn watch_path_for_changes(&self, _path: &Path) -> Result<(), AssetIoError> {
         #[cfg(feature = "filesystem_watcher")]
         {
@@ -110,7 +123,7 @@ impl AssetIo for FileAssetIo {
             let mut watcher = self.filesystem_watcher.write();
             if let Some(ref mut watcher) = *watcher {
                 watcher
-                    .watch(&path)
+                    .watch_file(&path)
                     .map_err(|_error| AssetIoError::PathWatchError(path))?;
             }
         }
@@ -149,35 +162,133 @@ impl AssetIo for FileAssetIo {
     all(not(target_arch = "wasm32"), not(target_os = "android"))
 ))]
 pub fn filesystem_watcher_system(asset_server: Res<AssetServer>) {
-    let mut changed = HashSet::default();
-    let asset_io =
-        if let Some(asset_io) = asset_server.server.asset_io.downcast_ref::<FileAssetIo>() {
-            asset_io
+    if let Some(asset_io) = asset_server.server.asset_io.downcast_ref::<FileAssetIo>() {
+        reload_changed_assets(&asset_server, None, asset_io);
+    }
+    for source in asset_server.source_names() {
+        let asset_io = match asset_server.get_source(Some(&source)) {
+            Ok(asset_io) => asset_io,
+            Err(_) => continue,
+        };
+        if let Some(asset_io) = asset_io.downcast_ref::<FileAssetIo>() {
+            reload_changed_assets(&asset_server, Some(&source), asset_io);
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "filesystem_watcher",
+    all(not(target_arch = "wasm32"), not(target_os = "android"))
+))]
+fn reload_changed_assets(asset_server: &AssetServer, source: Option<&str>, asset_io: &FileAssetIo) {
+    for path in asset_io.changed_paths() {
+        let relative_path = path.strip_prefix(&asset_io.root_path).unwrap();
+        if is_saved_file(asset_server, source, relative_path, &path) {
+            continue;
+        }
+        // a changed `.meta` file reloads the asset it holds the settings of
+        let relative_path = if relative_path.extension() == Some(crate::META_EXTENSION.as_ref()) {
+            relative_path.with_extension("")
         } else {
-            return;
+            relative_path.to_owned()
         };
-    let watcher = asset_io.filesystem_watcher.read();
-    if let Some(ref watcher) = *watcher {
-        loop {
-            let event = match watcher.receiver.try_recv() {
-                Ok(result) => result.unwrap(),
-                Err(TryRecvError::Empty) => break,
-                Err(TryRecvError::Disconnected) => panic!("FilesystemWatcher disconnected."),
-            };
-            if let notify::event::Event {
-                kind: notify::event::EventKind::Modify(_),
-                paths,
-                ..
-            } = event
-            {
-                for path in &paths {
-                    if !changed.contains(path) {
-                        let relative_path = path.strip_prefix(&asset_io.root_path).unwrap();
-                        let _ = asset_server.load_untracked(relative_path.into(), true);
+        let asset_path = AssetPath::from(relative_path.as_path()).with_source_ref(source);
+        asset_server.reload_changed_path(asset_path);
+    }
+}
+
+#[cfg(feature = "filesystem_watcher")]
+impl FileAssetIo {
+    /// Returns the full paths of the watched files that were created or modified since the last
+    /// call.
+    fn changed_paths(&self) -> Vec<PathBuf> {
+        let mut changed = Vec::new();
+        let watcher = self.filesystem_watcher.read();
+        if let Some(ref watcher) = *watcher {
+            loop {
+                let event = match watcher.receiver.try_recv() {
+                    Ok(result) => result.unwrap(),
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => panic!("FilesystemWatcher disconnected."),
+                };
+                if let notify::event::Event {
+                    kind: notify::event::EventKind::Modify(_) | notify::event::EventKind::Create(_),
+                    paths,
+                    ..
+                } = event
+                {
+                    for path in paths {
+                        // files that don't exist yet are watched through their directory, which
+                        // also reports the events of the other files it contains
+                        if watcher.watched_files.contains(&path) && !changed.contains(&path) {
+                            changed.push(path);
+                        }
                     }
                 }
-                changed.extend(paths);
             }
         }
+        changed
+    }
+}
+
+/// Returns whether the file at `full_path` still holds the bytes written by
+/// [`AssetServer::save`], in which case it doesn't need to be reloaded.
+#[cfg(all(
+    feature = "filesystem_watcher",
+    all(not(target_arch = "wasm32"), not(target_os = "android"))
+))]
+fn is_saved_file(
+    asset_server: &AssetServer,
+    source: Option<&str>,
+    relative_path: &Path,
+    full_path: &Path,
+) -> bool {
+    use std::hash::{Hash, Hasher};
+
+    let key = (source.map(String::from), relative_path.to_owned());
+    let saved_hash = match asset_server.server.saved_hashes.read().get(&key) {
+        Some(saved_hash) => *saved_hash,
+        None => return false,
+    };
+    let unchanged = fs::read(full_path).map_or(false, |bytes| {
+        let mut hasher = crate::path::get_hasher();
+        bytes.hash(&mut hasher);
+        hasher.finish() == saved_hash
+    });
+    if !unchanged {
+        asset_server.server.saved_hashes.write().remove(&key);
+    }
+    unchanged
+}
+
+#[cfg(all(
+    test,
+    feature = "filesystem_watcher",
+    all(not(target_arch = "wasm32"), not(target_os = "android"))
+))]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn watches_missing_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let asset_io = FileAssetIo::new(dir.path(), true);
+        std::fs::write(dir.path().join("a.txt"), "a").unwrap();
+        asset_io
+            .watch_path_for_changes(Path::new("a.txt.meta"))
+            .unwrap();
+
+        std::fs::write(dir.path().join("b.txt"), "b").unwrap();
+        std::fs::write(dir.path().join("a.txt.meta"), "()").unwrap();
+
+        let mut changed = Vec::new();
+        let start = Instant::now();
+        while changed.is_empty() && start.elapsed() < Duration::from_secs(10) {
+            std::thread::sleep(Duration::from_millis(10));
+            changed.extend(asset_io.changed_paths());
+        }
+        // events of the other files of the directory are ignored
+        assert_eq!(changed, vec![dir.path().join("a.txt.meta")]);
     }
 }
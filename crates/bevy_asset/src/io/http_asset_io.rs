@@ -0,0 +1,595 @@
+use crate::{AssetIo, AssetIoError, AssetPath, AssetServer, FileType, Metadata};
+use anyhow::Result;
+use bevy_ecs::system::Res;
+use bevy_tasks::IoTaskPool;
+use bevy_utils::{BoxedFuture, HashMap, HashSet, Instant};
+use crossbeam_channel::{Receiver, Sender};
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    io::{self, Read},
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+/// The extension of the files next to cached assets holding their HTTP cache validators.
+const HTTP_CACHE_RECORD_EXTENSION: &str = "http";
+
+/// The HTTP cache validators of an asset, sent back to the server to only download the asset
+/// again if it changed.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct HttpCacheRecord {
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+impl HttpCacheRecord {
+    fn from_response(response: &ureq::Response) -> Self {
+        Self {
+            etag: response.header("ETag").map(String::from),
+            last_modified: response.header("Last-Modified").map(String::from),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
+
+    fn add_conditions(&self, mut request: ureq::Request) -> ureq::Request {
+        if let Some(etag) = &self.etag {
+            request = request.set("If-None-Match", etag);
+        }
+        if let Some(last_modified) = &self.last_modified {
+            request = request.set("If-Modified-Since", last_modified);
+        }
+        request
+    }
+}
+
+struct HttpAssetIoInner {
+    base_url: String,
+    agent: ureq::Agent,
+    cache_folder: Option<PathBuf>,
+    /// The validators of the assets loaded so far, compared with the server's when polling.
+    records: RwLock<HashMap<PathBuf, HttpCacheRecord>>,
+    watched_paths: RwLock<Option<HashSet<PathBuf>>>,
+    changed_sender: Sender<PathBuf>,
+}
+
+/// An [`AssetIo`] fetching assets from an HTTP server.
+///
+/// The asset at `path/to/asset.png` is fetched from `{base_url}/path/to/asset.png`. `https` URLs
+/// require the `http_asset_io_tls` feature.
+///
+/// With a cache folder, downloaded assets are stored on disk and only downloaded again if the
+/// server reports they changed, using their `ETag` and `Last-Modified` headers. The cached copy is
+/// used instead when the server can't be reached or answers with a server error (a `5xx` status).
+/// When the server answers that the asset doesn't exist (`404`), loading fails with
+/// [`AssetIoError::NotFound`] and the cached copy is removed.
+///
+/// [`AssetIo::get_metadata`] never sends a request, since it is called on the caller's thread:
+/// only the assets loaded or cached so far are known to be files.
+///
+/// When watching for changes, the watched assets are polled with `HEAD` requests on the
+/// [`IoTaskPool`] and reloaded when their validators change.
+///
+/// Requests fail when the server can't be connected to within 10 seconds, or stops sending the
+/// response for 30 seconds.
+///
+/// HTTP has no directory listing, so [`AssetServer::load_folder`] is not supported.
+pub struct HttpAssetIo {
+    inner: Arc<HttpAssetIoInner>,
+    poll_interval: Duration,
+    last_poll: Mutex<Option<Instant>>,
+    changed_receiver: Receiver<PathBuf>,
+}
+
+impl HttpAssetIo {
+    /// The default interval between two polls of the watched assets.
+    pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+    pub fn new(base_url: impl Into<String>) -> Self {
+        let base_url = base_url.into().trim_end_matches('/').to_string();
+        #[cfg(not(feature = "http_asset_io_tls"))]
+        if base_url.starts_with("https://") {
+            bevy_log::warn!(
+                "Loading assets from {} requires the `http_asset_io_tls` feature",
+                base_url
+            );
+        }
+        let (changed_sender, changed_receiver) = crossbeam_channel::unbounded();
+        Self {
+            inner: Arc::new(HttpAssetIoInner {
+                base_url,
+                agent: ureq::AgentBuilder::new()
+                    .timeout_connect(Duration::from_secs(10))
+                    .timeout_read(Duration::from_secs(30))
+                    .build(),
+                cache_folder: None,
+                records: Default::default(),
+                watched_paths: Default::default(),
+                changed_sender,
+            }),
+            poll_interval: Self::DEFAULT_POLL_INTERVAL,
+            last_poll: Default::default(),
+            changed_receiver,
+        }
+    }
+
+    /// Caches downloaded assets in `cache_folder`, to avoid downloading unchanged assets again
+    /// and to fall back to them when offline.
+    #[must_use]
+    pub fn with_cache_folder(mut self, cache_folder: impl Into<PathBuf>) -> Self {
+        self.inner_mut().cache_folder = Some(cache_folder.into());
+        self
+    }
+
+    /// Sets the interval between two polls of the watched assets, see
+    /// [`DEFAULT_POLL_INTERVAL`](Self::DEFAULT_POLL_INTERVAL).
+    #[must_use]
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    fn inner_mut(&mut self) -> &mut HttpAssetIoInner {
+        Arc::get_mut(&mut self.inner).expect("`HttpAssetIo` should not be shared while built.")
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.inner.base_url
+    }
+
+    /// Polls the watched assets on the [`IoTaskPool`] if the poll interval has elapsed since the
+    /// last poll, and returns the paths of the assets found changed since the last call.
+    pub fn poll_changes(&self) -> Vec<PathBuf> {
+        if self.inner.watched_paths.read().is_some() {
+            let mut last_poll = self.last_poll.lock();
+            if last_poll.map_or(true, |last_poll| last_poll.elapsed() >= self.poll_interval) {
+                *last_poll = Some(Instant::now());
+                let inner = self.inner.clone();
+                IoTaskPool::get()
+                    .spawn(async move { inner.poll_watched_paths() })
+                    .detach();
+            }
+        }
+        self.changed_receiver.try_iter().collect()
+    }
+}
+
+impl HttpAssetIoInner {
+    fn get_url(&self, path: &Path) -> String {
+        let mut url = self.base_url.clone();
+        for component in path.components() {
+            if let Component::Normal(segment) = component {
+                url.push('/');
+                encode_url_segment(&segment.to_string_lossy(), &mut url);
+            }
+        }
+        url
+    }
+
+    /// Returns the path of the cached copy of the asset at `path`.
+    ///
+    /// Only the normal components of `path` are kept, as in the URL, so that absolute paths and
+    /// `..` can't reach files outside of the cache folder.
+    fn get_cache_path(&self, path: &Path) -> Option<PathBuf> {
+        let folder = self.cache_folder.as_ref()?;
+        let relative_path: PathBuf = path
+            .components()
+            .filter(|component| matches!(component, Component::Normal(_)))
+            .collect();
+        if relative_path.as_os_str().is_empty() {
+            return None;
+        }
+        Some(folder.join(relative_path))
+    }
+
+    fn read_cache(&self, path: &Path) -> Option<(Vec<u8>, HttpCacheRecord)> {
+        let cache_path = self.get_cache_path(path)?;
+        let bytes = fs::read(&cache_path).ok()?;
+        let record = fs::read(get_cache_record_path(&cache_path))
+            .ok()
+            .and_then(|record| ron::de::from_bytes(&record).ok())
+            .unwrap_or_default();
+        Some((bytes, record))
+    }
+
+    fn write_cache(&self, path: &Path, bytes: &[u8], record: &HttpCacheRecord) -> io::Result<()> {
+        let cache_path = match self.get_cache_path(path) {
+            Some(cache_path) => cache_path,
+            None => return Ok(()),
+        };
+        if let Some(parent) = cache_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&cache_path, bytes)?;
+        let record = ron::ser::to_string(record).map_err(|err| io_error(err.to_string()))?;
+        fs::write(get_cache_record_path(&cache_path), record)
+    }
+
+    fn fetch(&self, path: &Path) -> Result<Vec<u8>, AssetIoError> {
+        let cached = self.read_cache(path);
+        let mut request = self.agent.get(&self.get_url(path));
+        if let Some((_, record)) = &cached {
+            request = record.add_conditions(request);
+        }
+
+        match request.call() {
+            Ok(response) if response.status() == 304 => {
+                let (bytes, record) =
+                    cached.expect("Conditional requests are only sent when cached.");
+                self.records.write().insert(path.to_owned(), record);
+                Ok(bytes)
+            }
+            Ok(response) => {
+                let record = HttpCacheRecord::from_response(&response);
+                let mut bytes = Vec::new();
+                response.into_reader().read_to_end(&mut bytes)?;
+                if let Err(err) = self.write_cache(path, &bytes, &record) {
+                    bevy_log::warn!("Failed to cache asset {}: {}", path.display(), err);
+                }
+                self.records.write().insert(path.to_owned(), record);
+                Ok(bytes)
+            }
+            Err(ureq::Error::Status(404, _)) => {
+                self.records.write().remove(path);
+                if cached.is_some() {
+                    self.remove_cache(path);
+                }
+                Err(AssetIoError::NotFound(path.to_owned()))
+            }
+            // only server errors fall back to the cached copy, other statuses are final
+            Err(ureq::Error::Status(status, response)) => {
+                let err = format!(
+                    "unexpected HTTP status {} {} for {}",
+                    status,
+                    response.status_text(),
+                    response.get_url()
+                );
+                match cached {
+                    Some((bytes, _)) if status >= 500 => Ok(self.use_cache(path, bytes, &err)),
+                    _ => Err(AssetIoError::Io(io_error(err))),
+                }
+            }
+            Err(ureq::Error::Transport(err)) => match cached {
+                Some((bytes, _)) => Ok(self.use_cache(path, bytes, &err.to_string())),
+                None => Err(AssetIoError::Io(io_error(err.to_string()))),
+            },
+        }
+    }
+
+    fn use_cache(&self, path: &Path, bytes: Vec<u8>, err: &str) -> Vec<u8> {
+        bevy_log::warn!(
+            "Failed to fetch {} from the asset server, using the cached copy: {}",
+            path.display(),
+            err
+        );
+        bytes
+    }
+
+    fn remove_cache(&self, path: &Path) {
+        if let Some(cache_path) = self.get_cache_path(path) {
+            let _ = fs::remove_file(get_cache_record_path(&cache_path));
+            let _ = fs::remove_file(cache_path);
+        }
+    }
+
+    /// Sends a `HEAD` request for each watched asset, and reports the ones whose validators
+    /// changed since they were loaded.
+    fn poll_watched_paths(&self) {
+        let watched_paths: Vec<_> = match &*self.watched_paths.read() {
+            Some(watched_paths) => watched_paths.iter().cloned().collect(),
+            None => return,
+        };
+        for path in watched_paths {
+            let response = match self.agent.head(&self.get_url(&path)).call() {
+                Ok(response) => response,
+                // the asset server is offline or the asset was removed, keep the loaded version
+                Err(_) => continue,
+            };
+            let record = HttpCacheRecord::from_response(&response);
+            if record.is_empty() {
+                continue;
+            }
+            let changed = match self.records.read().get(&path) {
+                Some(loaded) => *loaded != record,
+                // a watched file that didn't exist when it was loaded, like a `.meta` file, was
+                // created since
+                None => true,
+            };
+            if changed {
+                // the asset is fetched again when reloaded, which updates its record
+                self.records.write().insert(path.clone(), record);
+                let _ = self.changed_sender.send(path);
+            }
+        }
+    }
+}
+
+impl AssetIo for HttpAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move { self.inner.fetch(path) })
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        Err(AssetIoError::NotFound(path.to_owned()))
+    }
+
+    fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+        let is_cached = || {
+            self.inner
+                .get_cache_path(path)
+                .map_or(false, |cache_path| cache_path.is_file())
+        };
+        if self.inner.records.read().contains_key(path) || is_cached() {
+            Ok(Metadata::new(FileType::File))
+        } else {
+            Err(AssetIoError::NotFound(path.to_owned()))
+        }
+    }
+
+    fn watch_path_for_changes(&self, path: &Path) -> Result<(), AssetIoError> {
+        if let Some(watched_paths) = &mut *self.inner.watched_paths.write() {
+            watched_paths.insert(path.to_owned());
+        }
+        Ok(())
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        self.inner
+            .watched_paths
+            .write()
+            .get_or_insert_with(Default::default);
+        Ok(())
+    }
+}
+
+/// Reloads the assets of the [`HttpAssetIo`] sources of the [`AssetServer`] that changed on the
+/// HTTP server.
+pub fn http_asset_io_polling_system(asset_server: Res<AssetServer>) {
+    let sources = std::iter::once(None).chain(asset_server.source_names().into_iter().map(Some));
+    for source in sources {
+        let asset_io = match asset_server.get_source(source.as_deref()) {
+            Ok(asset_io) => asset_io,
+            Err(_) => continue,
+        };
+        if let Some(asset_io) = asset_io.downcast_ref::<HttpAssetIo>() {
+            for path in asset_io.poll_changes() {
+                let asset_path = AssetPath::from(path.as_path()).with_source_ref(source.as_deref());
+                asset_server.reload_changed_path(asset_path);
+            }
+        }
+    }
+}
+
+fn get_cache_record_path(cache_path: &Path) -> PathBuf {
+    let mut record_path = cache_path.as_os_str().to_owned();
+    record_path.push(".");
+    record_path.push(HTTP_CACHE_RECORD_EXTENSION);
+    record_path.into()
+}
+
+/// Percent-encodes the characters of `segment` that are not allowed in a URL path segment.
+fn encode_url_segment(segment: &str, url: &mut String) {
+    for byte in segment.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~!$&'()*+,;=:@".contains(&byte) {
+            url.push(byte as char);
+        } else {
+            url.push_str(&format!("%{:02X}", byte));
+        }
+    }
+}
+
+fn io_error(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    /// The `(body, etag)` pairs served by a [`TestServer`], by path.
+    type TestFiles = Arc<Mutex<HashMap<String, (Vec<u8>, String)>>>;
+
+    /// A minimal HTTP server serving `(body, etag)` pairs, answering conditional requests.
+    struct TestServer {
+        url: String,
+        files: TestFiles,
+        /// The status answered for every request instead of the files, if any.
+        status: Arc<Mutex<Option<&'static str>>>,
+        not_modified_count: Arc<AtomicUsize>,
+    }
+
+    impl TestServer {
+        fn start() -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let url = format!("http://{}/assets", listener.local_addr().unwrap());
+            let files: TestFiles = Default::default();
+            let status: Arc<Mutex<Option<&'static str>>> = Default::default();
+            let not_modified_count = Arc::new(AtomicUsize::new(0));
+            let (server_files, server_status, server_not_modified_count) =
+                (files.clone(), status.clone(), not_modified_count.clone());
+            std::thread::spawn(move || {
+                for stream in listener.incoming() {
+                    let mut stream = stream.unwrap();
+                    let mut reader = BufReader::new(stream.try_clone().unwrap());
+                    let mut request_line = String::new();
+                    reader.read_line(&mut request_line).unwrap();
+                    let mut if_none_match = None;
+                    loop {
+                        let mut header = String::new();
+                        reader.read_line(&mut header).unwrap();
+                        if header.trim().is_empty() {
+                            break;
+                        }
+                        if let Some((name, value)) = header.split_once(':') {
+                            if name.eq_ignore_ascii_case("if-none-match") {
+                                if_none_match = Some(value.trim().to_string());
+                            }
+                        }
+                    }
+
+                    let mut parts = request_line.split_whitespace();
+                    let method = parts.next().unwrap().to_string();
+                    let path = parts.next().unwrap().trim_start_matches("/assets/");
+                    let files = server_files.lock();
+                    let (status, etag, body) = match files.get(path) {
+                        _ if server_status.lock().is_some() => {
+                            (server_status.lock().unwrap(), None, Vec::new())
+                        }
+                        Some((_, etag)) if if_none_match.as_ref() == Some(etag) => {
+                            server_not_modified_count.fetch_add(1, Ordering::SeqCst);
+                            ("304 Not Modified", Some(etag.clone()), Vec::new())
+                        }
+                        Some((body, etag)) => ("200 OK", Some(etag.clone()), body.clone()),
+                        None => ("404 Not Found", None, Vec::new()),
+                    };
+                    let mut response = format!(
+                        "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n",
+                        status,
+                        body.len()
+                    );
+                    if let Some(etag) = etag {
+                        response += &format!("ETag: {}\r\n", etag);
+                    }
+                    response += "\r\n";
+                    stream.write_all(response.as_bytes()).unwrap();
+                    if method != "HEAD" {
+                        stream.write_all(&body).unwrap();
+                    }
+                }
+            });
+            Self {
+                url,
+                files,
+                status,
+                not_modified_count,
+            }
+        }
+
+        fn set_file(&self, path: &str, body: &[u8], etag: &str) {
+            self.files
+                .lock()
+                .insert(path.to_string(), (body.to_vec(), format!("\"{}\"", etag)));
+        }
+    }
+
+    fn load(asset_io: &HttpAssetIo, path: &str) -> Result<Vec<u8>, AssetIoError> {
+        futures_lite::future::block_on(asset_io.load_path(Path::new(path)))
+    }
+
+    #[test]
+    fn http_asset_io() {
+        let server = TestServer::start();
+        server.set_file("textures/player.png", b"player", "1");
+        let cache = tempfile::tempdir().unwrap();
+        let asset_io = HttpAssetIo::new(&server.url).with_cache_folder(cache.path());
+
+        assert_eq!(load(&asset_io, "textures/player.png").unwrap(), b"player");
+        assert_eq!(
+            fs::read(cache.path().join("textures/player.png")).unwrap(),
+            b"player"
+        );
+        assert!(matches!(
+            load(&asset_io, "missing.png"),
+            Err(AssetIoError::NotFound(_))
+        ));
+        assert!(asset_io.is_file(Path::new("textures/player.png")));
+        assert!(!asset_io.is_file(Path::new("missing.png")));
+
+        // unchanged assets are not downloaded again
+        assert_eq!(load(&asset_io, "textures/player.png").unwrap(), b"player");
+        assert_eq!(server.not_modified_count.load(Ordering::SeqCst), 1);
+
+        // changed assets are reported when polling the watched assets
+        asset_io.watch_for_changes().unwrap();
+        asset_io
+            .watch_path_for_changes(Path::new("textures/player.png"))
+            .unwrap();
+        asset_io.inner.poll_watched_paths();
+        assert!(asset_io.changed_receiver.try_iter().next().is_none());
+        server.set_file("textures/player.png", b"player 2", "2");
+        asset_io.inner.poll_watched_paths();
+        assert_eq!(
+            asset_io.changed_receiver.try_iter().collect::<Vec<_>>(),
+            vec![PathBuf::from("textures/player.png")]
+        );
+        assert_eq!(load(&asset_io, "textures/player.png").unwrap(), b"player 2");
+
+        // when the server can't be reached, the cached copy is used
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let offline_url = format!("http://{}/assets", listener.local_addr().unwrap());
+        drop(listener);
+        let offline_asset_io = HttpAssetIo::new(offline_url).with_cache_folder(cache.path());
+        assert_eq!(
+            load(&offline_asset_io, "textures/player.png").unwrap(),
+            b"player 2"
+        );
+        assert!(offline_asset_io.is_file(Path::new("textures/player.png")));
+        assert!(matches!(
+            load(&offline_asset_io, "missing.png"),
+            Err(AssetIoError::Io(_))
+        ));
+
+        // server errors fall back to the cached copy, client errors don't
+        *server.status.lock() = Some("503 Service Unavailable");
+        assert_eq!(load(&asset_io, "textures/player.png").unwrap(), b"player 2");
+        *server.status.lock() = Some("403 Forbidden");
+        assert!(matches!(
+            load(&asset_io, "textures/player.png"),
+            Err(AssetIoError::Io(_))
+        ));
+        // a removed asset is removed from the cache as well
+        *server.status.lock() = None;
+        server.files.lock().clear();
+        assert!(matches!(
+            load(&asset_io, "textures/player.png"),
+            Err(AssetIoError::NotFound(_))
+        ));
+        assert!(!cache.path().join("textures/player.png").exists());
+        assert!(!offline_asset_io.is_file(Path::new("textures/player.png")));
+    }
+
+    #[test]
+    fn encode_url() {
+        let asset_io = HttpAssetIo::new("http://localhost/assets/");
+        assert_eq!(
+            asset_io.inner.get_url(Path::new("ui/my font#1.ttf")),
+            "http://localhost/assets/ui/my%20font%231.ttf"
+        );
+    }
+
+    #[test]
+    fn cache_paths_stay_in_the_cache_folder() {
+        let asset_io = HttpAssetIo::new("http://localhost/assets").with_cache_folder("cache");
+        let cache_path = |path: &str| asset_io.inner.get_cache_path(Path::new(path));
+        assert_eq!(
+            cache_path("textures/./player.png"),
+            Some(PathBuf::from("cache/textures/player.png"))
+        );
+        assert_eq!(
+            cache_path("../../outside.png"),
+            Some(PathBuf::from("cache/outside.png"))
+        );
+        assert_eq!(
+            cache_path("/etc/passwd"),
+            Some(PathBuf::from("cache/etc/passwd"))
+        );
+        assert_eq!(cache_path(".."), None);
+        assert_eq!(
+            asset_io.inner.get_url(Path::new("../../outside.png")),
+            "http://localhost/assets/outside.png"
+        );
+    }
+}
@@ -0,0 +1,168 @@
+use crate::{AssetIo, AssetIoError, AssetServer, FileType, Metadata};
+use anyhow::Result;
+use bevy_ecs::world::World;
+use bevy_utils::{BoxedFuture, HashMap, HashSet};
+use parking_lot::RwLock;
+use std::{
+    borrow::Cow,
+    path::{Path, PathBuf},
+};
+
+/// The name of the asset source holding the assets registered with
+/// [`embedded_asset!`](crate::embedded_asset).
+pub const EMBEDDED_ASSET_SOURCE: &str = "embedded";
+
+/// An [`AssetIo`] serving assets from memory.
+///
+/// Directories don't need to be created: a path is a directory if some asset is stored below it.
+#[derive(Default)]
+pub struct MemoryAssetIo {
+    assets: RwLock<HashMap<PathBuf, Cow<'static, [u8]>>>,
+}
+
+impl MemoryAssetIo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stores `bytes` as the asset at `path`, replacing any asset at the same path.
+    pub fn insert(&self, path: impl Into<PathBuf>, bytes: impl Into<Cow<'static, [u8]>>) {
+        self.assets.write().insert(path.into(), bytes.into());
+    }
+
+    /// Removes the asset at `path`, returning whether it existed.
+    pub fn remove(&self, path: &Path) -> bool {
+        self.assets.write().remove(path).is_some()
+    }
+
+    pub fn contains(&self, path: &Path) -> bool {
+        self.assets.read().contains_key(path)
+    }
+}
+
+impl AssetIo for MemoryAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move {
+            self.assets
+                .read()
+                .get(path)
+                .map(|bytes| bytes.to_vec())
+                .ok_or_else(|| AssetIoError::NotFound(path.to_owned()))
+        })
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        let mut children = HashSet::default();
+        for asset_path in self.assets.read().keys() {
+            if let Some(child) = asset_path
+                .strip_prefix(path)
+                .ok()
+                .and_then(|relative_path| relative_path.iter().next())
+            {
+                children.insert(path.join(child));
+            }
+        }
+        if children.is_empty() {
+            return Err(AssetIoError::NotFound(path.to_owned()));
+        }
+        Ok(Box::new(children.into_iter()))
+    }
+
+    fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+        let assets = self.assets.read();
+        if assets.contains_key(path) {
+            Ok(Metadata::new(FileType::File))
+        } else if assets.keys().any(|asset_path| asset_path.starts_with(path)) {
+            Ok(Metadata::new(FileType::Directory))
+        } else {
+            Err(AssetIoError::NotFound(path.to_owned()))
+        }
+    }
+
+    fn save_path<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move {
+            self.insert(path, bytes.to_vec());
+            Ok(())
+        })
+    }
+
+    fn watch_path_for_changes(&self, _path: &Path) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+}
+
+/// Stores `bytes` as the asset at `path` in the [`EMBEDDED_ASSET_SOURCE`] of the
+/// [`AssetServer`], adding that source if needed.
+///
+/// The asset can then be loaded from `"embedded://path"`. This is usually called through
+/// [`embedded_asset!`](crate::embedded_asset).
+pub fn embed_asset(world: &World, path: impl Into<PathBuf>, bytes: &'static [u8]) {
+    let asset_server = world.resource::<AssetServer>();
+    let asset_io = match asset_server.get_source(Some(EMBEDDED_ASSET_SOURCE)) {
+        Ok(asset_io) => asset_io,
+        Err(_) => {
+            asset_server.add_source(EMBEDDED_ASSET_SOURCE, MemoryAssetIo::default());
+            asset_server
+                .get_source(Some(EMBEDDED_ASSET_SOURCE))
+                .expect("The embedded asset source was just added.")
+        }
+    };
+    asset_io
+        .downcast_ref::<MemoryAssetIo>()
+        .expect("The embedded asset source should be a `MemoryAssetIo`.")
+        .insert(path, bytes);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memory_asset_io() {
+        let asset_io = MemoryAssetIo::new();
+        asset_io.insert("textures/player.png", &b"player"[..]);
+        asset_io.insert("textures/ui/button.png", b"button".to_vec());
+        asset_io.insert("fonts/mono.ttf", &b"mono"[..]);
+
+        let bytes =
+            futures_lite::future::block_on(asset_io.load_path(Path::new("textures/player.png")))
+                .unwrap();
+        assert_eq!(bytes, b"player");
+        assert!(matches!(
+            futures_lite::future::block_on(asset_io.load_path(Path::new("textures"))),
+            Err(AssetIoError::NotFound(_))
+        ));
+
+        assert!(asset_io.is_dir(Path::new("textures")));
+        assert!(asset_io.is_dir(Path::new("")));
+        assert!(asset_io.is_file(Path::new("textures/ui/button.png")));
+        assert!(!asset_io.is_file(Path::new("textures/ui")));
+
+        let mut children: Vec<_> = asset_io
+            .read_directory(Path::new("textures"))
+            .unwrap()
+            .collect();
+        children.sort();
+        assert_eq!(
+            children,
+            vec![
+                PathBuf::from("textures/player.png"),
+                PathBuf::from("textures/ui")
+            ]
+        );
+
+        assert!(asset_io.remove(Path::new("fonts/mono.ttf")));
+        assert!(!asset_io.is_dir(Path::new("fonts")));
+    }
+}
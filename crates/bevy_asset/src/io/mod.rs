@@ -5,7 +5,12 @@ mod file_asset_io;
 #[cfg(target_arch = "wasm32")]
 mod wasm_asset_io;
 
+#[cfg(all(feature = "http_asset_io", not(target_arch = "wasm32")))]
+mod http_asset_io;
+mod memory_asset_io;
 mod metadata;
+#[cfg(feature = "asset_pack")]
+mod pack_asset_io;
 
 #[cfg(target_os = "android")]
 pub use android_asset_io::*;
@@ -14,7 +19,12 @@ pub use file_asset_io::*;
 #[cfg(target_arch = "wasm32")]
 pub use wasm_asset_io::*;
 
+#[cfg(all(feature = "http_asset_io", not(target_arch = "wasm32")))]
+pub use http_asset_io::*;
+pub use memory_asset_io::*;
 pub use metadata::*;
+#[cfg(feature = "asset_pack")]
+pub use pack_asset_io::*;
 
 use anyhow::Result;
 use bevy_utils::BoxedFuture;
@@ -34,6 +44,12 @@ pub enum AssetIoError {
     Io(#[from] io::Error),
     #[error("failed to watch path: {0}")]
     PathWatchError(PathBuf),
+    #[error("asset source is read-only, can't write: {0}")]
+    ReadOnly(PathBuf),
+    #[error("asset content doesn't match its hash, it may be corrupted or tampered with: {0}")]
+    HashMismatch(PathBuf),
+    #[error("invalid asset pack: {0}")]
+    InvalidAssetPack(String),
 }
 
 /// Handles load requests from an `AssetServer`
@@ -47,6 +63,16 @@ pub trait AssetIo: Downcast + Send + Sync + 'static {
     fn watch_path_for_changes(&self, path: &Path) -> Result<(), AssetIoError>;
     fn watch_for_changes(&self) -> Result<(), AssetIoError>;
 
+    /// Writes `bytes` to the file at `path`, creating the file and its parent directories if
+    /// needed. Asset sources are read-only unless they implement this.
+    fn save_path<'a>(
+        &'a self,
+        path: &'a Path,
+        _bytes: &'a [u8],
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move { Err(AssetIoError::ReadOnly(path.to_owned())) })
+    }
+
     fn is_dir(&self, path: &Path) -> bool {
         self.get_metadata(path)
             .as_ref()
@@ -0,0 +1,612 @@
+use crate::{stable_hash::hash_bytes, AssetIo, AssetIoError, FileType, Metadata};
+use anyhow::Result;
+use bevy_utils::{BoxedFuture, HashMap, HashSet};
+use flate2::{read::DeflateDecoder, write::DeflateEncoder};
+use parking_lot::Mutex;
+use std::{
+    fs::{self, File},
+    io::{self, Read, Seek, SeekFrom, Write},
+    path::{Component, Path, PathBuf},
+};
+
+/// The bytes every asset pack starts with.
+const PACK_MAGIC: &[u8; 8] = b"BEVYPACK";
+/// The version of the asset pack format, bumped on incompatible changes.
+const PACK_FORMAT_VERSION: u32 = 2;
+/// The size of the header: the magic bytes, the version, the entry count and the index size.
+const HEADER_SIZE: u64 = (PACK_MAGIC.len() + 4 + 4 + 8) as u64;
+/// The size of an index entry with an empty path.
+const MIN_ENTRY_SIZE: u64 = 4 + 8 + 8 + 8 + 1 + 8;
+
+/// How an entry of an asset pack is compressed
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum PackCompression {
+    /// The entry is stored as is.
+    None,
+    /// The entry is compressed with deflate.
+    Deflate,
+}
+
+impl PackCompression {
+    fn to_byte(self) -> u8 {
+        match self {
+            PackCompression::None => 0,
+            PackCompression::Deflate => 1,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(PackCompression::None),
+            1 => Some(PackCompression::Deflate),
+            _ => None,
+        }
+    }
+}
+
+/// An entry of the index of an asset pack
+#[derive(Clone, Debug)]
+pub struct PackEntry {
+    /// The offset of the stored bytes, from the start of the data section.
+    pub offset: u64,
+    /// The length of the stored, possibly compressed, bytes.
+    pub stored_size: u64,
+    /// The length of the asset once decompressed.
+    pub size: u64,
+    pub compression: PackCompression,
+    /// The XXH64 hash, with a seed of `0`, of the decompressed asset, checked when it is loaded.
+    pub hash: u64,
+}
+
+enum PackStorage {
+    File(Mutex<File>),
+    Memory(Vec<u8>),
+}
+
+/// An [`AssetIo`] loading assets from a single asset pack file, built with an
+/// [`AssetPackBuilder`].
+///
+/// Directories are derived from the paths of the packed assets, so
+/// [`AssetServer::load_folder`](crate::AssetServer::load_folder) works the same as on disk. The
+/// content hash of each asset is checked when it is loaded, to detect corrupted or tampered packs,
+/// and the index is checked against the size of the pack when it is opened.
+pub struct PackAssetIo {
+    storage: PackStorage,
+    data_offset: u64,
+    entries: HashMap<PathBuf, PackEntry>,
+    directories: HashMap<PathBuf, HashSet<PathBuf>>,
+}
+
+impl PackAssetIo {
+    /// Opens the asset pack at `path`, reading its index. Assets are read from the file as they
+    /// are loaded.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, AssetIoError> {
+        let mut file = File::open(path)?;
+        let pack_size = file.metadata()?.len();
+        let (data_offset, index) = read_index(&mut file, pack_size)?;
+        Ok(Self::new(
+            PackStorage::File(Mutex::new(file)),
+            data_offset,
+            index,
+        ))
+    }
+
+    /// Reads an asset pack held in memory, for example one included in the binary.
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, AssetIoError> {
+        let (data_offset, index) = read_index(&mut io::Cursor::new(&bytes), bytes.len() as u64)?;
+        Ok(Self::new(PackStorage::Memory(bytes), data_offset, index))
+    }
+
+    fn new(storage: PackStorage, data_offset: u64, index: Vec<(PathBuf, PackEntry)>) -> Self {
+        let mut directories: HashMap<PathBuf, HashSet<PathBuf>> = HashMap::default();
+        directories.insert(PathBuf::new(), HashSet::default());
+        for (path, _) in &index {
+            // add the path to its parent, and the parents that weren't known yet to theirs
+            let mut child = path.as_path();
+            while let Some(parent) = child.parent() {
+                let is_new_directory = !directories.contains_key(parent);
+                directories
+                    .entry(parent.to_owned())
+                    .or_default()
+                    .insert(child.to_owned());
+                if !is_new_directory {
+                    break;
+                }
+                child = parent;
+            }
+        }
+        Self {
+            storage,
+            data_offset,
+            entries: index.into_iter().collect(),
+            directories,
+        }
+    }
+
+    /// Returns the index entry of the asset at `path`.
+    pub fn get_entry(&self, path: &Path) -> Option<&PackEntry> {
+        self.entries.get(&normalize_path(path))
+    }
+
+    /// Returns the paths of all the assets in the pack.
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.entries.keys().map(PathBuf::as_path)
+    }
+
+    fn read_entry(&self, path: &Path, entry: &PackEntry) -> Result<Vec<u8>, AssetIoError> {
+        // the range was checked against the size of the pack when it was opened
+        let start = self.data_offset + entry.offset;
+        let stored = match &self.storage {
+            PackStorage::File(file) => {
+                let mut file = file.lock();
+                file.seek(SeekFrom::Start(start))?;
+                let mut stored = Vec::new();
+                (&mut *file)
+                    .take(entry.stored_size)
+                    .read_to_end(&mut stored)?;
+                if stored.len() as u64 != entry.stored_size {
+                    return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+                }
+                stored
+            }
+            PackStorage::Memory(bytes) => bytes
+                .get(start as usize..(start + entry.stored_size) as usize)
+                .ok_or_else(|| invalid_pack(format!("{} is outside of the pack", path.display())))?
+                .to_vec(),
+        };
+
+        let bytes = match entry.compression {
+            PackCompression::None => stored,
+            PackCompression::Deflate => {
+                // reading one more byte than expected is enough to detect a size mismatch
+                let mut bytes = Vec::new();
+                DeflateDecoder::new(stored.as_slice())
+                    .take(entry.size.saturating_add(1))
+                    .read_to_end(&mut bytes)?;
+                bytes
+            }
+        };
+        if bytes.len() as u64 != entry.size || hash_bytes(&bytes) != entry.hash {
+            return Err(AssetIoError::HashMismatch(path.to_owned()));
+        }
+        Ok(bytes)
+    }
+}
+
+impl AssetIo for PackAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move {
+            let entry = self
+                .get_entry(path)
+                .ok_or_else(|| AssetIoError::NotFound(path.to_owned()))?;
+            self.read_entry(path, entry)
+        })
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        let children = self
+            .directories
+            .get(&normalize_path(path))
+            .ok_or_else(|| AssetIoError::NotFound(path.to_owned()))?;
+        Ok(Box::new(children.clone().into_iter()))
+    }
+
+    fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+        let path = normalize_path(path);
+        if self.entries.contains_key(&path) {
+            Ok(Metadata::new(FileType::File))
+        } else if self.directories.contains_key(&path) {
+            Ok(Metadata::new(FileType::Directory))
+        } else {
+            Err(AssetIoError::NotFound(path))
+        }
+    }
+
+    fn watch_path_for_changes(&self, _path: &Path) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+}
+
+/// Packs assets into a single file read by [`PackAssetIo`].
+///
+/// ```no_run
+/// # use bevy_asset::{AssetPackBuilder, PackCompression};
+/// let mut builder = AssetPackBuilder::new().with_compression(PackCompression::Deflate);
+/// builder.add_directory("assets").unwrap();
+/// builder.write_to_file("assets.pack").unwrap();
+/// ```
+pub struct AssetPackBuilder {
+    compression: PackCompression,
+    files: Vec<(PathBuf, Vec<u8>, PackCompression)>,
+}
+
+impl Default for AssetPackBuilder {
+    fn default() -> Self {
+        Self {
+            compression: PackCompression::Deflate,
+            files: Vec::new(),
+        }
+    }
+}
+
+impl AssetPackBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the compression of the files added after this, [`PackCompression::Deflate`] by
+    /// default.
+    #[must_use]
+    pub fn with_compression(mut self, compression: PackCompression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Adds the asset at `path` in the pack, replacing any asset at the same path.
+    pub fn add_file(&mut self, path: impl AsRef<Path>, bytes: Vec<u8>) -> &mut Self {
+        let compression = self.compression;
+        self.add_file_with_compression(path, bytes, compression)
+    }
+
+    /// Adds the asset at `path` in the pack with the given compression, for example to store
+    /// files that are already compressed as is.
+    pub fn add_file_with_compression(
+        &mut self,
+        path: impl AsRef<Path>,
+        bytes: Vec<u8>,
+        compression: PackCompression,
+    ) -> &mut Self {
+        let path = normalize_path(path.as_ref());
+        self.files.retain(|(existing, _, _)| *existing != path);
+        self.files.push((path, bytes, compression));
+        self
+    }
+
+    /// Adds all the files below `root`, recursively, with paths relative to `root`.
+    pub fn add_directory(&mut self, root: impl AsRef<Path>) -> io::Result<&mut Self> {
+        let root = root.as_ref();
+        let mut directories = vec![root.to_owned()];
+        while let Some(directory) = directories.pop() {
+            for entry in fs::read_dir(&directory)? {
+                let path = entry?.path();
+                if path.is_dir() {
+                    directories.push(path);
+                } else {
+                    let bytes = fs::read(&path)?;
+                    let relative_path = path.strip_prefix(root).unwrap().to_owned();
+                    self.add_file(relative_path, bytes);
+                }
+            }
+        }
+        Ok(self)
+    }
+
+    /// Writes the asset pack, the index followed by the data of every asset.
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let mut files: Vec<_> = self.files.iter().collect();
+        files.sort_by(|(a, _, _), (b, _, _)| a.cmp(b));
+
+        let mut index = Vec::new();
+        let mut data = Vec::new();
+        for (path, bytes, compression) in files {
+            let mut compression = *compression;
+            let stored = match compression {
+                PackCompression::None => bytes.clone(),
+                PackCompression::Deflate => {
+                    let mut encoder = DeflateEncoder::new(Vec::new(), Default::default());
+                    encoder.write_all(bytes)?;
+                    let compressed = encoder.finish()?;
+                    // don't bother decompressing files that don't get smaller
+                    if compressed.len() < bytes.len() {
+                        compressed
+                    } else {
+                        compression = PackCompression::None;
+                        bytes.clone()
+                    }
+                }
+            };
+
+            let path = path_to_pack_string(path)?;
+            write_u32(&mut index, path.len() as u32)?;
+            index.write_all(path.as_bytes())?;
+            write_u64(&mut index, data.len() as u64)?;
+            write_u64(&mut index, stored.len() as u64)?;
+            write_u64(&mut index, bytes.len() as u64)?;
+            index.write_all(&[compression.to_byte()])?;
+            write_u64(&mut index, hash_bytes(bytes))?;
+            data.extend(stored);
+        }
+
+        writer.write_all(PACK_MAGIC)?;
+        write_u32(&mut writer, PACK_FORMAT_VERSION)?;
+        write_u32(&mut writer, self.files.len() as u32)?;
+        write_u64(&mut writer, index.len() as u64)?;
+        writer.write_all(&index)?;
+        writer.write_all(&data)
+    }
+
+    pub fn write_to_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        self.write(io::BufWriter::new(File::create(path)?))
+    }
+}
+
+/// Reads the header and index of an asset pack of `pack_size` bytes, returning the offset of its
+/// data section.
+///
+/// Every size and offset is checked against `pack_size`, so that a corrupted pack can't cause
+/// huge allocations or reads past its end.
+fn read_index<R: Read>(
+    reader: &mut R,
+    pack_size: u64,
+) -> Result<(u64, Vec<(PathBuf, PackEntry)>), AssetIoError> {
+    let mut magic = [0; 8];
+    reader.read_exact(&mut magic)?;
+    if &magic != PACK_MAGIC {
+        return Err(invalid_pack("not an asset pack"));
+    }
+    let version = read_u32(reader)?;
+    if version != PACK_FORMAT_VERSION {
+        return Err(invalid_pack(format!(
+            "unsupported asset pack version {version}, expected {PACK_FORMAT_VERSION}"
+        )));
+    }
+    let entry_count = read_u32(reader)?;
+    let index_size = read_u64(reader)?;
+    let data_offset = HEADER_SIZE
+        .checked_add(index_size)
+        .filter(|data_offset| *data_offset <= pack_size)
+        .ok_or_else(|| invalid_pack("the index is larger than the pack"))?;
+    if (entry_count as u64).saturating_mul(MIN_ENTRY_SIZE) > index_size {
+        return Err(invalid_pack("the index is too small for its entries"));
+    }
+
+    let mut reader = reader.take(index_size);
+    let mut index = Vec::with_capacity(entry_count as usize);
+    for _ in 0..entry_count {
+        let path_size = read_u32(&mut reader)?;
+        if path_size as u64 > reader.limit() {
+            return Err(invalid_pack("an asset path is larger than the index"));
+        }
+        let mut path = vec![0; path_size as usize];
+        reader.read_exact(&mut path)?;
+        let path = String::from_utf8(path).map_err(|_| invalid_pack("invalid asset path"))?;
+        let offset = read_u64(&mut reader)?;
+        let stored_size = read_u64(&mut reader)?;
+        let size = read_u64(&mut reader)?;
+        let mut compression = [0];
+        reader.read_exact(&mut compression)?;
+        let compression = PackCompression::from_byte(compression[0])
+            .ok_or_else(|| invalid_pack("unknown compression"))?;
+        let hash = read_u64(&mut reader)?;
+        let end = data_offset
+            .checked_add(offset)
+            .and_then(|start| start.checked_add(stored_size));
+        if !matches!(end, Some(end) if end <= pack_size) {
+            return Err(invalid_pack(format!("{path} is outside of the pack")));
+        }
+        if compression == PackCompression::None && stored_size != size {
+            return Err(invalid_pack(format!("{path} has an invalid size")));
+        }
+        index.push((
+            path.split('/').collect(),
+            PackEntry {
+                offset,
+                stored_size,
+                size,
+                compression,
+                hash,
+            },
+        ));
+    }
+
+    Ok((data_offset, index))
+}
+
+/// Removes `.` components and redundant separators, so that paths match the index.
+fn normalize_path(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|component| !matches!(component, Component::CurDir))
+        .collect()
+}
+
+fn path_to_pack_string(path: &Path) -> io::Result<String> {
+    let components = path
+        .components()
+        .map(|component| match component {
+            Component::Normal(name) => name
+                .to_str()
+                .ok_or_else(|| invalid_data("asset paths must be valid UTF-8")),
+            _ => Err(invalid_data("asset paths must be relative")),
+        })
+        .collect::<io::Result<Vec<_>>>()?;
+    Ok(components.join("/"))
+}
+
+fn invalid_pack(reason: impl Into<String>) -> AssetIoError {
+    AssetIoError::InvalidAssetPack(reason.into())
+}
+
+fn invalid_data<E: Into<Box<dyn std::error::Error + Send + Sync>>>(error: E) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+fn write_u32<W: Write>(writer: &mut W, value: u32) -> io::Result<()> {
+    writer.write_all(&value.to_le_bytes())
+}
+
+fn write_u64<W: Write>(writer: &mut W, value: u64) -> io::Result<()> {
+    writer.write_all(&value.to_le_bytes())
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AssetServer;
+
+    fn block_on<T>(future: impl std::future::Future<Output = T>) -> T {
+        futures_lite::future::block_on(future)
+    }
+
+    #[test]
+    fn pack_asset_io() {
+        let dir = tempfile::tempdir().unwrap();
+        let assets = dir.path().join("assets");
+        fs::create_dir_all(assets.join("textures/ui")).unwrap();
+        fs::write(assets.join("textures/player.png"), b"player").unwrap();
+        fs::write(assets.join("textures/ui/button.png"), vec![7; 1024]).unwrap();
+        fs::write(assets.join("scene.scn"), b"scene").unwrap();
+
+        let pack_path = dir.path().join("assets.pack");
+        let mut builder = AssetPackBuilder::new();
+        builder.add_directory(&assets).unwrap();
+        builder.write_to_file(&pack_path).unwrap();
+
+        let asset_io = PackAssetIo::open(&pack_path).unwrap();
+        assert_eq!(
+            block_on(asset_io.load_path(Path::new("textures/ui/button.png"))).unwrap(),
+            vec![7; 1024]
+        );
+        assert_eq!(
+            block_on(asset_io.load_path(Path::new("./scene.scn"))).unwrap(),
+            b"scene"
+        );
+        let button = asset_io
+            .get_entry(Path::new("textures/ui/button.png"))
+            .unwrap();
+        assert_eq!(button.compression, PackCompression::Deflate);
+        assert!(button.stored_size < button.size);
+        // compressing this one would make it bigger
+        let player = asset_io
+            .get_entry(Path::new("textures/player.png"))
+            .unwrap();
+        assert_eq!(player.compression, PackCompression::None);
+        assert!(matches!(
+            block_on(asset_io.load_path(Path::new("missing.png"))),
+            Err(AssetIoError::NotFound(_))
+        ));
+
+        assert!(asset_io.is_dir(Path::new("")));
+        assert!(asset_io.is_dir(Path::new("textures/ui")));
+        assert!(asset_io.is_file(Path::new("textures/player.png")));
+        let mut children: Vec<_> = asset_io
+            .read_directory(Path::new("textures"))
+            .unwrap()
+            .collect();
+        children.sort();
+        assert_eq!(
+            children,
+            vec![
+                PathBuf::from("textures/player.png"),
+                PathBuf::from("textures/ui")
+            ]
+        );
+
+        // the same pack read from memory, with a tampered asset
+        let mut bytes = fs::read(&pack_path).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0xff;
+        let asset_io = PackAssetIo::from_bytes(bytes).unwrap();
+        assert!(block_on(asset_io.load_path(Path::new("scene.scn"))).is_ok());
+        assert!(matches!(
+            block_on(asset_io.load_path(Path::new("textures/ui/button.png"))),
+            Err(AssetIoError::HashMismatch(_)) | Err(AssetIoError::Io(_))
+        ));
+        assert!(PackAssetIo::from_bytes(b"not a pack".to_vec()).is_err());
+    }
+
+    #[test]
+    fn invalid_packs() {
+        let mut builder = AssetPackBuilder::new().with_compression(PackCompression::None);
+        builder.add_file("a.txt", b"abc".to_vec());
+        let mut pack = Vec::new();
+        builder.write(&mut pack).unwrap();
+        assert!(PackAssetIo::from_bytes(pack.clone()).is_ok());
+
+        let is_invalid = |bytes: Vec<u8>| {
+            matches!(
+                PackAssetIo::from_bytes(bytes),
+                Err(AssetIoError::InvalidAssetPack(_)) | Err(AssetIoError::Io(_))
+            )
+        };
+        let with_u64 = |at: usize, value: u64| {
+            let mut bytes = pack.clone();
+            bytes[at..at + 8].copy_from_slice(&value.to_le_bytes());
+            bytes
+        };
+        // the header is followed by the size of the path, the path and the offset
+        let stored_size_at = HEADER_SIZE as usize + 4 + "a.txt".len() + 8;
+
+        // truncated
+        assert!(is_invalid(pack[..pack.len() - 1].to_vec()));
+        assert!(is_invalid(pack[..HEADER_SIZE as usize + 4].to_vec()));
+        // more entries than the index can hold
+        let mut bytes = pack.clone();
+        bytes[12..16].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(is_invalid(bytes));
+        // an index larger than the pack, or overflowing the data offset
+        assert!(is_invalid(with_u64(16, 1 << 40)));
+        assert!(is_invalid(with_u64(16, u64::MAX)));
+        // entries outside of the pack, or overflowing their end
+        assert!(is_invalid(with_u64(stored_size_at, 1 << 40)));
+        assert!(is_invalid(with_u64(stored_size_at, u64::MAX)));
+        assert!(is_invalid(with_u64(stored_size_at - 8, u64::MAX - 1)));
+        // an uncompressed entry with the wrong size
+        assert!(is_invalid(with_u64(stored_size_at + 8, 1 << 40)));
+    }
+
+    #[test]
+    fn load_folder_from_pack() {
+        struct TextLoader;
+        impl crate::AssetLoader for TextLoader {
+            fn load<'a>(
+                &'a self,
+                _bytes: &'a [u8],
+                _load_context: &'a mut crate::LoadContext,
+            ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+                Box::pin(async move { Ok(()) })
+            }
+
+            fn extensions(&self) -> &[&str] {
+                &["txt"]
+            }
+        }
+
+        let mut builder = AssetPackBuilder::new().with_compression(PackCompression::None);
+        builder
+            .add_file("text/a.txt", b"a".to_vec())
+            .add_file("text/nested/b.txt", b"b".to_vec())
+            .add_file("text/c.png", b"c".to_vec());
+        let mut bytes = Vec::new();
+        builder.write(&mut bytes).unwrap();
+
+        bevy_tasks::IoTaskPool::init(Default::default);
+        let asset_server = AssetServer::new(PackAssetIo::from_bytes(bytes).unwrap());
+        asset_server.add_loader(TextLoader);
+        let handles = asset_server.load_folder("text").unwrap();
+        assert_eq!(handles.len(), 2);
+        assert!(asset_server.load_folder("text/a.txt").is_err());
+    }
+}
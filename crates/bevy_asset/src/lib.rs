@@ -12,7 +12,12 @@ mod handle;
 mod info;
 mod io;
 mod loader;
+mod loading_tracker;
 mod path;
+mod processor;
+mod reflect;
+mod saver;
+mod stable_hash;
 
 pub mod prelude {
     #[doc(hidden)]
@@ -26,10 +31,18 @@ pub use handle::*;
 pub use info::*;
 pub use io::*;
 pub use loader::*;
+pub use loading_tracker::*;
 pub use path::*;
+pub use processor::*;
+pub use reflect::*;
+pub use saver::*;
 
 use bevy_app::{prelude::Plugin, App};
-use bevy_ecs::schedule::{StageLabel, SystemStage};
+use bevy_ecs::{
+    reflect::ReflectResource,
+    schedule::{StageLabel, SystemStage},
+};
+use bevy_reflect::Reflect;
 
 /// The names of asset stages in an App Schedule
 #[derive(Debug, Hash, PartialEq, Eq, Clone, StageLabel)]
@@ -43,6 +56,8 @@ pub enum AssetStage {
 #[derive(Default)]
 pub struct AssetPlugin;
 
+#[derive(Reflect)]
+#[reflect(Resource)]
 pub struct AssetServerSettings {
     pub asset_folder: String,
     /// Whether to watch for changes in asset files. Requires the `filesystem_watcher` feature,
@@ -97,9 +112,15 @@ impl Plugin for AssetPlugin {
             SystemStage::parallel(),
         )
         .register_type::<HandleId>()
+        .register_type::<AssetServerSettings>()
+        .init_resource::<LoadingTracker>()
         .add_system_to_stage(
             bevy_app::CoreStage::PreUpdate,
             asset_server::free_unused_assets_system,
+        )
+        .add_system_to_stage(
+            bevy_app::CoreStage::PreUpdate,
+            update_loading_tracker_system,
         );
 
         #[cfg(all(
@@ -107,5 +128,8 @@ impl Plugin for AssetPlugin {
             all(not(target_arch = "wasm32"), not(target_os = "android"))
         ))]
         app.add_system_to_stage(AssetStage::LoadAssets, io::filesystem_watcher_system);
+
+        #[cfg(all(feature = "http_asset_io", not(target_arch = "wasm32")))]
+        app.add_system_to_stage(AssetStage::LoadAssets, io::http_asset_io_polling_system);
     }
 }
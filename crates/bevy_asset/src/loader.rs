@@ -1,14 +1,18 @@
 use crate::{
-    path::AssetPath, AssetIo, AssetIoError, AssetMeta, AssetServer, Assets, Handle, HandleId,
-    RefChangeChannel,
+    path::AssetPath, AssetIo, AssetIoError, AssetMeta, AssetServer, AssetServerError, Assets,
+    Handle, HandleId, HandleUntyped, RefChangeChannel,
 };
 use anyhow::Result;
 use bevy_ecs::system::{Res, ResMut};
-use bevy_reflect::{TypeUuid, TypeUuidDynamic};
+use bevy_reflect::{Reflect, TypeUuid, TypeUuidDynamic};
 use bevy_utils::{BoxedFuture, HashMap};
 use crossbeam_channel::{Receiver, Sender};
 use downcast_rs::{impl_downcast, Downcast};
-use std::path::Path;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 /// A loader for an asset source
 pub trait AssetLoader: Send + Sync + 'static {
@@ -18,6 +22,44 @@ pub trait AssetLoader: Send + Sync + 'static {
         load_context: &'a mut LoadContext,
     ) -> BoxedFuture<'a, Result<(), anyhow::Error>>;
     fn extensions(&self) -> &[&str];
+
+    /// Returns the settings used for assets that don't provide their own, or `None` if this
+    /// loader doesn't accept settings.
+    ///
+    /// The concrete type returned here is the type `.meta` files are deserialized into, and the
+    /// type expected by [`LoadContext::settings`] and [`AssetServer::load_with_settings`].
+    fn default_settings(&self) -> Option<Box<dyn AssetLoaderSettings>> {
+        None
+    }
+}
+
+/// The extension of the sidecar files holding [`AssetLoaderSettings`] for an asset.
+pub const META_EXTENSION: &str = "meta";
+
+/// Returns the path of the `.meta` file holding the settings of the asset at `path`.
+///
+/// For `textures/player.png`, this is `textures/player.png.meta`.
+pub fn get_meta_path(path: &Path) -> PathBuf {
+    let mut meta_path = path.as_os_str().to_owned();
+    meta_path.push(".");
+    meta_path.push(META_EXTENSION);
+    meta_path.into()
+}
+
+/// Per-asset settings of an [`AssetLoader`].
+///
+/// Settings are read from a `.meta` file next to the asset, written in RON, or given to
+/// [`AssetServer::load_with_settings`]. This is implemented for every type that is [`Reflect`],
+/// [`Serialize`] and [`DeserializeOwned`].
+pub trait AssetLoaderSettings: Reflect {
+    /// Deserializes settings of the same type as `self` from the contents of a `.meta` file.
+    fn deserialize_meta(&self, bytes: &[u8]) -> Result<Box<dyn AssetLoaderSettings>, ron::Error>;
+}
+
+impl<T: Reflect + Serialize + DeserializeOwned> AssetLoaderSettings for T {
+    fn deserialize_meta(&self, bytes: &[u8]) -> Result<Box<dyn AssetLoaderSettings>, ron::Error> {
+        Ok(Box::new(ron::de::from_bytes::<T>(bytes)?))
+    }
 }
 
 pub trait Asset: TypeUuid + AssetDynamic {}
@@ -80,24 +122,47 @@ impl<T: Asset> From<LoadedAsset<T>> for BoxedLoadedAsset {
 pub struct LoadContext<'a> {
     pub(crate) ref_change_channel: &'a RefChangeChannel,
     pub(crate) asset_io: &'a dyn AssetIo,
+    pub(crate) asset_server: &'a AssetServer,
     pub(crate) labeled_assets: HashMap<Option<String>, BoxedLoadedAsset>,
     pub(crate) path: &'a Path,
+    pub(crate) source: Option<&'a str>,
     pub(crate) version: usize,
+    pub(crate) settings: Option<&'a dyn AssetLoaderSettings>,
+    pub(crate) direct_dependencies: Vec<AssetPath<'static>>,
+    /// The other assets of the files loaded with [`LoadContext::load_direct`], created at their
+    /// own path along with the asset being loaded.
+    pub(crate) nested_assets: Vec<(AssetPath<'static>, BoxedLoadedAsset)>,
+    /// The dependencies of the assets loaded with [`LoadContext::load_direct`].
+    pub(crate) nested_dependencies: Vec<AssetPath<'static>>,
+    /// The files being loaded, from the asset loaded by the [`AssetServer`] down to this one
+    /// through [`LoadContext::load_direct`], to detect cycles.
+    pub(crate) loading_paths: Vec<AssetPath<'static>>,
 }
 
 impl<'a> LoadContext<'a> {
     pub(crate) fn new(
         path: &'a Path,
-        ref_change_channel: &'a RefChangeChannel,
+        source: Option<&'a str>,
+        asset_server: &'a AssetServer,
         asset_io: &'a dyn AssetIo,
         version: usize,
+        settings: Option<&'a dyn AssetLoaderSettings>,
     ) -> Self {
         Self {
-            ref_change_channel,
+            ref_change_channel: &asset_server.server.asset_ref_counter.channel,
             asset_io,
+            asset_server,
+            direct_dependencies: Vec::new(),
+            nested_assets: Vec::new(),
+            nested_dependencies: Vec::new(),
+            loading_paths: vec![AssetPath::new_ref(path, None)
+                .with_source_ref(source)
+                .to_owned()],
             labeled_assets: Default::default(),
             version,
             path,
+            source,
+            settings,
         }
     }
 
@@ -105,6 +170,21 @@ impl<'a> LoadContext<'a> {
         self.path
     }
 
+    /// The name of the asset source the asset is loaded from, or `None` for the default source.
+    pub fn source(&self) -> Option<&str> {
+        self.source
+    }
+
+    /// Returns the settings of the asset being loaded.
+    ///
+    /// These come from [`AssetServer::load_with_settings`], the asset's `.meta` file, or
+    /// [`AssetLoader::default_settings`], in that order. Returns `None` if the loader doesn't
+    /// accept settings or if `S` is not its settings type.
+    pub fn settings<S: AssetLoaderSettings>(&self) -> Option<&S> {
+        self.settings
+            .and_then(|settings| settings.as_reflect().downcast_ref::<S>())
+    }
+
     pub fn has_labeled_asset(&self, label: &str) -> bool {
         self.labeled_assets.contains_key(&Some(label.to_string()))
     }
@@ -117,17 +197,124 @@ impl<'a> LoadContext<'a> {
         assert!(!label.is_empty());
         self.labeled_assets
             .insert(Some(label.to_string()), asset.into());
-        self.get_handle(AssetPath::new_ref(self.path(), Some(label)))
+        self.get_labeled_handle(label)
+    }
+
+    /// Returns `path` in the asset source of the asset being loaded, unless it names a source.
+    ///
+    /// The [`AssetServer`] resolves the dependencies of loaded assets and the paths given to
+    /// [`load_direct`](LoadContext::load_direct) this way, so handles to those assets should be
+    /// created from the resolved path.
+    pub fn resolve_path<'b>(&self, path: impl Into<AssetPath<'b>>) -> AssetPath<'static> {
+        path.into().or_source(self.source).to_owned()
     }
 
     pub fn get_handle<I: Into<HandleId>, T: Asset>(&self, id: I) -> Handle<T> {
         Handle::strong(id.into(), self.ref_change_channel.sender.clone())
     }
 
+    pub fn get_handle_untyped<I: Into<HandleId>>(&self, id: I) -> HandleUntyped {
+        HandleUntyped::strong(id.into(), self.ref_change_channel.sender.clone())
+    }
+
+    /// Returns a handle to the asset with the given label in the asset being loaded.
+    pub fn get_labeled_handle<T: Asset>(&self, label: &str) -> Handle<T> {
+        self.get_handle(AssetPath::new_ref(self.path(), Some(label)).with_source_ref(self.source))
+    }
+
     pub async fn read_asset_bytes<P: AsRef<Path>>(&self, path: P) -> Result<Vec<u8>, AssetIoError> {
         self.asset_io.load_path(path.as_ref()).await
     }
 
+    /// Loads the asset at `path` with its [`AssetLoader`] and returns its value, without adding
+    /// it to the [`Assets`] collection.
+    ///
+    /// The asset is recorded as a dependency of the asset being loaded, which is reloaded when
+    /// it changes. Labeled assets can be loaded by giving their label in the path, and paths
+    /// without a source are loaded from the source of the asset being loaded.
+    ///
+    /// The other labeled assets of the file are created at their own path along with the asset
+    /// being loaded, so that the handles the returned value holds to them stay valid, and the
+    /// dependencies of the file's assets are loaded as well.
+    ///
+    /// Returns [`AssetServerError::LoadDirectCycle`] if the file is already being loaded, by
+    /// this loader or by one of the loaders that loaded it directly.
+    pub async fn load_direct<'b, T: Asset>(
+        &mut self,
+        path: impl Into<AssetPath<'b>>,
+    ) -> Result<T, AssetServerError> {
+        let asset_path = self.resolve_path(path);
+        let file_path = AssetPath::new_ref(asset_path.path(), None)
+            .with_source_ref(asset_path.source())
+            .to_owned();
+        if self
+            .loading_paths
+            .iter()
+            .any(|path| path.get_id() == file_path.get_id())
+        {
+            return Err(AssetServerError::LoadDirectCycle(file_path));
+        }
+        let asset_server = self.asset_server;
+        let asset_io = asset_server.get_source(asset_path.source())?;
+        let asset_loader = asset_server.get_path_asset_loader(asset_path.path())?;
+        let settings = asset_server
+            .load_asset_settings(&*asset_loader, &*asset_io, &asset_path)
+            .await?;
+        let bytes = asset_io.load_path(asset_path.path()).await?;
+
+        let mut load_context = LoadContext::new(
+            asset_path.path(),
+            asset_path.source(),
+            asset_server,
+            &*asset_io,
+            self.version,
+            settings.as_deref(),
+        );
+        load_context.loading_paths = self.loading_paths.clone();
+        load_context.loading_paths.push(file_path.clone());
+        asset_loader
+            .load(&bytes, &mut load_context)
+            .await
+            .map_err(AssetServerError::AssetLoaderError)?;
+
+        self.direct_dependencies.push(file_path);
+        self.direct_dependencies
+            .append(&mut load_context.direct_dependencies);
+
+        let asset = load_context
+            .labeled_assets
+            .remove(&asset_path.label().map(String::from))
+            .ok_or(AssetServerError::MissingLabeledAsset)?;
+        let value = asset.value.ok_or(AssetServerError::MissingLabeledAsset)?;
+        let value = value
+            .downcast::<T>()
+            .map_err(|_| AssetServerError::IncorrectHandleType)?;
+
+        let source = asset_path.source();
+        self.nested_dependencies.extend(
+            asset
+                .dependencies
+                .into_iter()
+                .map(|dependency| dependency.or_source(source)),
+        );
+        for (label, mut nested_asset) in load_context.labeled_assets.drain() {
+            self.nested_dependencies.extend(
+                nested_asset
+                    .dependencies
+                    .drain(..)
+                    .map(|dependency| dependency.or_source(source)),
+            );
+            let nested_path = AssetPath::new_ref(asset_path.path(), label.as_deref())
+                .with_source_ref(source)
+                .to_owned();
+            self.nested_assets.push((nested_path, nested_asset));
+        }
+        self.nested_assets.append(&mut load_context.nested_assets);
+        self.nested_dependencies
+            .append(&mut load_context.nested_dependencies);
+        Ok(*value)
+    }
+
     pub fn get_asset_metas(&self) -> Vec<AssetMeta> {
         let mut asset_metas = Vec::new();
         for (label, asset) in &self.labeled_assets {
@@ -163,11 +350,17 @@ pub struct AssetLifecycleChannel<T> {
 pub enum AssetLifecycleEvent<T> {
     Create(AssetResult<T>),
     Free(HandleId),
+    Fail {
+        id: HandleId,
+        path: AssetPath<'static>,
+        error: Arc<AssetServerError>,
+    },
 }
 
 pub trait AssetLifecycle: Downcast + Send + Sync + 'static {
     fn create_asset(&self, id: HandleId, asset: Box<dyn AssetDynamic>, version: usize);
     fn free_asset(&self, id: HandleId);
+    fn fail_asset(&self, id: HandleId, path: AssetPath<'static>, error: Arc<AssetServerError>);
 }
 impl_downcast!(AssetLifecycle);
 
@@ -192,6 +385,12 @@ impl<T: AssetDynamic> AssetLifecycle for AssetLifecycleChannel<T> {
     fn free_asset(&self, id: HandleId) {
         self.sender.send(AssetLifecycleEvent::Free(id)).unwrap();
     }
+
+    fn fail_asset(&self, id: HandleId, path: AssetPath<'static>, error: Arc<AssetServerError>) {
+        self.sender
+            .send(AssetLifecycleEvent::Fail { id, path, error })
+            .unwrap();
+    }
 }
 
 impl<T> Default for AssetLifecycleChannel<T> {
@@ -0,0 +1,197 @@
+use crate::{Asset, AssetPath, AssetServer, AssetServerError, Handle, HandleUntyped, LoadState};
+use bevy_ecs::{
+    schedule::{State, StateData},
+    system::{Res, ResMut},
+};
+use bevy_utils::{HashMap, HashSet};
+use std::sync::Arc;
+
+/// The progress of the assets of a group of a [`LoadingTracker`], including their
+/// dependencies
+///
+/// Each source file counts once, even if several handles or labeled assets come from it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LoadingProgress {
+    /// The number of source files that have loaded.
+    pub loaded: usize,
+    /// The number of source files that failed to load.
+    pub failed: usize,
+    /// The number of source files known so far. It can grow as loading assets discover their
+    /// dependencies.
+    pub total: usize,
+}
+
+impl LoadingProgress {
+    /// Returns the fraction of the source files done loading, successfully or not, between
+    /// `0.0` and `1.0`.
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 {
+            1.0
+        } else {
+            (self.loaded + self.failed) as f32 / self.total as f32
+        }
+    }
+
+    /// Returns whether every source file is done loading, successfully or not.
+    pub fn is_finished(&self) -> bool {
+        self.loaded + self.failed == self.total
+    }
+}
+
+/// An asset of a group of a [`LoadingTracker`] that failed to load
+#[derive(Clone, Debug)]
+pub struct FailedAsset {
+    /// A weak handle to the asset.
+    pub handle: HandleUntyped,
+    pub path: AssetPath<'static>,
+    pub error: Arc<AssetServerError>,
+}
+
+#[derive(Default)]
+struct LoadingGroup {
+    handles: Vec<HandleUntyped>,
+    progress: LoadingProgress,
+    failed: Vec<FailedAsset>,
+}
+
+/// Tracks the loading progress of named groups of assets, for example to show a loading screen.
+///
+/// The tracker keeps the handles of its groups alive until the group is removed. Its progress is
+/// updated every frame, during [`CoreStage::PreUpdate`](bevy_app::CoreStage::PreUpdate).
+///
+/// ```no_run
+/// # use bevy_asset::{AssetServer, LoadingTracker};
+/// # use bevy_ecs::system::{Res, ResMut};
+/// # #[derive(Debug, bevy_reflect::TypeUuid)]
+/// # #[uuid = "0a4be21c-c734-4bcb-a8ba-4cac3a4a7e5e"]
+/// # struct Image;
+/// fn start_loading(asset_server: Res<AssetServer>, mut tracker: ResMut<LoadingTracker>) {
+///     let image = asset_server.load::<Image, _>("textures/player.png");
+///     tracker.track("level", &image);
+/// }
+///
+/// fn show_progress(tracker: Res<LoadingTracker>) {
+///     let progress = tracker.progress("level");
+///     println!("{:.0}%", progress.fraction() * 100.0);
+/// }
+/// ```
+#[derive(Default)]
+pub struct LoadingTracker {
+    groups: HashMap<String, LoadingGroup>,
+}
+
+impl LoadingTracker {
+    /// Adds the asset of `handle` to `group`, creating the group if needed.
+    pub fn track<T: Asset>(&mut self, group: impl Into<String>, handle: &Handle<T>) {
+        self.track_untyped(group, &handle.clone_untyped());
+    }
+
+    pub fn track_untyped(&mut self, group: impl Into<String>, handle: &HandleUntyped) {
+        let group = self.groups.entry(group.into()).or_default();
+        group.handles.push(handle.clone());
+        // the progress is unknown until the next update
+        group.progress.total += 1;
+    }
+
+    /// Removes `group`, returning whether it existed.
+    pub fn remove_group(&mut self, group: &str) -> bool {
+        self.groups.remove(group).is_some()
+    }
+
+    pub fn contains_group(&self, group: &str) -> bool {
+        self.groups.contains_key(group)
+    }
+
+    /// Returns the progress of `group`, which is finished if the group doesn't exist.
+    pub fn progress(&self, group: &str) -> LoadingProgress {
+        self.groups
+            .get(group)
+            .map_or_else(Default::default, |group| group.progress)
+    }
+
+    /// Returns whether every asset of `group` is done loading, successfully or not.
+    pub fn is_finished(&self, group: &str) -> bool {
+        self.progress(group).is_finished()
+    }
+
+    /// Returns whether every asset of `group` has loaded successfully.
+    pub fn is_loaded(&self, group: &str) -> bool {
+        let progress = self.progress(group);
+        progress.is_finished() && progress.failed == 0
+    }
+
+    /// Returns the assets of `group` that failed to load, with their errors.
+    pub fn failed(&self, group: &str) -> &[FailedAsset] {
+        self.groups
+            .get(group)
+            .map_or(&[], |group| group.failed.as_slice())
+    }
+
+    /// Updates the progress of every group from the load states of the [`AssetServer`].
+    pub fn update(&mut self, asset_server: &AssetServer) {
+        for group in self.groups.values_mut() {
+            let mut progress = LoadingProgress::default();
+            let mut failed = Vec::new();
+            let mut visited = HashSet::default();
+            for handle in &group.handles {
+                for (path, load_state) in asset_server.get_dependency_load_states(handle) {
+                    if !visited.insert(path.get_id().source_path_id()) {
+                        continue;
+                    }
+                    progress.total += 1;
+                    match load_state {
+                        LoadState::Loaded => progress.loaded += 1,
+                        LoadState::Failed => {
+                            progress.failed += 1;
+                            if let Some(error) = asset_server.get_source_load_error(&path) {
+                                failed.push(FailedAsset {
+                                    handle: HandleUntyped::weak(path.get_id().into()),
+                                    path,
+                                    error,
+                                });
+                            }
+                        }
+                        LoadState::NotLoaded | LoadState::Loading | LoadState::Unloaded => {}
+                    }
+                }
+                // assets not loaded through the asset server are already there
+                if asset_server.get_handle_path(handle).is_none() {
+                    progress.total += 1;
+                    progress.loaded += 1;
+                }
+            }
+            group.progress = progress;
+            group.failed = failed;
+        }
+    }
+}
+
+/// Updates the progress of the [`LoadingTracker`] groups.
+pub fn update_loading_tracker_system(
+    asset_server: Res<AssetServer>,
+    mut loading_tracker: ResMut<LoadingTracker>,
+) {
+    if !loading_tracker.groups.is_empty() {
+        loading_tracker.update(&asset_server);
+    }
+}
+
+/// Returns a system setting the [`State`] to `next` once every asset of `group` has loaded
+/// successfully.
+///
+/// It is usually added to the [`SystemSet`](bevy_ecs::schedule::SystemSet) of the loading state.
+pub fn transition_when_loaded<T: StateData>(
+    group: impl Into<String>,
+    next: T,
+) -> impl FnMut(Res<LoadingTracker>, ResMut<State<T>>) + Send + Sync + 'static {
+    let group = group.into();
+    move |loading_tracker: Res<LoadingTracker>, mut state: ResMut<State<T>>| {
+        if loading_tracker.contains_group(&group)
+            && loading_tracker.is_loaded(&group)
+            && *state.current() != next
+        {
+            // the transition may already be queued
+            let _ = state.set(next.clone());
+        }
+    }
+}
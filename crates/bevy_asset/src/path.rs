@@ -3,14 +3,27 @@ use bevy_utils::AHasher;
 use serde::{Deserialize, Serialize};
 use std::{
     borrow::Cow,
+    fmt::{self, Display},
     hash::{Hash, Hasher},
     path::{Path, PathBuf},
 };
 
+/// The separator between the name of an asset source and the path of an asset, as in
+/// `mods://weapon.png`.
+pub const ASSET_SOURCE_SEPARATOR: &str = "://";
+
+/// The path of an asset, made of an optional source name, a path and an optional label.
+///
+/// When parsed from a string such as `mods://weapons/sword.gltf#Mesh0`, the text before `://` is
+/// the name of the asset source (see [`AssetServer::add_source`](crate::AssetServer::add_source))
+/// and the text after `#` is the label. Paths without a source are loaded from the default
+/// source of the [`AssetServer`](crate::AssetServer).
 #[derive(Debug, Hash, Clone, Serialize, Deserialize)]
 pub struct AssetPath<'a> {
     path: Cow<'a, Path>,
     label: Option<Cow<'a, str>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    source: Option<Cow<'a, str>>,
 }
 
 impl<'a> AssetPath<'a> {
@@ -19,6 +32,7 @@ impl<'a> AssetPath<'a> {
         AssetPath {
             path: Cow::Borrowed(path),
             label: label.map(Cow::Borrowed),
+            source: None,
         }
     }
 
@@ -27,9 +41,24 @@ impl<'a> AssetPath<'a> {
         AssetPath {
             path: Cow::Owned(path),
             label: label.map(Cow::Owned),
+            source: None,
         }
     }
 
+    /// Returns this path in the asset source named `source`.
+    #[inline]
+    #[must_use]
+    pub fn with_source(mut self, source: impl Into<Cow<'a, str>>) -> AssetPath<'a> {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// The name of the asset source of this path, or `None` for the default source.
+    #[inline]
+    pub fn source(&self) -> Option<&str> {
+        self.source.as_ref().map(|source| source.as_ref())
+    }
+
     #[inline]
     pub fn get_id(&self) -> AssetPathId {
         AssetPathId::from(self)
@@ -53,7 +82,25 @@ impl<'a> AssetPath<'a> {
                 .label
                 .as_ref()
                 .map(|value| Cow::Owned(value.to_string())),
+            source: self
+                .source
+                .as_ref()
+                .map(|value| Cow::Owned(value.to_string())),
+        }
+    }
+
+    /// Returns this path in the asset source named `source`, unless it already names a source.
+    pub(crate) fn or_source(mut self, source: Option<&str>) -> AssetPath<'a> {
+        if self.source.is_none() {
+            self.source = source.map(|source| Cow::Owned(source.to_string()));
         }
+        self
+    }
+
+    #[inline]
+    pub(crate) fn with_source_ref(mut self, source: Option<&'a str>) -> AssetPath<'a> {
+        self.source = source.map(Cow::Borrowed);
+        self
     }
 }
 
@@ -77,8 +124,20 @@ pub struct LabelId(u64);
 
 impl<'a> From<&'a Path> for SourcePathId {
     fn from(value: &'a Path) -> Self {
+        SourcePathId::new(None, value)
+    }
+}
+
+impl SourcePathId {
+    /// Returns the id of the asset at `path` in the asset source named `source`.
+    ///
+    /// Paths of the default source have the same id as with [`SourcePathId::from`].
+    pub fn new(source: Option<&str>, path: &Path) -> Self {
         let mut hasher = get_hasher();
-        value.hash(&mut hasher);
+        if let Some(source) = source {
+            source.hash(&mut hasher);
+        }
+        path.hash(&mut hasher);
         SourcePathId(hasher.finish())
     }
 }
@@ -104,6 +163,10 @@ impl<'a> From<Option<&'a str>> for LabelId {
 }
 
 impl AssetPathId {
+    pub(crate) fn new(source_path_id: SourcePathId, label_id: LabelId) -> Self {
+        AssetPathId(source_path_id, label_id)
+    }
+
     pub fn source_path_id(&self) -> SourcePathId {
         self.0
     }
@@ -124,30 +187,46 @@ where
 {
     fn from(value: T) -> Self {
         let asset_path: AssetPath = value.into();
-        AssetPathId(
-            SourcePathId::from(asset_path.path()),
-            LabelId::from(asset_path.label()),
-        )
+        AssetPathId::from(&asset_path)
     }
 }
 
 impl<'a, 'b> From<&'a AssetPath<'b>> for AssetPathId {
     fn from(asset_path: &'a AssetPath<'b>) -> Self {
         AssetPathId(
-            SourcePathId::from(asset_path.path()),
+            SourcePathId::new(asset_path.source(), asset_path.path()),
             LabelId::from(asset_path.label()),
         )
     }
 }
 
+/// Formats the path as it is parsed by [`AssetPath::from`], as in `mods://weapons/sword.gltf#Mesh0`.
+impl<'a> Display for AssetPath<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(source) = self.source() {
+            write!(f, "{}{}", source, ASSET_SOURCE_SEPARATOR)?;
+        }
+        write!(f, "{}", self.path.display())?;
+        if let Some(label) = self.label() {
+            write!(f, "#{}", label)?;
+        }
+        Ok(())
+    }
+}
+
 impl<'a> From<&'a str> for AssetPath<'a> {
     fn from(asset_path: &'a str) -> Self {
+        let (source, asset_path) = match asset_path.split_once(ASSET_SOURCE_SEPARATOR) {
+            Some((source, asset_path)) => (Some(source), asset_path),
+            None => (None, asset_path),
+        };
         let mut parts = asset_path.split('#');
         let path = Path::new(parts.next().expect("Path must be set."));
         let label = parts.next();
         AssetPath {
             path: Cow::Borrowed(path),
             label: label.map(Cow::Borrowed),
+            source: source.map(Cow::Borrowed),
         }
     }
 }
@@ -163,6 +242,7 @@ impl<'a> From<&'a Path> for AssetPath<'a> {
         AssetPath {
             path: Cow::Borrowed(path),
             label: None,
+            source: None,
         }
     }
 }
@@ -172,6 +252,45 @@ impl<'a> From<PathBuf> for AssetPath<'a> {
         AssetPath {
             path: Cow::Owned(path),
             label: None,
+            source: None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_asset_path() {
+        let path = AssetPath::from("mods://weapons/sword.gltf#Mesh0");
+        assert_eq!(path.source(), Some("mods"));
+        assert_eq!(path.path(), Path::new("weapons/sword.gltf"));
+        assert_eq!(path.label(), Some("Mesh0"));
+
+        let path = AssetPath::from("weapons/sword.gltf");
+        assert_eq!(path.source(), None);
+        assert_eq!(path.label(), None);
+
+        for path in ["mods://weapons/sword.gltf#Mesh0", "weapons/sword.gltf"] {
+            assert_eq!(AssetPath::from(path).to_string(), path);
+        }
+    }
+
+    #[test]
+    fn asset_path_ids() {
+        let default = AssetPath::from("sword.gltf").get_id();
+        let mods = AssetPath::from("mods://sword.gltf").get_id();
+        assert_ne!(default, mods);
+        assert_eq!(
+            default.source_path_id(),
+            SourcePathId::from(Path::new("sword.gltf"))
+        );
+        assert_eq!(
+            mods,
+            AssetPath::new("sword.gltf".into(), None)
+                .with_source("mods")
+                .get_id()
+        );
+    }
+}
@@ -0,0 +1,693 @@
+#[cfg(all(
+    feature = "filesystem_watcher",
+    all(not(target_arch = "wasm32"), not(target_os = "android"))
+))]
+use crate::filesystem_watcher::FilesystemWatcher;
+use crate::{
+    get_meta_path,
+    stable_hash::{hash_bytes, StableHasher},
+    AssetIo, AssetIoError, META_EXTENSION,
+};
+use bevy_app::{App, Plugin};
+use bevy_ecs::world::World;
+use bevy_log::{info, warn};
+use bevy_utils::{BoxedFuture, HashMap, HashSet};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use thiserror::Error;
+
+/// The extension of the files recording how a processed asset was produced.
+pub const IMPORT_RECORD_EXTENSION: &str = "import";
+
+/// Errors that occur while processing assets with an [`AssetPipeline`]
+#[derive(Error, Debug)]
+pub enum AssetProcessError {
+    #[error("encountered an error while reading an asset: {0}")]
+    AssetIoError(#[from] AssetIoError),
+    #[error("encountered an io error while writing a processed asset: {0}")]
+    Io(#[from] io::Error),
+    #[error("encountered an error while writing an import record: {0}")]
+    ImportRecordError(#[from] ron::Error),
+    #[error("encountered an error while processing {}: {error}", path.display())]
+    ProcessorError { path: PathBuf, error: anyhow::Error },
+}
+
+/// Turns the bytes of a source asset into the bytes of a processed asset.
+///
+/// The processed asset is written at the same path as the source asset, so it is read by the
+/// same [`AssetLoader`](crate::AssetLoader), which must accept the processed bytes.
+pub trait AssetProcessor: Send + Sync + 'static {
+    fn process<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        process_context: &'a mut ProcessContext,
+    ) -> BoxedFuture<'a, Result<Vec<u8>, anyhow::Error>>;
+    fn extensions(&self) -> &[&str];
+
+    /// The version of this processor. Changing it reprocesses every asset it handles.
+    fn version(&self) -> u32 {
+        0
+    }
+}
+
+/// The context of an asset being processed by an [`AssetProcessor`]
+pub struct ProcessContext<'a> {
+    pub(crate) path: &'a Path,
+    pub(crate) meta: Option<&'a [u8]>,
+    pub(crate) asset_io: &'a dyn AssetIo,
+    pub(crate) dependencies: Vec<ImportDependency>,
+}
+
+impl<'a> ProcessContext<'a> {
+    pub fn path(&self) -> &Path {
+        self.path
+    }
+
+    /// Returns the contents of the `.meta` file of the asset, if it has one.
+    pub fn meta(&self) -> Option<&[u8]> {
+        self.meta
+    }
+
+    /// Reads the bytes of another source asset.
+    ///
+    /// The asset is recorded as a dependency: the asset being processed is processed again when
+    /// it changes.
+    pub async fn read_asset_bytes<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+    ) -> Result<Vec<u8>, AssetIoError> {
+        let path = path.as_ref();
+        let bytes = self.asset_io.load_path(path).await?;
+        self.dependencies.push(ImportDependency {
+            path: path.to_owned(),
+            hash: hash_bytes(&bytes),
+        });
+        Ok(bytes)
+    }
+}
+
+/// A source asset read while processing another asset
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ImportDependency {
+    pub path: PathBuf,
+    pub hash: u64,
+}
+
+/// Records how a processed asset was produced, next to it in a `.import` file.
+///
+/// An asset is only processed again when the hash computed from its current sources differs from
+/// the recorded one. Hashes are XXH64 hashes of the source bytes, so they stay valid across Rust
+/// releases and platforms.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportRecord {
+    /// The hash of the source bytes, the `.meta` file, the processor and the dependencies.
+    pub hash: u64,
+    pub dependencies: Vec<ImportDependency>,
+}
+
+/// The outcome of processing a single asset
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ProcessResult {
+    /// The asset was processed and written to the destination.
+    Processed,
+    /// The asset has no [`AssetProcessor`] and was copied to the destination.
+    Copied,
+    /// The processed asset was already up to date.
+    UpToDate,
+}
+
+/// The outcome of processing every source asset with [`AssetPipeline::process_all`]
+#[derive(Debug, Default)]
+pub struct ProcessAllResult {
+    /// The paths of the assets that were written to the destination.
+    pub written: Vec<PathBuf>,
+    /// The paths of the assets that failed to process, with their errors.
+    pub failed: Vec<(PathBuf, AssetProcessError)>,
+}
+
+/// Processes source assets into a folder of imported assets.
+///
+/// Every source asset is written at the same relative path in the destination folder, together
+/// with its `.meta` file. Assets with an [`AssetProcessor`] for their extension are processed,
+/// the others are copied as-is. A `.import` file next to each written asset holds its
+/// [`ImportRecord`], so that assets whose sources didn't change are skipped.
+///
+/// The pipeline can be run without an [`App`], for example from a small binary processing assets
+/// before shipping:
+///
+/// ```no_run
+/// # use bevy_asset::{AssetPipeline, FileAssetIo};
+/// let pipeline = AssetPipeline::new(FileAssetIo::new("assets", false), "imported_assets");
+/// // pipeline.add_processor(...);
+/// futures_lite::future::block_on(pipeline.process_all()).unwrap();
+/// ```
+pub struct AssetPipeline {
+    source_io: Box<dyn AssetIo>,
+    destination: PathBuf,
+    processors: Vec<Arc<dyn AssetProcessor>>,
+    extension_to_processor_index: HashMap<String, usize>,
+    dependents: RwLock<HashMap<PathBuf, HashSet<PathBuf>>>,
+}
+
+impl AssetPipeline {
+    pub fn new<T: AssetIo, P: AsRef<Path>>(source_io: T, destination: P) -> Self {
+        Self::with_boxed_io(Box::new(source_io), destination)
+    }
+
+    pub fn with_boxed_io<P: AsRef<Path>>(source_io: Box<dyn AssetIo>, destination: P) -> Self {
+        Self {
+            source_io,
+            destination: destination.as_ref().to_owned(),
+            processors: Vec::new(),
+            extension_to_processor_index: HashMap::default(),
+            dependents: Default::default(),
+        }
+    }
+
+    pub fn source_io(&self) -> &dyn AssetIo {
+        &*self.source_io
+    }
+
+    /// The folder processed assets are written to.
+    pub fn destination(&self) -> &Path {
+        &self.destination
+    }
+
+    pub fn add_processor<T: AssetProcessor>(&mut self, processor: T) {
+        let processor_index = self.processors.len();
+        for extension in processor.extensions().iter() {
+            self.extension_to_processor_index
+                .insert(extension.to_string(), processor_index);
+        }
+        self.processors.push(Arc::new(processor));
+    }
+
+    fn get_path_processor(&self, path: &Path) -> Option<Arc<dyn AssetProcessor>> {
+        let file_name = path.file_name()?.to_str()?.to_lowercase();
+        let mut ext = file_name.as_str();
+        while let Some(idx) = ext.find('.') {
+            ext = &ext[idx + 1..];
+            if let Some(index) = self.extension_to_processor_index.get(ext) {
+                return Some(self.processors[*index].clone());
+            }
+        }
+        None
+    }
+
+    /// Returns the source assets that read the asset at `path` while they were processed.
+    pub fn get_dependents(&self, path: &Path) -> Vec<PathBuf> {
+        self.dependents
+            .read()
+            .get(path)
+            .map(|dependents| dependents.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Processes every source asset, returning the paths of the assets that were written and of
+    /// the assets that failed to process.
+    ///
+    /// An asset failing to process is logged and doesn't stop the other assets from being
+    /// processed. Processed assets whose source asset was deleted are removed from the
+    /// destination, together with their `.meta` and `.import` files.
+    ///
+    /// Returns an error if the source folders can't be read or the deleted assets can't be removed.
+    pub async fn process_all(&self) -> Result<ProcessAllResult, AssetProcessError> {
+        let mut result = ProcessAllResult::default();
+        let mut sources = HashSet::default();
+        let mut directories = vec![PathBuf::new()];
+        while let Some(directory) = directories.pop() {
+            for path in self.source_io.read_directory(&directory)? {
+                if self.source_io.is_dir(&path) {
+                    directories.push(path);
+                } else if path.extension() != Some(META_EXTENSION.as_ref()) {
+                    match self.process(&path).await {
+                        Ok(ProcessResult::UpToDate) => {}
+                        Ok(_) => result.written.push(path.clone()),
+                        Err(err) => {
+                            warn!("failed to process {:?}: {}", path, err);
+                            result.failed.push((path.clone(), err));
+                        }
+                    }
+                    sources.insert(path);
+                }
+            }
+        }
+        self.prune(&sources)?;
+        Ok(result)
+    }
+
+    /// Removes the processed assets whose path isn't in `sources`.
+    fn prune(&self, sources: &HashSet<PathBuf>) -> Result<(), AssetProcessError> {
+        if !self.destination.is_dir() {
+            return Ok(());
+        }
+        let mut directories = vec![self.destination.clone()];
+        while let Some(directory) = directories.pop() {
+            for entry in std::fs::read_dir(&directory)? {
+                let record_path = entry?.path();
+                if record_path.is_dir() {
+                    directories.push(record_path);
+                    continue;
+                }
+                if record_path.extension() != Some(IMPORT_RECORD_EXTENSION.as_ref()) {
+                    continue;
+                }
+                let destination_path = record_path.with_extension("");
+                let path = match destination_path.strip_prefix(&self.destination) {
+                    Ok(path) => path,
+                    Err(_) => continue,
+                };
+                if sources.contains(path) {
+                    continue;
+                }
+                self.remove_dependents(path);
+                for removed in [
+                    get_meta_path(&destination_path),
+                    destination_path.clone(),
+                    record_path,
+                ] {
+                    match std::fs::remove_file(removed) {
+                        Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err.into()),
+                        _ => {}
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Processes the source asset at `path` if it, its `.meta` file, its processor or its
+    /// dependencies changed since it was last processed.
+    pub async fn process(&self, path: &Path) -> Result<ProcessResult, AssetProcessError> {
+        let bytes = self.source_io.load_path(path).await?;
+        let meta_path = get_meta_path(path);
+        let meta = match self.source_io.load_path(&meta_path).await {
+            Ok(meta) => Some(meta),
+            Err(AssetIoError::NotFound(_)) => None,
+            Err(err) => return Err(err.into()),
+        };
+        let processor = self.get_path_processor(path);
+
+        let mut hasher = StableHasher::default();
+        hasher.write_u64(hash_bytes(&bytes));
+        hasher.write_option_u64(meta.as_deref().map(hash_bytes));
+        hasher.write_bool(processor.is_some());
+        if let Some(processor) = &processor {
+            hasher.write_u64(processor.extensions().len() as u64);
+            for extension in processor.extensions() {
+                hasher.write_str(extension);
+            }
+            hasher.write_u64(processor.version() as u64);
+        }
+
+        let destination_path = self.destination.join(path);
+        let record_path = get_import_record_path(&destination_path);
+        if let Some(record) = read_import_record(&record_path) {
+            let mut hasher = hasher.clone();
+            for dependency in &record.dependencies {
+                let hash = match self.source_io.load_path(&dependency.path).await {
+                    Ok(bytes) => Some(hash_bytes(&bytes)),
+                    Err(_) => None,
+                };
+                hasher.write_path(&dependency.path);
+                hasher.write_option_u64(hash);
+            }
+            if record.hash == hasher.finish() && destination_path.exists() {
+                self.add_dependents(path, &record.dependencies);
+                return Ok(ProcessResult::UpToDate);
+            }
+        }
+
+        let (processed, dependencies, result) = match processor {
+            Some(processor) => {
+                let mut process_context = ProcessContext {
+                    path,
+                    meta: meta.as_deref(),
+                    asset_io: &*self.source_io,
+                    dependencies: Vec::new(),
+                };
+                let processed = processor
+                    .process(&bytes, &mut process_context)
+                    .await
+                    .map_err(|error| AssetProcessError::ProcessorError {
+                        path: path.to_owned(),
+                        error,
+                    })?;
+                (
+                    processed,
+                    process_context.dependencies,
+                    ProcessResult::Processed,
+                )
+            }
+            None => (bytes, Vec::new(), ProcessResult::Copied),
+        };
+
+        for dependency in &dependencies {
+            hasher.write_path(&dependency.path);
+            hasher.write_option_u64(Some(dependency.hash));
+        }
+        let record = ImportRecord {
+            hash: hasher.finish(),
+            dependencies,
+        };
+
+        if let Some(parent) = destination_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&destination_path, processed)?;
+        let destination_meta_path = get_meta_path(&destination_path);
+        match meta {
+            Some(meta) => std::fs::write(&destination_meta_path, meta)?,
+            None if destination_meta_path.exists() => std::fs::remove_file(destination_meta_path)?,
+            None => {}
+        }
+        std::fs::write(
+            &record_path,
+            ron::ser::to_string_pretty(&record, Default::default())?,
+        )?;
+        self.remove_dependents(path);
+        self.add_dependents(path, &record.dependencies);
+
+        Ok(result)
+    }
+
+    fn add_dependents(&self, path: &Path, dependencies: &[ImportDependency]) {
+        let mut dependents = self.dependents.write();
+        for dependency in dependencies {
+            dependents
+                .entry(dependency.path.clone())
+                .or_default()
+                .insert(path.to_owned());
+        }
+    }
+
+    fn remove_dependents(&self, path: &Path) {
+        self.dependents.write().retain(|_, dependents| {
+            dependents.remove(path);
+            !dependents.is_empty()
+        });
+    }
+}
+
+fn get_import_record_path(path: &Path) -> PathBuf {
+    let mut record_path = path.as_os_str().to_owned();
+    record_path.push(".");
+    record_path.push(IMPORT_RECORD_EXTENSION);
+    record_path.into()
+}
+
+fn read_import_record(path: &Path) -> Option<ImportRecord> {
+    let bytes = std::fs::read(path).ok()?;
+    ron::de::from_bytes(&bytes).ok()
+}
+
+/// Processes the assets of the [`AssetServerSettings`](crate::AssetServerSettings) asset folder
+/// into [`destination_folder`](AssetProcessorPlugin::destination_folder), and makes the
+/// [`AssetServer`](crate::AssetServer) load assets from there.
+///
+/// This plugin must be added before the [`AssetPlugin`](crate::AssetPlugin), and processors are
+/// added with [`AddAsset::add_asset_processor`](crate::AddAsset::add_asset_processor). Assets are
+/// processed during [`StartupStage::PreStartup`]. When
+/// [`watch_for_changes`](AssetProcessorPlugin::watch_for_changes) is enabled, source assets are
+/// processed again when they change, which triggers a hot reload if the asset server also
+/// watches for changes.
+pub struct AssetProcessorPlugin {
+    pub destination_folder: String,
+    /// Whether to watch the source assets for changes. Requires the `filesystem_watcher`
+    /// feature, and cannot be supported on the wasm32 arch nor android os.
+    pub watch_for_changes: bool,
+}
+
+impl Default for AssetProcessorPlugin {
+    fn default() -> Self {
+        Self {
+            destination_folder: "imported_assets".to_string(),
+            watch_for_changes: false,
+        }
+    }
+}
+
+impl Plugin for AssetProcessorPlugin {
+    fn build(&self, _app: &mut App) {
+        #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+        {
+            use crate::{AssetServer, AssetServerSettings, FileAssetIo};
+            use bevy_app::StartupStage;
+            use bevy_ecs::system::IntoExclusiveSystem;
+
+            let app = _app;
+            let settings = app
+                .world
+                .get_resource_or_insert_with(AssetServerSettings::default);
+            let source_io = FileAssetIo::new(&settings.asset_folder, false);
+            let destination = FileAssetIo::get_root_path().join(&self.destination_folder);
+            let watch_asset_server = settings.watch_for_changes;
+
+            if app.world.contains_resource::<AssetServer>() {
+                warn!("The AssetServer already exists, so processed assets won't be loaded. Add the AssetProcessorPlugin before the AssetPlugin.");
+            } else {
+                let asset_io = FileAssetIo::new(&self.destination_folder, watch_asset_server);
+                app.insert_resource(AssetServer::new(asset_io));
+            }
+
+            if self.watch_for_changes {
+                #[cfg(feature = "filesystem_watcher")]
+                {
+                    let mut watcher = FilesystemWatcher::default();
+                    match watcher.watch(source_io.root_path()) {
+                        Ok(()) => {
+                            app.insert_resource(AssetPipelineWatcher(watcher))
+                                .add_system_to_stage(
+                                    bevy_app::CoreStage::PreUpdate,
+                                    asset_pipeline_watcher_system.exclusive_system(),
+                                );
+                        }
+                        Err(err) => warn!("failed to watch source assets: {}", err),
+                    }
+                }
+                #[cfg(not(feature = "filesystem_watcher"))]
+                warn!("Watching source assets for changes requires the filesystem_watcher feature");
+            }
+
+            app.insert_resource(AssetPipeline::new(source_io, destination))
+                .add_startup_system_to_stage(
+                    StartupStage::PreStartup,
+                    process_assets_system.exclusive_system(),
+                );
+        }
+        #[cfg(any(target_arch = "wasm32", target_os = "android"))]
+        warn!("Asset processing cannot be used on wasm32 / android targets");
+    }
+}
+
+/// Processes every source asset of the [`AssetPipeline`].
+pub fn process_assets_system(world: &mut World) {
+    let pipeline = world.resource::<AssetPipeline>();
+    match futures_lite::future::block_on(pipeline.process_all()) {
+        Ok(result) => {
+            if !result.written.is_empty() {
+                info!("processed {} assets", result.written.len());
+            }
+        }
+        Err(err) => warn!("failed to process assets: {}", err),
+    }
+}
+
+#[cfg(all(
+    feature = "filesystem_watcher",
+    all(not(target_arch = "wasm32"), not(target_os = "android"))
+))]
+struct AssetPipelineWatcher(FilesystemWatcher);
+
+/// Processes source assets again when they, or the assets they depend on, change.
+#[cfg(all(
+    feature = "filesystem_watcher",
+    all(not(target_arch = "wasm32"), not(target_os = "android"))
+))]
+fn asset_pipeline_watcher_system(world: &mut World) {
+    let mut changed = HashSet::default();
+    {
+        let pipeline = world.resource::<AssetPipeline>();
+        let source_io = match pipeline.source_io().downcast_ref::<crate::FileAssetIo>() {
+            Some(source_io) => source_io,
+            None => return,
+        };
+        let watcher = world.resource::<AssetPipelineWatcher>();
+        while let Ok(event) = watcher.0.receiver.try_recv() {
+            if let Ok(notify::event::Event {
+                kind: notify::event::EventKind::Modify(_) | notify::event::EventKind::Create(_),
+                paths,
+                ..
+            }) = event
+            {
+                for path in paths {
+                    if let Ok(relative_path) = path.strip_prefix(source_io.root_path()) {
+                        // a changed `.meta` file processes the asset it holds the settings of
+                        if relative_path.extension() == Some(META_EXTENSION.as_ref()) {
+                            changed.insert(relative_path.with_extension(""));
+                        } else if path.is_file() {
+                            changed.insert(relative_path.to_owned());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let pipeline = world.resource::<AssetPipeline>();
+    let mut to_process: Vec<PathBuf> = changed.iter().cloned().collect();
+    for path in &changed {
+        to_process.extend(pipeline.get_dependents(path));
+    }
+    let mut processed = HashSet::default();
+    for path in to_process {
+        if !processed.insert(path.clone()) {
+            continue;
+        }
+        match futures_lite::future::block_on(pipeline.process(&path)) {
+            Ok(ProcessResult::UpToDate) => {}
+            Ok(_) => info!("processed {:?}", path),
+            Err(err) => warn!("failed to process {:?}: {}", path, err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FileAssetIo;
+
+    struct UppercaseProcessor;
+    impl AssetProcessor for UppercaseProcessor {
+        fn process<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            ctx: &'a mut ProcessContext,
+        ) -> BoxedFuture<'a, Result<Vec<u8>, anyhow::Error>> {
+            Box::pin(async move {
+                let mut text = String::from_utf8(bytes.to_vec())?;
+                if let Some(include) = text.strip_prefix("include ") {
+                    let included = ctx.read_asset_bytes(include.trim()).await?;
+                    text = String::from_utf8(included)?;
+                }
+                Ok(text.to_uppercase().into_bytes())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["txt"]
+        }
+    }
+
+    #[test]
+    fn process_assets() {
+        let source = tempfile::tempdir().unwrap();
+        let destination = tempfile::tempdir().unwrap();
+        std::fs::create_dir(source.path().join("text")).unwrap();
+        std::fs::write(source.path().join("text/a.txt"), "include b.part").unwrap();
+        std::fs::write(source.path().join("text/a.txt.meta"), "()").unwrap();
+        std::fs::write(source.path().join("b.part"), "b").unwrap();
+        std::fs::write(source.path().join("c.txt"), "c").unwrap();
+
+        let mut pipeline =
+            AssetPipeline::new(FileAssetIo::new(source.path(), false), destination.path());
+        pipeline.add_processor(UppercaseProcessor);
+        let process_all = || {
+            let result = futures_lite::future::block_on(pipeline.process_all()).unwrap();
+            assert!(result.failed.is_empty());
+            let mut written = result.written;
+            written.sort();
+            written
+        };
+        let read = |path: &str| std::fs::read_to_string(destination.path().join(path)).unwrap();
+
+        assert_eq!(
+            process_all(),
+            vec![
+                PathBuf::from("b.part"),
+                PathBuf::from("c.txt"),
+                PathBuf::from("text/a.txt")
+            ]
+        );
+        assert_eq!(read("text/a.txt"), "B");
+        assert_eq!(read("text/a.txt.meta"), "()");
+        assert_eq!(read("b.part"), "b");
+        assert_eq!(read("c.txt"), "C");
+        assert_eq!(
+            pipeline.get_dependents(Path::new("b.part")),
+            vec![PathBuf::from("text/a.txt")]
+        );
+
+        // nothing changed
+        assert!(process_all().is_empty());
+
+        // changing a dependency processes its dependents again
+        std::fs::write(source.path().join("b.part"), "bb").unwrap();
+        assert_eq!(
+            process_all(),
+            vec![PathBuf::from("b.part"), PathBuf::from("text/a.txt")]
+        );
+        assert_eq!(read("text/a.txt"), "BB");
+
+        // changing a `.meta` file processes its asset again
+        std::fs::write(source.path().join("text/a.txt.meta"), "(a: 1)").unwrap();
+        assert_eq!(process_all(), vec![PathBuf::from("text/a.txt")]);
+        assert_eq!(read("text/a.txt.meta"), "(a: 1)");
+
+        // the hash only depends on the content, not on the Rust release
+        let record = read_import_record(&destination.path().join("c.txt.import")).unwrap();
+        let mut hasher = StableHasher::default();
+        hasher.write_u64(hash_bytes(b"c"));
+        hasher.write_option_u64(None);
+        hasher.write_bool(true);
+        hasher.write_u64(1);
+        hasher.write_str("txt");
+        hasher.write_u64(0);
+        assert_eq!(record.hash, hasher.finish());
+
+        // deleting a source asset removes its processed asset
+        std::fs::remove_file(source.path().join("text/a.txt")).unwrap();
+        std::fs::remove_file(source.path().join("text/a.txt.meta")).unwrap();
+        assert!(process_all().is_empty());
+        for removed in ["text/a.txt", "text/a.txt.meta", "text/a.txt.import"] {
+            assert!(!destination.path().join(removed).exists(), "{}", removed);
+        }
+        assert!(destination.path().join("c.txt.import").exists());
+        assert!(pipeline.get_dependents(Path::new("b.part")).is_empty());
+    }
+
+    #[test]
+    fn process_all_continues_after_a_failure() {
+        let source = tempfile::tempdir().unwrap();
+        let destination = tempfile::tempdir().unwrap();
+        std::fs::write(source.path().join("a.txt"), "include missing.part").unwrap();
+        std::fs::write(source.path().join("b.txt"), "b").unwrap();
+        std::fs::write(destination.path().join("deleted.txt"), "D").unwrap();
+        std::fs::write(destination.path().join("deleted.txt.import"), "").unwrap();
+
+        let mut pipeline =
+            AssetPipeline::new(FileAssetIo::new(source.path(), false), destination.path());
+        pipeline.add_processor(UppercaseProcessor);
+        let result = futures_lite::future::block_on(pipeline.process_all()).unwrap();
+
+        assert_eq!(result.written, vec![PathBuf::from("b.txt")]);
+        assert_eq!(result.failed.len(), 1);
+        assert_eq!(result.failed[0].0, PathBuf::from("a.txt"));
+        assert!(matches!(
+            result.failed[0].1,
+            AssetProcessError::ProcessorError { .. }
+        ));
+        assert_eq!(
+            std::fs::read_to_string(destination.path().join("b.txt")).unwrap(),
+            "B"
+        );
+        assert!(!destination.path().join("a.txt").exists());
+        // the assets whose source was deleted are still pruned
+        assert!(!destination.path().join("deleted.txt").exists());
+    }
+}
@@ -0,0 +1,266 @@
+use crate::{Asset, Assets, Handle, HandleId, HandleUntyped};
+use bevy_ecs::world::World;
+use bevy_reflect::{FromReflect, FromType, Reflect, ReflectRef};
+use bevy_utils::Uuid;
+use std::any::TypeId;
+
+/// A struct used to operate on the reflected [`Assets`] collection of an asset type.
+///
+/// A [`ReflectAsset`] for type `T` can be obtained via
+/// [`bevy_reflect::TypeRegistration::data`], once the asset type has been registered with
+/// [`AddAsset::register_asset_reflect`](crate::AddAsset::register_asset_reflect).
+#[derive(Clone)]
+pub struct ReflectAsset {
+    type_uuid: Uuid,
+    handle_type_id: TypeId,
+    get: fn(&World, HandleId) -> Option<&dyn Reflect>,
+    get_mut: fn(&mut World, HandleId) -> Option<&mut dyn Reflect>,
+    add: fn(&mut World, &dyn Reflect) -> HandleUntyped,
+    set: fn(&mut World, HandleId, &dyn Reflect) -> HandleUntyped,
+    remove: fn(&mut World, HandleId) -> Option<Box<dyn Reflect>>,
+    len: fn(&World) -> usize,
+    ids: fn(&World) -> Vec<HandleId>,
+}
+
+impl ReflectAsset {
+    /// The [`TypeUuid`](bevy_reflect::TypeUuid) of the asset type.
+    pub fn type_uuid(&self) -> Uuid {
+        self.type_uuid
+    }
+
+    /// The [`TypeId`] of the [`Handle`] of the asset type.
+    pub fn handle_type_id(&self) -> TypeId {
+        self.handle_type_id
+    }
+
+    pub fn get<'w, H: Into<HandleId>>(
+        &self,
+        world: &'w World,
+        handle: H,
+    ) -> Option<&'w dyn Reflect> {
+        (self.get)(world, handle.into())
+    }
+
+    pub fn get_mut<'w, H: Into<HandleId>>(
+        &self,
+        world: &'w mut World,
+        handle: H,
+    ) -> Option<&'w mut dyn Reflect> {
+        (self.get_mut)(world, handle.into())
+    }
+
+    /// Adds an asset built from `value` with [`FromReflect`], returning a strong handle to it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the asset can't be built from `value`.
+    pub fn add(&self, world: &mut World, value: &dyn Reflect) -> HandleUntyped {
+        (self.add)(world, value)
+    }
+
+    /// Sets the asset of `handle` to an asset built from `value` with [`FromReflect`], returning a
+    /// strong handle to it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the asset can't be built from `value`.
+    pub fn set<H: Into<HandleId>>(
+        &self,
+        world: &mut World,
+        handle: H,
+        value: &dyn Reflect,
+    ) -> HandleUntyped {
+        (self.set)(world, handle.into(), value)
+    }
+
+    pub fn remove<H: Into<HandleId>>(
+        &self,
+        world: &mut World,
+        handle: H,
+    ) -> Option<Box<dyn Reflect>> {
+        (self.remove)(world, handle.into())
+    }
+
+    /// Returns the number of assets of this type.
+    pub fn len(&self, world: &World) -> usize {
+        (self.len)(world)
+    }
+
+    pub fn is_empty(&self, world: &World) -> bool {
+        self.len(world) == 0
+    }
+
+    /// Returns the ids of the assets of this type.
+    pub fn ids(&self, world: &World) -> Vec<HandleId> {
+        (self.ids)(world)
+    }
+}
+
+impl<A: Asset + FromReflect> FromType<A> for ReflectAsset {
+    fn from_type() -> Self {
+        ReflectAsset {
+            type_uuid: A::TYPE_UUID,
+            handle_type_id: TypeId::of::<Handle<A>>(),
+            get: |world, handle_id| {
+                let assets = world.get_resource::<Assets<A>>()?;
+                assets
+                    .get(&Handle::weak(handle_id))
+                    .map(|asset| asset as &dyn Reflect)
+            },
+            get_mut: |world, handle_id| {
+                let assets = world.get_resource_mut::<Assets<A>>()?.into_inner();
+                assets
+                    .get_mut(&Handle::weak(handle_id))
+                    .map(|asset| asset as &mut dyn Reflect)
+            },
+            add: |world, value| {
+                let asset = from_reflect::<A>(value);
+                world.resource_mut::<Assets<A>>().add(asset).clone_untyped()
+            },
+            set: |world, handle_id, value| {
+                let asset = from_reflect::<A>(value);
+                world
+                    .resource_mut::<Assets<A>>()
+                    .set(handle_id, asset)
+                    .clone_untyped()
+            },
+            remove: |world, handle_id| {
+                let asset = world.get_resource_mut::<Assets<A>>()?.remove(handle_id)?;
+                Some(Box::new(asset))
+            },
+            len: |world| world.get_resource::<Assets<A>>().map_or(0, Assets::len),
+            ids: |world| {
+                world
+                    .get_resource::<Assets<A>>()
+                    .map(|assets| assets.ids().collect())
+                    .unwrap_or_default()
+            },
+        }
+    }
+}
+
+fn from_reflect<A: Asset + FromReflect>(value: &dyn Reflect) -> A {
+    A::from_reflect(value).unwrap_or_else(|| {
+        panic!(
+            "Failed to build a {} from the value of type {}.",
+            std::any::type_name::<A>(),
+            value.type_name()
+        )
+    })
+}
+
+/// A struct used to operate on reflected [`Handle`]s of an asset type.
+///
+/// It is registered with the [`Handle`] type of every asset added with
+/// [`AddAsset::add_asset`](crate::AddAsset::add_asset). The handles can be either the [`Handle`]
+/// itself or a dynamic struct cloned from it, such as the components of a scene.
+#[derive(Clone)]
+pub struct ReflectHandle {
+    asset_type_uuid: Uuid,
+    asset_type_id: TypeId,
+    typed: fn(HandleUntyped) -> Box<dyn Reflect>,
+}
+
+impl ReflectHandle {
+    /// The [`TypeUuid`](bevy_reflect::TypeUuid) of the asset type.
+    pub fn asset_type_uuid(&self) -> Uuid {
+        self.asset_type_uuid
+    }
+
+    pub fn asset_type_id(&self) -> TypeId {
+        self.asset_type_id
+    }
+
+    /// Returns the id of the reflected `handle`, or `None` if it isn't a handle.
+    pub fn handle_id(&self, handle: &dyn Reflect) -> Option<HandleId> {
+        match handle.reflect_ref() {
+            ReflectRef::Struct(handle) => handle.field("id")?.downcast_ref::<HandleId>().copied(),
+            _ => None,
+        }
+    }
+
+    /// Returns a weak [`HandleUntyped`] to the asset of the reflected `handle`, or `None` if it
+    /// isn't a handle.
+    pub fn downcast_handle_untyped(&self, handle: &dyn Reflect) -> Option<HandleUntyped> {
+        self.handle_id(handle).map(HandleUntyped::weak)
+    }
+
+    /// Turns `handle` into a [`Handle`] of the asset type, keeping it strong if it is.
+    pub fn typed(&self, handle: HandleUntyped) -> Box<dyn Reflect> {
+        (self.typed)(handle)
+    }
+}
+
+impl<A: Asset> FromType<Handle<A>> for ReflectHandle {
+    fn from_type() -> Self {
+        ReflectHandle {
+            asset_type_uuid: A::TYPE_UUID,
+            asset_type_id: TypeId::of::<A>(),
+            typed: |handle| Box::new(handle.typed::<A>()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AddAsset, AssetPlugin};
+    use bevy_app::App;
+    use bevy_reflect::{TypeRegistryArc, TypeUuid};
+
+    #[derive(Debug, Reflect, FromReflect, TypeUuid)]
+    #[uuid = "0ab6c4a7-5e43-4a36-9dd4-7c3f2a2c9b41"]
+    struct Level {
+        name: String,
+    }
+
+    #[test]
+    fn reflect_asset() {
+        let mut app = App::new();
+        app.add_plugin(AssetPlugin)
+            .add_asset::<Level>()
+            .register_asset_reflect::<Level>();
+
+        let type_registry = app.world.resource::<TypeRegistryArc>().clone();
+        let type_registry = type_registry.read();
+        let reflect_asset = type_registry
+            .get_type_data::<ReflectAsset>(TypeId::of::<Level>())
+            .unwrap();
+        assert_eq!(reflect_asset.type_uuid(), Level::TYPE_UUID);
+
+        let handle = reflect_asset.add(
+            &mut app.world,
+            &Level {
+                name: "intro".to_string(),
+            },
+        );
+        assert_eq!(reflect_asset.len(&app.world), 1);
+        assert_eq!(reflect_asset.ids(&app.world), vec![handle.id]);
+
+        let level = reflect_asset.get_mut(&mut app.world, &handle).unwrap();
+        level.apply(&Level {
+            name: "outro".to_string(),
+        });
+        let level = reflect_asset.get(&app.world, &handle).unwrap();
+        assert_eq!(level.downcast_ref::<Level>().unwrap().name, "outro");
+
+        // handles can be found from their reflected or dynamic values
+        let reflect_handle = type_registry
+            .get_type_data::<ReflectHandle>(reflect_asset.handle_type_id())
+            .unwrap();
+        let typed = reflect_handle.typed(handle.clone());
+        assert_eq!(reflect_handle.handle_id(&*typed), Some(handle.id));
+        assert_eq!(
+            reflect_handle.handle_id(&*typed.clone_value()),
+            Some(handle.id)
+        );
+        assert_eq!(
+            reflect_handle.downcast_handle_untyped(&*typed),
+            Some(HandleUntyped::weak(handle.id))
+        );
+
+        let level = reflect_asset.remove(&mut app.world, &handle).unwrap();
+        assert_eq!(level.downcast_ref::<Level>().unwrap().name, "outro");
+        assert!(reflect_asset.is_empty(&app.world));
+    }
+}
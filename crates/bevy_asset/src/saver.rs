@@ -0,0 +1,86 @@
+use crate::{path::AssetPath, Asset, AssetDynamic, AssetServer, Assets, HandleId};
+use anyhow::Result;
+use bevy_ecs::system::Res;
+use bevy_reflect::TypeUuid;
+use bevy_utils::Uuid;
+use std::path::Path;
+
+/// A saver for an asset type, the counterpart of an [`AssetLoader`](crate::AssetLoader)
+///
+/// Savers are used by [`AssetServer::save`] to turn an asset into the bytes of a file with one of
+/// their [`extensions`](AssetSaver::extensions).
+pub trait AssetSaver: Send + Sync + 'static {
+    type Asset: Asset;
+
+    fn save(&self, asset: &Self::Asset, save_context: &SaveContext) -> Result<Vec<u8>>;
+    fn extensions(&self) -> &[&str];
+}
+
+/// The context of an asset being saved by an [`AssetSaver`]
+pub struct SaveContext<'a> {
+    pub(crate) path: &'a AssetPath<'a>,
+    pub(crate) asset_server: &'a AssetServer,
+}
+
+impl<'a> SaveContext<'a> {
+    /// The path the asset is saved to.
+    pub fn path(&self) -> &Path {
+        self.path.path()
+    }
+
+    /// The name of the asset source the asset is saved to, or `None` for the default source.
+    pub fn source(&self) -> Option<&str> {
+        self.path.source()
+    }
+
+    /// The [`AssetServer`] saving the asset.
+    pub fn asset_server(&self) -> &AssetServer {
+        self.asset_server
+    }
+
+    /// Returns the path of the asset of the given handle, to save references to other assets.
+    pub fn get_handle_path<H: Into<HandleId>>(&self, handle: H) -> Option<AssetPath<'static>> {
+        self.asset_server
+            .get_handle_path(handle)
+            .map(|path| path.to_owned())
+    }
+}
+
+/// An [`AssetSaver`] with its asset type erased, as stored by the [`AssetServer`]
+pub(crate) trait ErasedAssetSaver: Send + Sync + 'static {
+    fn save(&self, asset: &dyn AssetDynamic, save_context: &SaveContext) -> Result<Vec<u8>>;
+    fn extensions(&self) -> &[&str];
+    fn asset_type_uuid(&self) -> Uuid;
+}
+
+impl<S: AssetSaver> ErasedAssetSaver for S {
+    fn save(&self, asset: &dyn AssetDynamic, save_context: &SaveContext) -> Result<Vec<u8>> {
+        let asset = asset.downcast_ref::<S::Asset>().unwrap_or_else(|| {
+            panic!(
+                "Failed to downcast asset to {}.",
+                std::any::type_name::<S::Asset>()
+            )
+        });
+        AssetSaver::save(self, asset, save_context)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        AssetSaver::extensions(self)
+    }
+
+    fn asset_type_uuid(&self) -> Uuid {
+        S::Asset::TYPE_UUID
+    }
+}
+
+/// A request to save an asset, queued by [`AssetServer::save`]
+pub(crate) struct SaveRequest {
+    pub(crate) handle_id: HandleId,
+    pub(crate) type_uuid: Uuid,
+    pub(crate) path: AssetPath<'static>,
+}
+
+/// Saves the assets of type `T` queued with [`AssetServer::save`].
+pub fn save_assets_system<T: Asset>(asset_server: Res<AssetServer>, assets: Res<Assets<T>>) {
+    asset_server.save_queued_assets(&assets);
+}
@@ -0,0 +1,170 @@
+//! Hashes that are persisted to disk, such as the hashes of import records and asset packs.
+//!
+//! Unlike [`DefaultHasher`](std::collections::hash_map::DefaultHasher), whose algorithm is
+//! unspecified and may change between Rust releases, these use XXH64 with a seed of `0` and only
+//! ever hash explicit little-endian encodings of their inputs, so they never depend on the
+//! [`Hash`](std::hash::Hash) implementations of the standard library.
+
+use std::path::Path;
+
+const PRIME_1: u64 = 0x9E37_79B1_85EB_CA87;
+const PRIME_2: u64 = 0xC2B2_AE3D_27D4_EB4F;
+const PRIME_3: u64 = 0x1656_67B1_9E37_79F9;
+const PRIME_4: u64 = 0x85EB_CA77_C2B2_AE63;
+const PRIME_5: u64 = 0x27D4_EB2F_1656_67C5;
+
+/// Returns the XXH64 hash of `bytes`, with a seed of `0`.
+pub(crate) fn hash_bytes(bytes: &[u8]) -> u64 {
+    let seed = 0u64;
+    let mut rest = bytes;
+    let mut hash = if bytes.len() >= 32 {
+        let mut accumulators = [
+            seed.wrapping_add(PRIME_1).wrapping_add(PRIME_2),
+            seed.wrapping_add(PRIME_2),
+            seed,
+            seed.wrapping_sub(PRIME_1),
+        ];
+        while rest.len() >= 32 {
+            for (i, accumulator) in accumulators.iter_mut().enumerate() {
+                *accumulator = round(*accumulator, read_u64(&rest[i * 8..]));
+            }
+            rest = &rest[32..];
+        }
+        let [v1, v2, v3, v4] = accumulators;
+        let mut hash = v1
+            .rotate_left(1)
+            .wrapping_add(v2.rotate_left(7))
+            .wrapping_add(v3.rotate_left(12))
+            .wrapping_add(v4.rotate_left(18));
+        for accumulator in accumulators {
+            hash = (hash ^ round(0, accumulator))
+                .wrapping_mul(PRIME_1)
+                .wrapping_add(PRIME_4);
+        }
+        hash
+    } else {
+        seed.wrapping_add(PRIME_5)
+    };
+
+    hash = hash.wrapping_add(bytes.len() as u64);
+    while rest.len() >= 8 {
+        hash ^= round(0, read_u64(rest));
+        hash = hash
+            .rotate_left(27)
+            .wrapping_mul(PRIME_1)
+            .wrapping_add(PRIME_4);
+        rest = &rest[8..];
+    }
+    if rest.len() >= 4 {
+        let value = u32::from_le_bytes([rest[0], rest[1], rest[2], rest[3]]) as u64;
+        hash ^= value.wrapping_mul(PRIME_1);
+        hash = hash
+            .rotate_left(23)
+            .wrapping_mul(PRIME_2)
+            .wrapping_add(PRIME_3);
+        rest = &rest[4..];
+    }
+    for &byte in rest {
+        hash ^= (byte as u64).wrapping_mul(PRIME_5);
+        hash = hash.rotate_left(11).wrapping_mul(PRIME_1);
+    }
+
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(PRIME_2);
+    hash ^= hash >> 29;
+    hash = hash.wrapping_mul(PRIME_3);
+    hash ^ (hash >> 32)
+}
+
+fn round(accumulator: u64, input: u64) -> u64 {
+    accumulator
+        .wrapping_add(input.wrapping_mul(PRIME_2))
+        .rotate_left(31)
+        .wrapping_mul(PRIME_1)
+}
+
+fn read_u64(bytes: &[u8]) -> u64 {
+    let mut value = [0; 8];
+    value.copy_from_slice(&bytes[..8]);
+    u64::from_le_bytes(value)
+}
+
+/// Combines several values into a single [`hash_bytes`] hash.
+///
+/// Variable-length values are prefixed with their length so that different sequences of values
+/// never produce the same input.
+#[derive(Clone, Default)]
+pub(crate) struct StableHasher {
+    bytes: Vec<u8>,
+}
+
+impl StableHasher {
+    pub fn write_u64(&mut self, value: u64) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn write_bool(&mut self, value: bool) {
+        self.bytes.push(value as u8);
+    }
+
+    pub fn write_str(&mut self, value: &str) {
+        self.write_u64(value.len() as u64);
+        self.bytes.extend_from_slice(value.as_bytes());
+    }
+
+    /// Writes `path` with `/` separators, so that it hashes the same on every platform.
+    pub fn write_path(&mut self, path: &Path) {
+        let path = path
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        self.write_str(&path);
+    }
+
+    pub fn write_option_u64(&mut self, value: Option<u64>) {
+        self.write_bool(value.is_some());
+        if let Some(value) = value {
+            self.write_u64(value);
+        }
+    }
+
+    pub fn finish(&self) -> u64 {
+        hash_bytes(&self.bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{hash_bytes, StableHasher};
+    use std::path::Path;
+
+    #[test]
+    fn xxh64_reference_values() {
+        assert_eq!(hash_bytes(b""), 0xEF46_DB37_51D8_E999);
+        assert_eq!(hash_bytes(b"a"), 0xD24E_C4F1_A98C_6E5B);
+        assert_eq!(hash_bytes(b"abc"), 0x44BC_2CF5_AD77_0999);
+        assert_eq!(
+            hash_bytes(b"Nobody inspects the spammish repetition"),
+            0xFBCE_A83C_8A37_8BF1
+        );
+    }
+
+    #[test]
+    fn hasher_separates_values() {
+        let hash = |values: &[&str]| {
+            let mut hasher = StableHasher::default();
+            for value in values {
+                hasher.write_str(value);
+            }
+            hasher.finish()
+        };
+        assert_ne!(hash(&["ab", "c"]), hash(&["a", "bc"]));
+
+        let mut a = StableHasher::default();
+        a.write_path(Path::new("a/b.png"));
+        let mut b = StableHasher::default();
+        b.write_str("a/b.png");
+        assert_eq!(a.finish(), b.finish());
+    }
+}
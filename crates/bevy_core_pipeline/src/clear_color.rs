@@ -22,7 +22,8 @@ impl Default for ClearColorConfig {
 ///
 /// This color appears as the "background" color for simple apps, when
 /// there are portions of the screen with nothing rendered.
-#[derive(Component, Clone, Debug, Deref, DerefMut, ExtractResource)]
+#[derive(Component, Clone, Debug, Deref, DerefMut, ExtractResource, Reflect)]
+#[reflect(Resource)]
 pub struct ClearColor(pub Color);
 
 impl Default for ClearColor {
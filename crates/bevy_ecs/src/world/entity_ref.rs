@@ -126,6 +126,18 @@ impl<'w> EntityRef<'w> {
         // SAFE: entity_location is valid, component_id is valid as checked by the line above
         unsafe { get_component(self.world, component_id, self.entity, self.location) }
     }
+
+    /// Retrieves the change ticks of the component with the given [`ComponentId`], like
+    /// [`EntityRef::get_change_ticks`] for components whose type isn't known at compile time.
+    #[inline]
+    pub fn get_change_ticks_by_id(&self, component_id: ComponentId) -> Option<&'w ComponentTicks> {
+        self.world.components().get_info(component_id)?;
+        // SAFE: entity_location is valid, component_id is valid as checked by the line above
+        unsafe {
+            get_ticks(self.world, component_id, self.entity, self.location)
+                .map(|ticks| ticks.deref())
+        }
+    }
 }
 
 /// A mutable reference to a particular [`Entity`] and all of its components
@@ -960,6 +972,37 @@ mod tests {
         assert_eq!(test_component.0, 43);
     }
 
+    #[test]
+    fn entity_ref_get_change_ticks_by_id() {
+        let mut world = World::new();
+        let entity = world.spawn().insert(TestComponent(42)).id();
+        let without = world.spawn().id();
+        let component_id = world
+            .components()
+            .get_id(std::any::TypeId::of::<TestComponent>())
+            .unwrap();
+        let last_change_tick = world.increment_change_tick();
+
+        let ticks = world.entity(entity).get_change_ticks_by_id(component_id);
+        assert!(!ticks
+            .unwrap()
+            .is_changed(last_change_tick, world.read_change_tick()));
+        world.get_mut::<TestComponent>(entity).unwrap().0 = 43;
+        let ticks = world.entity(entity).get_change_ticks_by_id(component_id);
+        assert!(ticks
+            .unwrap()
+            .is_changed(last_change_tick, world.read_change_tick()));
+
+        assert!(world
+            .entity(without)
+            .get_change_ticks_by_id(component_id)
+            .is_none());
+        assert!(world
+            .entity(entity)
+            .get_change_ticks_by_id(ComponentId::new(usize::MAX))
+            .is_none());
+    }
+
     #[test]
     fn entity_ref_get_by_id_invalid_component_id() {
         let invalid_component_id = ComponentId::new(usize::MAX);
@@ -1,6 +1,10 @@
 use crate::{Axis, Input};
 use bevy_ecs::event::{EventReader, EventWriter};
+use bevy_ecs::reflect::ReflectResource;
 use bevy_ecs::system::{Res, ResMut};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+#[cfg(feature = "serialize")]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
 use bevy_utils::{tracing::info, HashMap, HashSet};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
@@ -110,8 +114,13 @@ pub enum GamepadButtonType {
     DPadRight,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Reflect)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serialize",
+    reflect_value(PartialEq, Hash, Serialize, Deserialize)
+)]
+#[cfg_attr(not(feature = "serialize"), reflect_value(PartialEq, Hash))]
 pub struct GamepadButton {
     pub gamepad: Gamepad,
     pub button_type: GamepadButtonType,
@@ -139,8 +148,13 @@ pub enum GamepadAxisType {
     DPadY,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Reflect)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serialize",
+    reflect_value(PartialEq, Hash, Serialize, Deserialize)
+)]
+#[cfg_attr(not(feature = "serialize"), reflect_value(PartialEq, Hash))]
 pub struct GamepadAxis {
     pub gamepad: Gamepad,
     pub axis_type: GamepadAxisType,
@@ -152,7 +166,8 @@ impl GamepadAxis {
     }
 }
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Reflect)]
+#[reflect(Resource)]
 pub struct GamepadSettings {
     pub default_button_settings: ButtonSettings,
     pub default_axis_settings: AxisSettings,
@@ -182,7 +197,8 @@ impl GamepadSettings {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Reflect)]
+#[reflect(Default)]
 pub struct ButtonSettings {
     pub press: f32,
     pub release: f32,
@@ -215,7 +231,8 @@ impl ButtonSettings {
 /// Otherwise, values will not be rounded.
 ///
 /// The valid range is from -1.0 to 1.0, inclusive.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Reflect)]
+#[reflect(Default)]
 pub struct AxisSettings {
     pub positive_high: f32,
     pub positive_low: f32,
@@ -259,7 +276,8 @@ impl AxisSettings {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Reflect)]
+#[reflect(Default)]
 pub struct ButtonAxisSettings {
     pub high: f32,
     pub low: f32,
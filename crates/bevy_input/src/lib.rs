@@ -30,8 +30,8 @@ use prelude::Gamepads;
 use touch::{touch_screen_input_system, TouchInput, Touches};
 
 use gamepad::{
-    gamepad_connection_system, gamepad_event_system, GamepadAxis, GamepadButton, GamepadEvent,
-    GamepadEventRaw, GamepadSettings,
+    gamepad_connection_system, gamepad_event_system, AxisSettings, ButtonAxisSettings,
+    ButtonSettings, GamepadAxis, GamepadButton, GamepadEvent, GamepadEventRaw, GamepadSettings,
 };
 
 /// Adds keyboard and mouse input to an App
@@ -64,6 +64,12 @@ impl Plugin for InputPlugin {
             .add_event::<GamepadEvent>()
             .add_event::<GamepadEventRaw>()
             .init_resource::<GamepadSettings>()
+            .register_type::<GamepadSettings>()
+            .register_type::<GamepadButton>()
+            .register_type::<GamepadAxis>()
+            .register_type::<ButtonSettings>()
+            .register_type::<AxisSettings>()
+            .register_type::<ButtonAxisSettings>()
             .init_resource::<Gamepads>()
             .init_resource::<Input<GamepadButton>>()
             .init_resource::<Axis<GamepadAxis>>()
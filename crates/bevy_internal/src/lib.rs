@@ -65,6 +65,11 @@ pub mod scene {
     pub use bevy_scene::*;
 }
 
+pub mod settings {
+    //! App configuration loaded from a settings file.
+    pub use bevy_settings::*;
+}
+
 pub mod tasks {
     //! Pools for async, IO, and compute tasks.
     pub use bevy_tasks::*;
@@ -131,6 +136,12 @@ pub mod pbr {
     pub use bevy_pbr::*;
 }
 
+#[cfg(feature = "bevy_remote")]
+pub mod remote {
+    //! Remote inspection and editing of a running app.
+    pub use bevy_remote::*;
+}
+
 #[cfg(feature = "bevy_render")]
 pub mod render {
     //! Cameras, meshes, textures, shaders, and pipelines.
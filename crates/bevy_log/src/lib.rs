@@ -30,11 +30,16 @@ pub use bevy_utils::tracing::{
     Level,
 };
 
-use bevy_app::{App, Plugin};
+use bevy_app::{App, CoreStage, Plugin};
+use bevy_ecs::{
+    reflect::ReflectResource,
+    system::{Res, ResMut},
+};
+use bevy_reflect::Reflect;
 use tracing_log::LogTracer;
 #[cfg(feature = "tracing-chrome")]
 use tracing_subscriber::fmt::{format::DefaultFields, FormattedFields};
-use tracing_subscriber::{prelude::*, registry::Registry, EnvFilter};
+use tracing_subscriber::{prelude::*, registry::Registry, reload, EnvFilter};
 
 /// Adds logging to Apps. This plugin is part of the `DefaultPlugins`. Adding
 /// this plugin will setup a collector appropriate to your target platform:
@@ -69,6 +74,9 @@ use tracing_subscriber::{prelude::*, registry::Registry, EnvFilter};
 /// If you define the `RUST_LOG` environment variable, the [`LogSettings`] resource
 /// will be ignored.
 ///
+/// Otherwise, changes made to the [`LogSettings`] resource after startup are applied at the
+/// start of the next frame.
+///
 /// If you want to setup your own tracing collector, you should disable this
 /// plugin from `DefaultPlugins` with [`App::add_plugins_with`]:
 /// ```no_run
@@ -91,6 +99,8 @@ use tracing_subscriber::{prelude::*, registry::Registry, EnvFilter};
 pub struct LogPlugin;
 
 /// `LogPlugin` settings
+#[derive(Reflect)]
+#[reflect(Resource)]
 pub struct LogSettings {
     /// Filters logs using the [`EnvFilter`] format
     pub filter: String,
@@ -100,6 +110,39 @@ pub struct LogSettings {
     pub level: Level,
 }
 
+impl LogSettings {
+    fn env_filter(&self) -> String {
+        format!("{},{}", self.level, self.filter)
+    }
+}
+
+/// The filter of the global subscriber set up by [`LogPlugin`], along with the [`LogSettings`]
+/// it was built from.
+struct LogFilter {
+    handle: reload::Handle<EnvFilter, Registry>,
+    applied: String,
+}
+
+/// Rebuilds the filter of the global subscriber when the [`LogSettings`] changed.
+fn update_log_filter(settings: Res<LogSettings>, mut filter: ResMut<LogFilter>) {
+    if !settings.is_changed() {
+        return;
+    }
+    let env_filter = settings.env_filter();
+    if env_filter == filter.applied {
+        return;
+    }
+    match EnvFilter::try_new(&env_filter) {
+        Ok(new_filter) => {
+            if let Err(err) = filter.handle.reload(new_filter) {
+                error!("failed to update the log filter: {}", err);
+            }
+        }
+        Err(err) => error!("invalid log filter `{}`: {}", env_filter, err),
+    }
+    filter.applied = env_filter;
+}
+
 impl Default for LogSettings {
     fn default() -> Self {
         Self {
@@ -120,14 +163,27 @@ impl Plugin for LogPlugin {
             }));
         }
 
-        let default_filter = {
-            let settings = app.world.get_resource_or_insert_with(LogSettings::default);
-            format!("{},{}", settings.level, settings.filter)
-        };
+        app.register_type::<LogSettings>().register_type::<Level>();
+
+        let default_filter = app
+            .world
+            .get_resource_or_insert_with(LogSettings::default)
+            .env_filter();
         LogTracer::init().unwrap();
-        let filter_layer = EnvFilter::try_from_default_env()
-            .or_else(|_| EnvFilter::try_new(&default_filter))
-            .unwrap();
+        let env_filter = EnvFilter::try_from_default_env();
+        let from_env = env_filter.is_ok();
+        let (filter_layer, handle) = reload::Layer::new(
+            env_filter
+                .or_else(|_| EnvFilter::try_new(&default_filter))
+                .unwrap(),
+        );
+        if !from_env {
+            app.insert_resource(LogFilter {
+                handle,
+                applied: default_filter,
+            })
+            .add_system_to_stage(CoreStage::First, update_log_filter);
+        }
         let subscriber = Registry::default().with(filter_layer);
 
         #[cfg(feature = "trace")]
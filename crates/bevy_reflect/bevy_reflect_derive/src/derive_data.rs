@@ -2,6 +2,7 @@ use crate::container_attributes::ReflectTraits;
 use crate::field_attributes::{parse_field_attrs, ReflectFieldAttr};
 use crate::utility::get_bevy_reflect_path;
 use crate::{REFLECT_ATTRIBUTE_NAME, REFLECT_VALUE_ATTRIBUTE_NAME};
+use syn::spanned::Spanned;
 use syn::{Data, DataStruct, DeriveInput, Field, Fields, Generics, Ident, Meta, Path};
 
 pub(crate) enum DeriveType {
@@ -114,14 +115,27 @@ impl<'a> ReflectDeriveData<'a> {
             .iter()
             .enumerate()
             .map(|(index, field)| {
-                let attrs = parse_field_attrs(&field.attrs).unwrap_or_else(|err| {
-                    if let Some(ref mut errors) = errors {
-                        errors.combine(err);
-                    } else {
-                        errors = Some(err);
-                    }
-                    ReflectFieldAttr::default()
-                });
+                let attrs = parse_field_attrs(&field.attrs)
+                    .and_then(|attrs| {
+                        if field.ident.is_none()
+                            && (attrs.skip_serializing || !attrs.metadata.is_empty())
+                        {
+                            Err(syn::Error::new(
+                                field.span(),
+                                "`skip_serializing` and `meta` are only supported on named fields",
+                            ))
+                        } else {
+                            Ok(attrs)
+                        }
+                    })
+                    .unwrap_or_else(|err| {
+                        if let Some(ref mut errors) = errors {
+                            errors.combine(err);
+                        } else {
+                            errors = Some(err);
+                        }
+                        ReflectFieldAttr::default()
+                    });
 
                 StructField {
                     index,
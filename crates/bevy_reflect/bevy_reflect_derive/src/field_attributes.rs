@@ -11,6 +11,8 @@ use syn::{Attribute, Lit, Meta, NestedMeta};
 
 pub(crate) static IGNORE_ATTR: &str = "ignore";
 pub(crate) static DEFAULT_ATTR: &str = "default";
+pub(crate) static SKIP_SERIALIZING_ATTR: &str = "skip_serializing";
+pub(crate) static META_ATTR: &str = "meta";
 
 /// A container for attributes defined on a reflected type's field.
 #[derive(Default)]
@@ -19,6 +21,10 @@ pub(crate) struct ReflectFieldAttr {
     pub ignore: bool,
     /// Sets the default behavior of this field.
     pub default: DefaultBehavior,
+    /// Determines if this field should be skipped when serializing.
+    pub skip_serializing: bool,
+    /// Metadata entries attached to this field with `#[reflect(meta(key = value))]`.
+    pub metadata: Vec<(String, Lit)>,
 }
 
 /// Controls how the default value is determined for a field.
@@ -66,7 +72,8 @@ pub(crate) fn parse_field_attrs(attrs: &[Attribute]) -> Result<ReflectFieldAttr,
     }
 }
 
-/// Recursively parses attribute metadata for things like `#[reflect(ignore)]` and `#[reflect(default = "foo")]`
+/// Recursively parses attribute metadata for things like `#[reflect(ignore)]`, `#[reflect(default = "foo")]`
+/// and `#[reflect(meta(tooltip = "foo"))]`
 fn parse_meta(args: &mut ReflectFieldAttr, meta: &Meta) -> Result<(), syn::Error> {
     match meta {
         Meta::Path(path) if path.is_ident(IGNORE_ATTR) => {
@@ -77,6 +84,10 @@ fn parse_meta(args: &mut ReflectFieldAttr, meta: &Meta) -> Result<(), syn::Error
             args.default = DefaultBehavior::Default;
             Ok(())
         }
+        Meta::Path(path) if path.is_ident(SKIP_SERIALIZING_ATTR) => {
+            args.skip_serializing = true;
+            Ok(())
+        }
         Meta::Path(path) => Err(syn::Error::new(
             path.span(),
             format!("unknown attribute parameter: {}", path.to_token_stream()),
@@ -103,6 +114,25 @@ fn parse_meta(args: &mut ReflectFieldAttr, meta: &Meta) -> Result<(), syn::Error
                 format!("unknown attribute parameter: {}", path.to_token_stream()),
             ))
         }
+        Meta::List(list) if list.path.is_ident(META_ATTR) => {
+            for nested in list.nested.iter() {
+                match nested {
+                    NestedMeta::Meta(Meta::NameValue(pair)) => {
+                        let key = pair.path.get_ident().ok_or_else(|| {
+                            syn::Error::new(pair.path.span(), "expected a metadata key")
+                        })?;
+                        args.metadata.push((key.to_string(), pair.lit.clone()));
+                    }
+                    other => {
+                        return Err(syn::Error::new(
+                            other.span(),
+                            "expected a metadata entry of the form `key = literal`",
+                        ))
+                    }
+                }
+            }
+            Ok(())
+        }
         Meta::List(list) if !list.path.is_ident(REFLECT_ATTRIBUTE_NAME) => {
             Err(syn::Error::new(list.path.span(), "unexpected property"))
         }
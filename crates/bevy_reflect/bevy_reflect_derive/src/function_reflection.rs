@@ -0,0 +1,102 @@
+use crate::{field_attributes::IGNORE_ATTR, REFLECT_ATTRIBUTE_NAME};
+use bevy_macro_utils::BevyManifest;
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Attribute, ImplItem, ItemImpl, Meta, NestedMeta, Visibility};
+
+/// An impl attribute macro that registers the public methods of an inherent impl block as
+/// reflected functions.
+///
+/// Methods marked with `#[reflect(ignore)]` are not registered, for example those whose
+/// arguments can't be reflected.
+///
+/// This generates an implementation of `GetTypeFunctions` for the type, which allows it to
+/// be registered with `#[reflect(Functions)]`.
+pub(crate) fn reflect_functions(_args: &TokenStream, input: TokenStream) -> TokenStream {
+    let mut item_impl = parse_macro_input!(input as ItemImpl);
+    if let Some((_, path, _)) = &item_impl.trait_ {
+        return syn::Error::new_spanned(
+            path,
+            "#[reflect_functions] can only be used on inherent impl blocks",
+        )
+        .into_compile_error()
+        .into();
+    }
+
+    let mut methods = Vec::new();
+    let mut errors: Option<syn::Error> = None;
+    for item in &mut item_impl.items {
+        if let ImplItem::Method(method) = item {
+            match take_ignore_attr(&mut method.attrs) {
+                Ok(true) => {}
+                Ok(false) => {
+                    if matches!(method.vis, Visibility::Public(_))
+                        && method.sig.generics.params.is_empty()
+                        && method.sig.asyncness.is_none()
+                    {
+                        methods.push(method.sig.ident.clone());
+                    }
+                }
+                Err(err) => match &mut errors {
+                    Some(error) => error.combine(err),
+                    None => errors = Some(err),
+                },
+            }
+        }
+    }
+    if let Some(error) = errors {
+        return error.into_compile_error().into();
+    }
+
+    let bevy_reflect_path = BevyManifest::default().get_path("bevy_reflect");
+    let self_ty = &item_impl.self_ty;
+    let (impl_generics, _, where_clause) = item_impl.generics.split_for_impl();
+
+    let functions = methods.iter().map(|ident| {
+        let name = ident.to_string();
+        quote! {
+            #bevy_reflect_path::IntoFunction::into_function(Self::#ident, #name)
+        }
+    });
+
+    TokenStream::from(quote! {
+        #item_impl
+
+        impl #impl_generics #bevy_reflect_path::GetTypeFunctions for #self_ty #where_clause {
+            fn get_type_functions() -> Vec<#bevy_reflect_path::DynamicFunction> {
+                vec![#(#functions),*]
+            }
+        }
+    })
+}
+
+/// Removes the `#[reflect(ignore)]` attributes of a method, which would be unknown to the
+/// compiler, and returns whether there was one.
+fn take_ignore_attr(attrs: &mut Vec<Attribute>) -> Result<bool, syn::Error> {
+    let mut ignore = false;
+    let mut result = Ok(());
+    attrs.retain(|attr| {
+        if !attr.path.is_ident(REFLECT_ATTRIBUTE_NAME) {
+            return true;
+        }
+        match attr.parse_meta() {
+            Ok(Meta::List(list))
+                if list.nested.len() == 1
+                    && matches!(
+                        &list.nested[0],
+                        NestedMeta::Meta(Meta::Path(path)) if path.is_ident(IGNORE_ATTR)
+                    ) =>
+            {
+                ignore = true;
+            }
+            _ => {
+                result = Err(syn::Error::new_spanned(
+                    attr,
+                    "expected `#[reflect(ignore)]` on a method of a #[reflect_functions] impl block",
+                ));
+            }
+        }
+        false
+    });
+    result.map(|()| ignore)
+}
@@ -1,9 +1,42 @@
 use crate::container_attributes::ReflectTraits;
+use crate::derive_data::StructField;
+use crate::field_attributes::DefaultBehavior;
 use crate::ReflectDeriveData;
 use proc_macro::TokenStream;
 use proc_macro2::Ident;
 use quote::quote;
-use syn::{Generics, Index, Member, Path};
+use syn::{Generics, Index, Lit, Member, Path};
+
+/// Returns the builder calls applying the `#[reflect(...)]` attributes of a field to its `NamedField`.
+fn get_named_field_attrs(
+    field: &StructField,
+    bevy_reflect_path: &Path,
+) -> proc_macro2::TokenStream {
+    let ty = &field.data.ty;
+    let default = match &field.attrs.default {
+        DefaultBehavior::Required => None,
+        DefaultBehavior::Default => Some(quote! {
+            .with_default(|| Box::new(<#ty as Default>::default()))
+        }),
+        DefaultBehavior::Func(path) => Some(quote! {
+            .with_default(|| -> Box<dyn #bevy_reflect_path::Reflect> {
+                let value: #ty = #path();
+                Box::new(value)
+            })
+        }),
+    };
+    let skip_serializing = field
+        .attrs
+        .skip_serializing
+        .then(|| quote!(.with_skip_serializing(true)));
+    let metadata = field.attrs.metadata.iter().map(|(key, lit)| match lit {
+        Lit::Str(lit) => quote!(.with_metadata(#key, String::from(#lit))),
+        lit => quote!(.with_metadata(#key, #lit)),
+    });
+    quote! {
+        #default #skip_serializing #(#metadata)*
+    }
+}
 
 /// Implements `Struct`, `GetTypeRegistration`, and `Reflect` for the given derive data.
 pub(crate) fn impl_struct(derive_data: &ReflectDeriveData) -> TokenStream {
@@ -38,6 +71,10 @@ pub(crate) fn impl_struct(derive_data: &ReflectDeriveData) -> TokenStream {
         .collect::<Vec<_>>();
     let field_count = field_idents.len();
     let field_indices = (0..field_count).collect::<Vec<usize>>();
+    let field_attrs = derive_data
+        .active_fields()
+        .map(|field| get_named_field_attrs(field, bevy_reflect_path))
+        .collect::<Vec<_>>();
 
     let hash_fn = derive_data.traits().get_hash_impl(bevy_reflect_path);
     let partial_eq_fn = derive_data
@@ -57,7 +94,7 @@ pub(crate) fn impl_struct(derive_data: &ReflectDeriveData) -> TokenStream {
         derive_data.generics(),
         quote! {
            let fields: [#bevy_reflect_path::NamedField; #field_count] = [
-                #(#bevy_reflect_path::NamedField::new::<#field_types, _>(#field_names),)*
+                #(#bevy_reflect_path::NamedField::new::<#field_types, _>(#field_names)#field_attrs,)*
             ];
             let info = #bevy_reflect_path::StructInfo::new::<Self>(&fields);
             #bevy_reflect_path::TypeInfo::Struct(info)
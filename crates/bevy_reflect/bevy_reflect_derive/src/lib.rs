@@ -5,12 +5,13 @@
 //! such as `Struct`, `GetTypeRegistration`, and more— all with a single derive!
 //!
 //! Some other noteworthy exports include the derive macros for [`FromReflect`] and
-//! [`TypeUuid`], as well as the [`reflect_trait`] attribute macro.
+//! [`TypeUuid`], as well as the [`reflect_trait`] and [`reflect_functions`] attribute macros.
 //!
 //! [`Reflect`]: crate::derive_reflect
 //! [`FromReflect`]: crate::derive_from_reflect
 //! [`TypeUuid`]: crate::derive_type_uuid
 //! [`reflect_trait`]: macro@reflect_trait
+//! [`reflect_functions`]: macro@reflect_functions
 
 extern crate proc_macro;
 
@@ -18,6 +19,7 @@ mod container_attributes;
 mod derive_data;
 mod field_attributes;
 mod from_reflect;
+mod function_reflection;
 mod impls;
 mod reflect_value;
 mod registration;
@@ -35,6 +37,19 @@ use syn::{parse_macro_input, DeriveInput};
 pub(crate) static REFLECT_ATTRIBUTE_NAME: &str = "reflect";
 pub(crate) static REFLECT_VALUE_ATTRIBUTE_NAME: &str = "reflect_value";
 
+/// Derives the `Reflect` trait.
+///
+/// This macro supports the following field attributes:
+/// * `#[reflect(ignore)]`: Ignores the field. This requires the field to implement [`Default`].
+/// * `#[reflect(default)]`: Records the field's [`Default`] implementation in its `NamedField`,
+///   which is used when the field is missing from deserialized data.
+/// * `#[reflect(default = "some_func")]`: Same as above, using the function with the given name.
+/// * `#[reflect(skip_serializing)]`: Keeps the field reflected but skips it when serializing.
+/// * `#[reflect(meta(key = literal, ...))]`: Attaches metadata to the field's `NamedField`.
+///   String literals are stored as a `String`, other literals with their own type.
+///
+/// The `skip_serializing` and `meta` attributes are only supported on named fields.
+///
 #[proc_macro_derive(Reflect, attributes(reflect, reflect_value, module))]
 pub fn derive_reflect(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
@@ -95,6 +110,11 @@ pub fn reflect_trait(args: TokenStream, input: TokenStream) -> TokenStream {
     trait_reflection::reflect_trait(&args, input)
 }
 
+#[proc_macro_attribute]
+pub fn reflect_functions(args: TokenStream, input: TokenStream) -> TokenStream {
+    function_reflection::reflect_functions(&args, input)
+}
+
 #[proc_macro]
 pub fn impl_reflect_value(input: TokenStream) -> TokenStream {
     let reflect_value_def = parse_macro_input!(input as ReflectValueDef);
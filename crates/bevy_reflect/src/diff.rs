@@ -0,0 +1,622 @@
+use crate::{
+    std_traits::ReflectDefault, GetPath, List, Map, Reflect, ReflectMut, ReflectRef, TypeRegistry,
+};
+use thiserror::Error;
+
+/// A change between two reflected values, part of a [`ReflectDiff`].
+///
+/// The `path` of a change is the path of the changed value, as used by [`GetPath`]. An empty
+/// path is the value itself.
+#[derive(Debug)]
+pub enum DiffChange {
+    /// The value at `path` was modified: `value` is [applied](Reflect::apply) to it.
+    Modified {
+        path: String,
+        value: Box<dyn Reflect>,
+    },
+    /// `value` was inserted at `index` in the [`List`] at `path`.
+    ListInserted {
+        path: String,
+        index: usize,
+        value: Box<dyn Reflect>,
+    },
+    /// The element at `index` was removed from the [`List`] at `path`.
+    ListRemoved { path: String, index: usize },
+    /// `value` was inserted for `key` in the [`Map`] at `path`, or replaced its previous value.
+    MapInserted {
+        path: String,
+        key: Box<dyn Reflect>,
+        value: Box<dyn Reflect>,
+    },
+    /// The entry of `key` was removed from the [`Map`] at `path`.
+    MapRemoved { path: String, key: Box<dyn Reflect> },
+}
+
+impl DiffChange {
+    /// The path of the value this change applies to.
+    pub fn path(&self) -> &str {
+        match self {
+            DiffChange::Modified { path, .. }
+            | DiffChange::ListInserted { path, .. }
+            | DiffChange::ListRemoved { path, .. }
+            | DiffChange::MapInserted { path, .. }
+            | DiffChange::MapRemoved { path, .. } => path,
+        }
+    }
+
+    pub fn clone_dynamic(&self) -> Self {
+        match self {
+            DiffChange::Modified { path, value } => DiffChange::Modified {
+                path: path.clone(),
+                value: value.clone_value(),
+            },
+            DiffChange::ListInserted { path, index, value } => DiffChange::ListInserted {
+                path: path.clone(),
+                index: *index,
+                value: value.clone_value(),
+            },
+            DiffChange::ListRemoved { path, index } => DiffChange::ListRemoved {
+                path: path.clone(),
+                index: *index,
+            },
+            DiffChange::MapInserted { path, key, value } => DiffChange::MapInserted {
+                path: path.clone(),
+                key: key.clone_value(),
+                value: value.clone_value(),
+            },
+            DiffChange::MapRemoved { path, key } => DiffChange::MapRemoved {
+                path: path.clone(),
+                key: key.clone_value(),
+            },
+        }
+    }
+}
+
+/// The changes turning a reflected value into another value of the same type.
+///
+/// [`Struct`](crate::Struct)s, [`TupleStruct`](crate::TupleStruct)s,
+/// [`Tuple`](crate::Tuple)s and [`Array`]s are compared field by field, so that only the changed
+/// fields are part of the diff. [`List`] elements inserted or removed before or after the
+/// unchanged elements, and [`Map`] entries, are recorded as insertions and removals.
+///
+/// A diff can be serialized with a [`ReflectDiffSerializer`](crate::serde::ReflectDiffSerializer).
+///
+/// ```
+/// # use bevy_reflect::{Reflect, ReflectDiff};
+/// #[derive(Reflect, Clone, PartialEq, Debug)]
+/// struct Inventory {
+///     gold: u32,
+///     items: Vec<String>,
+/// }
+///
+/// let before = Inventory {
+///     gold: 10,
+///     items: vec!["sword".to_string()],
+/// };
+/// let after = Inventory {
+///     gold: 4,
+///     items: vec!["sword".to_string(), "shield".to_string()],
+/// };
+///
+/// let diff = ReflectDiff::new(&before, &after);
+/// assert_eq!(diff.changes().len(), 2);
+///
+/// let mut value = before.clone();
+/// diff.apply(&mut value).unwrap();
+/// assert_eq!(value, after);
+///
+/// // the reverse diff undoes the changes
+/// ReflectDiff::new(&after, &before).apply(&mut value).unwrap();
+/// assert_eq!(value, before);
+/// ```
+#[derive(Debug, Default)]
+pub struct ReflectDiff {
+    changes: Vec<DiffChange>,
+}
+
+impl ReflectDiff {
+    /// Returns the changes turning `base` into `edited`.
+    pub fn new(base: &dyn Reflect, edited: &dyn Reflect) -> Self {
+        let mut diff = ReflectDiff::default();
+        diff.diff_values(base, edited, String::new());
+        diff
+    }
+
+    /// Creates a diff from its changes, applied in order.
+    pub fn from_changes(changes: Vec<DiffChange>) -> Self {
+        ReflectDiff { changes }
+    }
+
+    pub fn changes(&self) -> &[DiffChange] {
+        &self.changes
+    }
+
+    pub fn into_changes(self) -> Vec<DiffChange> {
+        self.changes
+    }
+
+    /// Returns `true` if the values of the diff are equal.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    pub fn clone_dynamic(&self) -> Self {
+        ReflectDiff {
+            changes: self.changes.iter().map(DiffChange::clone_dynamic).collect(),
+        }
+    }
+
+    /// Applies the changes of this diff to `value`, in order.
+    ///
+    /// A [`DiffChange::MapInserted`] is applied to the value of its key if the map already has
+    /// one. Otherwise the value is inserted as is, which fails for the dynamic values of structs
+    /// and other containers: use [`ReflectDiff::apply_with_registry`] to insert them.
+    ///
+    /// If a change can't be applied, the error is returned and the following changes are not
+    /// applied.
+    pub fn apply(&self, value: &mut dyn Reflect) -> Result<(), ReflectDiffError> {
+        self.apply_changes(value, None)
+    }
+
+    /// Applies the changes of this diff to `value`, like [`ReflectDiff::apply`].
+    ///
+    /// The keys and values inserted in maps are created with the [`ReflectDefault`] of their
+    /// type in `registry` when it has one, so that dynamic values can be inserted in maps with
+    /// concrete types like `HashMap`.
+    pub fn apply_with_registry(
+        &self,
+        value: &mut dyn Reflect,
+        registry: &TypeRegistry,
+    ) -> Result<(), ReflectDiffError> {
+        self.apply_changes(value, Some(registry))
+    }
+
+    fn apply_changes(
+        &self,
+        value: &mut dyn Reflect,
+        registry: Option<&TypeRegistry>,
+    ) -> Result<(), ReflectDiffError> {
+        for change in &self.changes {
+            let path = change.path();
+            let target = value
+                .path_mut(path)
+                .map_err(|error| ReflectDiffError::InvalidPath {
+                    path: path.to_string(),
+                    error: error.to_string(),
+                })?;
+            match change {
+                DiffChange::Modified { value, .. } => target.apply(&**value),
+                DiffChange::ListInserted { index, value, .. } => {
+                    let list = expect_list(target, path)?;
+                    if *index > list.len() {
+                        return Err(ReflectDiffError::InvalidListIndex {
+                            path: path.to_string(),
+                            index: *index,
+                        });
+                    }
+                    list.insert(*index, value.clone_value()).map_err(|value| {
+                        ReflectDiffError::InvalidValue {
+                            path: path.to_string(),
+                            type_name: value.type_name().to_string(),
+                        }
+                    })?;
+                }
+                DiffChange::ListRemoved { index, .. } => {
+                    let list = expect_list(target, path)?;
+                    if *index >= list.len() {
+                        return Err(ReflectDiffError::InvalidListIndex {
+                            path: path.to_string(),
+                            index: *index,
+                        });
+                    }
+                    list.remove(*index)
+                        .ok_or_else(|| ReflectDiffError::UnsupportedRemove {
+                            path: path.to_string(),
+                        })?;
+                }
+                DiffChange::MapInserted { key, value, .. } => {
+                    let map = expect_map(target, path)?;
+                    if let Some(entry) = map.get_mut(&**key) {
+                        entry.apply(&**value);
+                        continue;
+                    }
+                    map.insert_boxed(
+                        concrete_value(&**key, registry),
+                        concrete_value(&**value, registry),
+                    )
+                    .map_err(|error| ReflectDiffError::InvalidValue {
+                        path: path.to_string(),
+                        type_name: error.value.type_name().to_string(),
+                    })?;
+                }
+                DiffChange::MapRemoved { key, .. } => {
+                    expect_map(target, path)?.remove(&**key).ok_or_else(|| {
+                        ReflectDiffError::MissingMapKey {
+                            path: path.to_string(),
+                        }
+                    })?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn diff_values(&mut self, base: &dyn Reflect, edited: &dyn Reflect, path: String) {
+        if base.reflect_partial_eq(edited) == Some(true) {
+            return;
+        }
+        if base.type_name() == edited.type_name() {
+            match (base.reflect_ref(), edited.reflect_ref()) {
+                (ReflectRef::Struct(base), ReflectRef::Struct(edited))
+                    if base.field_len() == edited.field_len()
+                        && (0..edited.field_len())
+                            .all(|i| base.field(edited.name_at(i).unwrap()).is_some()) =>
+                {
+                    for (i, value) in edited.iter_fields().enumerate() {
+                        let name = edited.name_at(i).unwrap();
+                        self.diff_values(base.field(name).unwrap(), value, join(&path, name));
+                    }
+                    return;
+                }
+                (ReflectRef::TupleStruct(base), ReflectRef::TupleStruct(edited))
+                    if base.field_len() == edited.field_len() =>
+                {
+                    for (i, value) in edited.iter_fields().enumerate() {
+                        let field_path = join(&path, &i.to_string());
+                        self.diff_values(base.field(i).unwrap(), value, field_path);
+                    }
+                    return;
+                }
+                (ReflectRef::Tuple(base), ReflectRef::Tuple(edited))
+                    if base.field_len() == edited.field_len() =>
+                {
+                    for (i, value) in edited.iter_fields().enumerate() {
+                        let field_path = join(&path, &i.to_string());
+                        self.diff_values(base.field(i).unwrap(), value, field_path);
+                    }
+                    return;
+                }
+                (ReflectRef::Array(base), ReflectRef::Array(edited))
+                    if base.len() == edited.len() =>
+                {
+                    for (i, value) in edited.iter().enumerate() {
+                        let item_path = format!("{}[{}]", path, i);
+                        self.diff_values(base.get(i).unwrap(), value, item_path);
+                    }
+                    return;
+                }
+                (ReflectRef::List(base), ReflectRef::List(edited)) => {
+                    self.diff_lists(base, edited, path);
+                    return;
+                }
+                (ReflectRef::Map(base), ReflectRef::Map(edited)) => {
+                    self.diff_maps(base, edited, path);
+                    return;
+                }
+                _ => {}
+            }
+        }
+        self.changes.push(DiffChange::Modified {
+            path,
+            value: edited.clone_value(),
+        });
+    }
+
+    /// Compares the elements of the lists after their common prefix and before their common
+    /// suffix one to one, then records the remaining elements as removed or inserted.
+    fn diff_lists(&mut self, base: &dyn List, edited: &dyn List, path: String) {
+        let equal = |a: &dyn Reflect, b: &dyn Reflect| a.reflect_partial_eq(b) == Some(true);
+        let min_len = base.len().min(edited.len());
+        let prefix = (0..min_len)
+            .take_while(|i| equal(base.get(*i).unwrap(), edited.get(*i).unwrap()))
+            .count();
+        let suffix = (0..min_len - prefix)
+            .take_while(|i| {
+                equal(
+                    base.get(base.len() - 1 - i).unwrap(),
+                    edited.get(edited.len() - 1 - i).unwrap(),
+                )
+            })
+            .count();
+        let base_end = base.len() - suffix;
+        let edited_end = edited.len() - suffix;
+        let common_end = prefix + (base_end - prefix).min(edited_end - prefix);
+
+        for i in prefix..common_end {
+            let item_path = format!("{}[{}]", path, i);
+            self.diff_values(base.get(i).unwrap(), edited.get(i).unwrap(), item_path);
+        }
+        // removed from the end so that the indices of the other removed elements don't change
+        for index in (common_end..base_end).rev() {
+            self.changes.push(DiffChange::ListRemoved {
+                path: path.clone(),
+                index,
+            });
+        }
+        for index in common_end..edited_end {
+            self.changes.push(DiffChange::ListInserted {
+                path: path.clone(),
+                index,
+                value: edited.get(index).unwrap().clone_value(),
+            });
+        }
+    }
+
+    fn diff_maps(&mut self, base: &dyn Map, edited: &dyn Map, path: String) {
+        for (key, _) in base.iter() {
+            if edited.get(key).is_none() {
+                self.changes.push(DiffChange::MapRemoved {
+                    path: path.clone(),
+                    key: key.clone_value(),
+                });
+            }
+        }
+        for (key, value) in edited.iter() {
+            let changed = base.get(key).map_or(true, |base_value| {
+                base_value.reflect_partial_eq(value) != Some(true)
+            });
+            if changed {
+                self.changes.push(DiffChange::MapInserted {
+                    path: path.clone(),
+                    key: key.clone_value(),
+                    value: value.clone_value(),
+                });
+            }
+        }
+    }
+}
+
+fn join(path: &str, field: &str) -> String {
+    if path.is_empty() {
+        field.to_string()
+    } else {
+        format!("{}.{}", path, field)
+    }
+}
+
+/// Clones `value`, into a value of its concrete type if it has a [`ReflectDefault`] in
+/// `registry`.
+fn concrete_value(value: &dyn Reflect, registry: Option<&TypeRegistry>) -> Box<dyn Reflect> {
+    let default = registry
+        .and_then(|registry| registry.get_with_name(value.type_name()))
+        .and_then(|registration| registration.data::<ReflectDefault>());
+    match default {
+        Some(default) => {
+            let mut concrete = default.default();
+            concrete.apply(value);
+            concrete
+        }
+        None => value.clone_value(),
+    }
+}
+
+fn expect_list<'a>(
+    value: &'a mut dyn Reflect,
+    path: &str,
+) -> Result<&'a mut dyn List, ReflectDiffError> {
+    match value.reflect_mut() {
+        ReflectMut::List(list) => Ok(list),
+        _ => Err(ReflectDiffError::ExpectedList {
+            path: path.to_string(),
+        }),
+    }
+}
+
+fn expect_map<'a>(
+    value: &'a mut dyn Reflect,
+    path: &str,
+) -> Result<&'a mut dyn Map, ReflectDiffError> {
+    match value.reflect_mut() {
+        ReflectMut::Map(map) => Ok(map),
+        _ => Err(ReflectDiffError::ExpectedMap {
+            path: path.to_string(),
+        }),
+    }
+}
+
+/// An error returned when a [`ReflectDiff`] can't be applied to a value.
+#[derive(Debug, PartialEq, Eq, Error)]
+pub enum ReflectDiffError {
+    #[error("invalid diff path `{path}`: {error}")]
+    InvalidPath { path: String, error: String },
+    #[error("expected a list at `{path}`, but found a different reflect value")]
+    ExpectedList { path: String },
+    #[error("expected a map at `{path}`, but found a different reflect value")]
+    ExpectedMap { path: String },
+    #[error("the list at `{path}` doesn't have an element at index {index}")]
+    InvalidListIndex { path: String, index: usize },
+    #[error("the map at `{path}` doesn't have the removed key")]
+    MissingMapKey { path: String },
+    #[error("a value of type `{type_name}` can't be inserted in the collection at `{path}`")]
+    InvalidValue { path: String, type_name: String },
+    #[error("the list at `{path}` doesn't support removing elements")]
+    UnsupportedRemove { path: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as bevy_reflect;
+    use crate::{
+        serde::{ReflectDiffDeserializer, ReflectDiffSerializer},
+        std_traits::ReflectDefault,
+        FromReflect, TypeRegistry,
+    };
+    use ::serde::de::DeserializeSeed;
+    use bevy_utils::HashMap;
+
+    #[derive(Reflect, FromReflect, Clone, PartialEq, Debug)]
+    struct Player {
+        name: String,
+        position: (f32, f32),
+        stats: [u32; 3],
+        inventory: Vec<Item>,
+        flags: HashMap<String, bool>,
+    }
+
+    #[derive(Reflect, FromReflect, Clone, PartialEq, Debug, Default)]
+    #[reflect(Default)]
+    struct Item(String, u32);
+
+    #[derive(Reflect, FromReflect, Clone, PartialEq, Debug)]
+    struct Chest {
+        slots: HashMap<String, Item>,
+    }
+
+    fn player() -> Player {
+        let mut flags = HashMap::default();
+        flags.insert("met_king".to_string(), true);
+        flags.insert("found_key".to_string(), false);
+        Player {
+            name: "Alice".to_string(),
+            position: (0.0, 1.0),
+            stats: [1, 2, 3],
+            inventory: vec![
+                Item("sword".to_string(), 1),
+                Item("potion".to_string(), 3),
+                Item("map".to_string(), 1),
+            ],
+            flags,
+        }
+    }
+
+    fn edited_player() -> Player {
+        let mut edited = player();
+        edited.position.1 = 2.0;
+        edited.stats[2] = 4;
+        edited.inventory[1].1 = 2;
+        edited.inventory.insert(1, Item("shield".to_string(), 1));
+        edited.inventory.pop();
+        edited.flags.remove("met_king");
+        edited.flags.insert("found_key".to_string(), true);
+        edited.flags.insert("opened_door".to_string(), true);
+        edited
+    }
+
+    #[test]
+    fn diff_fields() {
+        let base = player();
+        let mut edited = base.clone();
+        edited.position.1 = 2.0;
+        edited.stats[2] = 4;
+        edited.inventory[1].1 = 2;
+
+        let diff = ReflectDiff::new(&base, &edited);
+        let paths: Vec<_> = diff.changes().iter().map(DiffChange::path).collect();
+        assert_eq!(paths, vec!["position.1", "stats[2]", "inventory[1].1"]);
+        assert!(diff
+            .changes()
+            .iter()
+            .all(|change| matches!(change, DiffChange::Modified { .. })));
+
+        assert!(ReflectDiff::new(&base, &base.clone()).is_empty());
+    }
+
+    #[test]
+    fn diff_lists_and_maps() {
+        let base = player();
+        let edited = edited_player();
+
+        let diff = ReflectDiff::new(&base, &edited);
+        let mut value = base.clone();
+        diff.apply(&mut value).unwrap();
+        assert_eq!(value, edited);
+
+        let mut value = edited.clone();
+        ReflectDiff::new(&edited, &base).apply(&mut value).unwrap();
+        assert_eq!(value, base);
+
+        let removed = ReflectDiff::new(&vec![1, 2, 3, 4], &vec![1, 4]);
+        assert_eq!(removed.changes().len(), 2);
+        assert!(matches!(
+            removed.changes()[0],
+            DiffChange::ListRemoved { index: 2, .. }
+        ));
+
+        let mut value = base;
+        assert_eq!(
+            ReflectDiff::new(&vec![1], &vec![1, 2]).apply(&mut value),
+            Err(ReflectDiffError::ExpectedList {
+                path: String::new()
+            })
+        );
+    }
+
+    #[test]
+    fn diff_serialization() {
+        let mut registry = TypeRegistry::default();
+        registry.register::<u32>();
+        registry.register::<f32>();
+        registry.register::<bool>();
+        registry.register::<String>();
+
+        let base = player();
+        let edited = edited_player();
+        let diff = ReflectDiff::new(&base, &edited);
+
+        let serializer = ReflectDiffSerializer::new(&diff, &registry);
+        let serialized = ron::ser::to_string(&serializer).unwrap();
+        let mut deserializer = ron::de::Deserializer::from_str(&serialized).unwrap();
+        let deserialized = ReflectDiffDeserializer::new(&registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+        assert_eq!(deserialized.changes().len(), diff.changes().len());
+
+        let mut value = base;
+        deserialized.apply(&mut value).unwrap();
+        assert_eq!(value, edited);
+    }
+
+    #[test]
+    fn diff_maps_with_struct_values() {
+        let mut slots = HashMap::default();
+        slots.insert("a".to_string(), Item("sword".to_string(), 1));
+        slots.insert("b".to_string(), Item("potion".to_string(), 3));
+        let base = Chest { slots };
+
+        // changed values are applied to the existing ones, new values have no concrete type
+        let mut changed = base.clone();
+        changed.slots.get_mut("b").unwrap().1 = 2;
+        let mut value = base.clone();
+        ReflectDiff::new(&base, &changed).apply(&mut value).unwrap();
+        assert_eq!(value, changed);
+
+        let mut inserted = base.clone();
+        inserted
+            .slots
+            .insert("c".to_string(), Item("map".to_string(), 1));
+        assert_eq!(
+            ReflectDiff::new(&base, &inserted).apply(&mut value),
+            Err(ReflectDiffError::InvalidValue {
+                path: "slots".to_string(),
+                type_name: std::any::type_name::<Item>().to_string(),
+            })
+        );
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<u32>();
+        registry.register::<String>();
+        registry.register::<Item>();
+
+        let mut edited = changed;
+        edited
+            .slots
+            .insert("c".to_string(), Item("map".to_string(), 1));
+        let diff = ReflectDiff::new(&base, &edited);
+        let mut value = base.clone();
+        diff.apply_with_registry(&mut value, &registry).unwrap();
+        assert_eq!(value, edited);
+
+        let serializer = ReflectDiffSerializer::new(&diff, &registry);
+        let serialized = ron::ser::to_string(&serializer).unwrap();
+        let mut deserializer = ron::de::Deserializer::from_str(&serialized).unwrap();
+        let deserialized = ReflectDiffDeserializer::new(&registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+        let mut value = base;
+        deserialized
+            .apply_with_registry(&mut value, &registry)
+            .unwrap();
+        assert_eq!(value, edited);
+    }
+}
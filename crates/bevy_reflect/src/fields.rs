@@ -1,13 +1,21 @@
 use crate::Reflect;
 use std::any::{Any, TypeId};
 use std::borrow::Cow;
+use std::sync::Arc;
 
 /// The named field of a reflected struct.
+///
+/// Besides its name and type, a named field carries the attributes given to it
+/// with `#[reflect(...)]`: its default value, whether it is skipped when
+/// serializing, and arbitrary metadata.
 #[derive(Clone, Debug)]
 pub struct NamedField {
     name: Cow<'static, str>,
     type_name: &'static str,
     type_id: TypeId,
+    default: Option<fn() -> Box<dyn Reflect>>,
+    skip_serializing: bool,
+    metadata: Vec<(Cow<'static, str>, Arc<dyn Reflect>)>,
 }
 
 impl NamedField {
@@ -17,9 +25,42 @@ impl NamedField {
             name: name.into(),
             type_name: std::any::type_name::<T>(),
             type_id: TypeId::of::<T>(),
+            default: None,
+            skip_serializing: false,
+            metadata: Vec::new(),
         }
     }
 
+    /// Sets the function creating the default value of the field.
+    ///
+    /// This is set by `#[reflect(default)]` and `#[reflect(default = "path")]`.
+    pub fn with_default(mut self, default: fn() -> Box<dyn Reflect>) -> Self {
+        self.default = Some(default);
+        self
+    }
+
+    /// Sets whether the field is skipped when serializing.
+    ///
+    /// This is set by `#[reflect(skip_serializing)]`.
+    pub fn with_skip_serializing(mut self, skip_serializing: bool) -> Self {
+        self.skip_serializing = skip_serializing;
+        self
+    }
+
+    /// Adds a metadata entry to the field, replacing any previous entry with the same key.
+    ///
+    /// This is set by `#[reflect(meta(key = value))]`.
+    pub fn with_metadata<T: Reflect>(
+        mut self,
+        key: impl Into<Cow<'static, str>>,
+        value: T,
+    ) -> Self {
+        let key = key.into();
+        self.metadata.retain(|(existing, _)| *existing != key);
+        self.metadata.push((key, Arc::new(value)));
+        self
+    }
+
     /// The name of the field.
     pub fn name(&self) -> &Cow<'static, str> {
         &self.name
@@ -41,6 +82,36 @@ impl NamedField {
     pub fn is<T: Any>(&self) -> bool {
         TypeId::of::<T>() == self.type_id
     }
+
+    /// Returns true if the field has a default value.
+    pub fn has_default(&self) -> bool {
+        self.default.is_some()
+    }
+
+    /// Creates the default value of the field, if it has one.
+    pub fn default_value(&self) -> Option<Box<dyn Reflect>> {
+        self.default.map(|default| default())
+    }
+
+    /// Returns true if the field is skipped when serializing.
+    pub fn skip_serializing(&self) -> bool {
+        self.skip_serializing
+    }
+
+    /// Returns the metadata entry of the field with the given key, if any.
+    pub fn metadata(&self, key: &str) -> Option<&dyn Reflect> {
+        self.metadata
+            .iter()
+            .find(|(existing, _)| existing == key)
+            .map(|(_, value)| value.as_ref())
+    }
+
+    /// Returns an iterator over the metadata entries of the field, in declaration order.
+    pub fn iter_metadata(&self) -> impl Iterator<Item = (&str, &dyn Reflect)> {
+        self.metadata
+            .iter()
+            .map(|(key, value)| (key.as_ref(), value.as_ref()))
+    }
 }
 
 /// The unnamed field of a reflected tuple or tuple struct.
@@ -0,0 +1,742 @@
+use crate::{FromReflect, FromType, Reflect, TypeInfo, Typed};
+use std::{borrow::Cow, fmt::Debug, sync::Arc};
+use thiserror::Error;
+
+/// The way in which a function takes one of its arguments.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Ownership {
+    /// The argument is taken by value.
+    Owned,
+    /// The argument is taken by shared reference.
+    Ref,
+    /// The argument is taken by mutable reference.
+    Mut,
+}
+
+/// A container for compile-time information about an argument of a [`DynamicFunction`].
+#[derive(Clone, Debug)]
+pub struct ArgInfo {
+    index: usize,
+    ownership: Ownership,
+    type_info: &'static TypeInfo,
+}
+
+impl ArgInfo {
+    /// Create a new [`ArgInfo`] for an argument of type `T`.
+    ///
+    /// The index of the argument is assigned by [`FunctionInfo::new`].
+    pub fn new<T: Typed>(ownership: Ownership) -> Self {
+        Self {
+            index: 0,
+            ownership,
+            type_info: T::type_info(),
+        }
+    }
+
+    /// The position of the argument in the function signature.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// How the argument is taken by the function.
+    pub fn ownership(&self) -> Ownership {
+        self.ownership
+    }
+
+    /// The [`TypeInfo`] of the argument.
+    ///
+    /// For reference arguments, this is the info of the referenced type.
+    pub fn type_info(&self) -> &'static TypeInfo {
+        self.type_info
+    }
+
+    /// The [type name] of the argument.
+    ///
+    /// [type name]: std::any::type_name
+    pub fn type_name(&self) -> &'static str {
+        self.type_info.type_name()
+    }
+}
+
+/// A container for compile-time information about a [`DynamicFunction`].
+#[derive(Clone, Debug)]
+pub struct FunctionInfo {
+    name: Cow<'static, str>,
+    args: Vec<ArgInfo>,
+    return_info: &'static TypeInfo,
+}
+
+impl FunctionInfo {
+    /// Create a new [`FunctionInfo`].
+    ///
+    /// # Arguments
+    ///
+    /// * `name`: The name of the function
+    /// * `args`: The arguments of the function, in order
+    ///
+    pub fn new<R: Typed>(name: impl Into<Cow<'static, str>>, args: Vec<ArgInfo>) -> Self {
+        let args = args
+            .into_iter()
+            .enumerate()
+            .map(|(index, arg)| ArgInfo { index, ..arg })
+            .collect();
+        Self {
+            name: name.into(),
+            args,
+            return_info: R::type_info(),
+        }
+    }
+
+    /// The name of the function.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The arguments of the function, in order.
+    pub fn args(&self) -> &[ArgInfo] {
+        &self.args
+    }
+
+    /// The [`TypeInfo`] of the value returned by the function.
+    pub fn return_info(&self) -> &'static TypeInfo {
+        self.return_info
+    }
+}
+
+/// A single argument passed to a [`DynamicFunction`].
+pub enum Arg<'a> {
+    Owned(Box<dyn Reflect>),
+    Ref(&'a dyn Reflect),
+    Mut(&'a mut dyn Reflect),
+}
+
+impl<'a> Arg<'a> {
+    /// Returns a reference to the argument's value.
+    pub fn value(&self) -> &dyn Reflect {
+        match self {
+            Arg::Owned(value) => value.as_ref(),
+            Arg::Ref(value) => *value,
+            Arg::Mut(value) => &**value,
+        }
+    }
+
+    /// How the argument was passed.
+    pub fn ownership(&self) -> Ownership {
+        match self {
+            Arg::Owned(_) => Ownership::Owned,
+            Arg::Ref(_) => Ownership::Ref,
+            Arg::Mut(_) => Ownership::Mut,
+        }
+    }
+
+    fn check_type<T: Reflect>(&self, index: usize) -> Result<(), FunctionError> {
+        let received = self.value().type_name();
+        let expected = std::any::type_name::<T>();
+        if received == expected {
+            Ok(())
+        } else {
+            Err(FunctionError::InvalidArgType {
+                index,
+                expected: expected.to_string(),
+                received: received.to_string(),
+            })
+        }
+    }
+
+    /// Takes the value of this argument as a `T`.
+    ///
+    /// Borrowed arguments, as well as dynamic representations of `T`, are
+    /// converted using [`FromReflect`].
+    fn take_owned<T: FromReflect>(&mut self, index: usize) -> Result<T, FunctionError> {
+        self.check_type::<T>(index)?;
+        let invalid_value = || FunctionError::InvalidArgValue {
+            index,
+            type_name: std::any::type_name::<T>().to_string(),
+        };
+        match std::mem::replace(self, Arg::Ref(&())) {
+            Arg::Owned(value) => value
+                .take::<T>()
+                .or_else(|value| T::from_reflect(value.as_ref()).ok_or_else(invalid_value)),
+            Arg::Ref(value) => T::from_reflect(value).ok_or_else(invalid_value),
+            Arg::Mut(value) => T::from_reflect(value).ok_or_else(invalid_value),
+        }
+    }
+
+    fn as_ref<T: Reflect>(&self, index: usize) -> Result<&T, FunctionError> {
+        self.check_type::<T>(index)?;
+        self.value()
+            .downcast_ref::<T>()
+            .ok_or_else(|| FunctionError::InvalidArgValue {
+                index,
+                type_name: std::any::type_name::<T>().to_string(),
+            })
+    }
+
+    fn as_mut<T: Reflect>(&mut self, index: usize) -> Result<&mut T, FunctionError> {
+        self.check_type::<T>(index)?;
+        let value = match self {
+            Arg::Owned(value) => value.as_mut(),
+            Arg::Mut(value) => &mut **value,
+            Arg::Ref(_) => {
+                return Err(FunctionError::InvalidArgOwnership {
+                    index,
+                    expected: Ownership::Mut,
+                    received: Ownership::Ref,
+                })
+            }
+        };
+        value
+            .downcast_mut::<T>()
+            .ok_or_else(|| FunctionError::InvalidArgValue {
+                index,
+                type_name: std::any::type_name::<T>().to_string(),
+            })
+    }
+}
+
+/// An ordered list of [arguments] passed to a [`DynamicFunction`].
+///
+/// [arguments]: Arg
+#[derive(Default)]
+pub struct ArgList<'a> {
+    args: Vec<Arg<'a>>,
+}
+
+impl<'a> ArgList<'a> {
+    /// Create an empty argument list.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends an [`Arg`] to the list.
+    pub fn push_arg(mut self, arg: Arg<'a>) -> Self {
+        self.args.push(arg);
+        self
+    }
+
+    /// Appends an owned value to the list.
+    pub fn push_owned<T: Reflect>(self, value: T) -> Self {
+        self.push_arg(Arg::Owned(Box::new(value)))
+    }
+
+    /// Appends a boxed value to the list.
+    pub fn push_boxed(self, value: Box<dyn Reflect>) -> Self {
+        self.push_arg(Arg::Owned(value))
+    }
+
+    /// Appends a shared reference to the list.
+    pub fn push_ref(self, value: &'a dyn Reflect) -> Self {
+        self.push_arg(Arg::Ref(value))
+    }
+
+    /// Appends a mutable reference to the list.
+    pub fn push_mut(self, value: &'a mut dyn Reflect) -> Self {
+        self.push_arg(Arg::Mut(value))
+    }
+
+    /// Returns the number of arguments in the list.
+    pub fn len(&self) -> usize {
+        self.args.len()
+    }
+
+    /// Returns true if the list contains no arguments.
+    pub fn is_empty(&self) -> bool {
+        self.args.is_empty()
+    }
+
+    /// Returns an iterator over the arguments in the list.
+    pub fn iter(&self) -> impl Iterator<Item = &Arg<'a>> {
+        self.args.iter()
+    }
+}
+
+/// An error returned when calling a [`DynamicFunction`].
+#[derive(Debug, PartialEq, Eq, Error)]
+pub enum FunctionError {
+    #[error("expected {expected} arguments but received {received}")]
+    ArgCountMismatch { expected: usize, received: usize },
+    #[error("expected argument {index} to be of type `{expected}` but received `{received}`")]
+    InvalidArgType {
+        index: usize,
+        expected: String,
+        received: String,
+    },
+    #[error(
+        "expected argument {index} to be passed as {expected:?} but it was passed as {received:?}"
+    )]
+    InvalidArgOwnership {
+        index: usize,
+        expected: Ownership,
+        received: Ownership,
+    },
+    #[error("argument {index} could not be converted to `{type_name}`")]
+    InvalidArgValue { index: usize, type_name: String },
+}
+
+type BoxedFunction =
+    dyn for<'a> Fn(ArgList<'a>) -> Result<Box<dyn Reflect>, FunctionError> + Send + Sync;
+
+/// A type-erased function which can be called with reflected arguments.
+///
+/// A `DynamicFunction` is usually created from a Rust function or method via
+/// [`IntoFunction`], and can then be stored in the [`TypeRegistry`] and called
+/// without knowledge of its signature at compile time.
+///
+/// Arguments are validated against the function's [`FunctionInfo`] before the
+/// underlying function is invoked, and its return value is boxed as a
+/// [`Reflect`] trait object.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_reflect::{ArgList, IntoFunction};
+/// fn add(a: i32, b: i32) -> i32 {
+///     a + b
+/// }
+///
+/// let function = add.into_function("add");
+/// let args = ArgList::new().push_owned(25_i32).push_owned(75_i32);
+/// let value = function.call(args).unwrap();
+/// assert_eq!(value.take::<i32>().unwrap(), 100);
+/// ```
+///
+/// [`TypeRegistry`]: crate::TypeRegistry
+#[derive(Clone)]
+pub struct DynamicFunction {
+    info: FunctionInfo,
+    func: Arc<BoxedFunction>,
+}
+
+impl DynamicFunction {
+    /// Create a new [`DynamicFunction`] from its info and a closure operating
+    /// on reflected arguments.
+    ///
+    /// The number of arguments is checked against `info` before `func` is
+    /// called.
+    pub fn new<F>(info: FunctionInfo, func: F) -> Self
+    where
+        F: for<'a> Fn(ArgList<'a>) -> Result<Box<dyn Reflect>, FunctionError>
+            + Send
+            + Sync
+            + 'static,
+    {
+        Self {
+            info,
+            func: Arc::new(func),
+        }
+    }
+
+    /// The name of the function.
+    pub fn name(&self) -> &str {
+        self.info.name()
+    }
+
+    /// The [`FunctionInfo`] of the function.
+    pub fn info(&self) -> &FunctionInfo {
+        &self.info
+    }
+
+    /// Calls the function with the given arguments.
+    ///
+    /// Returns an error if the number, types or ownership of the arguments do
+    /// not match the function signature.
+    pub fn call(&self, args: ArgList) -> Result<Box<dyn Reflect>, FunctionError> {
+        let expected = self.info.args.len();
+        if args.len() != expected {
+            return Err(FunctionError::ArgCountMismatch {
+                expected,
+                received: args.len(),
+            });
+        }
+        (self.func)(args)
+    }
+}
+
+impl Debug for DynamicFunction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DynamicFunction")
+            .field("info", &self.info)
+            .finish()
+    }
+}
+
+/// A trait for converting Rust functions and methods into a [`DynamicFunction`].
+///
+/// This is implemented for functions with up to six arguments, whose arguments
+/// implement [`FromReflect`] and whose return type implements [`Reflect`].
+/// Methods taking `&self` or `&mut self` are supported as well, with the
+/// receiver being passed as the first argument.
+///
+/// The `Marker` type parameter only exists to disambiguate implementations.
+pub trait IntoFunction<Marker> {
+    /// Converts `self` into a [`DynamicFunction`] with the given name.
+    fn into_function(self, name: impl Into<Cow<'static, str>>) -> DynamicFunction;
+}
+
+macro_rules! impl_into_function {
+    ($($arg:ident),*) => {
+        #[allow(non_snake_case, unused_variables, unused_mut)]
+        impl<F, R, $($arg,)*> IntoFunction<fn($($arg,)*) -> R> for F
+        where
+            F: Fn($($arg),*) -> R + Send + Sync + 'static,
+            R: Reflect + Typed,
+            $($arg: FromReflect + Typed,)*
+        {
+            fn into_function(self, name: impl Into<Cow<'static, str>>) -> DynamicFunction {
+                let info = FunctionInfo::new::<R>(
+                    name,
+                    vec![$(ArgInfo::new::<$arg>(Ownership::Owned)),*],
+                );
+                DynamicFunction::new(info, move |args| {
+                    let mut args = args.args;
+                    let mut args = args.iter_mut().enumerate();
+                    $(
+                        let (index, arg) = args.next().unwrap();
+                        let $arg = arg.take_owned::<$arg>(index)?;
+                    )*
+                    Ok(Box::new((self)($($arg),*)))
+                })
+            }
+        }
+
+        #[allow(non_snake_case, unused_variables, unused_mut)]
+        impl<F, Receiver, R, $($arg,)*> IntoFunction<(&'static Receiver, fn($($arg,)*) -> R)> for F
+        where
+            F: Fn(&Receiver, $($arg),*) -> R + Send + Sync + 'static,
+            Receiver: Reflect + Typed,
+            R: Reflect + Typed,
+            $($arg: FromReflect + Typed,)*
+        {
+            fn into_function(self, name: impl Into<Cow<'static, str>>) -> DynamicFunction {
+                let info = FunctionInfo::new::<R>(
+                    name,
+                    vec![
+                        ArgInfo::new::<Receiver>(Ownership::Ref),
+                        $(ArgInfo::new::<$arg>(Ownership::Owned)),*
+                    ],
+                );
+                DynamicFunction::new(info, move |args| {
+                    let mut args = args.args;
+                    let (receiver, args) = args.split_first_mut().unwrap();
+                    let mut args = args.iter_mut().enumerate();
+                    $(
+                        let (index, arg) = args.next().unwrap();
+                        let $arg = arg.take_owned::<$arg>(index + 1)?;
+                    )*
+                    let receiver = receiver.as_ref::<Receiver>(0)?;
+                    Ok(Box::new((self)(receiver, $($arg),*)))
+                })
+            }
+        }
+
+        #[allow(non_snake_case, unused_variables, unused_mut)]
+        impl<F, Receiver, R, $($arg,)*> IntoFunction<(&'static mut Receiver, fn($($arg,)*) -> R)> for F
+        where
+            F: Fn(&mut Receiver, $($arg),*) -> R + Send + Sync + 'static,
+            Receiver: Reflect + Typed,
+            R: Reflect + Typed,
+            $($arg: FromReflect + Typed,)*
+        {
+            fn into_function(self, name: impl Into<Cow<'static, str>>) -> DynamicFunction {
+                let info = FunctionInfo::new::<R>(
+                    name,
+                    vec![
+                        ArgInfo::new::<Receiver>(Ownership::Mut),
+                        $(ArgInfo::new::<$arg>(Ownership::Owned)),*
+                    ],
+                );
+                DynamicFunction::new(info, move |args| {
+                    let mut args = args.args;
+                    let (receiver, args) = args.split_first_mut().unwrap();
+                    let mut args = args.iter_mut().enumerate();
+                    $(
+                        let (index, arg) = args.next().unwrap();
+                        let $arg = arg.take_owned::<$arg>(index + 1)?;
+                    )*
+                    let receiver = receiver.as_mut::<Receiver>(0)?;
+                    Ok(Box::new((self)(receiver, $($arg),*)))
+                })
+            }
+        }
+    };
+}
+
+impl_into_function!();
+impl_into_function!(A0);
+impl_into_function!(A0, A1);
+impl_into_function!(A0, A1, A2);
+impl_into_function!(A0, A1, A2, A3);
+impl_into_function!(A0, A1, A2, A3, A4);
+impl_into_function!(A0, A1, A2, A3, A4, A5);
+
+/// A trait used to collect the reflected functions of a type.
+///
+/// This trait is automatically implemented by the [`#[reflect_functions]`][0]
+/// attribute macro for every `pub` method in the annotated `impl` block.
+///
+/// Every argument and the return type of these methods must be reflectable, otherwise the
+/// `impl` block doesn't compile:
+///
+/// ```compile_fail
+/// # use bevy_reflect::{Reflect, ReflectFunctions, reflect_functions};
+/// struct Callback(Box<dyn Fn()>);
+///
+/// #[derive(Reflect)]
+/// #[reflect(Functions)]
+/// struct Button;
+///
+/// #[reflect_functions]
+/// impl Button {
+///     pub fn on_click(&mut self, callback: Callback) {}
+/// }
+/// ```
+///
+/// Methods marked with `#[reflect(ignore)]` are not reflected:
+///
+/// ```
+/// # use bevy_reflect::{Reflect, ReflectFunctions, reflect_functions};
+/// # struct Callback(Box<dyn Fn()>);
+/// #[derive(Reflect)]
+/// #[reflect(Functions)]
+/// struct Button;
+///
+/// #[reflect_functions]
+/// impl Button {
+///     #[reflect(ignore)]
+///     pub fn on_click(&mut self, callback: Callback) {}
+/// }
+/// ```
+///
+/// [0]: crate::reflect_functions
+pub trait GetTypeFunctions {
+    /// Returns the reflected functions of this type.
+    fn get_type_functions() -> Vec<DynamicFunction>;
+}
+
+/// Type data containing the reflected functions and methods of a type.
+///
+/// A `ReflectFunctions` for type `T` can be obtained via
+/// [`FromType::from_type`], which is what `#[reflect(Functions)]` does.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_reflect::{ArgList, Reflect, ReflectFunctions, TypeRegistry, reflect_functions};
+/// #[derive(Reflect)]
+/// #[reflect(Functions)]
+/// struct Player {
+///     health: f32,
+/// }
+///
+/// #[reflect_functions]
+/// impl Player {
+///     pub fn heal(&mut self, amount: f32) {
+///         self.health += amount;
+///     }
+/// }
+///
+/// let mut registry = TypeRegistry::default();
+/// registry.register::<Player>();
+///
+/// let mut player = Player { health: 10.0 };
+/// let value: &mut dyn Reflect = &mut player;
+///
+/// let functions = registry
+///     .get_with_name(value.type_name())
+///     .and_then(|registration| registration.data::<ReflectFunctions>())
+///     .unwrap();
+/// let heal = functions.get("heal").unwrap();
+/// heal.call(ArgList::new().push_mut(value).push_owned(5.0_f32))
+///     .unwrap();
+///
+/// assert_eq!(player.health, 15.0);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ReflectFunctions {
+    functions: Vec<DynamicFunction>,
+}
+
+impl ReflectFunctions {
+    /// Returns the function with the given name, if any.
+    pub fn get(&self, name: &str) -> Option<&DynamicFunction> {
+        self.functions
+            .iter()
+            .find(|function| function.name() == name)
+    }
+
+    /// Inserts a function, replacing any previous function with the same name.
+    pub fn insert(&mut self, function: DynamicFunction) {
+        match self
+            .functions
+            .iter_mut()
+            .find(|existing| existing.name() == function.name())
+        {
+            Some(existing) => *existing = function,
+            None => self.functions.push(function),
+        }
+    }
+
+    /// Returns an iterator over the functions.
+    pub fn iter(&self) -> impl Iterator<Item = &DynamicFunction> {
+        self.functions.iter()
+    }
+
+    /// Returns the number of functions.
+    pub fn len(&self) -> usize {
+        self.functions.len()
+    }
+
+    /// Returns true if there are no functions.
+    pub fn is_empty(&self) -> bool {
+        self.functions.is_empty()
+    }
+}
+
+impl<T: GetTypeFunctions> FromType<T> for ReflectFunctions {
+    fn from_type() -> Self {
+        let mut functions = ReflectFunctions::default();
+        for function in T::get_type_functions() {
+            functions.insert(function);
+        }
+        functions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as bevy_reflect;
+    use crate::{reflect_functions, DynamicStruct, TypeRegistry};
+
+    #[derive(Reflect, FromReflect, Debug, PartialEq)]
+    #[reflect(Functions)]
+    struct Foo {
+        value: i32,
+    }
+
+    #[reflect_functions]
+    impl Foo {
+        pub fn new(value: i32) -> Self {
+            Self { value }
+        }
+
+        pub fn value(&self) -> i32 {
+            self.value
+        }
+
+        pub fn add(&mut self, a: i32, b: i32) {
+            self.value += a + b;
+        }
+
+        #[allow(dead_code)]
+        fn private(&self) {}
+
+        #[allow(dead_code)]
+        #[reflect(ignore)]
+        pub fn ignored(&self) {}
+    }
+
+    fn concat(a: String, b: String) -> String {
+        a + &b
+    }
+
+    #[test]
+    fn free_functions() {
+        let mut registry = TypeRegistry::default();
+        registry.register_function("concat", concat);
+
+        let function = registry.get_function("concat").unwrap();
+        assert_eq!(function.info().args().len(), 2);
+        assert_eq!(
+            function.info().return_info().type_name(),
+            std::any::type_name::<String>()
+        );
+
+        let a = String::from("Hello, ");
+        let args = ArgList::new()
+            .push_ref(&a)
+            .push_owned(String::from("World!"));
+        let value = function.call(args).unwrap();
+        assert_eq!(value.take::<String>().unwrap(), "Hello, World!");
+    }
+
+    #[test]
+    fn methods() {
+        let mut registry = TypeRegistry::default();
+        registry.register::<Foo>();
+
+        let functions = registry
+            .get_with_name(std::any::type_name::<Foo>())
+            .and_then(|registration| registration.data::<ReflectFunctions>())
+            .unwrap();
+        let mut names = functions.iter().map(|f| f.name()).collect::<Vec<_>>();
+        names.sort_unstable();
+        assert_eq!(names, vec!["add", "new", "value"]);
+
+        let new = functions.get("new").unwrap();
+        let foo = new.call(ArgList::new().push_owned(1_i32)).unwrap();
+        let mut foo = foo.take::<Foo>().unwrap();
+        assert_eq!(foo, Foo { value: 1 });
+
+        let add = functions.get("add").unwrap();
+        assert_eq!(add.info().args()[0].ownership(), Ownership::Mut);
+        let args = ArgList::new()
+            .push_mut(&mut foo)
+            .push_owned(2_i32)
+            .push_owned(3_i32);
+        add.call(args).unwrap();
+
+        let get = functions.get("value").unwrap();
+        let value = get.call(ArgList::new().push_ref(&foo)).unwrap();
+        assert_eq!(value.take::<i32>().unwrap(), 6);
+
+        // Dynamic representations of owned arguments are converted via `FromReflect`
+        let mut dynamic = DynamicStruct::default();
+        dynamic.set_name(std::any::type_name::<Foo>().to_string());
+        dynamic.insert("value", 4_i32);
+        let into_value = (|foo: Foo| foo.value).into_function("into_value");
+        let value = into_value
+            .call(ArgList::new().push_boxed(Box::new(dynamic)))
+            .unwrap();
+        assert_eq!(value.take::<i32>().unwrap(), 4);
+    }
+
+    #[test]
+    fn invalid_args() {
+        let function = concat.into_function("concat");
+        assert_eq!(
+            function
+                .call(ArgList::new().push_owned(String::new()))
+                .unwrap_err(),
+            FunctionError::ArgCountMismatch {
+                expected: 2,
+                received: 1
+            }
+        );
+        assert_eq!(
+            function
+                .call(ArgList::new().push_owned(String::new()).push_owned(1_u8))
+                .unwrap_err(),
+            FunctionError::InvalidArgType {
+                index: 1,
+                expected: std::any::type_name::<String>().to_string(),
+                received: std::any::type_name::<u8>().to_string(),
+            }
+        );
+
+        let add = Foo::add.into_function("add");
+        let foo = Foo { value: 0 };
+        let args = ArgList::new()
+            .push_ref(&foo)
+            .push_owned(1_i32)
+            .push_owned(2_i32);
+        assert_eq!(
+            add.call(args).unwrap_err(),
+            FunctionError::InvalidArgOwnership {
+                index: 0,
+                expected: Ownership::Mut,
+                received: Ownership::Ref,
+            }
+        );
+    }
+}
@@ -53,6 +53,22 @@ where
         });
         SmallVec::push(self, value);
     }
+
+    fn insert(&mut self, index: usize, value: Box<dyn Reflect>) -> Result<(), Box<dyn Reflect>> {
+        let value = match value.take::<T::Item>() {
+            Ok(value) => value,
+            Err(value) => match <T as smallvec::Array>::Item::from_reflect(&*value) {
+                Some(value) => value,
+                None => return Err(value),
+            },
+        };
+        SmallVec::insert(self, index, value);
+        Ok(())
+    }
+
+    fn remove(&mut self, index: usize) -> Option<Box<dyn Reflect>> {
+        Some(Box::new(SmallVec::remove(self, index)))
+    }
 }
 
 // SAFE: any and any_mut both return self
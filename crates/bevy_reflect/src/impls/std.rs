@@ -1,8 +1,9 @@
 use crate as bevy_reflect;
 use crate::{
     map_partial_eq, Array, ArrayInfo, ArrayIter, DynamicMap, FromReflect, FromType,
-    GetTypeRegistration, List, ListInfo, Map, MapInfo, MapIter, Reflect, ReflectDeserialize,
-    ReflectMut, ReflectRef, ReflectSerialize, TypeInfo, TypeRegistration, Typed, ValueInfo,
+    GetTypeRegistration, List, ListInfo, Map, MapInfo, MapInsertError, MapIter, Reflect,
+    ReflectDeserialize, ReflectMut, ReflectRef, ReflectSerialize, TypeInfo, TypeRegistration,
+    Typed, ValueInfo,
 };
 
 use crate::utility::{GenericTypeInfoCell, NonGenericTypeInfoCell};
@@ -103,6 +104,22 @@ impl<T: FromReflect> List for Vec<T> {
         });
         Vec::push(self, value);
     }
+
+    fn insert(&mut self, index: usize, value: Box<dyn Reflect>) -> Result<(), Box<dyn Reflect>> {
+        let value = match value.take::<T>() {
+            Ok(value) => value,
+            Err(value) => match T::from_reflect(&*value) {
+                Some(value) => value,
+                None => return Err(value),
+            },
+        };
+        Vec::insert(self, index, value);
+        Ok(())
+    }
+
+    fn remove(&mut self, index: usize) -> Option<Box<dyn Reflect>> {
+        Some(Box::new(Vec::remove(self, index)))
+    }
 }
 
 // SAFE: any and any_mut both return self
@@ -228,6 +245,32 @@ impl<K: Reflect + Eq + Hash, V: Reflect> Map for HashMap<K, V> {
         }
         dynamic_map
     }
+    fn insert_boxed(
+        &mut self,
+        key: Box<dyn Reflect>,
+        value: Box<dyn Reflect>,
+    ) -> Result<Option<Box<dyn Reflect>>, MapInsertError> {
+        let key = match key.take::<K>() {
+            Ok(key) => key,
+            Err(key) => return Err(MapInsertError { key, value }),
+        };
+        let value = match value.take::<V>() {
+            Ok(value) => value,
+            Err(value) => {
+                return Err(MapInsertError {
+                    key: Box::new(key),
+                    value,
+                })
+            }
+        };
+        Ok(HashMap::insert(self, key, value)
+            .map(|old_value| Box::new(old_value) as Box<dyn Reflect>))
+    }
+
+    fn remove(&mut self, key: &dyn Reflect) -> Option<Box<dyn Reflect>> {
+        let key = key.downcast_ref::<K>()?;
+        HashMap::remove(self, key).map(|value| Box::new(value) as Box<dyn Reflect>)
+    }
 }
 
 // SAFE: any and any_mut both return self
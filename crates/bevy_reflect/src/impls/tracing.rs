@@ -0,0 +1,149 @@
+use crate::{
+    utility::NonGenericTypeInfoCell, FromReflect, GetTypeRegistration, Reflect, ReflectDeserialize,
+    ReflectMut, ReflectRef, ReflectSerialize, TypeInfo, TypeRegistration, Typed, ValueInfo,
+};
+use bevy_utils::tracing::Level;
+use serde::{de::Error, Deserialize};
+use std::{
+    any::Any,
+    hash::{Hash, Hasher},
+};
+
+// SAFE: any and any_mut both return self
+unsafe impl Reflect for Level {
+    fn type_name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+
+    fn get_type_info(&self) -> &'static TypeInfo {
+        <Self as Typed>::type_info()
+    }
+
+    fn any(&self) -> &dyn Any {
+        self
+    }
+
+    fn any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn as_reflect(&self) -> &dyn Reflect {
+        self
+    }
+
+    fn as_reflect_mut(&mut self) -> &mut dyn Reflect {
+        self
+    }
+
+    fn apply(&mut self, value: &dyn Reflect) {
+        let value = value.any();
+        if let Some(value) = value.downcast_ref::<Self>() {
+            *self = *value;
+        } else {
+            panic!("Value is not a {}.", std::any::type_name::<Self>());
+        }
+    }
+
+    fn set(&mut self, value: Box<dyn Reflect>) -> Result<(), Box<dyn Reflect>> {
+        *self = value.take()?;
+        Ok(())
+    }
+
+    fn reflect_ref(&self) -> ReflectRef {
+        ReflectRef::Value(self)
+    }
+
+    fn reflect_mut(&mut self) -> ReflectMut {
+        ReflectMut::Value(self)
+    }
+
+    fn clone_value(&self) -> Box<dyn Reflect> {
+        Box::new(*self)
+    }
+
+    fn reflect_hash(&self) -> Option<u64> {
+        let mut hasher = crate::ReflectHasher::default();
+        Hash::hash(&std::any::Any::type_id(self), &mut hasher);
+        Hash::hash(self, &mut hasher);
+        Some(hasher.finish())
+    }
+
+    fn reflect_partial_eq(&self, value: &dyn Reflect) -> Option<bool> {
+        let value = value.any();
+        if let Some(value) = value.downcast_ref::<Self>() {
+            Some(std::cmp::PartialEq::eq(self, value))
+        } else {
+            Some(false)
+        }
+    }
+}
+
+impl Typed for Level {
+    fn type_info() -> &'static TypeInfo {
+        static CELL: NonGenericTypeInfoCell = NonGenericTypeInfoCell::new();
+        CELL.get_or_set(|| TypeInfo::Value(ValueInfo::new::<Self>()))
+    }
+}
+
+/// [`Level`] doesn't implement serde's traits, so it is (de)serialized as its name, e.g. `"INFO"`.
+impl GetTypeRegistration for Level {
+    fn get_type_registration() -> TypeRegistration {
+        let mut registration = TypeRegistration::of::<Level>();
+        registration.insert(ReflectDeserialize {
+            func: |deserializer| {
+                let name = String::deserialize(deserializer)?;
+                let level = name.parse::<Level>().map_err(|_| {
+                    erased_serde::Error::custom(format!("invalid log level `{}`", name))
+                })?;
+                Ok(Box::new(level))
+            },
+        });
+        registration.insert(ReflectSerialize {
+            get_serializable: |value| {
+                let level = value.downcast_ref::<Level>().unwrap_or_else(|| {
+                    panic!(
+                        "ReflectSerialize::get_serialize called with type `{}`, even though it was created for `{}`",
+                        value.type_name(),
+                        std::any::type_name::<Level>()
+                    )
+                });
+                crate::serde::Serializable::Owned(Box::new(level.as_str()))
+            },
+        });
+        registration
+    }
+}
+
+impl FromReflect for Level {
+    fn from_reflect(reflect: &dyn crate::Reflect) -> Option<Self> {
+        reflect.any().downcast_ref::<Level>().copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        serde::{ReflectDeserializer, ReflectSerializer},
+        TypeRegistry,
+    };
+    use bevy_utils::tracing::Level;
+    use serde::de::DeserializeSeed;
+
+    #[test]
+    fn level_round_trip() {
+        let mut registry = TypeRegistry::default();
+        registry.register::<Level>();
+
+        let serialized = ron::to_string(&ReflectSerializer::new(&Level::WARN, &registry)).unwrap();
+        assert_eq!(
+            serialized,
+            r#"{"type":"tracing_core::metadata::Level","value":"WARN"}"#
+        );
+
+        let mut deserializer = ron::de::Deserializer::from_str(&serialized).unwrap();
+        let value = ReflectDeserializer::new(&registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+        assert_eq!(value.take::<Level>().unwrap(), Level::WARN);
+    }
+}
@@ -1,11 +1,15 @@
 #![doc = include_str!("../README.md")]
 
 mod array;
+mod diff;
 mod fields;
+mod func;
 mod list;
 mod map;
 mod path;
 mod reflect;
+#[cfg(feature = "json_schema")]
+mod schema;
 mod struct_trait;
 mod tuple;
 mod tuple_struct;
@@ -18,6 +22,7 @@ mod impls {
     #[cfg(feature = "smallvec")]
     mod smallvec;
     mod std;
+    mod tracing;
 
     #[cfg(feature = "glam")]
     pub use self::glam::*;
@@ -40,12 +45,16 @@ pub mod prelude {
 }
 
 pub use array::*;
+pub use diff::*;
 pub use fields::*;
+pub use func::*;
 pub use impls::*;
 pub use list::*;
 pub use map::*;
 pub use path::*;
 pub use reflect::*;
+#[cfg(feature = "json_schema")]
+pub use schema::*;
 pub use struct_trait::*;
 pub use tuple::*;
 pub use tuple_struct::*;
@@ -485,6 +494,168 @@ mod tests {
         assert!(foo.reflect_partial_eq(&dynamic_struct).unwrap());
     }
 
+    #[test]
+    fn reflect_field_attributes() {
+        #[derive(Reflect, FromReflect, Debug, PartialEq)]
+        struct Foo {
+            a: u32,
+            #[reflect(default)]
+            b: Vec<u32>,
+            #[reflect(default = "get_c_default")]
+            c: String,
+            #[reflect(skip_serializing, default)]
+            d: u32,
+            #[reflect(meta(min = 0.0, max = 1.0, unit = "m"))]
+            e: f32,
+        }
+
+        fn get_c_default() -> String {
+            String::from("c")
+        }
+
+        let info = match Foo::type_info() {
+            TypeInfo::Struct(info) => info,
+            _ => panic!("expected struct info"),
+        };
+        let a = info.field("a").unwrap();
+        assert!(!a.has_default() && !a.skip_serializing());
+        assert_eq!(
+            info.field("c")
+                .unwrap()
+                .default_value()
+                .unwrap()
+                .take::<String>()
+                .unwrap(),
+            "c"
+        );
+        assert!(info.field("d").unwrap().skip_serializing());
+        let e = info.field("e").unwrap();
+        assert_eq!(e.metadata("max").unwrap().downcast_ref::<f64>(), Some(&1.0));
+        assert_eq!(
+            e.metadata("unit")
+                .unwrap()
+                .downcast_ref::<String>()
+                .unwrap(),
+            "m"
+        );
+        assert_eq!(
+            e.iter_metadata().map(|(key, _)| key).collect::<Vec<_>>(),
+            vec!["min", "max", "unit"]
+        );
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<u32>();
+        registry.register::<f32>();
+        registry.register::<String>();
+        registry.register::<Foo>();
+
+        let foo = Foo {
+            a: 1,
+            b: vec![2],
+            c: String::from("3"),
+            d: 4,
+            e: 0.5,
+        };
+        let serializer = ReflectSerializer::new(&foo, &registry);
+        let serialized = to_string_pretty(&serializer, PrettyConfig::default()).unwrap();
+        assert!(!serialized.contains("\"d\""));
+
+        // Fields added after the data was saved fall back to their default values
+        let saved = r#"{
+            "type": "bevy_reflect::tests::reflect_field_attributes::Foo",
+            "struct": {
+                "a": {
+                    "type": "u32",
+                    "value": 1,
+                },
+                "e": {
+                    "type": "f32",
+                    "value": 0.5,
+                },
+            },
+        }"#;
+        let mut deserializer = Deserializer::from_str(saved).unwrap();
+        let reflect_deserializer = ReflectDeserializer::new(&registry);
+        let value = reflect_deserializer.deserialize(&mut deserializer).unwrap();
+        let foo = Foo::from_reflect(value.as_ref()).unwrap();
+        assert_eq!(
+            foo,
+            Foo {
+                a: 1,
+                b: Vec::new(),
+                c: String::from("c"),
+                d: 0,
+                e: 0.5,
+            }
+        );
+
+        let dynamic_struct = value.take::<DynamicStruct>().unwrap();
+        assert_eq!(dynamic_struct.field_len(), 5);
+    }
+
+    #[test]
+    fn reflect_deserialize_renames() {
+        #[derive(Reflect, FromReflect, Debug, PartialEq)]
+        struct Foo {
+            a: u32,
+            b: String,
+        }
+
+        struct Renames;
+
+        impl crate::serde::DeserializeRenames for Renames {
+            fn rename_type(&self, type_name: &str) -> Option<String> {
+                match type_name {
+                    "OldFoo" => Some(std::any::type_name::<Foo>().to_string()),
+                    "OldString" => Some(std::any::type_name::<String>().to_string()),
+                    _ => None,
+                }
+            }
+
+            fn rename_field(&self, type_name: &str, field: &str) -> Option<String> {
+                (type_name == "OldFoo" && field == "old_a").then(|| "a".to_string())
+            }
+        }
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<u32>();
+        registry.register::<String>();
+        registry.register::<Foo>();
+
+        // the old name of the value type isn't registered, so it must be renamed before its lookup
+        let saved = r#"{
+            "type": "OldFoo",
+            "struct": {
+                "old_a": {
+                    "type": "u32",
+                    "value": 1,
+                },
+                "b": {
+                    "type": "OldString",
+                    "value": "b",
+                },
+            },
+        }"#;
+        let mut deserializer = Deserializer::from_str(saved).unwrap();
+        assert!(ReflectDeserializer::new(&registry)
+            .deserialize(&mut deserializer)
+            .is_err());
+
+        let mut deserializer = Deserializer::from_str(saved).unwrap();
+        let value = ReflectDeserializer::new(&registry)
+            .with_renames(&Renames)
+            .deserialize(&mut deserializer)
+            .unwrap();
+        assert_eq!(value.type_name(), std::any::type_name::<Foo>());
+        assert_eq!(
+            Foo::from_reflect(value.as_ref()).unwrap(),
+            Foo {
+                a: 1,
+                b: String::from("b"),
+            }
+        );
+    }
+
     #[test]
     fn reflect_take() {
         #[derive(Reflect, Debug, PartialEq)]
@@ -9,12 +9,37 @@ use crate::{
 
 /// An ordered, mutable list of [Reflect] items. This corresponds to types like [`std::vec::Vec`].
 ///
-/// This is a sub-trait of [`Array`] as it implements a [`push`](List::push) function, allowing
-/// it's internal size to grow.
+/// This is a sub-trait of [`Array`] as it implements [`push`](List::push), [`insert`](List::insert)
+/// and [`remove`](List::remove) functions, allowing it's internal size to change.
 pub trait List: Reflect + Array {
     /// Appends an element to the list.
     fn push(&mut self, value: Box<dyn Reflect>);
 
+    /// Inserts an element at `index` in the list, shifting the elements after it.
+    ///
+    /// If the value can't be converted to the type of the elements of the list, or if the list
+    /// doesn't support inserting elements, the value is returned back.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    fn insert(&mut self, index: usize, value: Box<dyn Reflect>) -> Result<(), Box<dyn Reflect>> {
+        let _ = index;
+        Err(value)
+    }
+
+    /// Removes and returns the element at `index` in the list, shifting the elements after it.
+    ///
+    /// Returns `None` if the list doesn't support removing elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    fn remove(&mut self, index: usize) -> Option<Box<dyn Reflect>> {
+        let _ = index;
+        None
+    }
+
     /// Clones the list, producing a [`DynamicList`].
     fn clone_dynamic(&self) -> DynamicList {
         DynamicList {
@@ -151,6 +176,15 @@ impl List for DynamicList {
         DynamicList::push_box(self, value);
     }
 
+    fn insert(&mut self, index: usize, value: Box<dyn Reflect>) -> Result<(), Box<dyn Reflect>> {
+        self.values.insert(index, value);
+        Ok(())
+    }
+
+    fn remove(&mut self, index: usize) -> Option<Box<dyn Reflect>> {
+        Some(self.values.remove(index))
+    }
+
     fn clone_dynamic(&self) -> DynamicList {
         DynamicList {
             name: self.name.clone(),
@@ -339,8 +373,29 @@ pub fn list_debug(dyn_list: &dyn List, f: &mut std::fmt::Formatter<'_>) -> std::
 #[cfg(test)]
 mod tests {
     use super::DynamicList;
+    use crate::{Array, List};
     use std::assert_eq;
 
+    #[test]
+    fn insert_and_remove() {
+        let mut list = vec![0usize, 2];
+        List::insert(&mut list, 1, Box::new(1usize)).unwrap();
+        assert_eq!(list, vec![0, 1, 2]);
+        let removed = List::remove(&mut list, 0).unwrap();
+        assert_eq!(removed.take::<usize>().unwrap(), 0);
+        assert_eq!(list, vec![1, 2]);
+        let rejected = List::insert(&mut list, 0, Box::new(1u8)).unwrap_err();
+        assert_eq!(rejected.take::<u8>().unwrap(), 1);
+
+        let mut list = DynamicList::default();
+        list.push(0usize);
+        list.push(2usize);
+        List::insert(&mut list, 1, Box::new(1usize)).unwrap();
+        assert_eq!(*list.get(1).unwrap().downcast_ref::<usize>().unwrap(), 1);
+        List::remove(&mut list, 0).unwrap();
+        assert_eq!(list.len(), 2);
+    }
+
     #[test]
     fn test_into_iter() {
         let mut list = DynamicList::default();
@@ -44,6 +44,36 @@ pub trait Map: Reflect {
 
     /// Clones the map, producing a [`DynamicMap`].
     fn clone_dynamic(&self) -> DynamicMap;
+
+    /// Inserts a key-value pair into the map.
+    ///
+    /// If the map already had a value for `key`, it is replaced and the old value is returned.
+    ///
+    /// If the key or the value can't be converted to the types of the entries of the map, or if
+    /// the map doesn't support inserting entries, they are returned back.
+    fn insert_boxed(
+        &mut self,
+        key: Box<dyn Reflect>,
+        value: Box<dyn Reflect>,
+    ) -> Result<Option<Box<dyn Reflect>>, MapInsertError> {
+        Err(MapInsertError { key, value })
+    }
+
+    /// Removes the entry of `key` from the map, returning its value.
+    ///
+    /// If no value is associated with `key`, or if the map doesn't support removing entries,
+    /// returns `None`.
+    fn remove(&mut self, key: &dyn Reflect) -> Option<Box<dyn Reflect>> {
+        let _ = key;
+        None
+    }
+}
+
+/// The entry rejected by [`Map::insert_boxed`].
+#[derive(Debug)]
+pub struct MapInsertError {
+    pub key: Box<dyn Reflect>,
+    pub value: Box<dyn Reflect>,
 }
 
 /// A container for compile-time map info.
@@ -156,13 +186,26 @@ impl DynamicMap {
 
     /// Inserts a key-value pair of [`Reflect`] values into the map.
     pub fn insert_boxed(&mut self, key: Box<dyn Reflect>, value: Box<dyn Reflect>) {
-        match self.indices.entry(key.reflect_hash().expect(HASH_ERROR)) {
+        let hash = key.reflect_hash().expect(HASH_ERROR);
+        self.insert_hashed(hash, key, value);
+    }
+
+    fn insert_hashed(
+        &mut self,
+        hash: u64,
+        key: Box<dyn Reflect>,
+        value: Box<dyn Reflect>,
+    ) -> Option<Box<dyn Reflect>> {
+        match self.indices.entry(hash) {
             Entry::Occupied(entry) => {
-                self.values[*entry.get()] = (key, value);
+                let (_, old_value) =
+                    std::mem::replace(&mut self.values[*entry.get()], (key, value));
+                Some(old_value)
             }
             Entry::Vacant(entry) => {
                 entry.insert(self.values.len());
                 self.values.push((key, value));
+                None
             }
         }
     }
@@ -210,6 +253,31 @@ impl Map for DynamicMap {
             .get(index)
             .map(|(key, value)| (&**key, &**value))
     }
+
+    fn insert_boxed(
+        &mut self,
+        key: Box<dyn Reflect>,
+        value: Box<dyn Reflect>,
+    ) -> Result<Option<Box<dyn Reflect>>, MapInsertError> {
+        match key.reflect_hash() {
+            Some(hash) => Ok(self.insert_hashed(hash, key, value)),
+            None => Err(MapInsertError { key, value }),
+        }
+    }
+
+    fn remove(&mut self, key: &dyn Reflect) -> Option<Box<dyn Reflect>> {
+        let index = self
+            .indices
+            .remove(&key.reflect_hash().expect(HASH_ERROR))?;
+        let (_, value) = self.values.remove(index);
+        // keep the order of the other entries
+        for other_index in self.indices.values_mut() {
+            if *other_index > index {
+                *other_index -= 1;
+            }
+        }
+        Some(value)
+    }
 }
 
 // SAFE: any and any_mut both return self
@@ -386,7 +454,71 @@ pub fn map_debug(dyn_map: &dyn Map, f: &mut std::fmt::Formatter<'_>) -> std::fmt
 
 #[cfg(test)]
 mod tests {
-    use super::DynamicMap;
+    use super::{DynamicMap, MapInsertError};
+    use crate::Map;
+    use bevy_utils::HashMap;
+
+    #[test]
+    fn insert_and_remove() {
+        let mut map = DynamicMap::default();
+        map.insert(0usize, 0u32);
+        map.insert(1usize, 1u32);
+        map.insert(2usize, 2u32);
+
+        let removed = Map::remove(&mut map, &1usize).unwrap();
+        assert_eq!(removed.take::<u32>().unwrap(), 1);
+        assert!(Map::remove(&mut map, &1usize).is_none());
+        // the other entries keep their order
+        let keys: Vec<_> = map
+            .iter()
+            .map(|(key, _)| *key.downcast_ref::<usize>().unwrap())
+            .collect();
+        assert_eq!(keys, vec![0, 2]);
+        assert_eq!(*map.get(&2usize).unwrap().downcast_ref::<u32>().unwrap(), 2);
+
+        let old_value = Map::insert_boxed(&mut map, Box::new(2usize), Box::new(3u32))
+            .unwrap()
+            .unwrap();
+        assert_eq!(old_value.take::<u32>().unwrap(), 2);
+
+        let mut map: HashMap<usize, u32> = HashMap::default();
+        assert!(
+            Map::insert_boxed(&mut map, Box::new(0usize), Box::new(0u32))
+                .unwrap()
+                .is_none()
+        );
+        let MapInsertError { key, value } =
+            Map::insert_boxed(&mut map, Box::new(1usize), Box::new(1.0f32)).unwrap_err();
+        assert_eq!(key.take::<usize>().unwrap(), 1);
+        assert_eq!(value.take::<f32>().unwrap(), 1.0);
+        let removed = Map::remove(&mut map, &0usize).unwrap();
+        assert_eq!(removed.take::<u32>().unwrap(), 0);
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn hash_map_of_values_without_from_reflect() {
+        use crate as bevy_reflect;
+        use crate::{Reflect, ReflectMut};
+
+        #[derive(Reflect)]
+        struct Foo(u32);
+
+        let mut map: HashMap<usize, Foo> = HashMap::default();
+        map.insert(0, Foo(1));
+        let map: &mut dyn Reflect = &mut map;
+        let map = match map.reflect_mut() {
+            ReflectMut::Map(map) => map,
+            _ => panic!("expected a map"),
+        };
+        assert!(Map::insert_boxed(map, Box::new(1usize), Box::new(Foo(2)))
+            .unwrap()
+            .is_none());
+        // dynamic values can't be converted to `Foo` without `FromReflect`
+        let dynamic = map.get(&0usize).unwrap().clone_value();
+        assert!(Map::insert_boxed(map, Box::new(2usize), dynamic).is_err());
+        assert_eq!(map.len(), 2);
+    }
 
     #[test]
     fn test_into_iter() {
@@ -1,9 +1,17 @@
-use std::num::ParseIntError;
-
-use crate::{Reflect, ReflectMut, ReflectRef};
+use std::{
+    borrow::Cow,
+    fmt,
+    hash::{Hash, Hasher},
+    num::ParseIntError,
+};
+
+use crate::{DynamicStruct, Map, Reflect, ReflectMut, ReflectRef, Struct};
 use thiserror::Error;
 
 /// An error returned from a failed path string query.
+///
+/// The `index` of each variant is the byte offset in the path string of the segment which
+/// failed to parse or resolve.
 #[derive(Debug, PartialEq, Eq, Error)]
 pub enum ReflectPathError<'a> {
     #[error("expected an identifier at the given index")]
@@ -15,8 +23,14 @@ pub enum ReflectPathError<'a> {
         index: usize,
         tuple_struct_index: usize,
     },
+    #[error("the current tuple doesn't have a field with the given index")]
+    InvalidTupleIndex { index: usize, tuple_index: usize },
     #[error("the current list doesn't have a value at the given index")]
     InvalidListIndex { index: usize, list_index: usize },
+    #[error("the current map doesn't have a value with the given key")]
+    InvalidMapKey { index: usize, key: &'a str },
+    #[error("the current map doesn't have a value with the given integer key")]
+    InvalidMapIndex { index: usize, map_index: usize },
     #[error("encountered an unexpected token")]
     UnexpectedToken { index: usize, token: &'a str },
     #[error("expected a token, but it wasn't there.")]
@@ -25,8 +39,16 @@ pub enum ReflectPathError<'a> {
     ExpectedStruct { index: usize },
     #[error("expected a list, but found a different reflect value")]
     ExpectedList { index: usize },
+    #[error("expected a map, but found a different reflect value")]
+    ExpectedMap { index: usize },
     #[error("failed to parse a usize")]
-    IndexParseError(#[from] ParseIntError),
+    IndexParseError { index: usize, error: ParseIntError },
+    #[error("expected a value of type {expected}, but found a value of type {found}")]
+    MismatchedType {
+        index: usize,
+        expected: String,
+        found: String,
+    },
     #[error("failed to downcast to the path result to the given type")]
     InvalidDowncast,
 }
@@ -36,7 +58,9 @@ pub enum ReflectPathError<'a> {
 /// Path strings use Rust syntax:
 /// - [`Struct`] items are accessed with a dot and a field name: `.field_name`
 /// - [`TupleStruct`] and [`Tuple`] items are accessed with a dot and a number: `.0`
-/// - [`List`] items are accessed with brackets: `[0]`
+/// - [`List`] and [`Array`] items are accessed with brackets: `[0]`
+/// - [`Map`] items are accessed with brackets and a quoted string key: `["key"]`, or with
+///   brackets and a number for maps with integer keys: `[0]`
 ///
 /// If the initial path element is a field of a struct, tuple struct, or tuple,
 /// the initial '.' may be omitted.
@@ -45,10 +69,17 @@ pub enum ReflectPathError<'a> {
 /// 2-tuples (like a `Vec<(T, U)>`), the path string `foo[3].0` would access tuple
 /// element 0 of element 3 of `foo`.
 ///
+/// Map keys may not contain a `"`. String keys match maps keyed by [`String`] or
+/// [`Cow<str>`](std::borrow::Cow), and integer keys match maps keyed by any primitive integer.
+///
+/// To resolve the same path many times, parse it once into a [`ParsedPath`]: the keys of its
+/// map segments are built once, instead of on every resolution.
+///
 /// [`Struct`]: crate::Struct
 /// [`TupleStruct`]: crate::TupleStruct
 /// [`Tuple`]: crate::Tuple
 /// [`List`]: crate::List
+/// [`Array`]: crate::Array
 pub trait GetPath {
     /// Returns a reference to the value specified by `path`.
     ///
@@ -104,65 +135,11 @@ impl<T: Reflect> GetPath for T {
 
 impl GetPath for dyn Reflect {
     fn path<'r, 'p>(&'r self, path: &'p str) -> Result<&'r dyn Reflect, ReflectPathError<'p>> {
-        let mut index = 0;
         let mut current: &dyn Reflect = self;
-        while let Some(token) = next_token(path, &mut index) {
-            let current_index = index;
-            match token {
-                Token::Dot => {
-                    if let Some(Token::Ident(value)) = next_token(path, &mut index) {
-                        current = read_field(current, value, current_index)?;
-                    } else {
-                        return Err(ReflectPathError::ExpectedIdent {
-                            index: current_index,
-                        });
-                    }
-                }
-                Token::OpenBracket => {
-                    if let Some(Token::Ident(value)) = next_token(path, &mut index) {
-                        match current.reflect_ref() {
-                            ReflectRef::List(reflect_list) => {
-                                let list_index = value.parse::<usize>()?;
-                                let list_item = reflect_list.get(list_index).ok_or(
-                                    ReflectPathError::InvalidListIndex {
-                                        index: current_index,
-                                        list_index,
-                                    },
-                                )?;
-                                current = list_item;
-                            }
-                            _ => {
-                                return Err(ReflectPathError::ExpectedList {
-                                    index: current_index,
-                                })
-                            }
-                        }
-                    } else {
-                        return Err(ReflectPathError::ExpectedIdent {
-                            index: current_index,
-                        });
-                    }
-
-                    if let Some(Token::CloseBracket) = next_token(path, &mut index) {
-                    } else {
-                        return Err(ReflectPathError::ExpectedToken {
-                            index: current_index,
-                            token: "]",
-                        });
-                    }
-                }
-                Token::CloseBracket => {
-                    return Err(ReflectPathError::UnexpectedToken {
-                        index: current_index,
-                        token: "]",
-                    })
-                }
-                Token::Ident(value) => {
-                    current = read_field(current, value, current_index)?;
-                }
-            }
+        for access in PathParser::new(path) {
+            let (access, index) = access?;
+            current = access.read_element(current, index)?;
         }
-
         Ok(current)
     }
 
@@ -170,69 +147,370 @@ impl GetPath for dyn Reflect {
         &'r mut self,
         path: &'p str,
     ) -> Result<&'r mut dyn Reflect, ReflectPathError<'p>> {
-        let mut index = 0;
         let mut current: &mut dyn Reflect = self;
-        while let Some(token) = next_token(path, &mut index) {
-            let current_index = index;
-            match token {
-                Token::Dot => {
-                    if let Some(Token::Ident(value)) = next_token(path, &mut index) {
-                        current = read_field_mut(current, value, current_index)?;
-                    } else {
-                        return Err(ReflectPathError::ExpectedIdent {
-                            index: current_index,
-                        });
-                    }
-                }
-                Token::OpenBracket => {
-                    if let Some(Token::Ident(value)) = next_token(path, &mut index) {
-                        match current.reflect_mut() {
-                            ReflectMut::List(reflect_list) => {
-                                let list_index = value.parse::<usize>()?;
-                                let list_item = reflect_list.get_mut(list_index).ok_or(
-                                    ReflectPathError::InvalidListIndex {
-                                        index: current_index,
-                                        list_index,
-                                    },
-                                )?;
-                                current = list_item;
-                            }
-                            _ => {
-                                return Err(ReflectPathError::ExpectedStruct {
-                                    index: current_index,
-                                })
-                            }
-                        }
-                    } else {
-                        return Err(ReflectPathError::ExpectedIdent {
-                            index: current_index,
-                        });
-                    }
-
-                    if let Some(Token::CloseBracket) = next_token(path, &mut index) {
-                    } else {
-                        return Err(ReflectPathError::ExpectedToken {
-                            index: current_index,
-                            token: "]",
-                        });
-                    }
+        for access in PathParser::new(path) {
+            let (access, index) = access?;
+            current = access.read_element_mut(current, index)?;
+        }
+        Ok(current)
+    }
+}
+
+/// A path string parsed once, to be resolved against many values.
+///
+/// See [`GetPath`] for the syntax of path strings. Two parsed paths are equal if they access the
+/// same values, regardless of the offsets of their segments in the original strings.
+///
+/// ```
+/// # use bevy_reflect::{ParsedPath, Reflect};
+/// #[derive(Reflect)]
+/// struct Color {
+///     r: f32,
+/// }
+///
+/// let path = ParsedPath::parse("r").unwrap();
+/// let mut color = Color { r: 0.5 };
+/// *path.element_mut::<f32>(&mut color).unwrap() = 1.0;
+/// assert_eq!(*path.element::<f32>(&color).unwrap(), 1.0);
+/// assert_eq!(path.to_string(), "r");
+/// ```
+#[derive(Debug, Clone)]
+pub struct ParsedPath(Box<[(Access, usize)]>);
+
+impl ParsedPath {
+    /// Parses a path string.
+    pub fn parse(path: &str) -> Result<Self, ReflectPathError<'_>> {
+        PathParser::new(path)
+            .map(|access| access.map(|(access, index)| (access.into_owned(), index)))
+            .collect::<Result<_, _>>()
+            .map(ParsedPath)
+    }
+
+    /// Returns a reference to the value specified by this path in `root`.
+    ///
+    /// To retrieve a statically typed reference, use [`element`][ParsedPath::element].
+    pub fn reflect_element<'r, 'p>(
+        &'p self,
+        root: &'r dyn Reflect,
+    ) -> Result<&'r dyn Reflect, ReflectPathError<'p>> {
+        let mut current = root;
+        for (access, index) in self.0.iter() {
+            current = access.as_ref().read_element(current, *index)?;
+        }
+        Ok(current)
+    }
+
+    /// Returns a mutable reference to the value specified by this path in `root`.
+    ///
+    /// To retrieve a statically typed mutable reference, use
+    /// [`element_mut`][ParsedPath::element_mut].
+    pub fn reflect_element_mut<'r, 'p>(
+        &'p self,
+        root: &'r mut dyn Reflect,
+    ) -> Result<&'r mut dyn Reflect, ReflectPathError<'p>> {
+        let mut current = root;
+        for (access, index) in self.0.iter() {
+            current = access.as_ref().read_element_mut(current, *index)?;
+        }
+        Ok(current)
+    }
+
+    /// Returns a statically typed reference to the value specified by this path in `root`.
+    pub fn element<'r, 'p, T: Reflect>(
+        &'p self,
+        root: &'r dyn Reflect,
+    ) -> Result<&'r T, ReflectPathError<'p>> {
+        self.reflect_element(root).and_then(|p| {
+            p.downcast_ref::<T>()
+                .ok_or(ReflectPathError::InvalidDowncast)
+        })
+    }
+
+    /// Returns a statically typed mutable reference to the value specified by this path in
+    /// `root`.
+    pub fn element_mut<'r, 'p, T: Reflect>(
+        &'p self,
+        root: &'r mut dyn Reflect,
+    ) -> Result<&'r mut T, ReflectPathError<'p>> {
+        self.reflect_element_mut(root).and_then(|p| {
+            p.downcast_mut::<T>()
+                .ok_or(ReflectPathError::InvalidDowncast)
+        })
+    }
+}
+
+impl ParsedPath {
+    fn accesses(&self) -> impl Iterator<Item = &Access> {
+        self.0.iter().map(|(access, _)| access)
+    }
+}
+
+impl PartialEq for ParsedPath {
+    fn eq(&self, other: &Self) -> bool {
+        self.accesses().eq(other.accesses())
+    }
+}
+
+impl Eq for ParsedPath {}
+
+impl Hash for ParsedPath {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for access in self.accesses() {
+            access.hash(state);
+        }
+    }
+}
+
+impl fmt::Display for ParsedPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (position, access) in self.accesses().enumerate() {
+            match access {
+                Access::Field(field) if position == 0 => write!(f, "{}", field)?,
+                Access::Field(field) => write!(f, ".{}", field)?,
+                Access::Index(index) => write!(f, "[{}]", index)?,
+                Access::Key(key) => write!(f, "[\"{}\"]", key.string)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Replaces the value specified by `path` in `root`.
+///
+/// If the last segment of the path is a field missing from a [`DynamicStruct`], the field is
+/// inserted instead.
+pub(crate) fn set_path<'p>(
+    root: &mut dyn Reflect,
+    path: &'p str,
+    value: Box<dyn Reflect>,
+) -> Result<(), ReflectPathError<'p>> {
+    let mut accesses = PathParser::new(path).collect::<Result<Vec<_>, _>>()?;
+    let (last, last_index) = accesses
+        .pop()
+        .ok_or(ReflectPathError::ExpectedIdent { index: 0 })?;
+    let mut parent = root;
+    for (access, index) in accesses {
+        parent = access.read_element_mut(parent, index)?;
+    }
+
+    if let AccessRef::Field(field) = last {
+        if let Some(dynamic_struct) = parent.downcast_mut::<DynamicStruct>() {
+            if dynamic_struct.field(field).is_none() {
+                dynamic_struct.insert_boxed(field, value);
+                return Ok(());
+            }
+        }
+    }
+
+    let target = last.read_element_mut(parent, last_index)?;
+    target
+        .set(value)
+        .map_err(|value| ReflectPathError::MismatchedType {
+            index: last_index,
+            expected: target.type_name().to_string(),
+            found: value.type_name().to_string(),
+        })
+}
+
+/// A single segment of a [`ParsedPath`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Access {
+    Field(String),
+    Index(usize),
+    Key(StringKey),
+}
+
+impl Access {
+    fn as_ref(&self) -> AccessRef<'_> {
+        match self {
+            Access::Field(field) => AccessRef::Field(field),
+            Access::Index(index) => AccessRef::Index(*index),
+            Access::Key(key) => AccessRef::Key(&key.string, Some(key)),
+        }
+    }
+}
+
+/// A single segment of a path, borrowed from a path string or a [`ParsedPath`].
+#[derive(Clone)]
+enum AccessRef<'a> {
+    /// A struct field name, or a tuple struct or tuple field index: `.field` or `.0`.
+    Field(&'a str),
+    /// A list or array index, or an integer map key: `[0]`.
+    Index(usize),
+    /// A string map key: `["key"]`, along with its typed keys if it comes from a [`ParsedPath`].
+    Key(&'a str, Option<&'a StringKey>),
+}
+
+impl<'a> AccessRef<'a> {
+    fn into_owned(self) -> Access {
+        match self {
+            AccessRef::Field(field) => Access::Field(field.to_string()),
+            AccessRef::Index(index) => Access::Index(index),
+            AccessRef::Key(key, typed_key) => {
+                Access::Key(typed_key.cloned().unwrap_or_else(|| StringKey::new(key)))
+            }
+        }
+    }
+
+    fn read_element<'r>(
+        self,
+        current: &'r dyn Reflect,
+        index: usize,
+    ) -> Result<&'r dyn Reflect, ReflectPathError<'a>> {
+        match self {
+            AccessRef::Field(field) => read_field(current, field, index),
+            AccessRef::Index(list_index) => match current.reflect_ref() {
+                ReflectRef::List(reflect_list) => reflect_list
+                    .get(list_index)
+                    .ok_or(ReflectPathError::InvalidListIndex { index, list_index }),
+                ReflectRef::Array(reflect_array) => reflect_array
+                    .get(list_index)
+                    .ok_or(ReflectPathError::InvalidListIndex { index, list_index }),
+                ReflectRef::Map(reflect_map) => IntegerKey::all(list_index)
+                    .find_map(|key| reflect_map.get(key.as_reflect()))
+                    .ok_or(ReflectPathError::InvalidMapIndex {
+                        index,
+                        map_index: list_index,
+                    }),
+                _ => Err(ReflectPathError::ExpectedList { index }),
+            },
+            AccessRef::Key(key, typed_key) => match current.reflect_ref() {
+                ReflectRef::Map(reflect_map) => match typed_key {
+                    Some(typed_key) => typed_key.get(reflect_map),
+                    None => StringKey::get_str(reflect_map, key),
                 }
-                Token::CloseBracket => {
-                    return Err(ReflectPathError::UnexpectedToken {
-                        index: current_index,
-                        token: "]",
-                    })
+                .ok_or(ReflectPathError::InvalidMapKey { index, key }),
+                _ => Err(ReflectPathError::ExpectedMap { index }),
+            },
+        }
+    }
+
+    fn read_element_mut<'r>(
+        self,
+        current: &'r mut dyn Reflect,
+        index: usize,
+    ) -> Result<&'r mut dyn Reflect, ReflectPathError<'a>> {
+        match self {
+            AccessRef::Field(field) => read_field_mut(current, field, index),
+            AccessRef::Index(list_index) => match current.reflect_mut() {
+                ReflectMut::List(reflect_list) => reflect_list
+                    .get_mut(list_index)
+                    .ok_or(ReflectPathError::InvalidListIndex { index, list_index }),
+                ReflectMut::Array(reflect_array) => reflect_array
+                    .get_mut(list_index)
+                    .ok_or(ReflectPathError::InvalidListIndex { index, list_index }),
+                ReflectMut::Map(reflect_map) => match IntegerKey::all(list_index)
+                    .find(|key| reflect_map.get(key.as_reflect()).is_some())
+                {
+                    Some(key) => reflect_map.get_mut(key.as_reflect()),
+                    None => None,
                 }
-                Token::Ident(value) => {
-                    current = read_field_mut(current, value, current_index)?;
+                .ok_or(ReflectPathError::InvalidMapIndex {
+                    index,
+                    map_index: list_index,
+                }),
+                _ => Err(ReflectPathError::ExpectedList { index }),
+            },
+            AccessRef::Key(key, typed_key) => match current.reflect_mut() {
+                ReflectMut::Map(reflect_map) => match typed_key {
+                    Some(typed_key) => typed_key.get_mut(reflect_map),
+                    None => StringKey::get_str_mut(reflect_map, key),
                 }
-            }
+                .ok_or(ReflectPathError::InvalidMapKey { index, key }),
+                _ => Err(ReflectPathError::ExpectedMap { index }),
+            },
         }
+    }
+}
 
-        Ok(current)
+/// A string map key of a [`ParsedPath`], typed once as each key type it can match.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct StringKey {
+    string: String,
+    cow: Cow<'static, str>,
+}
+
+impl StringKey {
+    fn new(key: &str) -> Self {
+        StringKey {
+            string: key.to_string(),
+            cow: Cow::Owned(key.to_string()),
+        }
+    }
+
+    fn get<'r>(&self, map: &'r dyn Map) -> Option<&'r dyn Reflect> {
+        map.get(&self.string).or_else(|| map.get(&self.cow))
+    }
+
+    fn get_mut<'r>(&self, map: &'r mut dyn Map) -> Option<&'r mut dyn Reflect> {
+        if map.get(&self.string).is_some() {
+            map.get_mut(&self.string)
+        } else {
+            map.get_mut(&self.cow)
+        }
+    }
+
+    /// Looks up a key that wasn't typed by a [`ParsedPath`], allocating it once: the `String`
+    /// is moved into the `Cow` if it isn't found.
+    fn get_str<'r>(map: &'r dyn Map, key: &str) -> Option<&'r dyn Reflect> {
+        let string = key.to_string();
+        if let Some(value) = map.get(&string) {
+            return Some(value);
+        }
+        let cow: Cow<'static, str> = Cow::Owned(string);
+        map.get(&cow)
+    }
+
+    fn get_str_mut<'r>(map: &'r mut dyn Map, key: &str) -> Option<&'r mut dyn Reflect> {
+        let string = key.to_string();
+        if map.get(&string).is_some() {
+            return map.get_mut(&string);
+        }
+        let cow: Cow<'static, str> = Cow::Owned(string);
+        map.get_mut(&cow)
     }
 }
 
+macro_rules! integer_keys {
+    ($($variant:ident($ty:ty)),*) => {
+        /// An integer map key, typed from the index of a `[0]` segment.
+        #[derive(Clone, Copy)]
+        enum IntegerKey {
+            $($variant($ty)),*
+        }
+
+        impl IntegerKey {
+            /// Returns `index` as each integer type it fits in.
+            fn all(index: usize) -> impl Iterator<Item = IntegerKey> {
+                [$(<$ty>::try_from(index).ok().map(IntegerKey::$variant)),*]
+                    .into_iter()
+                    .flatten()
+            }
+
+            fn as_reflect(&self) -> &dyn Reflect {
+                match self {
+                    $(IntegerKey::$variant(key) => key),*
+                }
+            }
+        }
+    };
+}
+
+integer_keys!(
+    Usize(usize),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    Isize(isize),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    I128(i128)
+);
+
 fn read_field<'r, 'p>(
     current: &'r dyn Reflect,
     field: &'p str,
@@ -248,7 +526,7 @@ fn read_field<'r, 'p>(
                 })?)
         }
         ReflectRef::TupleStruct(reflect_struct) => {
-            let tuple_index = field.parse::<usize>()?;
+            let tuple_index = parse_index(field, current_index)?;
             Ok(reflect_struct.field(tuple_index).ok_or(
                 ReflectPathError::InvalidTupleStructIndex {
                     index: current_index,
@@ -256,6 +534,15 @@ fn read_field<'r, 'p>(
                 },
             )?)
         }
+        ReflectRef::Tuple(reflect_tuple) => {
+            let tuple_index = parse_index(field, current_index)?;
+            Ok(reflect_tuple
+                .field(tuple_index)
+                .ok_or(ReflectPathError::InvalidTupleIndex {
+                    index: current_index,
+                    tuple_index,
+                })?)
+        }
         _ => Err(ReflectPathError::ExpectedStruct {
             index: current_index,
         }),
@@ -277,7 +564,7 @@ fn read_field_mut<'r, 'p>(
                 })?)
         }
         ReflectMut::TupleStruct(reflect_struct) => {
-            let tuple_index = field.parse::<usize>()?;
+            let tuple_index = parse_index(field, current_index)?;
             Ok(reflect_struct.field_mut(tuple_index).ok_or(
                 ReflectPathError::InvalidTupleStructIndex {
                     index: current_index,
@@ -285,12 +572,104 @@ fn read_field_mut<'r, 'p>(
                 },
             )?)
         }
+        ReflectMut::Tuple(reflect_tuple) => {
+            let tuple_index = parse_index(field, current_index)?;
+            Ok(reflect_tuple
+                .field_mut(tuple_index)
+                .ok_or(ReflectPathError::InvalidTupleIndex {
+                    index: current_index,
+                    tuple_index,
+                })?)
+        }
         _ => Err(ReflectPathError::ExpectedStruct {
             index: current_index,
         }),
     }
 }
 
+fn parse_index(value: &str, index: usize) -> Result<usize, ReflectPathError<'static>> {
+    value
+        .parse::<usize>()
+        .map_err(|error| ReflectPathError::IndexParseError { index, error })
+}
+
+/// Splits a path string into its segments, along with the offset of each segment.
+struct PathParser<'a> {
+    path: &'a str,
+    index: usize,
+}
+
+impl<'a> PathParser<'a> {
+    fn new(path: &'a str) -> Self {
+        Self { path, index: 0 }
+    }
+
+    fn next_access(&mut self) -> Option<Result<(AccessRef<'a>, usize), ReflectPathError<'a>>> {
+        let start = self.index;
+        let token = next_token(self.path, &mut self.index)?;
+        let current_index = self.index;
+        let access = match token {
+            Token::Dot => match next_token(self.path, &mut self.index) {
+                Some(Token::Ident(value)) => Ok((AccessRef::Field(value), current_index)),
+                _ => Err(ReflectPathError::ExpectedIdent {
+                    index: current_index,
+                }),
+            },
+            Token::OpenBracket => self.bracket_access(current_index),
+            Token::CloseBracket => Err(ReflectPathError::UnexpectedToken {
+                index: current_index,
+                token: "]",
+            }),
+            Token::Ident(value) => Ok((AccessRef::Field(value), start)),
+        };
+        Some(access)
+    }
+
+    /// Parses the contents of brackets, after the opening bracket.
+    fn bracket_access(
+        &mut self,
+        current_index: usize,
+    ) -> Result<(AccessRef<'a>, usize), ReflectPathError<'a>> {
+        let access = if let Some(rest) = self.path[self.index..].strip_prefix('"') {
+            let key_len = rest.find('"').ok_or(ReflectPathError::ExpectedToken {
+                index: current_index,
+                token: "\"",
+            })?;
+            let key = &rest[..key_len];
+            self.index += key_len + 2;
+            AccessRef::Key(key, None)
+        } else if let Some(Token::Ident(value)) = next_token(self.path, &mut self.index) {
+            AccessRef::Index(parse_index(value, current_index)?)
+        } else {
+            return Err(ReflectPathError::ExpectedIdent {
+                index: current_index,
+            });
+        };
+
+        if let Some(Token::CloseBracket) = next_token(self.path, &mut self.index) {
+            Ok((access, current_index))
+        } else {
+            Err(ReflectPathError::ExpectedToken {
+                index: current_index,
+                token: "]",
+            })
+        }
+    }
+}
+
+impl<'a> Iterator for PathParser<'a> {
+    type Item = Result<(AccessRef<'a>, usize), ReflectPathError<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let access = self.next_access();
+        if let Some(Err(_)) = access {
+            // Stop at the first error
+            self.index = self.path.len();
+        }
+        access
+    }
+}
+
 enum Token<'a> {
     Dot,
     OpenBracket,
@@ -320,7 +699,7 @@ fn next_token<'a>(path: &'a str, index: &mut usize) -> Option<Token<'a>> {
     }
 
     // we can assume we are parsing an ident now
-    for (char_index, character) in path[*index..].chars().enumerate() {
+    for (char_index, character) in path[*index..].char_indices() {
         match character {
             '.' | '[' | ']' => {
                 let ident = Token::Ident(&path[*index..*index + char_index]);
@@ -341,6 +720,8 @@ mod tests {
     use super::GetPath;
     use crate as bevy_reflect;
     use crate::*;
+    use bevy_utils::HashMap;
+    use std::borrow::Cow;
     #[test]
     fn reflect_path() {
         #[derive(Reflect)]
@@ -349,6 +730,7 @@ mod tests {
             x: B,
             y: Vec<C>,
             z: D,
+            t: (u8, [f32; 2]),
         }
 
         #[derive(Reflect)]
@@ -376,6 +758,7 @@ mod tests {
             },
             y: vec![C { baz: 1.0 }, C { baz: 2.0 }],
             z: D(E(10.0, 42)),
+            t: (1, [0.5, 1.5]),
         };
 
         assert_eq!(*a.get_path::<usize>("w").unwrap(), 1);
@@ -383,10 +766,15 @@ mod tests {
         assert_eq!(*a.get_path::<f32>("x.bar.baz").unwrap(), 3.14);
         assert_eq!(*a.get_path::<f32>("y[1].baz").unwrap(), 2.0);
         assert_eq!(*a.get_path::<usize>("z.0.1").unwrap(), 42);
+        assert_eq!(*a.get_path::<u8>("t.0").unwrap(), 1);
+        assert_eq!(*a.get_path::<f32>("t.1[1]").unwrap(), 1.5);
 
         *a.get_path_mut::<f32>("y[1].baz").unwrap() = 3.0;
         assert_eq!(a.y[1].baz, 3.0);
 
+        *a.get_path_mut::<f32>("t.1[0]").unwrap() = 2.5;
+        assert_eq!(a.t.1[0], 2.5);
+
         assert_eq!(
             a.path("x.notreal").err().unwrap(),
             ReflectPathError::InvalidField {
@@ -410,9 +798,200 @@ mod tests {
             ReflectPathError::ExpectedStruct { index: 2 }
         );
 
+        assert_eq!(
+            a.path("t.2").err().unwrap(),
+            ReflectPathError::InvalidTupleIndex {
+                index: 2,
+                tuple_index: 2
+            }
+        );
+
         assert!(matches!(
             a.path("y[badindex]"),
-            Err(ReflectPathError::IndexParseError(_))
+            Err(ReflectPathError::IndexParseError { index: 2, .. })
         ));
     }
+
+    #[test]
+    fn reflect_path_map_keys() {
+        #[derive(Reflect, FromReflect)]
+        struct Material {
+            color: Color,
+        }
+
+        #[derive(Reflect, FromReflect)]
+        struct Color {
+            r: f32,
+        }
+
+        #[derive(Reflect)]
+        struct Model {
+            materials: HashMap<String, Material>,
+            lods: HashMap<u32, f32>,
+            tags: HashMap<Cow<'static, str>, i8>,
+            offsets: HashMap<i8, f32>,
+        }
+
+        let mut model = Model {
+            materials: HashMap::default(),
+            lods: HashMap::default(),
+            tags: HashMap::default(),
+            offsets: HashMap::default(),
+        };
+        model.materials.insert(
+            "skin".to_string(),
+            Material {
+                color: Color { r: 0.5 },
+            },
+        );
+        model.lods.insert(2, 10.0);
+        model.tags.insert(Cow::Borrowed("layer"), 3);
+        model.offsets.insert(1, 0.25);
+
+        assert_eq!(
+            *model
+                .get_path::<f32>("materials[\"skin\"].color.r")
+                .unwrap(),
+            0.5
+        );
+        assert_eq!(*model.get_path::<f32>("lods[2]").unwrap(), 10.0);
+
+        *model
+            .get_path_mut::<f32>("materials[\"skin\"].color.r")
+            .unwrap() = 1.0;
+        assert_eq!(model.materials["skin"].color.r, 1.0);
+        *model.get_path_mut::<f32>("lods[2]").unwrap() = 20.0;
+        assert_eq!(model.lods[&2], 20.0);
+
+        assert_eq!(*model.get_path::<i8>("tags[\"layer\"]").unwrap(), 3);
+        *model.get_path_mut::<i8>("tags[\"layer\"]").unwrap() = 4;
+        assert_eq!(model.tags["layer"], 4);
+        assert_eq!(*model.get_path::<f32>("offsets[1]").unwrap(), 0.25);
+        let path = ParsedPath::parse("offsets[1]").unwrap();
+        *path.element_mut::<f32>(&mut model).unwrap() = 0.5;
+        assert_eq!(model.offsets[&1], 0.5);
+
+        assert_eq!(
+            model.path("materials[\"hair\"].color").err().unwrap(),
+            ReflectPathError::InvalidMapKey {
+                index: 10,
+                key: "hair"
+            }
+        );
+        assert_eq!(
+            model.path("lods[3]").err().unwrap(),
+            ReflectPathError::InvalidMapIndex {
+                index: 5,
+                map_index: 3
+            }
+        );
+        assert_eq!(
+            model
+                .path("materials[\"skin\"].color[\"r\"]")
+                .err()
+                .unwrap(),
+            ReflectPathError::ExpectedMap { index: 24 }
+        );
+        assert_eq!(
+            model.path("materials[\"skin").err().unwrap(),
+            ReflectPathError::ExpectedToken {
+                index: 10,
+                token: "\""
+            }
+        );
+        assert_eq!(
+            model.path("materials[\"skin\"").err().unwrap(),
+            ReflectPathError::ExpectedToken {
+                index: 10,
+                token: "]"
+            }
+        );
+    }
+
+    #[test]
+    fn parsed_path() {
+        #[derive(Reflect)]
+        struct A {
+            x: Vec<(u8, f32)>,
+            y: HashMap<String, usize>,
+        }
+
+        let mut a = A {
+            x: vec![(1, 2.0)],
+            y: HashMap::default(),
+        };
+        a.y.insert("a.b".to_string(), 3);
+
+        let path = ParsedPath::parse("x[0].1").unwrap();
+        assert_eq!(*path.element::<f32>(&a).unwrap(), 2.0);
+        *path.element_mut::<f32>(&mut a).unwrap() = 4.0;
+        assert_eq!(a.x[0].1, 4.0);
+        assert_eq!(path.to_string(), "x[0].1");
+
+        let path = ParsedPath::parse(".y[\"a.b\"]").unwrap();
+        assert_eq!(*path.element::<usize>(&a).unwrap(), 3);
+        assert_eq!(path.to_string(), "y[\"a.b\"]");
+        assert_eq!(ParsedPath::parse(&path.to_string()).unwrap(), path);
+
+        assert_eq!(
+            path.element::<u8>(&a).err().unwrap(),
+            ReflectPathError::InvalidDowncast
+        );
+        assert_eq!(
+            ParsedPath::parse("x[0]]").err().unwrap(),
+            ReflectPathError::UnexpectedToken {
+                index: 5,
+                token: "]"
+            }
+        );
+        assert!(matches!(
+            ParsedPath::parse("x[-1]"),
+            Err(ReflectPathError::IndexParseError { index: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn dynamic_struct_set_path() {
+        #[derive(Reflect, FromReflect)]
+        struct B {
+            x: f32,
+        }
+
+        let mut inner = DynamicStruct::default();
+        inner.insert("x", 1.0f32);
+        let mut dynamic = DynamicStruct::default();
+        dynamic.insert("inner", inner);
+        dynamic.insert("list", vec![B { x: 1.0 }]);
+
+        dynamic.set_path("inner.x", Box::new(2.0f32)).unwrap();
+        assert_eq!(*dynamic.get_path::<f32>("inner.x").unwrap(), 2.0);
+
+        dynamic.set_path("list[0].x", Box::new(3.0f32)).unwrap();
+        assert_eq!(*dynamic.get_path::<f32>("list[0].x").unwrap(), 3.0);
+
+        dynamic.set_path("inner.y", Box::new(4usize)).unwrap();
+        dynamic.set_path("z", Box::new(5u8)).unwrap();
+        assert_eq!(*dynamic.get_path::<usize>("inner.y").unwrap(), 4);
+        assert_eq!(*dynamic.get_path::<u8>("z").unwrap(), 5);
+
+        assert_eq!(
+            dynamic.set_path("list[0].x", Box::new(6usize)),
+            Err(ReflectPathError::MismatchedType {
+                index: 8,
+                expected: "f32".to_string(),
+                found: "usize".to_string(),
+            })
+        );
+        assert_eq!(
+            dynamic.set_path("list[0].y", Box::new(6.0f32)),
+            Err(ReflectPathError::InvalidField {
+                index: 8,
+                field: "y"
+            })
+        );
+        assert_eq!(
+            dynamic.set_path("", Box::new(6.0f32)),
+            Err(ReflectPathError::ExpectedIdent { index: 0 })
+        );
+    }
 }
@@ -0,0 +1,478 @@
+use crate::{serde::type_fields, FromType, TypeData, TypeInfo, TypeRegistration, TypeRegistry};
+use serde_json::{json, Map, Value};
+use std::{any::TypeId, borrow::Cow};
+
+/// The JSON Schema dialect used by [`SchemaExporter::export`].
+pub const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// A trait for value types which can describe their serialized form as a JSON Schema.
+///
+/// Reflected value types are serialized with their own [`Serialize`] implementation, so their
+/// structure can't be inferred from their [`TypeInfo`]. Types implementing this trait can be
+/// registered with `#[reflect_value(ValueSchema)]` to provide it instead. The schemas of the
+/// primitive types, such as `f32` or `String`, are known without being registered.
+///
+/// [`Serialize`]: serde::Serialize
+pub trait ValueSchema {
+    /// Returns the JSON Schema of the serialized form of this type.
+    fn value_schema() -> Value;
+}
+
+/// A struct used to retrieve the JSON Schema of a reflected value type.
+///
+/// A `ReflectValueSchema` for type `T` can be obtained via
+/// [`FromType::from_type`].
+#[derive(Clone)]
+pub struct ReflectValueSchema {
+    get_schema: fn() -> Value,
+}
+
+impl ReflectValueSchema {
+    /// Returns the JSON Schema of the serialized form of the value type.
+    pub fn schema(&self) -> Value {
+        (self.get_schema)()
+    }
+}
+
+impl<T: ValueSchema> FromType<T> for ReflectValueSchema {
+    fn from_type() -> Self {
+        ReflectValueSchema {
+            get_schema: T::value_schema,
+        }
+    }
+}
+
+macro_rules! impl_value_schema {
+    ($($ty:ty),* => $schema:expr) => {
+        $(
+            impl ValueSchema for $ty {
+                fn value_schema() -> Value {
+                    $schema
+                }
+            }
+        )*
+    };
+}
+
+impl_value_schema!(bool => json!({ "type": "boolean" }));
+impl_value_schema!(f32, f64 => json!({ "type": "number" }));
+impl_value_schema!(String, Cow<'static, str> => json!({ "type": "string" }));
+impl_value_schema!(char => json!({ "type": "string", "minLength": 1, "maxLength": 1 }));
+impl_value_schema!(u128, usize => json!({ "type": "integer", "minimum": 0 }));
+impl_value_schema!(i128, isize => json!({ "type": "integer" }));
+
+macro_rules! impl_bounded_integer_schema {
+    ($($ty:ty),*) => {
+        $(
+            impl ValueSchema for $ty {
+                fn value_schema() -> Value {
+                    json!({ "type": "integer", "minimum": <$ty>::MIN, "maximum": <$ty>::MAX })
+                }
+            }
+        )*
+    };
+}
+
+impl_bounded_integer_schema!(u8, u16, u32, u64, i8, i16, i32, i64);
+
+/// Returns the schema of the primitive type with the given [`TypeId`], if it is one.
+fn primitive_schema(type_id: TypeId) -> Option<Value> {
+    macro_rules! find_schema {
+        ($($ty:ty),*) => {
+            $(
+                if type_id == TypeId::of::<$ty>() {
+                    return Some(<$ty>::value_schema());
+                }
+            )*
+        };
+    }
+
+    find_schema!(bool, char, f32, f64, String, Cow<'static, str>);
+    find_schema!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+    None
+}
+
+/// Exports the registered types of a [`TypeRegistry`] as JSON Schema.
+///
+/// The schema of each type describes the output of [`ReflectSerializer`] for a value of that
+/// type: an object with a `type` entry holding the full type name, and an entry holding the
+/// serialized fields, elements or value, keyed by the kind of the type.
+///
+/// Fields, elements and entries of registered types reference the schema of their type through
+/// `$ref`, so the schema of a type can only be resolved within the document returned by
+/// [`SchemaExporter::export`].
+///
+/// Types carrying some [`TypeData`] can be marked with a custom keyword using
+/// [`SchemaExporter::mark_type_data`], which allows external tools to tell, for example, which
+/// types are components.
+///
+/// Since JSON object keys are always strings, the keys of reflected maps can't be represented in
+/// JSON. The schema of map keys is exported under the non-standard `x-key` keyword.
+///
+/// The serialized form of value types without a [`ValueSchema`] isn't known, so their value
+/// accepts anything and only has a `description`.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_reflect::{Reflect, SchemaExporter, TypeRegistry};
+/// #[derive(Reflect)]
+/// struct Player {
+///     name: String,
+///     health: f32,
+/// }
+///
+/// let mut registry = TypeRegistry::default();
+/// registry.register::<String>();
+/// registry.register::<Player>();
+///
+/// let schema = SchemaExporter::new(&registry).export();
+/// let player = &schema["$defs"][std::any::type_name::<Player>()];
+/// assert_eq!(player["required"][1], "struct");
+/// assert_eq!(player["properties"]["struct"]["required"][0], "name");
+/// ```
+///
+/// [`ReflectSerializer`]: crate::serde::ReflectSerializer
+pub struct SchemaExporter<'a> {
+    registry: &'a TypeRegistry,
+    markers: Vec<(String, HasTypeData)>,
+}
+
+/// Returns whether a type carries the type data of a marker.
+type HasTypeData = fn(&TypeRegistration) -> bool;
+
+impl<'a> SchemaExporter<'a> {
+    /// Create a new exporter for the types of `registry`.
+    pub fn new(registry: &'a TypeRegistry) -> Self {
+        Self {
+            registry,
+            markers: Vec::new(),
+        }
+    }
+
+    /// Marks the schema of every type carrying the type data `T` with `keyword: true`.
+    pub fn mark_type_data<T: TypeData>(mut self, keyword: impl Into<String>) -> Self {
+        self.markers.push((keyword.into(), |registration| {
+            registration.data::<T>().is_some()
+        }));
+        self
+    }
+
+    /// Returns a schema document containing the schemas of all registered types.
+    ///
+    /// The schema of each type is stored in `$defs` under its full type name, and the document
+    /// itself accepts the serialized form of a value of any of these types.
+    pub fn export(&self) -> Value {
+        let mut definitions = Map::new();
+        for registration in self.registry.iter() {
+            definitions.insert(
+                registration.type_name().to_string(),
+                self.type_schema(registration),
+            );
+        }
+        let any_of: Vec<Value> = definitions
+            .keys()
+            .map(|type_name| json!({ "$ref": definition_ref(type_name) }))
+            .collect();
+
+        json!({
+            "$schema": JSON_SCHEMA_DIALECT,
+            "$defs": definitions,
+            "anyOf": any_of,
+        })
+    }
+
+    /// Returns the schema of the serialized form of the type described by `registration`.
+    pub fn type_schema(&self, registration: &TypeRegistration) -> Value {
+        let (kind, payload) = match registration.type_info() {
+            TypeInfo::Struct(info) => {
+                let mut properties = Map::new();
+                let mut required = Vec::new();
+                for field in info.iter().filter(|field| !field.skip_serializing()) {
+                    properties.insert(
+                        field.name().to_string(),
+                        self.field_schema(field.type_id(), field.type_name()),
+                    );
+                    // Missing fields with a default are filled in by the deserializer
+                    if !field.has_default() {
+                        required.push(Value::from(field.name().as_ref()));
+                    }
+                }
+                let payload = json!({
+                    "type": "object",
+                    "properties": properties,
+                    "required": required,
+                    "additionalProperties": false,
+                });
+                (type_fields::STRUCT, payload)
+            }
+            TypeInfo::TupleStruct(info) => {
+                let fields = info
+                    .iter()
+                    .map(|field| self.field_schema(field.type_id(), field.type_name()));
+                (type_fields::TUPLE_STRUCT, tuple_schema(fields))
+            }
+            TypeInfo::Tuple(info) => {
+                let fields = info
+                    .iter()
+                    .map(|field| self.field_schema(field.type_id(), field.type_name()));
+                (type_fields::TUPLE, tuple_schema(fields))
+            }
+            TypeInfo::List(info) => {
+                let payload = json!({
+                    "type": "array",
+                    "items": self.field_schema(info.item_type_id(), info.item_type_name()),
+                });
+                (type_fields::LIST, payload)
+            }
+            TypeInfo::Array(info) => {
+                let payload = json!({
+                    "type": "array",
+                    "items": self.field_schema(info.item_type_id(), info.item_type_name()),
+                    "minItems": info.capacity(),
+                    "maxItems": info.capacity(),
+                });
+                (type_fields::ARRAY, payload)
+            }
+            TypeInfo::Map(info) => {
+                let payload = json!({
+                    "type": "object",
+                    "additionalProperties":
+                        self.field_schema(info.value_type_id(), info.value_type_name()),
+                    "x-key": self.field_schema(info.key_type_id(), info.key_type_name()),
+                });
+                (type_fields::MAP, payload)
+            }
+            TypeInfo::Value(_) => {
+                let payload = registration
+                    .data::<ReflectValueSchema>()
+                    .map(ReflectValueSchema::schema)
+                    .or_else(|| primitive_schema(registration.type_id()))
+                    .unwrap_or_else(|| {
+                        json!({
+                            "description": format!(
+                                "Value of type `{}` without a known schema",
+                                registration.type_name()
+                            )
+                        })
+                    });
+                (type_fields::VALUE, payload)
+            }
+            TypeInfo::Dynamic(_) => {
+                // The kind of a dynamic type is only known from its values
+                return self.mark(
+                    registration,
+                    json!({
+                        "title": registration.short_name(),
+                        "type": "object",
+                    }),
+                );
+            }
+        };
+
+        self.mark(
+            registration,
+            json!({
+                "title": registration.short_name(),
+                "type": "object",
+                "properties": {
+                    type_fields::TYPE: { "const": registration.type_name() },
+                    kind: payload,
+                },
+                "required": [type_fields::TYPE, kind],
+                "additionalProperties": false,
+            }),
+        )
+    }
+
+    fn field_schema(&self, type_id: TypeId, type_name: &str) -> Value {
+        if self.registry.get(type_id).is_some() {
+            json!({ "$ref": definition_ref(type_name) })
+        } else {
+            json!({ "description": format!("Unregistered type `{}`", type_name) })
+        }
+    }
+
+    fn mark(&self, registration: &TypeRegistration, mut schema: Value) -> Value {
+        let object = schema.as_object_mut().unwrap();
+        for (keyword, has_data) in &self.markers {
+            if has_data(registration) {
+                object.insert(keyword.clone(), Value::Bool(true));
+            }
+        }
+        schema
+    }
+}
+
+fn tuple_schema(fields: impl Iterator<Item = Value>) -> Value {
+    let fields: Vec<Value> = fields.collect();
+    json!({
+        "type": "array",
+        "minItems": fields.len(),
+        "maxItems": fields.len(),
+        "prefixItems": fields,
+        "items": false,
+    })
+}
+
+/// Returns a `$ref` to the definition of the given type, escaped as a JSON pointer within a
+/// URI fragment.
+fn definition_ref(type_name: &str) -> String {
+    let mut reference = String::from("#/$defs/");
+    for character in type_name.chars() {
+        match character {
+            '~' => reference.push_str("~0"),
+            '/' => reference.push_str("~1"),
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '.' | '_' | ':' | ',' | ';' | '(' | ')' => {
+                reference.push(character);
+            }
+            _ => {
+                let mut buffer = [0; 4];
+                for byte in character.encode_utf8(&mut buffer).bytes() {
+                    reference.push_str(&format!("%{:02X}", byte));
+                }
+            }
+        }
+    }
+    reference
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as bevy_reflect;
+    use crate::{serde::ReflectSerializer, Reflect, ReflectDeserialize, ReflectSerialize};
+    use bevy_utils::HashMap;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Reflect)]
+    struct Foo {
+        a: u8,
+        b: Vec<String>,
+        c: (f32, bool),
+        d: [i32; 2],
+        e: HashMap<u8, u8>,
+        f: Bar,
+    }
+
+    #[derive(Reflect)]
+    struct Bar(char);
+
+    #[derive(Reflect, Clone, Serialize, Deserialize)]
+    #[reflect_value(Serialize, Deserialize)]
+    struct Opaque(u8);
+
+    #[derive(Reflect, Clone, Serialize, Deserialize)]
+    #[reflect_value(Serialize, Deserialize, ValueSchema)]
+    struct Percent(u8);
+
+    impl ValueSchema for Percent {
+        fn value_schema() -> Value {
+            json!({ "type": "integer", "minimum": 0, "maximum": 100 })
+        }
+    }
+
+    #[derive(Clone)]
+    struct Marker;
+
+    fn registry() -> TypeRegistry {
+        let mut registry = TypeRegistry::default();
+        registry.register::<String>();
+        registry.register::<bool>();
+        registry.register::<char>();
+        registry.register::<Vec<String>>();
+        registry.register::<(f32, bool)>();
+        registry.register::<[i32; 2]>();
+        registry.register::<HashMap<u8, u8>>();
+        registry.register::<Bar>();
+        registry.register::<Foo>();
+        registry
+    }
+
+    #[test]
+    fn schema_matches_serializer() {
+        let mut registry = registry();
+        registry
+            .get_mut(TypeId::of::<Bar>())
+            .unwrap()
+            .insert(Marker);
+
+        let schema = SchemaExporter::new(&registry)
+            .mark_type_data::<Marker>("x-marker")
+            .export();
+        let definitions = &schema["$defs"];
+        let foo = &definitions[std::any::type_name::<Foo>()];
+        assert!(foo.get("x-marker").is_none());
+        assert_eq!(definitions[std::any::type_name::<Bar>()]["x-marker"], true);
+
+        let value = Bar('x');
+        let serialized = serde_json::to_value(ReflectSerializer::new(&value, &registry)).unwrap();
+        let bar = &definitions[std::any::type_name::<Bar>()];
+        assert_eq!(
+            bar["properties"]["type"]["const"],
+            serialized[type_fields::TYPE]
+        );
+        let fields = serialized[type_fields::TUPLE_STRUCT].as_array().unwrap();
+        assert_eq!(bar["properties"][type_fields::TUPLE_STRUCT]["maxItems"], 1);
+        assert_eq!(
+            fields[0][type_fields::TYPE],
+            definitions[std::any::type_name::<char>()]["properties"]["type"]["const"]
+        );
+        assert_eq!(
+            definitions[std::any::type_name::<char>()]["properties"]["value"]["type"],
+            "string"
+        );
+
+        let fields = &foo["properties"][type_fields::STRUCT]["properties"];
+        assert_eq!(fields["a"]["$ref"], "#/$defs/u8");
+        assert_eq!(
+            fields["c"]["$ref"],
+            format!(
+                "#/$defs/{}",
+                std::any::type_name::<(f32, bool)>().replace(' ', "%20")
+            )
+        );
+        let list = &definitions[std::any::type_name::<Vec<String>>()]["properties"];
+        assert_eq!(
+            list[type_fields::LIST]["items"]["$ref"],
+            "#/$defs/alloc::string::String"
+        );
+        let array = &definitions[std::any::type_name::<[i32; 2]>()]["properties"];
+        assert_eq!(array[type_fields::ARRAY]["minItems"], 2);
+        let map = &definitions[std::any::type_name::<HashMap<u8, u8>>()]["properties"];
+        assert_eq!(map[type_fields::MAP]["x-key"]["$ref"], "#/$defs/u8");
+    }
+
+    #[test]
+    fn unregistered_fields() {
+        let mut registry = TypeRegistry::empty();
+        registry.register::<Bar>();
+        let schema = SchemaExporter::new(&registry).export();
+        let bar = &schema["$defs"][std::any::type_name::<Bar>()];
+        let field = &bar["properties"][type_fields::TUPLE_STRUCT]["prefixItems"][0];
+        assert!(field.get("$ref").is_none());
+        assert_eq!(schema["anyOf"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn value_schemas() {
+        let mut registry = TypeRegistry::empty();
+        registry.register::<u8>();
+        registry.register::<Opaque>();
+        registry.register::<Percent>();
+        let schema = SchemaExporter::new(&registry).export();
+        let value_schema = |type_name| &schema["$defs"][type_name]["properties"]["value"];
+
+        assert_eq!(value_schema("u8")["maximum"], 255);
+        assert_eq!(
+            value_schema(std::any::type_name::<Percent>())["maximum"],
+            100
+        );
+        let opaque = value_schema(std::any::type_name::<Opaque>());
+        assert!(opaque.get("type").is_none());
+        assert!(opaque["description"]
+            .as_str()
+            .unwrap()
+            .contains(std::any::type_name::<Opaque>()));
+    }
+}
@@ -1,6 +1,8 @@
 use crate::{
-    serde::type_fields, DynamicArray, DynamicList, DynamicMap, DynamicStruct, DynamicTuple,
-    DynamicTupleStruct, Reflect, ReflectDeserialize, TypeRegistry,
+    serde::{diff_fields, type_fields},
+    DiffChange, DynamicArray, DynamicList, DynamicMap, DynamicStruct, DynamicTuple,
+    DynamicTupleStruct, Reflect, ReflectDeserialize, ReflectDiff, Struct, StructInfo, TypeInfo,
+    TypeRegistry,
 };
 use erased_serde::Deserializer;
 use serde::de::{self, DeserializeSeed, MapAccess, SeqAccess, Visitor};
@@ -12,13 +14,38 @@ pub trait DeserializeValue {
     ) -> Result<Box<dyn Reflect>, erased_serde::Error>;
 }
 
+/// Renames the types and struct fields of the values read by a [`ReflectDeserializer`], before
+/// their types are looked up in the [`TypeRegistry`].
+///
+/// This lets values saved before a type or one of its fields was renamed be deserialized, even
+/// when the old name of the type isn't registered anymore.
+pub trait DeserializeRenames {
+    /// Returns the current name of the type saved as `type_name`, if it was renamed.
+    fn rename_type(&self, type_name: &str) -> Option<String>;
+
+    /// Returns the current name of the field `field` of the struct saved as `type_name`, if it was
+    /// renamed.
+    fn rename_field(&self, type_name: &str, field: &str) -> Option<String>;
+}
+
 pub struct ReflectDeserializer<'a> {
     registry: &'a TypeRegistry,
+    renames: Option<&'a dyn DeserializeRenames>,
 }
 
 impl<'a> ReflectDeserializer<'a> {
     pub fn new(registry: &'a TypeRegistry) -> Self {
-        ReflectDeserializer { registry }
+        ReflectDeserializer {
+            registry,
+            renames: None,
+        }
+    }
+
+    /// Renames the types and fields of the deserialized values, and of the values nested in them,
+    /// with `renames`.
+    pub fn with_renames(mut self, renames: &'a dyn DeserializeRenames) -> Self {
+        self.renames = Some(renames);
+        self
     }
 }
 
@@ -31,12 +58,14 @@ impl<'a, 'de> DeserializeSeed<'de> for ReflectDeserializer<'a> {
     {
         deserializer.deserialize_any(ReflectVisitor {
             registry: self.registry,
+            renames: self.renames,
         })
     }
 }
 
 struct ReflectVisitor<'a> {
     registry: &'a TypeRegistry,
+    renames: Option<&'a dyn DeserializeRenames>,
 }
 
 impl<'a, 'de> Visitor<'de> for ReflectVisitor<'a> {
@@ -142,18 +171,27 @@ impl<'a, 'de> Visitor<'de> for ReflectVisitor<'a> {
         V: MapAccess<'de>,
     {
         let mut type_name: Option<String> = None;
+        // the name the type was saved with, which the renames of its fields refer to
+        let mut saved_type_name = String::new();
         while let Some(key) = map.next_key::<String>()? {
             match key.as_str() {
                 type_fields::TYPE => {
-                    type_name = Some(map.next_value()?);
+                    saved_type_name = map.next_value()?;
+                    type_name = Some(
+                        self.renames
+                            .and_then(|renames| renames.rename_type(&saved_type_name))
+                            .unwrap_or_else(|| saved_type_name.clone()),
+                    );
                 }
                 type_fields::MAP => {
-                    let _type_name = type_name
+                    let type_name = type_name
                         .take()
                         .ok_or_else(|| de::Error::missing_field(type_fields::TYPE))?;
-                    let map = map.next_value_seed(MapDeserializer {
+                    let mut map = map.next_value_seed(MapDeserializer {
                         registry: self.registry,
+                        renames: self.renames,
                     })?;
+                    map.set_name(type_name);
                     return Ok(Box::new(map));
                 }
                 type_fields::STRUCT => {
@@ -162,7 +200,16 @@ impl<'a, 'de> Visitor<'de> for ReflectVisitor<'a> {
                         .ok_or_else(|| de::Error::missing_field(type_fields::TYPE))?;
                     let mut dynamic_struct = map.next_value_seed(StructDeserializer {
                         registry: self.registry,
+                        renames: self.renames,
+                        type_name: &saved_type_name,
                     })?;
+                    if let Some(TypeInfo::Struct(info)) = self
+                        .registry
+                        .get_with_name(&type_name)
+                        .map(|registration| registration.type_info())
+                    {
+                        insert_default_fields(&mut dynamic_struct, info);
+                    }
                     dynamic_struct.set_name(type_name);
                     return Ok(Box::new(dynamic_struct));
                 }
@@ -172,6 +219,7 @@ impl<'a, 'de> Visitor<'de> for ReflectVisitor<'a> {
                         .ok_or_else(|| de::Error::missing_field(type_fields::TYPE))?;
                     let mut tuple_struct = map.next_value_seed(TupleStructDeserializer {
                         registry: self.registry,
+                        renames: self.renames,
                     })?;
                     tuple_struct.set_name(type_name);
                     return Ok(Box::new(tuple_struct));
@@ -182,25 +230,30 @@ impl<'a, 'de> Visitor<'de> for ReflectVisitor<'a> {
                         .ok_or_else(|| de::Error::missing_field(type_fields::TYPE))?;
                     let tuple = map.next_value_seed(TupleDeserializer {
                         registry: self.registry,
+                        renames: self.renames,
                     })?;
                     return Ok(Box::new(tuple));
                 }
                 type_fields::LIST => {
-                    let _type_name = type_name
+                    let type_name = type_name
                         .take()
                         .ok_or_else(|| de::Error::missing_field(type_fields::TYPE))?;
-                    let list = map.next_value_seed(ListDeserializer {
+                    let mut list = map.next_value_seed(ListDeserializer {
                         registry: self.registry,
+                        renames: self.renames,
                     })?;
+                    list.set_name(type_name);
                     return Ok(Box::new(list));
                 }
                 type_fields::ARRAY => {
-                    let _type_name = type_name
+                    let type_name = type_name
                         .take()
                         .ok_or_else(|| de::Error::missing_field(type_fields::TYPE))?;
-                    let array = map.next_value_seed(ArrayDeserializer {
+                    let mut array = map.next_value_seed(ArrayDeserializer {
                         registry: self.registry,
+                        renames: self.renames,
                     })?;
+                    array.set_name(type_name);
                     return Ok(Box::new(array));
                 }
                 type_fields::VALUE => {
@@ -234,6 +287,17 @@ impl<'a, 'de> Visitor<'de> for ReflectVisitor<'a> {
     }
 }
 
+/// Inserts the default value of every field of `info` missing from `dynamic_struct`.
+fn insert_default_fields(dynamic_struct: &mut DynamicStruct, info: &StructInfo) {
+    for field in info.iter() {
+        if dynamic_struct.field(field.name()).is_none() {
+            if let Some(value) = field.default_value() {
+                dynamic_struct.insert_boxed(field.name(), value);
+            }
+        }
+    }
+}
+
 struct DeserializeReflectDeserializer<'a> {
     reflect_deserialize: &'a ReflectDeserialize,
 }
@@ -251,6 +315,7 @@ impl<'a, 'de> DeserializeSeed<'de> for DeserializeReflectDeserializer<'a> {
 
 struct ListDeserializer<'a> {
     registry: &'a TypeRegistry,
+    renames: Option<&'a dyn DeserializeRenames>,
 }
 
 impl<'a, 'de> DeserializeSeed<'de> for ListDeserializer<'a> {
@@ -262,12 +327,14 @@ impl<'a, 'de> DeserializeSeed<'de> for ListDeserializer<'a> {
     {
         deserializer.deserialize_seq(ListVisitor {
             registry: self.registry,
+            renames: self.renames,
         })
     }
 }
 
 struct ListVisitor<'a> {
     registry: &'a TypeRegistry,
+    renames: Option<&'a dyn DeserializeRenames>,
 }
 
 impl<'a, 'de> Visitor<'de> for ListVisitor<'a> {
@@ -284,6 +351,7 @@ impl<'a, 'de> Visitor<'de> for ListVisitor<'a> {
         let mut list = DynamicList::default();
         while let Some(value) = seq.next_element_seed(ReflectDeserializer {
             registry: self.registry,
+            renames: self.renames,
         })? {
             list.push_box(value);
         }
@@ -293,6 +361,7 @@ impl<'a, 'de> Visitor<'de> for ListVisitor<'a> {
 
 struct ArrayDeserializer<'a> {
     registry: &'a TypeRegistry,
+    renames: Option<&'a dyn DeserializeRenames>,
 }
 
 impl<'a, 'de> DeserializeSeed<'de> for ArrayDeserializer<'a> {
@@ -304,12 +373,14 @@ impl<'a, 'de> DeserializeSeed<'de> for ArrayDeserializer<'a> {
     {
         deserializer.deserialize_seq(ArrayVisitor {
             registry: self.registry,
+            renames: self.renames,
         })
     }
 }
 
 struct ArrayVisitor<'a> {
     registry: &'a TypeRegistry,
+    renames: Option<&'a dyn DeserializeRenames>,
 }
 
 impl<'a, 'de> Visitor<'de> for ArrayVisitor<'a> {
@@ -326,6 +397,7 @@ impl<'a, 'de> Visitor<'de> for ArrayVisitor<'a> {
         let mut vec = Vec::with_capacity(seq.size_hint().unwrap_or_default());
         while let Some(value) = seq.next_element_seed(ReflectDeserializer {
             registry: self.registry,
+            renames: self.renames,
         })? {
             vec.push(value);
         }
@@ -336,6 +408,7 @@ impl<'a, 'de> Visitor<'de> for ArrayVisitor<'a> {
 
 struct MapDeserializer<'a> {
     registry: &'a TypeRegistry,
+    renames: Option<&'a dyn DeserializeRenames>,
 }
 
 impl<'a, 'de> DeserializeSeed<'de> for MapDeserializer<'a> {
@@ -347,12 +420,14 @@ impl<'a, 'de> DeserializeSeed<'de> for MapDeserializer<'a> {
     {
         deserializer.deserialize_map(MapVisitor {
             registry: self.registry,
+            renames: self.renames,
         })
     }
 }
 
 struct MapVisitor<'a> {
     registry: &'a TypeRegistry,
+    renames: Option<&'a dyn DeserializeRenames>,
 }
 
 impl<'a, 'de> Visitor<'de> for MapVisitor<'a> {
@@ -369,9 +444,11 @@ impl<'a, 'de> Visitor<'de> for MapVisitor<'a> {
         let mut dynamic_map = DynamicMap::default();
         while let Some(key) = map.next_key_seed(ReflectDeserializer {
             registry: self.registry,
+            renames: self.renames,
         })? {
             let value = map.next_value_seed(ReflectDeserializer {
                 registry: self.registry,
+                renames: self.renames,
             })?;
             dynamic_map.insert_boxed(key, value);
         }
@@ -382,6 +459,8 @@ impl<'a, 'de> Visitor<'de> for MapVisitor<'a> {
 
 struct StructDeserializer<'a> {
     registry: &'a TypeRegistry,
+    renames: Option<&'a dyn DeserializeRenames>,
+    type_name: &'a str,
 }
 
 impl<'a, 'de> DeserializeSeed<'de> for StructDeserializer<'a> {
@@ -393,12 +472,16 @@ impl<'a, 'de> DeserializeSeed<'de> for StructDeserializer<'a> {
     {
         deserializer.deserialize_map(StructVisitor {
             registry: self.registry,
+            renames: self.renames,
+            type_name: self.type_name,
         })
     }
 }
 
 struct StructVisitor<'a> {
     registry: &'a TypeRegistry,
+    renames: Option<&'a dyn DeserializeRenames>,
+    type_name: &'a str,
 }
 
 impl<'a, 'de> Visitor<'de> for StructVisitor<'a> {
@@ -414,8 +497,13 @@ impl<'a, 'de> Visitor<'de> for StructVisitor<'a> {
     {
         let mut dynamic_struct = DynamicStruct::default();
         while let Some(key) = map.next_key::<String>()? {
+            let key = self
+                .renames
+                .and_then(|renames| renames.rename_field(self.type_name, &key))
+                .unwrap_or(key);
             let value = map.next_value_seed(ReflectDeserializer {
                 registry: self.registry,
+                renames: self.renames,
             })?;
             dynamic_struct.insert_boxed(&key, value);
         }
@@ -426,6 +514,7 @@ impl<'a, 'de> Visitor<'de> for StructVisitor<'a> {
 
 struct TupleStructDeserializer<'a> {
     registry: &'a TypeRegistry,
+    renames: Option<&'a dyn DeserializeRenames>,
 }
 
 impl<'a, 'de> DeserializeSeed<'de> for TupleStructDeserializer<'a> {
@@ -437,12 +526,14 @@ impl<'a, 'de> DeserializeSeed<'de> for TupleStructDeserializer<'a> {
     {
         deserializer.deserialize_seq(TupleStructVisitor {
             registry: self.registry,
+            renames: self.renames,
         })
     }
 }
 
 struct TupleStructVisitor<'a> {
     registry: &'a TypeRegistry,
+    renames: Option<&'a dyn DeserializeRenames>,
 }
 
 impl<'a, 'de> Visitor<'de> for TupleStructVisitor<'a> {
@@ -459,6 +550,7 @@ impl<'a, 'de> Visitor<'de> for TupleStructVisitor<'a> {
         let mut tuple_struct = DynamicTupleStruct::default();
         while let Some(value) = seq.next_element_seed(ReflectDeserializer {
             registry: self.registry,
+            renames: self.renames,
         })? {
             tuple_struct.insert_boxed(value);
         }
@@ -468,6 +560,7 @@ impl<'a, 'de> Visitor<'de> for TupleStructVisitor<'a> {
 
 struct TupleDeserializer<'a> {
     registry: &'a TypeRegistry,
+    renames: Option<&'a dyn DeserializeRenames>,
 }
 
 impl<'a, 'de> DeserializeSeed<'de> for TupleDeserializer<'a> {
@@ -479,12 +572,14 @@ impl<'a, 'de> DeserializeSeed<'de> for TupleDeserializer<'a> {
     {
         deserializer.deserialize_seq(TupleVisitor {
             registry: self.registry,
+            renames: self.renames,
         })
     }
 }
 
 struct TupleVisitor<'a> {
     registry: &'a TypeRegistry,
+    renames: Option<&'a dyn DeserializeRenames>,
 }
 
 impl<'a, 'de> Visitor<'de> for TupleVisitor<'a> {
@@ -501,9 +596,181 @@ impl<'a, 'de> Visitor<'de> for TupleVisitor<'a> {
         let mut tuple = DynamicTuple::default();
         while let Some(value) = seq.next_element_seed(ReflectDeserializer {
             registry: self.registry,
+            renames: self.renames,
         })? {
             tuple.insert_boxed(value);
         }
         Ok(tuple)
     }
 }
+
+/// Deserializes a [`ReflectDiff`] serialized by a
+/// [`ReflectDiffSerializer`](crate::serde::ReflectDiffSerializer).
+///
+/// Like with a [`ReflectDeserializer`], the values of the changes are dynamic values, except for
+/// the values registered with [`ReflectDeserialize`].
+pub struct ReflectDiffDeserializer<'a> {
+    registry: &'a TypeRegistry,
+    renames: Option<&'a dyn DeserializeRenames>,
+}
+
+impl<'a> ReflectDiffDeserializer<'a> {
+    pub fn new(registry: &'a TypeRegistry) -> Self {
+        ReflectDiffDeserializer {
+            registry,
+            renames: None,
+        }
+    }
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for ReflectDiffDeserializer<'a> {
+    type Value = ReflectDiff;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(ReflectDiffVisitor {
+            registry: self.registry,
+            renames: self.renames,
+        })
+    }
+}
+
+struct ReflectDiffVisitor<'a> {
+    registry: &'a TypeRegistry,
+    renames: Option<&'a dyn DeserializeRenames>,
+}
+
+impl<'a, 'de> Visitor<'de> for ReflectDiffVisitor<'a> {
+    type Value = ReflectDiff;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("diff changes")
+    }
+
+    fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
+    where
+        V: SeqAccess<'de>,
+    {
+        let mut changes = Vec::new();
+        while let Some(change) = seq.next_element_seed(DiffChangeDeserializer {
+            registry: self.registry,
+            renames: self.renames,
+        })? {
+            changes.push(change);
+        }
+        Ok(ReflectDiff::from_changes(changes))
+    }
+}
+
+struct DiffChangeDeserializer<'a> {
+    registry: &'a TypeRegistry,
+    renames: Option<&'a dyn DeserializeRenames>,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for DiffChangeDeserializer<'a> {
+    type Value = DiffChange;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(DiffChangeVisitor {
+            registry: self.registry,
+            renames: self.renames,
+        })
+    }
+}
+
+struct DiffChangeVisitor<'a> {
+    registry: &'a TypeRegistry,
+    renames: Option<&'a dyn DeserializeRenames>,
+}
+
+impl<'a, 'de> Visitor<'de> for DiffChangeVisitor<'a> {
+    type Value = DiffChange;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("diff change")
+    }
+
+    fn visit_map<V>(self, mut map: V) -> Result<Self::Value, V::Error>
+    where
+        V: MapAccess<'de>,
+    {
+        let mut op: Option<String> = None;
+        let mut path: Option<String> = None;
+        let mut index: Option<usize> = None;
+        let mut key: Option<Box<dyn Reflect>> = None;
+        let mut value: Option<Box<dyn Reflect>> = None;
+        while let Some(field) = map.next_key::<String>()? {
+            match field.as_str() {
+                diff_fields::OP => op = Some(map.next_value()?),
+                diff_fields::PATH => path = Some(map.next_value()?),
+                diff_fields::INDEX => index = Some(map.next_value()?),
+                diff_fields::KEY => {
+                    key = Some(map.next_value_seed(ReflectDeserializer {
+                        registry: self.registry,
+                        renames: self.renames,
+                    })?);
+                }
+                diff_fields::VALUE => {
+                    value = Some(map.next_value_seed(ReflectDeserializer {
+                        registry: self.registry,
+                        renames: self.renames,
+                    })?);
+                }
+                _ => {
+                    return Err(de::Error::unknown_field(
+                        field.as_str(),
+                        &[
+                            diff_fields::OP,
+                            diff_fields::PATH,
+                            diff_fields::INDEX,
+                            diff_fields::KEY,
+                            diff_fields::VALUE,
+                        ],
+                    ))
+                }
+            }
+        }
+
+        let op = op.ok_or_else(|| de::Error::missing_field(diff_fields::OP))?;
+        let path = path.ok_or_else(|| de::Error::missing_field(diff_fields::PATH))?;
+        let index = || index.ok_or_else(|| de::Error::missing_field(diff_fields::INDEX));
+        let key = || key.ok_or_else(|| de::Error::missing_field(diff_fields::KEY));
+        let value = || value.ok_or_else(|| de::Error::missing_field(diff_fields::VALUE));
+        match op.as_str() {
+            diff_fields::MODIFIED => Ok(DiffChange::Modified {
+                path,
+                value: value()?,
+            }),
+            diff_fields::LIST_INSERTED => Ok(DiffChange::ListInserted {
+                path,
+                index: index()?,
+                value: value()?,
+            }),
+            diff_fields::LIST_REMOVED => Ok(DiffChange::ListRemoved {
+                path,
+                index: index()?,
+            }),
+            diff_fields::MAP_INSERTED => Ok(DiffChange::MapInserted {
+                path,
+                key: key()?,
+                value: value()?,
+            }),
+            diff_fields::MAP_REMOVED => Ok(DiffChange::MapRemoved { path, key: key()? }),
+            _ => Err(de::Error::unknown_variant(
+                op.as_str(),
+                &[
+                    diff_fields::MODIFIED,
+                    diff_fields::LIST_INSERTED,
+                    diff_fields::LIST_REMOVED,
+                    diff_fields::MAP_INSERTED,
+                    diff_fields::MAP_REMOVED,
+                ],
+            )),
+        }
+    }
+}
@@ -14,3 +14,17 @@ pub(crate) mod type_fields {
     pub const ARRAY: &str = "array";
     pub const VALUE: &str = "value";
 }
+
+pub(crate) mod diff_fields {
+    pub const OP: &str = "op";
+    pub const PATH: &str = "path";
+    pub const INDEX: &str = "index";
+    pub const KEY: &str = "key";
+    pub const VALUE: &str = "value";
+
+    pub const MODIFIED: &str = "modified";
+    pub const LIST_INSERTED: &str = "list_inserted";
+    pub const LIST_REMOVED: &str = "list_removed";
+    pub const MAP_INSERTED: &str = "map_inserted";
+    pub const MAP_REMOVED: &str = "map_removed";
+}
@@ -1,6 +1,7 @@
 use crate::{
-    serde::type_fields, Array, List, Map, Reflect, ReflectRef, ReflectSerialize, Struct, Tuple,
-    TupleStruct, TypeRegistry,
+    serde::{diff_fields, type_fields},
+    Array, DiffChange, List, Map, NamedField, Reflect, ReflectDiff, ReflectRef, ReflectSerialize,
+    Struct, Tuple, TupleStruct, TypeInfo, TypeRegistry,
 };
 use serde::{
     ser::{SerializeMap, SerializeSeq},
@@ -147,9 +148,30 @@ impl<'a> Serialize for StructValueSerializer<'a> {
     where
         S: serde::Serializer,
     {
-        let mut state = serializer.serialize_map(Some(self.struct_value.field_len()))?;
-        for (index, value) in self.struct_value.iter_fields().enumerate() {
-            let key = self.struct_value.name_at(index).unwrap();
+        // Dynamic structs only know their type through its name, so the field attributes are
+        // looked up in the registry
+        let struct_info = match self
+            .registry
+            .get_with_name(self.struct_value.type_name())
+            .map(|registration| registration.type_info())
+        {
+            Some(TypeInfo::Struct(info)) => Some(info),
+            _ => None,
+        };
+        let fields = self
+            .struct_value
+            .iter_fields()
+            .enumerate()
+            .map(|(index, value)| (self.struct_value.name_at(index).unwrap(), value))
+            .filter(|(name, _)| {
+                !struct_info
+                    .and_then(|info| info.field(name))
+                    .map_or(false, NamedField::skip_serializing)
+            })
+            .collect::<Vec<_>>();
+
+        let mut state = serializer.serialize_map(Some(fields.len()))?;
+        for (key, value) in fields {
             state.serialize_entry(key, &ReflectSerializer::new(value, self.registry))?;
         }
         state.end()
@@ -366,3 +388,91 @@ impl<'a> Serialize for ArrayValueSerializer<'a> {
         state.end()
     }
 }
+
+/// Serializes a [`ReflectDiff`] as a sequence of changes, each a map with an `op` entry, the
+/// `path` of the change, and the `index`, `key` and `value` of the change when it has them.
+pub struct ReflectDiffSerializer<'a> {
+    pub diff: &'a ReflectDiff,
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a> ReflectDiffSerializer<'a> {
+    pub fn new(diff: &'a ReflectDiff, registry: &'a TypeRegistry) -> Self {
+        ReflectDiffSerializer { diff, registry }
+    }
+}
+
+impl<'a> Serialize for ReflectDiffSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.diff.changes().len()))?;
+        for change in self.diff.changes() {
+            state.serialize_element(&DiffChangeSerializer {
+                change,
+                registry: self.registry,
+            })?;
+        }
+        state.end()
+    }
+}
+
+struct DiffChangeSerializer<'a> {
+    change: &'a DiffChange,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for DiffChangeSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_map(None)?;
+        match self.change {
+            DiffChange::Modified { path, value } => {
+                state.serialize_entry(diff_fields::OP, diff_fields::MODIFIED)?;
+                state.serialize_entry(diff_fields::PATH, path)?;
+                state.serialize_entry(
+                    diff_fields::VALUE,
+                    &ReflectSerializer::new(&**value, self.registry),
+                )?;
+            }
+            DiffChange::ListInserted { path, index, value } => {
+                state.serialize_entry(diff_fields::OP, diff_fields::LIST_INSERTED)?;
+                state.serialize_entry(diff_fields::PATH, path)?;
+                state.serialize_entry(diff_fields::INDEX, index)?;
+                state.serialize_entry(
+                    diff_fields::VALUE,
+                    &ReflectSerializer::new(&**value, self.registry),
+                )?;
+            }
+            DiffChange::ListRemoved { path, index } => {
+                state.serialize_entry(diff_fields::OP, diff_fields::LIST_REMOVED)?;
+                state.serialize_entry(diff_fields::PATH, path)?;
+                state.serialize_entry(diff_fields::INDEX, index)?;
+            }
+            DiffChange::MapInserted { path, key, value } => {
+                state.serialize_entry(diff_fields::OP, diff_fields::MAP_INSERTED)?;
+                state.serialize_entry(diff_fields::PATH, path)?;
+                state.serialize_entry(
+                    diff_fields::KEY,
+                    &ReflectSerializer::new(&**key, self.registry),
+                )?;
+                state.serialize_entry(
+                    diff_fields::VALUE,
+                    &ReflectSerializer::new(&**value, self.registry),
+                )?;
+            }
+            DiffChange::MapRemoved { path, key } => {
+                state.serialize_entry(diff_fields::OP, diff_fields::MAP_REMOVED)?;
+                state.serialize_entry(diff_fields::PATH, path)?;
+                state.serialize_entry(
+                    diff_fields::KEY,
+                    &ReflectSerializer::new(&**key, self.registry),
+                )?;
+            }
+        }
+        state.end()
+    }
+}
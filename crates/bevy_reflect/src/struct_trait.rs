@@ -1,5 +1,7 @@
 use crate::utility::NonGenericTypeInfoCell;
-use crate::{DynamicInfo, NamedField, Reflect, ReflectMut, ReflectRef, TypeInfo, Typed};
+use crate::{
+    DynamicInfo, NamedField, Reflect, ReflectMut, ReflectPathError, ReflectRef, TypeInfo, Typed,
+};
 use bevy_utils::{Entry, HashMap};
 use std::fmt::{Debug, Formatter};
 use std::{
@@ -276,6 +278,19 @@ impl DynamicStruct {
             self.insert_boxed(name, Box::new(value));
         }
     }
+
+    /// Replaces the value specified by `path` with `value`.
+    ///
+    /// See [`GetPath`](crate::GetPath) for the syntax of path strings. If the last segment of the
+    /// path is a field missing from this struct or from a nested [`DynamicStruct`], the field is
+    /// inserted. Otherwise, `value` must have the same type as the value it replaces.
+    pub fn set_path<'p>(
+        &mut self,
+        path: &'p str,
+        value: Box<dyn Reflect>,
+    ) -> Result<(), ReflectPathError<'p>> {
+        crate::path::set_path(self, path, value)
+    }
 }
 
 impl Struct for DynamicStruct {
@@ -1,10 +1,10 @@
 use crate::serde::Serializable;
-use crate::{Reflect, TypeInfo, Typed};
+use crate::{DynamicFunction, IntoFunction, Reflect, ReflectFunctions, TypeInfo, Typed};
 use bevy_utils::{HashMap, HashSet};
 use downcast_rs::{impl_downcast, Downcast};
 use parking_lot::{RwLock, RwLockReadGuard, RwLockWriteGuard};
 use serde::Deserialize;
-use std::{any::TypeId, fmt::Debug, sync::Arc};
+use std::{any::TypeId, borrow::Cow, fmt::Debug, sync::Arc};
 
 /// A registry of reflected types.
 pub struct TypeRegistry {
@@ -12,6 +12,7 @@ pub struct TypeRegistry {
     short_name_to_id: HashMap<String, TypeId>,
     full_name_to_id: HashMap<String, TypeId>,
     ambiguous_names: HashSet<String>,
+    functions: HashMap<String, DynamicFunction>,
 }
 
 // TODO:  remove this wrapper once we migrate to Atelier Assets and the Scene AssetLoader doesn't
@@ -49,6 +50,7 @@ impl TypeRegistry {
             short_name_to_id: Default::default(),
             full_name_to_id: Default::default(),
             ambiguous_names: Default::default(),
+            functions: Default::default(),
         }
     }
 
@@ -197,6 +199,55 @@ impl TypeRegistry {
             .map(|registration| registration.type_info())
     }
 
+    /// Registers a free function under the given name.
+    ///
+    /// If a function with the same name was previously registered, it is replaced.
+    pub fn register_function<Marker>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        function: impl IntoFunction<Marker>,
+    ) {
+        let function = function.into_function(name);
+        self.functions.insert(function.name().to_string(), function);
+    }
+
+    /// Registers a function as one of the [`ReflectFunctions`] of the type `T`.
+    ///
+    /// The type `T` is registered first if it has not been registered yet.
+    /// If `T` already has a function with the same name, it is replaced.
+    pub fn register_type_function<T, Marker>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        function: impl IntoFunction<Marker>,
+    ) where
+        T: GetTypeRegistration + 'static,
+    {
+        let type_id = TypeId::of::<T>();
+        if !self.registrations.contains_key(&type_id) {
+            self.register::<T>();
+        }
+        let registration = self.registrations.get_mut(&type_id).unwrap();
+        if registration.data::<ReflectFunctions>().is_none() {
+            registration.insert(ReflectFunctions::default());
+        }
+        registration
+            .data_mut::<ReflectFunctions>()
+            .unwrap()
+            .insert(function.into_function(name));
+    }
+
+    /// Returns a reference to the free function registered with the given name.
+    ///
+    /// If no function with the given name has been registered, returns `None`.
+    pub fn get_function(&self, name: &str) -> Option<&DynamicFunction> {
+        self.functions.get(name)
+    }
+
+    /// Returns an iterator over the registered free functions.
+    pub fn iter_functions(&self) -> impl Iterator<Item = &DynamicFunction> {
+        self.functions.values()
+    }
+
     /// Returns an iterator over the [`TypeRegistration`]s of the registered
     /// types.
     pub fn iter(&self) -> impl Iterator<Item = &TypeRegistration> {
@@ -396,7 +447,7 @@ pub trait FromType<T> {
 /// [`FromType::from_type`].
 #[derive(Clone)]
 pub struct ReflectSerialize {
-    get_serializable: for<'a> fn(value: &'a dyn Reflect) -> Serializable,
+    pub(crate) get_serializable: for<'a> fn(value: &'a dyn Reflect) -> Serializable,
 }
 
 impl<T: Reflect + erased_serde::Serialize> FromType<T> for ReflectSerialize {
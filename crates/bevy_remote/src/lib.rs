@@ -0,0 +1,436 @@
+//! Remote inspection and editing of a running app.
+//!
+//! [`RemotePlugin`] lets external tools list entities, read and write their reflected
+//! components, spawn and despawn entities, query entities by their components and subscribe to
+//! component changes. See the [`protocol`] module for the format of the messages.
+
+mod methods;
+pub mod protocol;
+mod server;
+
+pub use server::*;
+
+use bevy_app::prelude::*;
+use bevy_ecs::{
+    schedule::ExclusiveSystemDescriptorCoercion,
+    system::IntoExclusiveSystem,
+    world::{Mut, World},
+};
+use bevy_reflect::TypeRegistryArc;
+use bevy_utils::tracing::info;
+use server::Message;
+use std::net::SocketAddr;
+
+/// The port [`RemotePlugin`] listens on by default.
+pub const DEFAULT_PORT: u16 = 15702;
+
+/// How clients connect to the [`RemoteServer`] added by [`RemotePlugin`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RemoteTransport {
+    /// Listens on a TCP socket. Defaults to `127.0.0.1` on [`DEFAULT_PORT`].
+    Tcp(SocketAddr),
+    /// Reads requests from the standard input and writes responses to the standard output.
+    ///
+    /// The standard output is shared with the logs and any other output of the app, see the
+    /// [`protocol`] module.
+    Stdio,
+    /// Only accepts in-process clients created with [`RemoteServer::connect_local`].
+    Local,
+}
+
+impl Default for RemoteTransport {
+    fn default() -> Self {
+        RemoteTransport::Tcp(SocketAddr::from(([127, 0, 0, 1], DEFAULT_PORT)))
+    }
+}
+
+/// Adds a [`RemoteServer`] handling the requests of remote clients once per frame.
+///
+/// Only components registered in the [`TypeRegistry`](bevy_reflect::TypeRegistry) with
+/// [`ReflectComponent`](bevy_ecs::reflect::ReflectComponent) can be accessed.
+///
+/// # Panics
+///
+/// Panics if the server can't listen with its [`RemoteTransport`], for example if the TCP
+/// address is already in use.
+#[derive(Default)]
+pub struct RemotePlugin {
+    pub transport: RemoteTransport,
+}
+
+impl Plugin for RemotePlugin {
+    fn build(&self, app: &mut App) {
+        let server = RemoteServer::default();
+        match &self.transport {
+            RemoteTransport::Tcp(addr) => match server.listen_tcp(addr) {
+                Ok(addr) => info!("Remote server listening on {}", addr),
+                Err(err) => panic!("Remote server failed to listen on {}: {}", addr, err),
+            },
+            RemoteTransport::Stdio => {
+                if let Err(err) = server.listen_stdio() {
+                    panic!("Remote server failed to listen on stdio: {}", err);
+                }
+            }
+            RemoteTransport::Local => {}
+        }
+        app.init_resource::<TypeRegistryArc>()
+            .insert_resource(server)
+            .add_system_to_stage(
+                CoreStage::Last,
+                process_remote_requests.exclusive_system().at_end(),
+            );
+    }
+}
+
+/// Handles all pending requests of the clients of the [`RemoteServer`], then notifies
+/// subscriptions of the changes made since the last run.
+pub fn process_remote_requests(world: &mut World) {
+    let type_registry = world.resource::<TypeRegistryArc>().clone();
+    let type_registry = type_registry.read();
+    world.resource_scope(|world, mut server: Mut<RemoteServer>| {
+        while let Some(message) = server.try_recv() {
+            match message {
+                Message::Connected(client, sender) => server.connect(client, sender),
+                Message::Disconnected(client) => server.disconnect(client),
+                Message::Request(client, line) => {
+                    let response =
+                        methods::handle_request(world, &type_registry, &mut server, client, &line);
+                    server.send(client, &response);
+                }
+            }
+        }
+        methods::notify_subscriptions(world, &type_registry, &mut server);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{protocol, RemoteClient, RemotePlugin, RemoteServer, RemoteTransport};
+    use bevy_app::App;
+    use bevy_core::Name;
+    use bevy_ecs::{prelude::*, reflect::ReflectComponent};
+    use bevy_reflect::{Reflect, ReflectSerialize};
+    use serde::{Serialize, Serializer};
+    use serde_json::{json, Value};
+    use std::{
+        borrow::Cow,
+        io::{BufRead, BufReader, Write},
+        net::{TcpListener, TcpStream},
+        sync::{
+            atomic::{AtomicU32, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Health {
+        value: f32,
+    }
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Enemy;
+
+    fn setup(transport: RemoteTransport) -> App {
+        let mut app = App::new();
+        app.register_type::<Name>()
+            .register_type::<Health>()
+            .register_type::<Enemy>()
+            .register_type::<f32>()
+            .register_type::<Cow<'static, str>>()
+            .add_plugin(RemotePlugin { transport });
+        app
+    }
+
+    fn request(app: &mut App, client: &RemoteClient, method: &str, params: Value) -> Value {
+        let id = client.send(method, params);
+        app.update();
+        let response = client.try_recv().expect("no response");
+        assert_eq!(response["id"], json!(id));
+        response
+    }
+
+    fn result(app: &mut App, client: &RemoteClient, method: &str, params: Value) -> Value {
+        let response = request(app, client, method, params);
+        assert!(response.get("error").is_none(), "{}", response);
+        response["result"].clone()
+    }
+
+    fn health(value: f32) -> Value {
+        json!({
+            "type": "bevy_remote::tests::Health",
+            "struct": { "value": { "type": "f32", "value": value } }
+        })
+    }
+
+    #[test]
+    fn list_get_and_set() {
+        let mut app = setup(RemoteTransport::Local);
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle((Name::new("player"), Health { value: 10.0 }))
+            .id();
+        let client = app.world.resource::<RemoteServer>().connect_local();
+
+        let entities = result(&mut app, &client, protocol::LIST_ENTITIES, Value::Null);
+        assert_eq!(
+            entities,
+            json!([{ "entity": player.to_bits(), "name": "player" }])
+        );
+
+        let components = result(
+            &mut app,
+            &client,
+            protocol::GET,
+            json!({ "entity": player.to_bits(), "components": ["Health"] }),
+        );
+        assert_eq!(
+            components,
+            json!({ "bevy_remote::tests::Health": health(10.0) })
+        );
+
+        result(
+            &mut app,
+            &client,
+            protocol::SET,
+            json!({ "entity": player.to_bits(), "components": [health(5.0)] }),
+        );
+        assert_eq!(app.world.get::<Health>(player).unwrap().value, 5.0);
+
+        let components = result(
+            &mut app,
+            &client,
+            protocol::GET,
+            json!({ "entity": player.to_bits() }),
+        );
+        assert!(components.get("bevy_remote::tests::Health").is_some());
+        assert!(components.get("bevy_core::name::Name").is_some());
+    }
+
+    #[test]
+    fn spawn_query_and_despawn() {
+        let mut app = setup(RemoteTransport::Local);
+        app.world.spawn().insert(Health { value: 1.0 });
+        let client = app.world.resource::<RemoteServer>().connect_local();
+
+        let spawned = result(
+            &mut app,
+            &client,
+            protocol::SPAWN,
+            json!({ "components": [health(3.0), {
+                "type": "bevy_remote::tests::Enemy",
+                "struct": {}
+            }] }),
+        );
+        let enemy = Entity::from_bits(spawned["entity"].as_u64().unwrap());
+        assert_eq!(app.world.get::<Health>(enemy).unwrap().value, 3.0);
+        assert!(app.world.get::<Enemy>(enemy).is_some());
+
+        let rows = result(
+            &mut app,
+            &client,
+            protocol::QUERY,
+            json!({ "with": ["Health"], "without": ["Enemy"] }),
+        );
+        assert_eq!(rows.as_array().unwrap().len(), 1);
+        assert_eq!(
+            rows[0]["components"]["bevy_remote::tests::Health"],
+            health(1.0)
+        );
+
+        result(
+            &mut app,
+            &client,
+            protocol::REMOVE,
+            json!({ "entity": enemy.to_bits(), "components": ["Enemy"] }),
+        );
+        assert!(app.world.get::<Enemy>(enemy).is_none());
+
+        result(
+            &mut app,
+            &client,
+            protocol::DESPAWN,
+            json!({ "entity": enemy.to_bits() }),
+        );
+        assert!(app.world.get_entity(enemy).is_none());
+
+        let response = request(
+            &mut app,
+            &client,
+            protocol::DESPAWN,
+            json!({ "entity": enemy.to_bits() }),
+        );
+        assert_eq!(
+            response["error"],
+            json!(format!("entity {} does not exist", enemy.to_bits()))
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let mut app = setup(RemoteTransport::Local);
+        let client = app.world.resource::<RemoteServer>().connect_local();
+
+        let response = request(&mut app, &client, "fly", Value::Null);
+        assert_eq!(response["error"], json!("unknown method: fly"));
+
+        let response = request(
+            &mut app,
+            &client,
+            protocol::QUERY,
+            json!({ "with": ["f32"] }),
+        );
+        assert_eq!(
+            response["error"],
+            json!("type f32 is not a reflected component")
+        );
+
+        client.send_raw("not json");
+        app.update();
+        let response = client.try_recv().unwrap();
+        assert_eq!(response["id"], Value::Null);
+        assert!(response["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("invalid request"));
+    }
+
+    #[test]
+    fn subscriptions() {
+        let mut app = setup(RemoteTransport::Local);
+        let entity = app.world.spawn().insert(Health { value: 1.0 }).id();
+        let client = app.world.resource::<RemoteServer>().connect_local();
+
+        let subscription = result(
+            &mut app,
+            &client,
+            protocol::SUBSCRIBE,
+            json!({ "components": ["Health"] }),
+        );
+        let initial = client.try_recv().unwrap();
+        assert_eq!(
+            initial,
+            json!({
+                "subscription": subscription,
+                "changes": [{
+                    "entity": entity.to_bits(),
+                    "component": "bevy_remote::tests::Health",
+                    "value": health(1.0)
+                }]
+            })
+        );
+
+        app.update();
+        assert!(client.try_recv().is_none());
+
+        app.world.get_mut::<Health>(entity).unwrap().value = 2.0;
+        app.update();
+        let changed = client.try_recv().unwrap();
+        assert_eq!(changed["changes"][0]["value"], health(2.0));
+
+        app.world.entity_mut(entity).remove::<Health>();
+        app.update();
+        let removed = client.try_recv().unwrap();
+        assert_eq!(removed["changes"][0]["value"], Value::Null);
+
+        result(
+            &mut app,
+            &client,
+            protocol::UNSUBSCRIBE,
+            json!({ "subscription": subscription }),
+        );
+        app.world.entity_mut(entity).insert(Health { value: 3.0 });
+        app.update();
+        assert!(client.try_recv().is_none());
+    }
+
+    /// A component whose value can change without triggering change detection.
+    #[derive(Component, Reflect, Clone, Default)]
+    #[reflect_value(Component, Serialize)]
+    struct Shared(Arc<AtomicU32>);
+
+    impl Serialize for Shared {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_u32(self.0.load(Ordering::Relaxed))
+        }
+    }
+
+    #[test]
+    fn subscriptions_only_serialize_changed_components() {
+        let mut app = setup(RemoteTransport::Local);
+        app.register_type::<Shared>();
+        let shared = Shared::default();
+        let entity = app.world.spawn().insert(shared.clone()).id();
+        let client = app.world.resource::<RemoteServer>().connect_local();
+
+        result(
+            &mut app,
+            &client,
+            protocol::SUBSCRIBE,
+            json!({ "components": ["Shared"] }),
+        );
+        let initial = client.try_recv().unwrap();
+        assert_eq!(initial["changes"][0]["value"]["value"], 0);
+
+        // not detected, since the component isn't marked as changed
+        shared.0.store(1, Ordering::Relaxed);
+        app.update();
+        assert!(client.try_recv().is_none());
+
+        app.world.get_mut::<Shared>(entity).unwrap().set_changed();
+        app.update();
+        let changed = client.try_recv().unwrap();
+        assert_eq!(changed["changes"][0]["value"]["value"], 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Remote server failed to listen")]
+    fn tcp_address_in_use() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        setup(RemoteTransport::Tcp(listener.local_addr().unwrap()));
+    }
+
+    #[test]
+    fn tcp() {
+        let mut app = setup(RemoteTransport::Local);
+        let addr = app
+            .world
+            .resource::<RemoteServer>()
+            .listen_tcp("127.0.0.1:0")
+            .unwrap();
+        let entity = app.world.spawn().insert(Name::new("remote")).id();
+
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream
+            .set_read_timeout(Some(Duration::from_secs(10)))
+            .unwrap();
+        writeln!(stream, r#"{{"id": "a", "method": "list_entities"}}"#).unwrap();
+
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        let reader = BufReader::new(stream.try_clone().unwrap());
+        std::thread::spawn(move || {
+            for line in reader.lines() {
+                if sender.send(line.unwrap()).is_err() {
+                    break;
+                }
+            }
+        });
+        let response = (0..1000)
+            .find_map(|_| {
+                app.update();
+                receiver.recv_timeout(Duration::from_millis(10)).ok()
+            })
+            .expect("no response");
+        let response: Value = serde_json::from_str(&response).unwrap();
+        assert_eq!(
+            response,
+            json!({
+                "id": "a",
+                "result": [{ "entity": entity.to_bits(), "name": "remote" }]
+            })
+        );
+    }
+}
@@ -0,0 +1,448 @@
+use crate::{
+    protocol::{self, *},
+    server::{ClientId, RemoteServer},
+};
+use bevy_core::Name;
+use bevy_ecs::{entity::Entity, reflect::ReflectComponent, world::World};
+use bevy_hierarchy::DespawnRecursiveExt;
+use bevy_reflect::{
+    serde::{ReflectDeserializer, ReflectSerializer},
+    Reflect, TypeRegistration, TypeRegistry,
+};
+use serde::{
+    de::{DeserializeOwned, DeserializeSeed},
+    ser::{SerializeMap, SerializeSeq},
+    Serialize, Serializer,
+};
+use serde_json::{json, Value};
+use std::{any::TypeId, collections::BTreeMap};
+
+/// A subscription of a client to the changes of some components.
+pub(crate) struct Subscription {
+    pub id: u64,
+    pub client: ClientId,
+    entity: Option<Entity>,
+    components: Vec<(String, TypeId, ReflectComponent)>,
+    /// The values of the components sent in the last notification.
+    last: BTreeMap<(u64, String), Value>,
+    /// The change tick of the world when the last notification was sent.
+    last_change_tick: u32,
+}
+
+/// Handles a single request line, returning the response to send back to the client.
+pub(crate) fn handle_request(
+    world: &mut World,
+    registry: &TypeRegistry,
+    server: &mut RemoteServer,
+    client: ClientId,
+    line: &str,
+) -> Value {
+    let request: Request = match serde_json::from_str(line) {
+        Ok(request) => request,
+        Err(err) => return error_response(Value::Null, RemoteError::InvalidRequest(err)),
+    };
+    let params = request.params;
+    let result = match request.method.as_str() {
+        protocol::LIST_ENTITIES => list_entities(world),
+        protocol::GET => parse(params).and_then(|params| get(world, registry, params)),
+        protocol::SET => parse(params).and_then(|params| set(world, registry, params)),
+        protocol::REMOVE => parse(params).and_then(|params| remove(world, registry, params)),
+        protocol::SPAWN => parse(params).and_then(|params| spawn(world, registry, params)),
+        protocol::DESPAWN => parse(params).and_then(|params| despawn(world, params)),
+        protocol::QUERY => parse(params).and_then(|params| query(world, registry, params)),
+        protocol::SUBSCRIBE => {
+            parse(params).and_then(|params| subscribe(world, registry, server, client, params))
+        }
+        protocol::UNSUBSCRIBE => {
+            parse(params).and_then(|params| unsubscribe(server, client, params))
+        }
+        method => Err(RemoteError::UnknownMethod(method.to_string())),
+    };
+    match result {
+        Ok(result) => json!({ "id": request.id, "result": result }),
+        Err(err) => error_response(request.id, err),
+    }
+}
+
+/// Sends the changes of the watched components to the owner of each subscription.
+///
+/// Only the components changed since the last notification, according to their change ticks,
+/// are serialized and compared to the values sent before.
+pub(crate) fn notify_subscriptions(
+    world: &mut World,
+    registry: &TypeRegistry,
+    server: &mut RemoteServer,
+) {
+    if server.subscriptions.is_empty() {
+        return;
+    }
+    let all_entities = all_entities(world);
+    // the components changed after this are detected by the next notification
+    let change_tick = world.increment_change_tick();
+    let mut notifications = Vec::new();
+    for subscription in &mut server.subscriptions {
+        let entities = match subscription.entity {
+            Some(entity) => std::slice::from_ref(&entity).to_vec(),
+            None => all_entities.clone(),
+        };
+        let mut current = BTreeMap::new();
+        let mut changes = Vec::new();
+        for entity in entities {
+            let entity_ref = match world.get_entity(entity) {
+                Some(entity_ref) => entity_ref,
+                None => continue,
+            };
+            for (type_name, type_id, reflect_component) in &subscription.components {
+                let ticks = match world
+                    .components()
+                    .get_id(*type_id)
+                    .and_then(|component_id| entity_ref.get_change_ticks_by_id(component_id))
+                {
+                    Some(ticks) => ticks,
+                    None => continue,
+                };
+                let key = (entity.to_bits(), type_name.clone());
+                let previous = subscription.last.remove(&key);
+                let value = match previous {
+                    Some(previous)
+                        if !ticks.is_changed(subscription.last_change_tick, change_tick) =>
+                    {
+                        previous
+                    }
+                    previous => {
+                        let value = reflect_component
+                            .reflect_component(world, entity)
+                            .and_then(|component| serialize(component, registry).ok());
+                        let value = match value {
+                            Some(value) => value,
+                            None => continue,
+                        };
+                        if previous.as_ref() != Some(&value) {
+                            changes.push(ComponentChange {
+                                entity: entity.to_bits(),
+                                component: type_name.clone(),
+                                value: value.clone(),
+                            });
+                        }
+                        value
+                    }
+                };
+                current.insert(key, value);
+            }
+        }
+        // the components left from the last notification were removed
+        for (entity, component) in std::mem::replace(&mut subscription.last, current).into_keys() {
+            changes.push(ComponentChange {
+                entity,
+                component,
+                value: Value::Null,
+            });
+        }
+        subscription.last_change_tick = change_tick;
+
+        if !changes.is_empty() {
+            let notification = Notification {
+                subscription: subscription.id,
+                changes,
+            };
+            notifications.push((subscription.client, notification));
+        }
+    }
+    for (client, notification) in notifications {
+        server.send(client, &serde_json::to_value(notification).unwrap());
+    }
+}
+
+fn error_response(id: Value, error: RemoteError) -> Value {
+    json!({ "id": id, "error": error.to_string() })
+}
+
+fn parse<T: DeserializeOwned>(params: Value) -> Result<T, RemoteError> {
+    serde_json::from_value(params).map_err(RemoteError::InvalidParams)
+}
+
+fn entity(world: &World, bits: u64) -> Result<Entity, RemoteError> {
+    let entity = Entity::from_bits(bits);
+    world
+        .get_entity(entity)
+        .map(|_| entity)
+        .ok_or(RemoteError::NoSuchEntity(bits))
+}
+
+fn all_entities(world: &mut World) -> Vec<Entity> {
+    let mut entities: Vec<Entity> = world.query::<Entity>().iter(world).collect();
+    entities.sort_by_key(|entity| entity.to_bits());
+    entities
+}
+
+/// Looks up the registration of a component type by its full or short name.
+fn component_registration<'a>(
+    registry: &'a TypeRegistry,
+    type_name: &str,
+) -> Result<(&'a TypeRegistration, &'a ReflectComponent), RemoteError> {
+    let registration = registry
+        .get_with_name(type_name)
+        .or_else(|| registry.get_with_short_name(type_name))
+        .ok_or_else(|| RemoteError::UnregisteredType(type_name.to_string()))?;
+    let reflect_component = registration
+        .data::<ReflectComponent>()
+        .ok_or_else(|| RemoteError::NotAComponent(type_name.to_string()))?;
+    Ok((registration, reflect_component))
+}
+
+fn serialize(value: &dyn Reflect, registry: &TypeRegistry) -> Result<Value, RemoteError> {
+    serde_json::to_value(ReflectSerializer::new(value, registry)).map_err(|error| {
+        RemoteError::Serialize {
+            type_name: value.type_name().to_string(),
+            error,
+        }
+    })
+}
+
+type DeserializedComponent = (Box<dyn Reflect>, ReflectComponent);
+
+/// Deserializes components, making sure each of them has a registered [`ReflectComponent`].
+fn deserialize_components(
+    values: Vec<Value>,
+    registry: &TypeRegistry,
+) -> Result<Vec<DeserializedComponent>, RemoteError> {
+    values
+        .into_iter()
+        .map(|value| {
+            // `ReflectDeserializer` expects the `type` key to come first, but `Value` sorts its keys
+            let json = serde_json::to_string(&TypeFirst(&value)).unwrap();
+            let component = ReflectDeserializer::new(registry)
+                .deserialize(&mut serde_json::Deserializer::from_str(&json))
+                .map_err(RemoteError::Deserialize)?;
+            let (_, reflect_component) = component_registration(registry, component.type_name())?;
+            Ok((component, reflect_component.clone()))
+        })
+        .collect()
+}
+
+/// Serializes a [`Value`], writing the `type` key of every object before its other keys.
+struct TypeFirst<'a>(&'a Value);
+
+impl<'a> Serialize for TypeFirst<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0 {
+            Value::Object(object) => {
+                let mut map = serializer.serialize_map(Some(object.len()))?;
+                if let Some(type_name) = object.get("type") {
+                    map.serialize_entry("type", type_name)?;
+                }
+                for (key, value) in object.iter().filter(|(key, _)| *key != "type") {
+                    map.serialize_entry(key, &TypeFirst(value))?;
+                }
+                map.end()
+            }
+            Value::Array(array) => {
+                let mut seq = serializer.serialize_seq(Some(array.len()))?;
+                for value in array {
+                    seq.serialize_element(&TypeFirst(value))?;
+                }
+                seq.end()
+            }
+            value => value.serialize(serializer),
+        }
+    }
+}
+
+fn list_entities(world: &mut World) -> Result<Value, RemoteError> {
+    let infos: Vec<EntityInfo> = all_entities(world)
+        .into_iter()
+        .map(|entity| EntityInfo {
+            entity: entity.to_bits(),
+            name: world
+                .get::<Name>(entity)
+                .map(|name| name.as_str().to_string()),
+        })
+        .collect();
+    Ok(serde_json::to_value(infos).unwrap())
+}
+
+fn get(world: &World, registry: &TypeRegistry, params: GetParams) -> Result<Value, RemoteError> {
+    let entity = entity(world, params.entity)?;
+    let mut components = serde_json::Map::new();
+    match params.components {
+        Some(type_names) => {
+            for type_name in type_names {
+                let (registration, reflect_component) =
+                    component_registration(registry, &type_name)?;
+                if let Some(component) = reflect_component.reflect_component(world, entity) {
+                    components.insert(
+                        registration.type_name().to_string(),
+                        serialize(component, registry)?,
+                    );
+                }
+            }
+        }
+        None => {
+            let entity_ref = world.entity(entity);
+            for component_id in entity_ref.archetype().components() {
+                let registration = world
+                    .components()
+                    .get_info(component_id)
+                    .and_then(|info| info.type_id())
+                    .and_then(|type_id| registry.get(type_id));
+                let reflect_component = registration.and_then(|registration| {
+                    Some((registration, registration.data::<ReflectComponent>()?))
+                });
+                if let Some((registration, reflect_component)) = reflect_component {
+                    if let Some(component) = reflect_component.reflect_component(world, entity) {
+                        components.insert(
+                            registration.type_name().to_string(),
+                            serialize(component, registry)?,
+                        );
+                    }
+                }
+            }
+        }
+    }
+    Ok(Value::Object(components))
+}
+
+fn set(
+    world: &mut World,
+    registry: &TypeRegistry,
+    params: SetParams,
+) -> Result<Value, RemoteError> {
+    let entity = entity(world, params.entity)?;
+    for (component, reflect_component) in deserialize_components(params.components, registry)? {
+        if reflect_component.reflect_component(world, entity).is_some() {
+            reflect_component.apply_component(world, entity, &*component);
+        } else {
+            reflect_component.add_component(world, entity, &*component);
+        }
+    }
+    Ok(Value::Null)
+}
+
+fn remove(
+    world: &mut World,
+    registry: &TypeRegistry,
+    params: RemoveParams,
+) -> Result<Value, RemoteError> {
+    let entity = entity(world, params.entity)?;
+    let reflect_components = params
+        .components
+        .iter()
+        .map(|type_name| component_registration(registry, type_name))
+        .collect::<Result<Vec<_>, _>>()?;
+    for (_, reflect_component) in reflect_components {
+        reflect_component.remove_component(world, entity);
+    }
+    Ok(Value::Null)
+}
+
+fn spawn(
+    world: &mut World,
+    registry: &TypeRegistry,
+    params: SpawnParams,
+) -> Result<Value, RemoteError> {
+    let components = deserialize_components(params.components, registry)?;
+    let entity = world.spawn().id();
+    for (component, reflect_component) in components {
+        reflect_component.add_component(world, entity, &*component);
+    }
+    Ok(json!({ "entity": entity.to_bits() }))
+}
+
+fn despawn(world: &mut World, params: DespawnParams) -> Result<Value, RemoteError> {
+    let entity = entity(world, params.entity)?;
+    world.entity_mut(entity).despawn_recursive();
+    Ok(Value::Null)
+}
+
+fn query(
+    world: &mut World,
+    registry: &TypeRegistry,
+    params: QueryParams,
+) -> Result<Value, RemoteError> {
+    let resolve = |type_names: &[String]| {
+        type_names
+            .iter()
+            .map(|type_name| component_registration(registry, type_name))
+            .collect::<Result<Vec<_>, _>>()
+    };
+    let with = resolve(&params.with)?;
+    let without = resolve(&params.without)?;
+
+    let mut rows = Vec::new();
+    for entity in all_entities(world) {
+        let entity_ref = world.entity(entity);
+        let matches = with
+            .iter()
+            .all(|(registration, _)| entity_ref.contains_type_id(registration.type_id()))
+            && !without
+                .iter()
+                .any(|(registration, _)| entity_ref.contains_type_id(registration.type_id()));
+        if !matches {
+            continue;
+        }
+        let mut components = serde_json::Map::new();
+        for (registration, reflect_component) in &with {
+            if let Some(component) = reflect_component.reflect_component(world, entity) {
+                components.insert(
+                    registration.type_name().to_string(),
+                    serialize(component, registry)?,
+                );
+            }
+        }
+        rows.push(QueryRow {
+            entity: entity.to_bits(),
+            components,
+        });
+    }
+    Ok(serde_json::to_value(rows).unwrap())
+}
+
+fn subscribe(
+    world: &World,
+    registry: &TypeRegistry,
+    server: &mut RemoteServer,
+    client: ClientId,
+    params: SubscribeParams,
+) -> Result<Value, RemoteError> {
+    let entity = params.entity.map(|bits| entity(world, bits)).transpose()?;
+    let components = params
+        .components
+        .iter()
+        .map(|type_name| {
+            let (registration, reflect_component) = component_registration(registry, type_name)?;
+            Ok((
+                registration.type_name().to_string(),
+                registration.type_id(),
+                reflect_component.clone(),
+            ))
+        })
+        .collect::<Result<Vec<_>, RemoteError>>()?;
+
+    let id = server.next_subscription_id;
+    server.next_subscription_id += 1;
+    server.subscriptions.push(Subscription {
+        id,
+        client,
+        entity,
+        components,
+        last: BTreeMap::new(),
+        last_change_tick: 0,
+    });
+    Ok(Value::from(id))
+}
+
+fn unsubscribe(
+    server: &mut RemoteServer,
+    client: ClientId,
+    params: UnsubscribeParams,
+) -> Result<Value, RemoteError> {
+    let index = server
+        .subscriptions
+        .iter()
+        .position(|subscription| {
+            subscription.id == params.subscription && subscription.client == client
+        })
+        .ok_or(RemoteError::NoSuchSubscription(params.subscription))?;
+    server.subscriptions.remove(index);
+    Ok(Value::Null)
+}
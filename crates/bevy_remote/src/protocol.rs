@@ -0,0 +1,175 @@
+//! Messages exchanged between a [`RemoteServer`](crate::RemoteServer) and its clients.
+//!
+//! Every message is a single line of JSON. A request has the form
+//! `{"id": 1, "method": "get", "params": {...}}`. The `id` may be any JSON value and is echoed
+//! back in the response, which is either `{"id": 1, "result": ...}` or
+//! `{"id": 1, "error": "..."}`.
+//!
+//! Entities are identified by their [`Entity::to_bits`](bevy_ecs::entity::Entity::to_bits)
+//! representation, and component values use the format of
+//! [`ReflectSerializer`](bevy_reflect::serde::ReflectSerializer). Component types may be given
+//! by their full or short type name.
+//!
+//! While a subscription is active, the server also sends notifications of the form
+//! `{"subscription": 1, "changes": [{"entity": 4294967296, "component": "...", "value": ...}]}`
+//! where `value` is `null` if the component was removed.
+//!
+//! With the [`Stdio`](crate::RemoteTransport::Stdio) transport, the messages share the standard
+//! output with everything else the app prints, such as the logs of the `LogPlugin`. Clients
+//! should skip the lines that aren't JSON objects, or the app should print nothing else to the
+//! standard output.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+/// Lists all entities along with their [`Name`](bevy_core::Name), if any.
+///
+/// Takes no params and returns an array of [`EntityInfo`].
+pub const LIST_ENTITIES: &str = "list_entities";
+/// Gets components of an entity. Takes [`GetParams`] and returns a map of type names to values.
+pub const GET: &str = "get";
+/// Inserts or updates components of an entity. Takes [`SetParams`] and returns `null`.
+pub const SET: &str = "set";
+/// Removes components from an entity. Takes [`RemoveParams`] and returns `null`.
+pub const REMOVE: &str = "remove";
+/// Spawns an entity. Takes [`SpawnParams`] and returns the new entity.
+pub const SPAWN: &str = "spawn";
+/// Despawns an entity and its children. Takes [`DespawnParams`] and returns `null`.
+pub const DESPAWN: &str = "despawn";
+/// Queries entities by their components. Takes [`QueryParams`] and returns an array of
+/// [`QueryRow`].
+pub const QUERY: &str = "query";
+/// Subscribes to changes of components. Takes [`SubscribeParams`] and returns the id of the
+/// subscription.
+pub const SUBSCRIBE: &str = "subscribe";
+/// Cancels a subscription. Takes [`UnsubscribeParams`] and returns `null`.
+pub const UNSUBSCRIBE: &str = "unsubscribe";
+
+/// A request sent by a client.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Request {
+    #[serde(default)]
+    pub id: Value,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+/// The params of a [`GET`] request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GetParams {
+    pub entity: u64,
+    /// The components to get. If omitted, all reflected components of the entity are returned.
+    #[serde(default)]
+    pub components: Option<Vec<String>>,
+}
+
+/// The params of a [`SET`] request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SetParams {
+    pub entity: u64,
+    /// The serialized components. Existing components are patched, others are inserted.
+    pub components: Vec<Value>,
+}
+
+/// The params of a [`REMOVE`] request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RemoveParams {
+    pub entity: u64,
+    pub components: Vec<String>,
+}
+
+/// The params of a [`SPAWN`] request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpawnParams {
+    #[serde(default)]
+    pub components: Vec<Value>,
+}
+
+/// The params of a [`DESPAWN`] request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DespawnParams {
+    pub entity: u64,
+}
+
+/// The params of a [`QUERY`] request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QueryParams {
+    /// The components an entity must have. Their values are returned.
+    pub with: Vec<String>,
+    /// The components an entity must not have.
+    #[serde(default)]
+    pub without: Vec<String>,
+}
+
+/// The params of a [`SUBSCRIBE`] request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SubscribeParams {
+    /// The entity to watch. If omitted, all entities are watched.
+    #[serde(default)]
+    pub entity: Option<u64>,
+    pub components: Vec<String>,
+}
+
+/// The params of an [`UNSUBSCRIBE`] request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UnsubscribeParams {
+    pub subscription: u64,
+}
+
+/// An entity returned by [`LIST_ENTITIES`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntityInfo {
+    pub entity: u64,
+    pub name: Option<String>,
+}
+
+/// An entity returned by [`QUERY`], along with the values of the queried components.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QueryRow {
+    pub entity: u64,
+    pub components: serde_json::Map<String, Value>,
+}
+
+/// A change of a component reported to a subscription.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComponentChange {
+    pub entity: u64,
+    pub component: String,
+    /// The new value of the component, or `null` if it was removed.
+    pub value: Value,
+}
+
+/// A notification sent to the client owning a subscription.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Notification {
+    pub subscription: u64,
+    pub changes: Vec<ComponentChange>,
+}
+
+/// An error returned in response to a request.
+#[derive(Error, Debug)]
+pub enum RemoteError {
+    #[error("invalid request: {0}")]
+    InvalidRequest(serde_json::Error),
+    #[error("invalid params: {0}")]
+    InvalidParams(serde_json::Error),
+    #[error("unknown method: {0}")]
+    UnknownMethod(String),
+    #[error("no registration found for type {0}")]
+    UnregisteredType(String),
+    #[error("type {0} is not a reflected component")]
+    NotAComponent(String),
+    #[error("entity {0} does not exist")]
+    NoSuchEntity(u64),
+    #[error("no subscription with id {0}")]
+    NoSuchSubscription(u64),
+    #[error("failed to serialize {type_name}: {error}")]
+    Serialize {
+        type_name: String,
+        error: serde_json::Error,
+    },
+    #[error("failed to deserialize component: {0}")]
+    Deserialize(serde_json::Error),
+}
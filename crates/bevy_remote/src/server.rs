@@ -0,0 +1,239 @@
+use crate::{methods::Subscription, protocol::Request};
+use bevy_utils::{tracing::error, HashMap};
+use crossbeam_channel::{Receiver, Sender};
+use serde_json::Value;
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+/// A unique identifier of a client connected to a [`RemoteServer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ClientId(u64);
+
+pub(crate) enum Message {
+    Connected(ClientId, Sender<String>),
+    Request(ClientId, String),
+    Disconnected(ClientId),
+}
+
+/// Collects the requests of remote clients, to be handled by
+/// [`process_remote_requests`](crate::process_remote_requests).
+///
+/// Clients may connect over TCP with [`RemoteServer::listen_tcp`], over the standard input and
+/// output of the process with [`RemoteServer::listen_stdio`], or in-process with
+/// [`RemoteServer::connect_local`].
+pub struct RemoteServer {
+    sender: Sender<Message>,
+    receiver: Receiver<Message>,
+    next_client_id: Arc<AtomicU64>,
+    clients: HashMap<ClientId, Sender<String>>,
+    pub(crate) subscriptions: Vec<Subscription>,
+    pub(crate) next_subscription_id: u64,
+}
+
+impl Default for RemoteServer {
+    fn default() -> Self {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        Self {
+            sender,
+            receiver,
+            next_client_id: Default::default(),
+            clients: Default::default(),
+            subscriptions: Default::default(),
+            next_subscription_id: 0,
+        }
+    }
+}
+
+impl RemoteServer {
+    fn new_client_id(&self) -> ClientId {
+        ClientId(self.next_client_id.fetch_add(1, Ordering::Relaxed))
+    }
+
+    /// Connects a new in-process client.
+    pub fn connect_local(&self) -> RemoteClient {
+        let id = self.new_client_id();
+        let (response_sender, responses) = crossbeam_channel::unbounded();
+        self.sender
+            .send(Message::Connected(id, response_sender))
+            .unwrap();
+        RemoteClient {
+            id,
+            sender: self.sender.clone(),
+            responses,
+            next_request_id: AtomicU64::new(0),
+        }
+    }
+
+    /// Accepts clients on a TCP socket bound to `addr`.
+    ///
+    /// Connections are handled on background threads. Returns the address the socket is
+    /// bound to, which is useful when binding to port 0.
+    pub fn listen_tcp(&self, addr: impl ToSocketAddrs) -> io::Result<SocketAddr> {
+        let listener = TcpListener::bind(addr)?;
+        let local_addr = listener.local_addr()?;
+        let sender = self.sender.clone();
+        let next_client_id = self.next_client_id.clone();
+        thread::Builder::new()
+            .name("remote listener".to_string())
+            .spawn(move || {
+                for stream in listener.incoming() {
+                    match stream {
+                        Ok(stream) => {
+                            let id = ClientId(next_client_id.fetch_add(1, Ordering::Relaxed));
+                            if let Err(err) = spawn_tcp_client(id, stream, sender.clone()) {
+                                error!("Failed to accept remote client: {}", err);
+                            }
+                        }
+                        Err(err) => error!("Failed to accept remote client: {}", err),
+                    }
+                }
+            })?;
+        Ok(local_addr)
+    }
+
+    /// Accepts a single client reading requests from the standard input and writing responses
+    /// to the standard output.
+    pub fn listen_stdio(&self) -> io::Result<()> {
+        let id = self.new_client_id();
+        spawn_client(
+            id,
+            BufReader::new(io::stdin()),
+            io::stdout(),
+            self.sender.clone(),
+        )
+    }
+
+    pub(crate) fn try_recv(&self) -> Option<Message> {
+        self.receiver.try_recv().ok()
+    }
+
+    pub(crate) fn connect(&mut self, id: ClientId, sender: Sender<String>) {
+        self.clients.insert(id, sender);
+    }
+
+    pub(crate) fn disconnect(&mut self, id: ClientId) {
+        self.clients.remove(&id);
+        self.subscriptions
+            .retain(|subscription| subscription.client != id);
+    }
+
+    /// Sends a message to a client, disconnecting it if it is gone.
+    pub(crate) fn send(&mut self, id: ClientId, message: &Value) {
+        let sent = self
+            .clients
+            .get(&id)
+            .map(|client| client.send(message.to_string()));
+        if !matches!(sent, Some(Ok(()))) {
+            self.disconnect(id);
+        }
+    }
+}
+
+fn spawn_tcp_client(id: ClientId, stream: TcpStream, sender: Sender<Message>) -> io::Result<()> {
+    let reader = BufReader::new(stream.try_clone()?);
+    spawn_client(id, reader, stream, sender)
+}
+
+fn spawn_client(
+    id: ClientId,
+    reader: impl BufRead + Send + 'static,
+    mut writer: impl Write + Send + 'static,
+    sender: Sender<Message>,
+) -> io::Result<()> {
+    let (response_sender, responses) = crossbeam_channel::unbounded::<String>();
+    sender
+        .send(Message::Connected(id, response_sender))
+        .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "remote server was dropped"))?;
+
+    thread::Builder::new()
+        .name("remote writer".to_string())
+        .spawn(move || {
+            for response in responses {
+                if writeln!(writer, "{}", response)
+                    .and_then(|_| writer.flush())
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        })?;
+    thread::Builder::new()
+        .name("remote reader".to_string())
+        .spawn(move || {
+            for line in reader.lines() {
+                match line {
+                    Ok(line) if line.trim().is_empty() => {}
+                    Ok(line) => {
+                        if sender.send(Message::Request(id, line)).is_err() {
+                            return;
+                        }
+                    }
+                    Err(_) => break,
+                }
+            }
+            let _ = sender.send(Message::Disconnected(id));
+        })?;
+    Ok(())
+}
+
+/// An in-process client of a [`RemoteServer`], created with [`RemoteServer::connect_local`].
+///
+/// Requests are handled the next time [`process_remote_requests`](crate::process_remote_requests)
+/// runs, usually during the next app update.
+pub struct RemoteClient {
+    id: ClientId,
+    sender: Sender<Message>,
+    responses: Receiver<String>,
+    next_request_id: AtomicU64,
+}
+
+impl RemoteClient {
+    /// The id of this client.
+    pub fn id(&self) -> ClientId {
+        self.id
+    }
+
+    /// Sends a request with the given method and params, returning the id of the request.
+    pub fn send(&self, method: &str, params: Value) -> u64 {
+        let id = self.next_request_id.fetch_add(1, Ordering::Relaxed);
+        let request = Request {
+            id: Value::from(id),
+            method: method.to_string(),
+            params,
+        };
+        self.send_raw(serde_json::to_string(&request).unwrap());
+        id
+    }
+
+    /// Sends a raw line of JSON to the server.
+    pub fn send_raw(&self, line: impl Into<String>) {
+        // The server can only be gone if the app was dropped, in which case no response will come
+        let _ = self.sender.send(Message::Request(self.id, line.into()));
+    }
+
+    /// Returns the next response or notification, if one was received.
+    pub fn try_recv(&self) -> Option<Value> {
+        let message = self.responses.try_recv().ok()?;
+        serde_json::from_str(&message).ok()
+    }
+
+    /// Waits for the next response or notification for at most `timeout`.
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<Value> {
+        let message = self.responses.recv_timeout(timeout).ok()?;
+        serde_json::from_str(&message).ok()
+    }
+}
+
+impl Drop for RemoteClient {
+    fn drop(&mut self) {
+        let _ = self.sender.send(Message::Disconnected(self.id));
+    }
+}
@@ -1,12 +1,14 @@
 use anyhow::Result;
-use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy_asset::{AssetLoader, AssetLoaderSettings, LoadContext, LoadedAsset};
 use bevy_ecs::prelude::{FromWorld, World};
+use bevy_reflect::{Reflect, ReflectDeserialize, ReflectSerialize};
 use bevy_utils::BoxedFuture;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::{
     renderer::RenderDevice,
-    texture::{Image, ImageType, TextureError},
+    texture::{Image, ImageSampler, ImageType, TextureError},
 };
 
 use super::CompressedImageFormats;
@@ -36,6 +38,55 @@ const FILE_EXTENSIONS: &[&str] = &[
     "ktx2",
 ];
 
+/// Settings of the [`ImageTextureLoader`], read from the `.meta` file of an image.
+///
+/// For example, a pixel art sprite `player.png` can be given a `player.png.meta` file containing
+/// `(sampler: Nearest)`.
+#[derive(Debug, Clone, Reflect, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ImageLoaderSettings {
+    /// Whether the image holds sRGB color data. Set this to `false` for linear data such as
+    /// normal maps.
+    pub is_srgb: bool,
+    /// The sampler used to render the image.
+    pub sampler: ImageLoaderSampler,
+}
+
+impl Default for ImageLoaderSettings {
+    fn default() -> Self {
+        Self {
+            is_srgb: true,
+            sampler: ImageLoaderSampler::Default,
+        }
+    }
+}
+
+/// The sampler of an image loaded by the [`ImageTextureLoader`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+#[reflect_value(PartialEq, Serialize, Deserialize)]
+pub enum ImageLoaderSampler {
+    /// Use the global [`DefaultImageSampler`](super::DefaultImageSampler).
+    Default,
+    /// Use `Linear` min and mag filters.
+    Linear,
+    /// Use `Nearest` min and mag filters.
+    Nearest,
+}
+
+impl From<ImageLoaderSampler> for ImageSampler {
+    fn from(sampler: ImageLoaderSampler) -> Self {
+        match sampler {
+            ImageLoaderSampler::Default => ImageSampler::Default,
+            ImageLoaderSampler::Linear => {
+                ImageSampler::Descriptor(ImageSampler::linear_descriptor())
+            }
+            ImageLoaderSampler::Nearest => {
+                ImageSampler::Descriptor(ImageSampler::nearest_descriptor())
+            }
+        }
+    }
+}
+
 impl AssetLoader for ImageTextureLoader {
     fn load<'a>(
         &'a self,
@@ -45,17 +96,22 @@ impl AssetLoader for ImageTextureLoader {
         Box::pin(async move {
             // use the file extension for the image type
             let ext = load_context.path().extension().unwrap().to_str().unwrap();
+            let settings = load_context
+                .settings::<ImageLoaderSettings>()
+                .cloned()
+                .unwrap_or_default();
 
-            let dyn_img = Image::from_buffer(
+            let mut dyn_img = Image::from_buffer(
                 bytes,
                 ImageType::Extension(ext),
                 self.supported_compressed_formats,
-                true,
+                settings.is_srgb,
             )
             .map_err(|err| FileTextureError {
                 error: err,
                 path: format!("{}", load_context.path().display()),
             })?;
+            dyn_img.sampler_descriptor = settings.sampler.into();
 
             load_context.set_default_asset(LoadedAsset::new(dyn_img));
             Ok(())
@@ -65,6 +121,10 @@ impl AssetLoader for ImageTextureLoader {
     fn extensions(&self) -> &[&str] {
         FILE_EXTENSIONS
     }
+
+    fn default_settings(&self) -> Option<Box<dyn AssetLoaderSettings>> {
+        Some(Box::new(ImageLoaderSettings::default()))
+    }
 }
 
 impl FromWorld for ImageTextureLoader {
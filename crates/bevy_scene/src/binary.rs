@@ -0,0 +1,796 @@
+use crate::{
+    serde::{handles_to_asset_paths, SceneDeserializer, SceneSerializer},
+    DynamicEntity, DynamicScene, MigrationRenames, PrefabInstance, PrefabOverride,
+};
+use bevy_asset::{AssetPath, Handle, HandleId};
+use bevy_reflect::{
+    serde::DeserializeRenames, DynamicArray, DynamicList, DynamicMap, DynamicStruct, DynamicTuple,
+    DynamicTupleStruct, NamedField, Reflect, ReflectDeserialize, ReflectRef, ReflectSerialize,
+    Struct, StructInfo, TypeInfo, TypeRegistry,
+};
+use bevy_utils::HashMap;
+use bincode::Options;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use thiserror::Error;
+
+/// The bytes every binary scene starts with.
+pub const BINARY_SCENE_MAGIC: [u8; 4] = *b"BSCN";
+
+/// The version of the binary scene format, written as a little-endian `u32` after the
+/// [`BINARY_SCENE_MAGIC`].
+///
+/// It is the version of the encoding of scenes, unrelated to the version of their content used by
+/// [`SceneMigrations`](crate::SceneMigrations).
+pub const BINARY_SCENE_FORMAT_VERSION: u32 = 1;
+
+/// The extension of binary scene files, which are loaded and saved in the binary format by the
+/// [`SceneLoader`](crate::SceneLoader) and [`SceneSaver`](crate::SceneSaver).
+pub const BINARY_SCENE_EXTENSION: &str = "scn.bin";
+
+#[derive(Error, Debug)]
+pub enum BinarySceneError {
+    #[error("not a binary scene")]
+    InvalidMagic,
+    #[error(
+        "unsupported binary scene format version {0}, expected {}",
+        BINARY_SCENE_FORMAT_VERSION
+    )]
+    UnsupportedVersion(u32),
+    #[error("invalid binary scene: {0}")]
+    Bincode(#[from] bincode::Error),
+    #[error("binary scene refers to the name {0}, but only has {1} names of this kind")]
+    InvalidName(u32, usize),
+    #[error("type `{type_name}` did not register ReflectSerialize")]
+    MissingReflectSerialize { type_name: String },
+    #[error("no registration found for `{type_name}`")]
+    UnregisteredType { type_name: String },
+    #[error("the TypeRegistration for `{type_name}` doesn't have ReflectDeserialize")]
+    MissingReflectDeserialize { type_name: String },
+}
+
+/// Returns `true` if the scene at `path` is in the binary format, based on its extension.
+pub fn is_binary_scene_path(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|file_name| file_name.to_str())
+        .map_or(false, |file_name| {
+            file_name
+                .to_lowercase()
+                .ends_with(&format!(".{}", BINARY_SCENE_EXTENSION))
+        })
+}
+
+/// Serializes the scene of `serializer` in the binary scene format, a compact alternative to RON
+/// that is much faster to parse.
+///
+/// The content of the scene is the same as with RON, including its version and the paths of its
+/// assets. Types and fields are written once in tables of names, and referred to by their index
+/// in them. Registered types are written with their short name, unless it is ambiguous.
+pub fn serialize_binary(serializer: &SceneSerializer) -> Result<Vec<u8>, BinarySceneError> {
+    let registry = serializer.registry.read();
+    let mut writer = BinaryWriter {
+        registry: &registry,
+        type_names: Names::default(),
+        field_names: Names::default(),
+    };
+    let with_asset_paths = |value: &dyn Reflect| match serializer.asset_server {
+        Some(asset_server) => handles_to_asset_paths(value, &registry, asset_server),
+        None => value.clone_value(),
+    };
+
+    let mut entities = Vec::with_capacity(serializer.scene.entities.len());
+    for entity in &serializer.scene.entities {
+        let mut components = Vec::with_capacity(entity.components.len());
+        for component in &entity.components {
+            components.push(writer.write(&*with_asset_paths(&**component))?);
+        }
+        entities.push(BinaryEntity {
+            entity: entity.entity,
+            components,
+        });
+    }
+
+    let mut resources = Vec::with_capacity(serializer.scene.resources.len());
+    for resource in &serializer.scene.resources {
+        resources.push(writer.write(&*with_asset_paths(&**resource))?);
+    }
+
+    let mut prefabs = Vec::with_capacity(serializer.scene.prefabs.len());
+    for prefab in &serializer.scene.prefabs {
+        let mut overrides = Vec::with_capacity(prefab.overrides.len());
+        for prefab_override in &prefab.overrides {
+            overrides.push(BinaryPrefabOverride {
+                entity_path: prefab_override.entity_path.clone(),
+                component: writer.type_name(&prefab_override.component),
+                field_path: prefab_override.field_path.clone(),
+                value: writer.write(&*with_asset_paths(&*prefab_override.value))?,
+            });
+        }
+        prefabs.push(BinaryPrefab {
+            entity: prefab.entity,
+            scene: prefab.path.to_string(),
+            overrides,
+        });
+    }
+
+    let scene = BinaryScene {
+        version: serializer.version,
+        type_names: writer.type_names.names,
+        field_names: writer.field_names.names,
+        entities,
+        resources,
+        prefabs,
+    };
+    let mut bytes = BINARY_SCENE_MAGIC.to_vec();
+    bytes.extend_from_slice(&BINARY_SCENE_FORMAT_VERSION.to_le_bytes());
+    bincode_options().serialize_into(&mut bytes, &scene)?;
+    Ok(bytes)
+}
+
+/// Deserializes a scene in the binary scene format, upgrading it with the migrations of
+/// `deserializer`.
+pub fn deserialize_binary(
+    bytes: &[u8],
+    deserializer: SceneDeserializer,
+) -> Result<DynamicScene, BinarySceneError> {
+    let bytes = bytes
+        .strip_prefix(&BINARY_SCENE_MAGIC[..])
+        .ok_or(BinarySceneError::InvalidMagic)?;
+    if bytes.len() < 4 {
+        return Err(BinarySceneError::InvalidMagic);
+    }
+    let (format_version, bytes) = bytes.split_at(4);
+    let format_version = u32::from_le_bytes(format_version.try_into().unwrap());
+    if format_version != BINARY_SCENE_FORMAT_VERSION {
+        return Err(BinarySceneError::UnsupportedVersion(format_version));
+    }
+    let binary_scene: BinaryScene = bincode_options().deserialize(bytes)?;
+    let registry = deserializer.type_registry;
+    // types written with their short name are looked up by their full name
+    let type_names: Vec<String> = binary_scene
+        .type_names
+        .iter()
+        .map(|name| match registry.get_with_short_name(name) {
+            Some(registration) if registry.get_with_name(name).is_none() => {
+                registration.type_name().to_string()
+            }
+            _ => name.clone(),
+        })
+        .collect();
+    let renames = deserializer
+        .migrations
+        .map(|migrations| migrations.renames(binary_scene.version));
+    let reader = BinaryReader {
+        registry,
+        type_names: &type_names,
+        field_names: &binary_scene.field_names,
+        renames: renames.as_ref(),
+    };
+
+    let mut entities = Vec::with_capacity(binary_scene.entities.len());
+    for entity in &binary_scene.entities {
+        let mut components = Vec::with_capacity(entity.components.len());
+        for component in &entity.components {
+            components.push(reader.read(component)?);
+        }
+        entities.push(DynamicEntity {
+            entity: entity.entity,
+            components,
+        });
+    }
+
+    let resources = binary_scene
+        .resources
+        .iter()
+        .map(|resource| reader.read(resource))
+        .collect::<Result<_, _>>()?;
+
+    let mut prefabs = Vec::with_capacity(binary_scene.prefabs.len());
+    for prefab in &binary_scene.prefabs {
+        let mut overrides = Vec::with_capacity(prefab.overrides.len());
+        for prefab_override in &prefab.overrides {
+            overrides.push(PrefabOverride {
+                entity_path: prefab_override.entity_path.clone(),
+                component: reader
+                    .saved_type_name(prefab_override.component)?
+                    .to_string(),
+                field_path: prefab_override.field_path.clone(),
+                value: reader.read(&prefab_override.value)?,
+            });
+        }
+        let path = AssetPath::from(prefab.scene.as_str()).to_owned();
+        prefabs.push(PrefabInstance {
+            entity: prefab.entity,
+            handle: Handle::weak(HandleId::from(path.clone())),
+            path,
+            overrides,
+        });
+    }
+
+    let mut scene = DynamicScene {
+        entities,
+        resources,
+        asset_handles: Vec::new(),
+        prefabs,
+    };
+    if let Some(migrations) = deserializer.migrations {
+        migrations.migrate_renamed(&mut scene, binary_scene.version);
+    }
+    Ok(scene)
+}
+
+fn bincode_options() -> impl Options {
+    bincode::DefaultOptions::new().allow_trailing_bytes()
+}
+
+#[derive(Serialize, Deserialize)]
+struct BinaryScene {
+    version: u32,
+    type_names: Vec<String>,
+    field_names: Vec<String>,
+    entities: Vec<BinaryEntity>,
+    resources: Vec<BinaryValue>,
+    prefabs: Vec<BinaryPrefab>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BinaryEntity {
+    entity: u32,
+    components: Vec<BinaryValue>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BinaryPrefab {
+    entity: u32,
+    scene: String,
+    overrides: Vec<BinaryPrefabOverride>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BinaryPrefabOverride {
+    entity_path: String,
+    component: u32,
+    field_path: String,
+    value: BinaryValue,
+}
+
+/// A reflected value, whose type and field names are indices in the type and field names of the
+/// scene
+#[derive(Serialize, Deserialize)]
+enum BinaryValue {
+    Struct {
+        name: u32,
+        fields: Vec<(u32, BinaryValue)>,
+    },
+    TupleStruct {
+        name: u32,
+        fields: Vec<BinaryValue>,
+    },
+    Tuple(Vec<BinaryValue>),
+    List {
+        name: u32,
+        items: Vec<BinaryValue>,
+    },
+    Array {
+        name: u32,
+        items: Vec<BinaryValue>,
+    },
+    Map {
+        name: u32,
+        entries: Vec<(BinaryValue, BinaryValue)>,
+    },
+    /// A value serialized by its [`ReflectSerialize`] type data, with bincode.
+    Value {
+        name: u32,
+        data: Vec<u8>,
+    },
+}
+
+/// A table of names, each written once
+#[derive(Default)]
+struct Names {
+    names: Vec<String>,
+    indices: HashMap<String, u32>,
+}
+
+impl Names {
+    fn index(&mut self, name: &str) -> u32 {
+        if let Some(index) = self.indices.get(name) {
+            return *index;
+        }
+        let index = self.names.len() as u32;
+        self.names.push(name.to_string());
+        self.indices.insert(name.to_string(), index);
+        index
+    }
+}
+
+/// Returns the [`StructInfo`] of a registered struct, whose fields marked with
+/// `#[reflect(skip_serializing)]` are skipped like in the text format.
+fn struct_info<'a>(registry: &'a TypeRegistry, type_name: &str) -> Option<&'a StructInfo> {
+    match registry
+        .get_with_name(type_name)
+        .map(|registration| registration.type_info())
+    {
+        Some(TypeInfo::Struct(info)) => Some(info),
+        _ => None,
+    }
+}
+
+struct BinaryWriter<'a> {
+    registry: &'a TypeRegistry,
+    type_names: Names,
+    field_names: Names,
+}
+
+impl<'a> BinaryWriter<'a> {
+    /// Returns the index of the name of a type, its short name if it is registered and not
+    /// ambiguous.
+    fn type_name(&mut self, type_name: &str) -> u32 {
+        let name = match self.registry.get_with_name(type_name) {
+            Some(registration)
+                if self
+                    .registry
+                    .get_with_short_name(registration.short_name())
+                    .map(|short_registration| short_registration.type_id())
+                    == Some(registration.type_id()) =>
+            {
+                registration.short_name()
+            }
+            _ => type_name,
+        };
+        self.type_names.index(name)
+    }
+
+    fn field_name(&mut self, field_name: &str) -> u32 {
+        self.field_names.index(field_name)
+    }
+
+    fn write(&mut self, value: &dyn Reflect) -> Result<BinaryValue, BinarySceneError> {
+        Ok(match value.reflect_ref() {
+            ReflectRef::Struct(value) => {
+                let struct_info = struct_info(self.registry, value.type_name());
+                let mut fields = Vec::with_capacity(value.field_len());
+                for (i, field) in value.iter_fields().enumerate() {
+                    let field_name = value.name_at(i).unwrap();
+                    if struct_info
+                        .and_then(|info| info.field(field_name))
+                        .map_or(false, NamedField::skip_serializing)
+                    {
+                        continue;
+                    }
+                    let field_name = self.field_name(field_name);
+                    fields.push((field_name, self.write(field)?));
+                }
+                BinaryValue::Struct {
+                    name: self.type_name(value.type_name()),
+                    fields,
+                }
+            }
+            ReflectRef::TupleStruct(value) => BinaryValue::TupleStruct {
+                name: self.type_name(value.type_name()),
+                fields: value
+                    .iter_fields()
+                    .map(|field| self.write(field))
+                    .collect::<Result<_, _>>()?,
+            },
+            ReflectRef::Tuple(value) => BinaryValue::Tuple(
+                value
+                    .iter_fields()
+                    .map(|field| self.write(field))
+                    .collect::<Result<_, _>>()?,
+            ),
+            ReflectRef::List(value) => BinaryValue::List {
+                name: self.type_name(value.type_name()),
+                items: value
+                    .iter()
+                    .map(|item| self.write(item))
+                    .collect::<Result<_, _>>()?,
+            },
+            ReflectRef::Array(value) => BinaryValue::Array {
+                name: self.type_name(value.type_name()),
+                items: value
+                    .iter()
+                    .map(|item| self.write(item))
+                    .collect::<Result<_, _>>()?,
+            },
+            ReflectRef::Map(value) => BinaryValue::Map {
+                name: self.type_name(value.type_name()),
+                entries: value
+                    .iter()
+                    .map(|(key, value)| Ok((self.write(key)?, self.write(value)?)))
+                    .collect::<Result<_, BinarySceneError>>()?,
+            },
+            ReflectRef::Value(value) => {
+                let reflect_serialize = self
+                    .registry
+                    .get_type_data::<ReflectSerialize>(value.type_id())
+                    .ok_or_else(|| BinarySceneError::MissingReflectSerialize {
+                        type_name: value.type_name().to_string(),
+                    })?;
+                let data = bincode_options()
+                    .serialize(reflect_serialize.get_serializable(value).borrow())?;
+                BinaryValue::Value {
+                    name: self.type_name(value.type_name()),
+                    data,
+                }
+            }
+        })
+    }
+}
+
+struct BinaryReader<'a> {
+    registry: &'a TypeRegistry,
+    /// The full names of the types of the scene, when they are registered.
+    type_names: &'a [String],
+    field_names: &'a [String],
+    renames: Option<&'a MigrationRenames<'a>>,
+}
+
+impl<'a> BinaryReader<'a> {
+    fn name(names: &'a [String], index: u32) -> Result<&'a str, BinarySceneError> {
+        names
+            .get(index as usize)
+            .map(String::as_str)
+            .ok_or(BinarySceneError::InvalidName(index, names.len()))
+    }
+
+    /// Returns the name the type `index` was saved with.
+    fn saved_type_name(&self, index: u32) -> Result<&'a str, BinarySceneError> {
+        Self::name(self.type_names, index)
+    }
+
+    /// Returns the current name of the type `index`.
+    fn type_name(&self, index: u32) -> Result<String, BinarySceneError> {
+        let type_name = self.saved_type_name(index)?;
+        Ok(self
+            .renames
+            .and_then(|renames| renames.rename_type(type_name))
+            .unwrap_or_else(|| type_name.to_string()))
+    }
+
+    fn read(&self, value: &BinaryValue) -> Result<Box<dyn Reflect>, BinarySceneError> {
+        Ok(match value {
+            BinaryValue::Struct { name, fields } => {
+                let saved_name = self.saved_type_name(*name)?;
+                let mut dynamic_struct = DynamicStruct::default();
+                dynamic_struct.set_name(self.type_name(*name)?);
+                for (field_name, field) in fields {
+                    let field_name = Self::name(self.field_names, *field_name)?;
+                    let field_name = self
+                        .renames
+                        .and_then(|renames| renames.rename_field(saved_name, field_name))
+                        .unwrap_or_else(|| field_name.to_string());
+                    dynamic_struct.insert_boxed(&field_name, self.read(field)?);
+                }
+                // fields that weren't written get their default value, like in the text format
+                if let Some(info) = struct_info(self.registry, dynamic_struct.name()) {
+                    for field in info.iter() {
+                        if dynamic_struct.field(field.name()).is_none() {
+                            if let Some(value) = field.default_value() {
+                                dynamic_struct.insert_boxed(field.name(), value);
+                            }
+                        }
+                    }
+                }
+                Box::new(dynamic_struct)
+            }
+            BinaryValue::TupleStruct { name, fields } => {
+                let mut tuple_struct = DynamicTupleStruct::default();
+                tuple_struct.set_name(self.type_name(*name)?);
+                for field in fields {
+                    tuple_struct.insert_boxed(self.read(field)?);
+                }
+                Box::new(tuple_struct)
+            }
+            BinaryValue::Tuple(fields) => {
+                let mut tuple = DynamicTuple::default();
+                for field in fields {
+                    tuple.insert_boxed(self.read(field)?);
+                }
+                Box::new(tuple)
+            }
+            BinaryValue::List { name, items } => {
+                let mut list = DynamicList::default();
+                list.set_name(self.type_name(*name)?);
+                for item in items {
+                    list.push_box(self.read(item)?);
+                }
+                Box::new(list)
+            }
+            BinaryValue::Array { name, items } => {
+                let mut array = DynamicArray::new(
+                    items
+                        .iter()
+                        .map(|item| self.read(item))
+                        .collect::<Result<_, _>>()?,
+                );
+                array.set_name(self.type_name(*name)?);
+                Box::new(array)
+            }
+            BinaryValue::Map { name, entries } => {
+                let mut map = DynamicMap::default();
+                map.set_name(self.type_name(*name)?);
+                for (key, value) in entries {
+                    map.insert_boxed(self.read(key)?, self.read(value)?);
+                }
+                Box::new(map)
+            }
+            BinaryValue::Value { name, data } => {
+                let type_name = self.type_name(*name)?;
+                let registration = self.registry.get_with_name(&type_name).ok_or_else(|| {
+                    BinarySceneError::UnregisteredType {
+                        type_name: type_name.clone(),
+                    }
+                })?;
+                let reflect_deserialize =
+                    registration.data::<ReflectDeserialize>().ok_or_else(|| {
+                        BinarySceneError::MissingReflectDeserialize {
+                            type_name: type_name.clone(),
+                        }
+                    })?;
+                let mut deserializer = bincode::Deserializer::from_slice(data, bincode_options());
+                reflect_deserialize.deserialize(&mut deserializer)?
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        deserialize_binary, serialize_binary, BinarySceneError, BINARY_SCENE_FORMAT_VERSION,
+    };
+    use crate::{
+        serde::{SceneDeserializer, SceneSerializer},
+        serialize_ron, DynamicEntity, DynamicScene,
+    };
+    use bevy_reflect::{DynamicStruct, GetField, Reflect, TypeRegistryArc};
+    use bevy_utils::HashMap;
+    use serde::de::DeserializeSeed;
+
+    #[derive(Reflect, Default)]
+    struct Door {
+        open: bool,
+        code: u32,
+        keys: Vec<u32>,
+        tags: HashMap<String, u32>,
+        size: [f32; 2],
+        offset: (i8, i8),
+    }
+
+    #[derive(Reflect, Default)]
+    struct Lock(String);
+
+    #[derive(Reflect, Default)]
+    struct Hinge {
+        angle: f32,
+        #[reflect(skip_serializing, default)]
+        cached_angle: f32,
+    }
+
+    const SCENE: &str = r#"(
+  version: 3,
+  entities: [
+    (
+      entity: 0,
+      components: [
+        {
+          "type": "bevy_scene::binary::tests::Door",
+          "struct": {
+            "open": {
+              "type": "bool",
+              "value": true,
+            },
+            "code": {
+              "type": "u32",
+              "value": 42,
+            },
+            "keys": {
+              "type": "alloc::vec::Vec<u32>",
+              "list": [
+                {
+                  "type": "u32",
+                  "value": 1,
+                },
+              ],
+            },
+            "tags": {
+              "type": "hashbrown::map::HashMap<alloc::string::String, u32>",
+              "map": {
+                {
+                  "type": "alloc::string::String",
+                  "value": "red",
+                }: {
+                  "type": "u32",
+                  "value": 2,
+                },
+              },
+            },
+            "size": {
+              "type": "[f32; 2]",
+              "array": [
+                {
+                  "type": "f32",
+                  "value": 1.0,
+                },
+                {
+                  "type": "f32",
+                  "value": 2.5,
+                },
+              ],
+            },
+            "offset": {
+              "type": "(i8, i8)",
+              "tuple": [
+                {
+                  "type": "i8",
+                  "value": -1,
+                },
+                {
+                  "type": "i8",
+                  "value": 1,
+                },
+              ],
+            },
+          },
+        },
+        {
+          "type": "bevy_scene::binary::tests::Lock",
+          "tuple_struct": [
+            {
+              "type": "alloc::string::String",
+              "value": "brass",
+            },
+          ],
+        },
+      ],
+    ),
+  ],
+  resources: [
+    {
+      "type": "u32",
+      "value": 7,
+    },
+  ],
+  prefabs: [
+    (
+      entity: 0,
+      scene: "door.scn.ron",
+      overrides: [
+        (
+          entity: "Door",
+          component: "bevy_scene::binary::tests::Door",
+          path: "code",
+          value: {
+            "type": "u32",
+            "value": 1,
+          },
+        ),
+      ],
+    ),
+  ],
+)"#;
+
+    fn registry() -> TypeRegistryArc {
+        let registry = TypeRegistryArc::default();
+        {
+            let mut registry = registry.write();
+            registry.register::<bool>();
+            registry.register::<u32>();
+            registry.register::<i8>();
+            registry.register::<f32>();
+            registry.register::<String>();
+            registry.register::<Vec<u32>>();
+            registry.register::<HashMap<String, u32>>();
+            registry.register::<Door>();
+            registry.register::<Lock>();
+            registry.register::<Hinge>();
+        }
+        registry
+    }
+
+    fn deserializer(registry: &bevy_reflect::TypeRegistry) -> SceneDeserializer<'_> {
+        SceneDeserializer {
+            type_registry: registry,
+            migrations: None,
+        }
+    }
+
+    fn to_binary(scene: &DynamicScene, registry: &TypeRegistryArc) -> Vec<u8> {
+        serialize_binary(&SceneSerializer::new(scene, registry).with_version(3)).unwrap()
+    }
+
+    fn to_ron(scene: &DynamicScene, registry: &TypeRegistryArc) -> String {
+        serialize_ron(SceneSerializer::new(scene, registry).with_version(3)).unwrap()
+    }
+
+    #[test]
+    fn text_binary_text_round_trip() {
+        let registry = registry();
+        let mut ron_deserializer = ron::de::Deserializer::from_str(SCENE).unwrap();
+        let scene = deserializer(&registry.read())
+            .deserialize(&mut ron_deserializer)
+            .unwrap();
+        assert_eq!(to_ron(&scene, &registry), SCENE);
+
+        let bytes = to_binary(&scene, &registry);
+        let scene = deserialize_binary(&bytes, deserializer(&registry.read())).unwrap();
+        assert_eq!(to_ron(&scene, &registry), SCENE);
+
+        // binary -> binary is stable too
+        assert_eq!(to_binary(&scene, &registry), bytes);
+    }
+
+    #[test]
+    fn registered_types_are_written_with_their_short_name() {
+        let registry = registry();
+        let mut ron_deserializer = ron::de::Deserializer::from_str(SCENE).unwrap();
+        let scene = deserializer(&registry.read())
+            .deserialize(&mut ron_deserializer)
+            .unwrap();
+        let bytes = to_binary(&scene, &registry);
+        let contains = |name: &str| {
+            bytes
+                .windows(name.len())
+                .any(|window| window == name.as_bytes())
+        };
+        assert!(contains("Door"));
+        assert!(!contains("bevy_scene::binary::tests::Door"));
+        assert!(!contains("alloc::string::String"));
+    }
+
+    #[test]
+    fn skipped_fields_are_not_written_and_read_with_their_default() {
+        let registry = registry();
+        let hinge = Hinge {
+            angle: 1.5,
+            cached_angle: 3.0,
+        };
+        let scene = DynamicScene {
+            entities: vec![DynamicEntity {
+                entity: 0,
+                components: vec![hinge.clone_value()],
+            }],
+            ..Default::default()
+        };
+        let bytes = to_binary(&scene, &registry);
+        assert!(!bytes
+            .windows("cached_angle".len())
+            .any(|window| window == b"cached_angle"));
+
+        let scene = deserialize_binary(&bytes, deserializer(&registry.read())).unwrap();
+        let component = &scene.entities[0].components[0];
+        let hinge = component.downcast_ref::<DynamicStruct>().unwrap();
+        assert_eq!(hinge.get_field::<f32>("angle"), Some(&1.5));
+        assert_eq!(hinge.get_field::<f32>("cached_angle"), Some(&0.0));
+
+        let mut value = Hinge::default();
+        value.apply(&**component);
+        assert_eq!((value.angle, value.cached_angle), (1.5, 0.0));
+    }
+
+    #[test]
+    fn invalid_headers_are_rejected() {
+        let registry = registry();
+        let bytes = to_binary(&DynamicScene::default(), &registry);
+        assert_eq!(bytes[4..8], BINARY_SCENE_FORMAT_VERSION.to_le_bytes()[..]);
+        assert!(deserialize_binary(&bytes, deserializer(&registry.read())).is_ok());
+
+        let mut future = bytes.clone();
+        future[4..8].copy_from_slice(&99u32.to_le_bytes());
+        assert!(matches!(
+            deserialize_binary(&future, deserializer(&registry.read())),
+            Err(BinarySceneError::UnsupportedVersion(99))
+        ));
+
+        assert!(matches!(
+            deserialize_binary(b"RSCN", deserializer(&registry.read())),
+            Err(BinarySceneError::InvalidMagic)
+        ));
+        assert!(matches!(
+            deserialize_binary(b"BSCN\x01", deserializer(&registry.read())),
+            Err(BinarySceneError::InvalidMagic)
+        ));
+    }
+}
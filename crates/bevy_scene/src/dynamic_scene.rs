@@ -1,14 +1,21 @@
-use crate::{serde::SceneSerializer, Scene, SceneSpawnError};
+use crate::{
+    serde::{visit_reflect_mut, SceneSerializer},
+    serialize_binary, BinarySceneError, PrefabInstance, Scene, SceneSpawnError,
+};
 use anyhow::Result;
+use bevy_asset::HandleUntyped;
 use bevy_ecs::{
-    entity::EntityMap,
-    reflect::{ReflectComponent, ReflectMapEntities},
+    entity::{Entity, EntityMap, MapEntitiesError},
+    reflect::{ReflectComponent, ReflectMapEntities, ReflectResource},
     world::World,
 };
-use bevy_reflect::{Reflect, TypeRegistryArc, TypeUuid};
+use bevy_reflect::{Reflect, TypeRegistry, TypeRegistryArc, TypeUuid};
 use serde::Serialize;
 
 /// A collection of serializable dynamic entities, each with its own run-time defined set of components.
+/// It is built from a world with [`DynamicScene::from_world`], or with a
+/// [`DynamicSceneBuilder`](crate::DynamicSceneBuilder) to choose what is extracted.
+///
 /// To spawn a dynamic scene, you can use either:
 /// * [`SceneSpawner::spawn_dynamic`](crate::SceneSpawner::spawn_dynamic)
 /// * adding the [`DynamicSceneBundle`](crate::DynamicSceneBundle) to an entity
@@ -19,6 +26,19 @@ use serde::Serialize;
 #[uuid = "749479b1-fb8c-4ff8-a775-623aa76014f5"]
 pub struct DynamicScene {
     pub entities: Vec<DynamicEntity>,
+    /// Reflected resources, inserted in the world along with the entities.
+    pub resources: Vec<Box<dyn Reflect>>,
+    /// Strong handles to the assets the components of the scene refer to by path, keeping them
+    /// loaded as long as the scene is.
+    ///
+    /// They are acquired by the [`SceneLoader`](crate::SceneLoader), as the handles of the
+    /// spawned components are weak.
+    pub asset_handles: Vec<HandleUntyped>,
+    /// The instances of other scenes nested in this scene.
+    ///
+    /// They are spawned by the [`SceneSpawner`](crate::SceneSpawner) with the entities of the
+    /// scene, but not by [`DynamicScene::write_to_world`].
+    pub prefabs: Vec<PrefabInstance>,
 }
 
 /// A reflection-powered serializable representation of an entity and its components.
@@ -30,6 +50,20 @@ pub struct DynamicEntity {
     pub components: Vec<Box<dyn Reflect>>,
 }
 
+impl DynamicEntity {
+    /// Returns a copy of this entity, with its components cloned with [`Reflect::clone_value`].
+    pub fn clone_dynamic(&self) -> Self {
+        DynamicEntity {
+            entity: self.entity,
+            components: self
+                .components
+                .iter()
+                .map(|component| component.clone_value())
+                .collect(),
+        }
+    }
+}
+
 impl DynamicScene {
     /// Create a new dynamic scene from a given scene.
     pub fn from_scene(scene: &Scene, type_registry: &TypeRegistryArc) -> Self {
@@ -79,7 +113,8 @@ impl DynamicScene {
     /// Write the dynamic entities and their corresponding components to the given world.
     ///
     /// This method will return a `SceneSpawnError` if either a type is not registered
-    /// or doesn't reflect the `Component` trait.
+    /// or doesn't reflect the `Component` trait, or if an entity the scene refers to
+    /// isn't part of it.
     pub fn write_to_world(
         &self,
         world: &mut World,
@@ -126,20 +161,212 @@ impl DynamicScene {
 
         for registration in type_registry.iter() {
             if let Some(map_entities_reflect) = registration.data::<ReflectMapEntities>() {
-                map_entities_reflect
-                    .map_entities(world, entity_map)
-                    .unwrap();
+                map_entities_reflect.map_entities(world, entity_map)?;
             }
         }
+        self.write_resources(world, &type_registry, entity_map)?;
 
         Ok(())
     }
 
-    // TODO: move to AssetSaver when it is implemented
+    /// Updates the entities written by [`DynamicScene::write_to_world`] with the `previous`
+    /// entities of this scene to match this scene, only changing what differs between them.
+    ///
+    /// Components whose value didn't change are left untouched, so that they keep the changes
+    /// made at runtime and don't trigger change detection. Components that are not part of either
+    /// version of the scene are kept. Entities added to the scene are spawned, and entities
+    /// removed from it are despawned. Resources are written as by
+    /// [`DynamicScene::write_to_world`].
+    ///
+    /// The entities the written components refer to are mapped with `entity_map` before writing
+    /// them. The unchanged components already refer to the spawned entities, so they aren't
+    /// mapped again.
+    pub fn patch_world(
+        &self,
+        previous: &[DynamicEntity],
+        world: &mut World,
+        entity_map: &mut EntityMap,
+    ) -> Result<(), SceneSpawnError> {
+        let registry = world.resource::<TypeRegistryArc>().clone();
+        let type_registry = registry.read();
+        let get_reflect_component = |component: &dyn Reflect| {
+            type_registry
+                .get_with_name(component.type_name())
+                .ok_or_else(|| SceneSpawnError::UnregisteredType {
+                    type_name: component.type_name().to_string(),
+                })
+                .and_then(|registration| {
+                    registration
+                        .data::<ReflectComponent>()
+                        .map(|reflect_component| (registration.type_id(), reflect_component))
+                        .ok_or_else(|| SceneSpawnError::UnregisteredComponent {
+                            type_name: component.type_name().to_string(),
+                        })
+                })
+        };
+
+        // despawn the entities removed from the scene
+        for previous_entity in previous {
+            if !self
+                .entities
+                .iter()
+                .any(|scene_entity| scene_entity.entity == previous_entity.entity)
+            {
+                let scene_entity = Entity::from_raw(previous_entity.entity);
+                if let Ok(entity) = entity_map.get(scene_entity) {
+                    world.despawn(entity);
+                    entity_map.remove(scene_entity);
+                }
+            }
+        }
+
+        // spawn the entities added to the scene first, so every entity of the scene can be mapped
+        let mut spawned_entities = Vec::with_capacity(self.entities.len());
+        for scene_entity in &self.entities {
+            let spawned_entity = match entity_map.get(Entity::from_raw(scene_entity.entity)) {
+                Ok(entity) if world.get_entity(entity).is_some() => {
+                    let previous_components = previous
+                        .iter()
+                        .find(|previous_entity| previous_entity.entity == scene_entity.entity)
+                        .map_or(&[][..], |previous_entity| &previous_entity.components);
+                    (entity, previous_components)
+                }
+                _ => {
+                    let entity = world.spawn().id();
+                    entity_map.insert(Entity::from_raw(scene_entity.entity), entity);
+                    (entity, &[][..])
+                }
+            };
+            spawned_entities.push(spawned_entity);
+        }
+
+        for (scene_entity, (entity, previous_components)) in
+            self.entities.iter().zip(spawned_entities)
+        {
+            // remove the components removed from the scene
+            for previous_component in previous_components {
+                if !scene_entity
+                    .components
+                    .iter()
+                    .any(|component| component.type_name() == previous_component.type_name())
+                {
+                    let (type_id, reflect_component) =
+                        get_reflect_component(&**previous_component)?;
+                    if world.entity(entity).contains_type_id(type_id) {
+                        reflect_component.remove_component(world, entity);
+                    }
+                }
+            }
+
+            // apply the components that changed
+            for component in &scene_entity.components {
+                let unchanged = previous_components
+                    .iter()
+                    .find(|previous_component| {
+                        previous_component.type_name() == component.type_name()
+                    })
+                    .and_then(|previous_component| {
+                        component.reflect_partial_eq(&**previous_component)
+                    })
+                    .unwrap_or(false);
+                if unchanged {
+                    continue;
+                }
+                let (type_id, reflect_component) = get_reflect_component(&**component)?;
+                let component = map_entities(&**component, entity_map)?;
+                if world.entity(entity).contains_type_id(type_id) {
+                    reflect_component.apply_component(world, entity, &*component);
+                } else {
+                    reflect_component.add_component(world, entity, &*component);
+                }
+            }
+        }
+        self.write_resources(world, &type_registry, entity_map)?;
+
+        Ok(())
+    }
+
+    /// Inserts the resources of the scene in the world, or applies them to the existing resources
+    /// that differ from them.
+    ///
+    /// The entities the resources refer to are mapped with `entity_map`, so the resources are
+    /// written after the entities of the scene.
+    fn write_resources(
+        &self,
+        world: &mut World,
+        type_registry: &TypeRegistry,
+        entity_map: &EntityMap,
+    ) -> Result<(), SceneSpawnError> {
+        for resource in &self.resources {
+            let resource = map_entities(&**resource, entity_map)?;
+            let registration = type_registry
+                .get_with_name(resource.type_name())
+                .ok_or_else(|| SceneSpawnError::UnregisteredType {
+                    type_name: resource.type_name().to_string(),
+                })?;
+            let reflect_resource = registration.data::<ReflectResource>().ok_or_else(|| {
+                SceneSpawnError::UnregisteredResource {
+                    type_name: resource.type_name().to_string(),
+                }
+            })?;
+            match reflect_resource.reflect_resource(world) {
+                Some(existing) => {
+                    if existing.reflect_partial_eq(&*resource) != Some(true) {
+                        reflect_resource.apply_resource(world, &*resource);
+                    }
+                }
+                None => reflect_resource.insert_resource(world, &*resource),
+            }
+        }
+        Ok(())
+    }
+
     /// Serialize this dynamic scene into rust object notation (ron).
+    ///
+    /// This is the format written by the [`SceneSaver`](crate::SceneSaver), except that the scene
+    /// has the version `0`: use [`SceneSerializer::with_version`] to save it with the current
+    /// version of the [`SceneMigrations`](crate::SceneMigrations).
+    ///
+    /// Without an [`AssetServer`](bevy_asset::AssetServer), handles are written with their raw
+    /// [`HandleId`](bevy_asset::HandleId), which isn't stable across runs. Use
+    /// [`SceneSerializer::with_asset_server`] to write the asset paths of the handles instead, as
+    /// the [`SceneSaver`](crate::SceneSaver) does.
     pub fn serialize_ron(&self, registry: &TypeRegistryArc) -> Result<String, ron::Error> {
         serialize_ron(SceneSerializer::new(self, registry))
     }
+
+    /// Serialize this dynamic scene in the binary scene format, see [`serialize_binary`].
+    ///
+    /// As with [`DynamicScene::serialize_ron`], the scene has the version `0`.
+    pub fn serialize_binary(
+        &self,
+        registry: &TypeRegistryArc,
+    ) -> Result<Vec<u8>, BinarySceneError> {
+        serialize_binary(&SceneSerializer::new(self, registry))
+    }
+}
+
+/// Returns a copy of `value` where the entities it refers to are mapped with `entity_map`.
+fn map_entities(
+    value: &dyn Reflect,
+    entity_map: &EntityMap,
+) -> Result<Box<dyn Reflect>, MapEntitiesError> {
+    let mut value = value.clone_value();
+    let mut result = Ok(());
+    visit_reflect_mut(
+        &mut *value,
+        &mut |value| match value.downcast_mut::<Entity>() {
+            Some(entity) => {
+                match entity_map.get(*entity) {
+                    Ok(mapped) => *entity = mapped,
+                    Err(err) => result = Err(err),
+                }
+                false
+            }
+            None => true,
+        },
+    );
+    result.map(|()| value)
 }
 
 /// Serialize a given Rust data structure into rust object notation (ron).
@@ -0,0 +1,529 @@
+use crate::{serde::visit_reflect_mut, DynamicEntity, DynamicScene};
+use bevy_ecs::{
+    entity::Entity,
+    reflect::{ReflectComponent, ReflectResource},
+    world::World,
+};
+use bevy_hierarchy::{Children, Parent};
+use bevy_reflect::{Reflect, TypeRegistryArc};
+use bevy_utils::{HashMap, HashSet};
+use std::any::{Any, TypeId};
+
+/// Builds a [`DynamicScene`] from a chosen set of entities, components and resources of a world
+///
+/// Unlike [`DynamicScene::from_world`], which extracts every entity and every reflected component,
+/// only the extracted entities are part of the scene, and component and resource types can be
+/// allowed or denied.
+///
+/// The entities referred to by the extracted components, such as the [`Children`] of an entity,
+/// should be extracted too, so that they can be mapped when the scene is spawned. The only
+/// exception is the [`Parent`] of an entity, which isn't extracted when the parent isn't: extracted
+/// hierarchies become the roots of the scene. The references to entities that aren't extracted
+/// fail to be mapped when the scene is spawned, with a
+/// [`SceneSpawnError::MapEntities`](crate::SceneSpawnError::MapEntities).
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_reflect::{Reflect, TypeRegistryArc};
+/// # use bevy_scene::DynamicSceneBuilder;
+/// #[derive(Component, Reflect, Default)]
+/// #[reflect(Component)]
+/// struct Player;
+///
+/// #[derive(Component, Reflect, Default)]
+/// #[reflect(Component)]
+/// struct Health(f32);
+///
+/// # let mut world = World::new();
+/// # let type_registry = TypeRegistryArc::default();
+/// # type_registry.write().register::<Player>();
+/// # type_registry.write().register::<Health>();
+/// # world.insert_resource(type_registry);
+/// # world.spawn().insert(Player).insert(Health(10.0));
+/// # world.spawn().insert(Health(5.0));
+/// let mut players = world.query_filtered::<Entity, With<Player>>();
+/// let mut builder = DynamicSceneBuilder::from_world(&world);
+/// builder
+///     .extract_entities(players.iter(&world))
+///     .deny::<Player>()
+///     .compact_entity_ids(true);
+/// let scene = builder.build();
+/// assert_eq!(scene.entities.len(), 1);
+/// assert_eq!(scene.entities[0].entity, 0);
+/// assert_eq!(scene.entities[0].components.len(), 1);
+/// ```
+pub struct DynamicSceneBuilder<'w> {
+    world: &'w World,
+    type_registry: TypeRegistryArc,
+    entities: Vec<Entity>,
+    extracted_entities: HashSet<Entity>,
+    component_filter: TypeFilter,
+    resource_filter: TypeFilter,
+    extract_resources: bool,
+    compact_entity_ids: bool,
+}
+
+/// The types allowed in a scene
+#[derive(Default)]
+struct TypeFilter {
+    /// The only allowed types, or `None` to allow every type that isn't denied.
+    allowed: Option<HashSet<TypeId>>,
+    denied: HashSet<TypeId>,
+}
+
+impl TypeFilter {
+    fn allow(&mut self, type_id: TypeId) {
+        self.allowed
+            .get_or_insert_with(HashSet::default)
+            .insert(type_id);
+        self.denied.remove(&type_id);
+    }
+
+    fn deny(&mut self, type_id: TypeId) {
+        if let Some(allowed) = &mut self.allowed {
+            allowed.remove(&type_id);
+        }
+        self.denied.insert(type_id);
+    }
+
+    fn is_allowed(&self, type_id: TypeId) -> bool {
+        !self.denied.contains(&type_id)
+            && self
+                .allowed
+                .as_ref()
+                .map_or(true, |allowed| allowed.contains(&type_id))
+    }
+}
+
+impl<'w> DynamicSceneBuilder<'w> {
+    /// Creates a builder extracting from `world`, with the [`TypeRegistryArc`] resource of the
+    /// world.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the world has no [`TypeRegistryArc`] resource.
+    pub fn from_world(world: &'w World) -> Self {
+        Self::from_world_with_type_registry(world, world.resource::<TypeRegistryArc>().clone())
+    }
+
+    pub fn from_world_with_type_registry(world: &'w World, type_registry: TypeRegistryArc) -> Self {
+        DynamicSceneBuilder {
+            world,
+            type_registry,
+            entities: Vec::new(),
+            extracted_entities: HashSet::default(),
+            component_filter: TypeFilter::default(),
+            resource_filter: TypeFilter::default(),
+            extract_resources: false,
+            compact_entity_ids: false,
+        }
+    }
+
+    /// Extracts `entity` and its reflected components.
+    pub fn extract_entity(&mut self, entity: Entity) -> &mut Self {
+        if self.extracted_entities.insert(entity) {
+            self.entities.push(entity);
+        }
+        self
+    }
+
+    /// Extracts the `entities`, for example the entities of a query.
+    pub fn extract_entities(&mut self, entities: impl IntoIterator<Item = Entity>) -> &mut Self {
+        for entity in entities {
+            self.extract_entity(entity);
+        }
+        self
+    }
+
+    /// Extracts `root` and all its descendants.
+    pub fn extract_hierarchy(&mut self, root: Entity) -> &mut Self {
+        let mut to_extract = vec![root];
+        while let Some(entity) = to_extract.pop() {
+            self.extract_entity(entity);
+            if let Some(children) = self.world.get::<Children>(entity) {
+                to_extract.extend(children.iter().rev());
+            }
+        }
+        self
+    }
+
+    /// Extracts the reflected resources of the world, those registered with [`ReflectResource`].
+    pub fn extract_resources(&mut self) -> &mut Self {
+        self.extract_resources = true;
+        self
+    }
+
+    /// Only extracts the allowed component types.
+    ///
+    /// Every reflected component that isn't [denied](Self::deny) is extracted until a type is
+    /// allowed.
+    pub fn allow<T: Any>(&mut self) -> &mut Self {
+        self.component_filter.allow(TypeId::of::<T>());
+        self
+    }
+
+    /// Doesn't extract components of type `T`.
+    pub fn deny<T: Any>(&mut self) -> &mut Self {
+        self.component_filter.deny(TypeId::of::<T>());
+        self
+    }
+
+    /// Only extracts the allowed resource types, see [`allow`](Self::allow).
+    pub fn allow_resource<T: Any>(&mut self) -> &mut Self {
+        self.resource_filter.allow(TypeId::of::<T>());
+        self
+    }
+
+    /// Doesn't extract resources of type `T`.
+    pub fn deny_resource<T: Any>(&mut self) -> &mut Self {
+        self.resource_filter.deny(TypeId::of::<T>());
+        self
+    }
+
+    /// Numbers the entities of the scene from `0` in the order they were extracted, instead of
+    /// using the ids of the world entities.
+    ///
+    /// The entities referred to by the extracted components and resources are renumbered too. The
+    /// entities that aren't extracted are numbered after the extracted ones, so that they can't be
+    /// mistaken for an entity of the scene.
+    pub fn compact_entity_ids(&mut self, compact_entity_ids: bool) -> &mut Self {
+        self.compact_entity_ids = compact_entity_ids;
+        self
+    }
+
+    pub fn build(&self) -> DynamicScene {
+        let world = self.world;
+        let type_registry = self.type_registry.read();
+        let scene_ids: HashMap<Entity, u32> = self
+            .entities
+            .iter()
+            .enumerate()
+            .map(|(index, entity)| {
+                let scene_id = if self.compact_entity_ids {
+                    index as u32
+                } else {
+                    entity.id()
+                };
+                (*entity, scene_id)
+            })
+            .collect();
+        let mut external_ids: HashMap<Entity, u32> = HashMap::default();
+        let mut map_entities = |value: &mut dyn Reflect| {
+            visit_reflect_mut(value, &mut |value| match value.downcast_mut::<Entity>() {
+                Some(entity) => {
+                    if let Some(scene_id) = scene_ids.get(entity) {
+                        *entity = Entity::from_raw(*scene_id);
+                    } else if self.compact_entity_ids {
+                        let next_id = (self.entities.len() + external_ids.len()) as u32;
+                        *entity = Entity::from_raw(*external_ids.entry(*entity).or_insert(next_id));
+                    }
+                    false
+                }
+                None => true,
+            });
+        };
+
+        let mut scene = DynamicScene::default();
+        for entity in &self.entities {
+            let entity_ref = match world.get_entity(*entity) {
+                Some(entity_ref) => entity_ref,
+                None => continue,
+            };
+            let mut components = Vec::new();
+            for component_id in entity_ref.archetype().components() {
+                let type_id = match world
+                    .components()
+                    .get_info(component_id)
+                    .and_then(|info| info.type_id())
+                {
+                    Some(type_id) => type_id,
+                    None => continue,
+                };
+                if !self.component_filter.is_allowed(type_id) {
+                    continue;
+                }
+                if type_id == TypeId::of::<Parent>()
+                    && !entity_ref
+                        .get::<Parent>()
+                        .map_or(false, |parent| self.extracted_entities.contains(&parent.0))
+                {
+                    continue;
+                }
+                let component = type_registry
+                    .get(type_id)
+                    .and_then(|registration| registration.data::<ReflectComponent>())
+                    .and_then(|reflect_component| {
+                        reflect_component.reflect_component(world, *entity)
+                    });
+                if let Some(component) = component {
+                    let mut component = component.clone_value();
+                    map_entities(&mut *component);
+                    components.push(component);
+                }
+            }
+            scene.entities.push(DynamicEntity {
+                entity: scene_ids[entity],
+                components,
+            });
+        }
+
+        if self.extract_resources {
+            for component_id in world.archetypes().resource().components() {
+                let type_id = match world
+                    .components()
+                    .get_info(component_id)
+                    .and_then(|info| info.type_id())
+                {
+                    Some(type_id) => type_id,
+                    None => continue,
+                };
+                if !self.resource_filter.is_allowed(type_id) {
+                    continue;
+                }
+                let resource = type_registry
+                    .get(type_id)
+                    .and_then(|registration| registration.data::<ReflectResource>())
+                    .and_then(|reflect_resource| reflect_resource.reflect_resource(world));
+                if let Some(resource) = resource {
+                    let mut resource = resource.clone_value();
+                    map_entities(&mut *resource);
+                    scene.resources.push(resource);
+                }
+            }
+        }
+
+        scene
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DynamicSceneBuilder;
+    use crate::{DynamicScene, SceneSpawnError};
+    use bevy_ecs::{
+        component::Component,
+        entity::{Entity, EntityMap},
+        reflect::{ReflectComponent, ReflectResource},
+        world::World,
+    };
+    use bevy_hierarchy::{BuildWorldChildren, Children, Parent};
+    use bevy_reflect::{Reflect, TypeRegistryArc};
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Health(u32);
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Armor(u32);
+
+    #[derive(Reflect)]
+    #[reflect(Resource)]
+    struct Selected(Entity);
+
+    impl Default for Selected {
+        fn default() -> Self {
+            Selected(Entity::from_raw(0))
+        }
+    }
+
+    #[derive(Reflect, Default)]
+    #[reflect(Resource)]
+    struct Score(u32);
+
+    fn test_world() -> World {
+        let registry = TypeRegistryArc::default();
+        {
+            let mut registry = registry.write();
+            registry.register::<u32>();
+            registry.register::<Entity>();
+            registry.register::<Health>();
+            registry.register::<Armor>();
+            registry.register::<Selected>();
+            registry.register::<Score>();
+            registry.register::<Parent>();
+            registry.register::<Children>();
+        }
+        let mut world = World::new();
+        world.insert_resource(registry);
+        world
+    }
+
+    fn component_names(scene: &DynamicScene) -> Vec<Vec<&str>> {
+        scene
+            .entities
+            .iter()
+            .map(|entity| {
+                let mut names: Vec<_> = entity
+                    .components
+                    .iter()
+                    .map(|component| component.type_name())
+                    .collect();
+                names.sort_unstable();
+                names
+            })
+            .collect()
+    }
+
+    #[test]
+    fn allowed_and_denied_components() {
+        let mut world = test_world();
+        let entity = world.spawn().insert(Health(3)).insert(Armor(2)).id();
+
+        let mut builder = DynamicSceneBuilder::from_world(&world);
+        builder.extract_entity(entity).deny::<Armor>();
+        assert_eq!(
+            component_names(&builder.build()),
+            vec![vec![std::any::type_name::<Health>()]]
+        );
+
+        let mut builder = DynamicSceneBuilder::from_world(&world);
+        builder.extract_entity(entity).allow::<Armor>();
+        assert_eq!(
+            component_names(&builder.build()),
+            vec![vec![std::any::type_name::<Armor>()]]
+        );
+
+        // denying an allowed type removes it from the allowed types
+        let mut builder = DynamicSceneBuilder::from_world(&world);
+        builder
+            .extract_entity(entity)
+            .allow::<Armor>()
+            .deny::<Armor>();
+        assert_eq!(component_names(&builder.build()), vec![Vec::<&str>::new()]);
+    }
+
+    #[test]
+    fn resources_are_only_extracted_when_requested() {
+        let mut world = test_world();
+        world.insert_resource(Score(7));
+        world.insert_resource(Selected(Entity::from_raw(0)));
+
+        let scene = DynamicSceneBuilder::from_world(&world).build();
+        assert!(scene.resources.is_empty());
+
+        let mut builder = DynamicSceneBuilder::from_world(&world);
+        builder.extract_resources().deny_resource::<Selected>();
+        let scene = builder.build();
+        assert_eq!(scene.resources.len(), 1);
+        assert_eq!(
+            scene.resources[0].type_name(),
+            std::any::type_name::<Score>()
+        );
+
+        let mut builder = DynamicSceneBuilder::from_world(&world);
+        builder.extract_resources().allow_resource::<Selected>();
+        let scene = builder.build();
+        assert_eq!(scene.resources.len(), 1);
+        assert_eq!(
+            scene.resources[0].type_name(),
+            std::any::type_name::<Selected>()
+        );
+    }
+
+    #[test]
+    fn extract_hierarchy_extracts_the_descendants() {
+        let mut world = test_world();
+        let outside = world.spawn().id();
+        let mut root = Entity::from_raw(0);
+        let mut grandchild = Entity::from_raw(0);
+        world.entity_mut(outside).with_children(|parent| {
+            root = parent
+                .spawn()
+                .with_children(|parent| {
+                    parent.spawn().with_children(|parent| {
+                        grandchild = parent.spawn().insert(Health(1)).id();
+                    });
+                    parent.spawn();
+                })
+                .id();
+        });
+
+        let mut builder = DynamicSceneBuilder::from_world(&world);
+        builder.extract_hierarchy(root).compact_entity_ids(true);
+        let scene = builder.build();
+        assert_eq!(scene.entities.len(), 4);
+        let ids: Vec<_> = scene.entities.iter().map(|entity| entity.entity).collect();
+        assert_eq!(ids, vec![0, 1, 2, 3]);
+        // the parent of the root isn't extracted
+        assert!(!component_names(&scene)[0].contains(&std::any::type_name::<Parent>()));
+        assert!(component_names(&scene)[2].contains(&std::any::type_name::<Health>()));
+
+        let mut spawned = test_world();
+        let mut entity_map = EntityMap::default();
+        scene.write_to_world(&mut spawned, &mut entity_map).unwrap();
+        let root = entity_map.get(Entity::from_raw(0)).unwrap();
+        let grandchild = entity_map.get(Entity::from_raw(2)).unwrap();
+        assert!(spawned.get::<Parent>(root).is_none());
+        assert_eq!(spawned.get::<Children>(root).unwrap().len(), 2);
+        let child = spawned.get::<Parent>(grandchild).unwrap().0;
+        assert_eq!(spawned.get::<Parent>(child).unwrap().0, root);
+    }
+
+    #[test]
+    fn compacted_external_references_do_not_collide() {
+        let mut world = test_world();
+        // the children have the ids 0 and 1 in the world, which the parent gets in the scene
+        let children = [world.spawn().id(), world.spawn().id()];
+        let parent = world.spawn().push_children(&children).id();
+        world.insert_resource(Selected(children[1]));
+
+        let mut builder = DynamicSceneBuilder::from_world(&world);
+        builder
+            .extract_entity(parent)
+            .extract_resources()
+            .allow_resource::<Selected>()
+            .compact_entity_ids(true);
+        let scene = builder.build();
+        assert_eq!(scene.entities[0].entity, 0);
+
+        let children_of = |value: &dyn Reflect| {
+            let mut entities = Vec::new();
+            crate::serde::visit_reflect_mut(&mut *value.clone_value(), &mut |value| match value
+                .downcast_ref::<Entity>(
+            ) {
+                Some(entity) => {
+                    entities.push(entity.id());
+                    false
+                }
+                None => true,
+            });
+            entities
+        };
+        let children = scene.entities[0]
+            .components
+            .iter()
+            .find(|component| component.type_name() == std::any::type_name::<Children>())
+            .unwrap();
+        // the children aren't extracted, so they are numbered after the extracted entities
+        assert_eq!(children_of(&**children), vec![1, 2]);
+        assert_eq!(children_of(&*scene.resources[0]), vec![2]);
+
+        let mut spawned = test_world();
+        let result = scene.write_to_world(&mut spawned, &mut EntityMap::default());
+        assert!(matches!(result, Err(SceneSpawnError::MapEntities(_))));
+    }
+
+    #[test]
+    fn resources_refer_to_the_spawned_entities() {
+        let mut world = test_world();
+        world.spawn().insert(Armor(1));
+        let selected = world.spawn().insert(Health(2)).id();
+        world.insert_resource(Selected(selected));
+
+        let mut builder = DynamicSceneBuilder::from_world(&world);
+        builder
+            .extract_entity(selected)
+            .extract_resources()
+            .compact_entity_ids(true);
+        let scene = builder.build();
+
+        let mut spawned = test_world();
+        spawned.spawn().insert(Armor(3));
+        let mut entity_map = EntityMap::default();
+        scene.write_to_world(&mut spawned, &mut entity_map).unwrap();
+        let selected = spawned.resource::<Selected>().0;
+        assert_eq!(selected, entity_map.get(Entity::from_raw(0)).unwrap());
+        assert_eq!(spawned.get::<Health>(selected).unwrap().0, 2);
+    }
+}
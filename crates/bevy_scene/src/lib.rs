@@ -1,19 +1,36 @@
+mod binary;
 mod bundle;
 mod dynamic_scene;
+mod dynamic_scene_builder;
+mod migration;
+mod prefab;
 mod scene;
 mod scene_loader;
+mod scene_saver;
 mod scene_spawner;
+#[cfg(feature = "json_schema")]
+mod schema;
 pub mod serde;
 
+pub use binary::*;
 pub use bundle::*;
 pub use dynamic_scene::*;
+pub use dynamic_scene_builder::*;
+pub use migration::*;
+pub use prefab::*;
 pub use scene::*;
 pub use scene_loader::*;
+pub use scene_saver::*;
 pub use scene_spawner::*;
+#[cfg(feature = "json_schema")]
+pub use schema::*;
 
 pub mod prelude {
     #[doc(hidden)]
-    pub use crate::{DynamicScene, DynamicSceneBundle, Scene, SceneBundle, SceneSpawner};
+    pub use crate::{
+        DynamicScene, DynamicSceneBuilder, DynamicSceneBundle, Scene, SceneBundle,
+        SceneInstanceReady, SceneSpawner,
+    };
 }
 
 use bevy_app::prelude::*;
@@ -25,10 +42,13 @@ pub struct ScenePlugin;
 
 impl Plugin for ScenePlugin {
     fn build(&self, app: &mut App) {
-        app.add_asset::<DynamicScene>()
+        app.init_resource::<SceneMigrationsArc>()
+            .add_asset::<DynamicScene>()
             .add_asset::<Scene>()
             .init_asset_loader::<SceneLoader>()
+            .init_asset_saver::<SceneSaver>()
             .init_resource::<SceneSpawner>()
+            .add_event::<SceneInstanceReady>()
             .add_system_to_stage(
                 CoreStage::PreUpdate,
                 scene_spawner_system.exclusive_system().at_end(),
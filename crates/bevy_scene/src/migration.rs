@@ -0,0 +1,686 @@
+use crate::{
+    deserialize_binary, is_binary_scene_path,
+    serde::{visit_reflect_mut, SceneDeserializer, SceneSerializer},
+    serialize_binary, DynamicScene, PrefabOverride,
+};
+use anyhow::Result;
+use bevy_reflect::{
+    serde::DeserializeRenames, DynamicStruct, DynamicTupleStruct, Reflect, Struct,
+    TypeRegistration, TypeRegistryArc,
+};
+use parking_lot::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+use serde::de::DeserializeSeed;
+use std::{fmt::Debug, path::Path, sync::Arc};
+
+type MigrationTransform = Box<dyn Fn(&mut dyn Reflect) + Send + Sync>;
+
+/// A change to the reflected values of a type, applied to the scenes saved before it
+enum Migration {
+    RenameType {
+        from: String,
+        to: String,
+    },
+    RenameField {
+        type_name: String,
+        from: String,
+        to: String,
+    },
+    DefaultField {
+        type_name: String,
+        field: String,
+        value: Box<dyn Reflect>,
+    },
+    Transform {
+        type_name: String,
+        transform: MigrationTransform,
+    },
+}
+
+/// The migrations upgrading the components of scenes saved by older versions of an application
+///
+/// Every migration has the scene version it upgrades to: a scene file of version `v` gets every
+/// migration of a version greater than `v` applied when it is deserialized, in the order of their
+/// versions, then in the order they were added. Scenes are saved with the
+/// [`current_version`](SceneMigrations::current_version), so that they aren't migrated again.
+///
+/// Migrations refer to types by the name they have at that point of the sequence, after the
+/// previous renames.
+///
+/// ```
+/// # use bevy_scene::SceneMigrations;
+/// let mut migrations = SceneMigrations::default();
+/// migrations
+///     .rename_type(1, "game::Hp", "game::Health")
+///     .rename_field(1, "game::Health", "value", "current")
+///     .default_field(2, "game::Health", "max", 100.0f32);
+/// assert_eq!(migrations.current_version(), 2);
+/// ```
+#[derive(Default)]
+pub struct SceneMigrations {
+    migrations: Vec<(u32, Migration)>,
+}
+
+impl SceneMigrations {
+    /// The version of the scenes saved with these migrations: the highest version of a migration,
+    /// or `0` if there are none.
+    pub fn current_version(&self) -> u32 {
+        self.migrations
+            .iter()
+            .map(|(version, _)| *version)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Renames the type `from` to `to`.
+    ///
+    /// Scenes are renamed while they are deserialized, so values of a type whose old name isn't
+    /// registered anymore, such as values deserialized with their `ReflectDeserialize` type data,
+    /// are looked up by their new name.
+    pub fn rename_type(
+        &mut self,
+        version: u32,
+        from: impl Into<String>,
+        to: impl Into<String>,
+    ) -> &mut Self {
+        self.add(
+            version,
+            Migration::RenameType {
+                from: from.into(),
+                to: to.into(),
+            },
+        )
+    }
+
+    /// Renames the field `from` of the structs named `type_name` to `to`, before the defaults of
+    /// their missing fields are inserted.
+    pub fn rename_field(
+        &mut self,
+        version: u32,
+        type_name: impl Into<String>,
+        from: impl Into<String>,
+        to: impl Into<String>,
+    ) -> &mut Self {
+        self.add(
+            version,
+            Migration::RenameField {
+                type_name: type_name.into(),
+                from: from.into(),
+                to: to.into(),
+            },
+        )
+    }
+
+    /// Sets the field `field` of the structs named `type_name` to `value` when it is missing.
+    pub fn default_field(
+        &mut self,
+        version: u32,
+        type_name: impl Into<String>,
+        field: impl Into<String>,
+        value: impl Reflect,
+    ) -> &mut Self {
+        self.add(
+            version,
+            Migration::DefaultField {
+                type_name: type_name.into(),
+                field: field.into(),
+                value: Box::new(value),
+            },
+        )
+    }
+
+    /// Calls `transform` on every value named `type_name`.
+    ///
+    /// The values are usually dynamic, such as a [`DynamicStruct`], since they were deserialized
+    /// from a scene file.
+    pub fn transform(
+        &mut self,
+        version: u32,
+        type_name: impl Into<String>,
+        transform: impl Fn(&mut dyn Reflect) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.add(
+            version,
+            Migration::Transform {
+                type_name: type_name.into(),
+                transform: Box::new(transform),
+            },
+        )
+    }
+
+    fn add(&mut self, version: u32, migration: Migration) -> &mut Self {
+        // keep the migrations of a version in the order they were added
+        let index = self
+            .migrations
+            .partition_point(|(other_version, _)| *other_version <= version);
+        self.migrations.insert(index, (version, migration));
+        self
+    }
+
+    /// The migrations applied to a scene file of version `from_version`, in order.
+    fn pending(&self, from_version: u32) -> &[(u32, Migration)] {
+        let start = self
+            .migrations
+            .partition_point(|(version, _)| *version <= from_version);
+        &self.migrations[start..]
+    }
+
+    /// The renames of the migrations applied to a scene file of version `from_version`, for the
+    /// [`ReflectDeserializer`](bevy_reflect::serde::ReflectDeserializer) of its values.
+    pub(crate) fn renames(&self, from_version: u32) -> MigrationRenames<'_> {
+        MigrationRenames {
+            migrations: self.pending(from_version),
+        }
+    }
+
+    /// Upgrades the components of `scene`, deserialized from a scene file of version
+    /// `from_version`, to the current version.
+    pub fn migrate(&self, scene: &mut DynamicScene, from_version: u32) {
+        for (_, migration) in self.pending(from_version) {
+            visit_scene_values(scene, |value| migration.apply(value));
+            for prefab in &mut scene.prefabs {
+                for prefab_override in &mut prefab.overrides {
+                    migration.apply_to_override(prefab_override);
+                }
+            }
+        }
+    }
+
+    /// Upgrades the components of `scene`, deserialized from a scene file of version
+    /// `from_version` with the [`renames`](Self::renames) of its migrations.
+    ///
+    /// The values already have their current names, so the other migrations are applied to the
+    /// current names of the types and fields they refer to.
+    pub(crate) fn migrate_renamed(&self, scene: &mut DynamicScene, from_version: u32) {
+        let pending = self.pending(from_version);
+        for (index, (_, migration)) in pending.iter().enumerate() {
+            let later = MigrationRenames {
+                migrations: &pending[index + 1..],
+            };
+            match migration {
+                Migration::RenameType { .. } | Migration::RenameField { .. } => {}
+                Migration::DefaultField {
+                    type_name,
+                    field,
+                    value: default_value,
+                } => {
+                    let (type_name, field) = later.current_names(type_name, Some(field));
+                    let field = field.unwrap();
+                    visit_scene_values(scene, |value| {
+                        insert_default_field(value, &type_name, &field, &**default_value);
+                    });
+                }
+                Migration::Transform {
+                    type_name,
+                    transform,
+                } => {
+                    let (type_name, _) = later.current_names(type_name, None);
+                    visit_scene_values(scene, |value| {
+                        if value.type_name() == type_name {
+                            transform(value);
+                        }
+                    });
+                }
+            }
+            // the component and field of the overrides are plain strings, renamed in order
+            for prefab in &mut scene.prefabs {
+                for prefab_override in &mut prefab.overrides {
+                    migration.apply_to_override(prefab_override);
+                }
+            }
+        }
+    }
+}
+
+/// Calls `f` with the components, resources and prefab override values of `scene`, and with every
+/// value nested in them.
+fn visit_scene_values(scene: &mut DynamicScene, mut f: impl FnMut(&mut dyn Reflect)) {
+    let mut visit = |value: &mut dyn Reflect| {
+        visit_reflect_mut(value, &mut |value| {
+            f(value);
+            true
+        });
+    };
+    for entity in &mut scene.entities {
+        for component in &mut entity.components {
+            visit(&mut **component);
+        }
+    }
+    for resource in &mut scene.resources {
+        visit(&mut **resource);
+    }
+    for prefab in &mut scene.prefabs {
+        for prefab_override in &mut prefab.overrides {
+            visit(&mut *prefab_override.value);
+        }
+    }
+}
+
+/// The type and field renames of a sequence of migrations, applied while a scene is deserialized
+/// so that its values are looked up in the type registry by their current names.
+pub(crate) struct MigrationRenames<'a> {
+    migrations: &'a [(u32, Migration)],
+}
+
+impl<'a> MigrationRenames<'a> {
+    /// Returns the names of the type `type_name`, and of its field `field`, after every rename.
+    ///
+    /// `type_name` can be the short name of the type, as written in binary scenes.
+    fn current_names(&self, type_name: &str, field: Option<&str>) -> (String, Option<String>) {
+        let mut type_name = type_name.to_string();
+        let mut field = field.map(str::to_string);
+        for (_, migration) in self.migrations {
+            match migration {
+                Migration::RenameType { from, to } => {
+                    if is_name_of(&type_name, from) {
+                        type_name = to.clone();
+                    }
+                }
+                Migration::RenameField {
+                    type_name: renamed_type,
+                    from,
+                    to,
+                } => {
+                    if is_name_of(&type_name, renamed_type) && field.as_ref() == Some(from) {
+                        field = Some(to.clone());
+                    }
+                }
+                Migration::DefaultField { .. } | Migration::Transform { .. } => {}
+            }
+        }
+        (type_name, field)
+    }
+}
+
+/// Returns `true` if `name` is the full or short name of the type `type_name`.
+fn is_name_of(name: &str, type_name: &str) -> bool {
+    name == type_name || name == TypeRegistration::get_short_name(type_name)
+}
+
+impl<'a> DeserializeRenames for MigrationRenames<'a> {
+    fn rename_type(&self, type_name: &str) -> Option<String> {
+        let (current_name, _) = self.current_names(type_name, None);
+        if current_name == type_name {
+            None
+        } else {
+            Some(current_name)
+        }
+    }
+
+    fn rename_field(&self, type_name: &str, field: &str) -> Option<String> {
+        let (_, current_field) = self.current_names(type_name, Some(field));
+        current_field.filter(|current_field| current_field != field)
+    }
+}
+
+impl Migration {
+    /// Updates the component and field a prefab override refers to.
+    fn apply_to_override(&self, prefab_override: &mut PrefabOverride) {
+        match self {
+            Migration::RenameType { from, to } => {
+                if prefab_override.component == *from {
+                    prefab_override.component = to.clone();
+                }
+            }
+            Migration::RenameField {
+                type_name,
+                from,
+                to,
+            } => {
+                if prefab_override.component == *type_name {
+                    let field_path = &prefab_override.field_path;
+                    let field_end = field_path.find(['.', '[']).unwrap_or(field_path.len());
+                    if field_path[..field_end] == **from {
+                        prefab_override.field_path = format!("{}{}", to, &field_path[field_end..]);
+                    }
+                }
+            }
+            Migration::DefaultField { .. } | Migration::Transform { .. } => {}
+        }
+    }
+
+    fn apply(&self, value: &mut dyn Reflect) {
+        match self {
+            Migration::RenameType { from, to } => {
+                if let Some(value) = value.downcast_mut::<DynamicStruct>() {
+                    if value.name() == from {
+                        value.set_name(to.clone());
+                    }
+                } else if let Some(value) = value.downcast_mut::<DynamicTupleStruct>() {
+                    if value.name() == from {
+                        value.set_name(to.clone());
+                    }
+                }
+            }
+            Migration::RenameField {
+                type_name,
+                from,
+                to,
+            } => {
+                if let Some(value) = value.downcast_mut::<DynamicStruct>() {
+                    if value.name() == type_name && value.field(from).is_some() {
+                        // fields can't be removed from a `DynamicStruct`
+                        let mut renamed = DynamicStruct::default();
+                        renamed.set_name(value.name().to_string());
+                        for i in 0..value.field_len() {
+                            let name = value.name_at(i).unwrap();
+                            let name = if name == from { to } else { name };
+                            renamed.insert_boxed(name, value.field_at(i).unwrap().clone_value());
+                        }
+                        *value = renamed;
+                    }
+                }
+            }
+            Migration::DefaultField {
+                type_name,
+                field,
+                value: default_value,
+            } => insert_default_field(value, type_name, field, &**default_value),
+            Migration::Transform {
+                type_name,
+                transform,
+            } => {
+                if value.type_name() == type_name {
+                    transform(value);
+                }
+            }
+        }
+    }
+}
+
+/// Sets the field `field` of `value` to `default_value` if it is a struct named `type_name`
+/// without that field.
+fn insert_default_field(
+    value: &mut dyn Reflect,
+    type_name: &str,
+    field: &str,
+    default_value: &dyn Reflect,
+) {
+    if let Some(value) = value.downcast_mut::<DynamicStruct>() {
+        if value.name() == type_name && value.field(field).is_none() {
+            value.insert_boxed(field, default_value.clone_value());
+        }
+    }
+}
+
+/// A synchronized wrapper around [`SceneMigrations`], shared by the
+/// [`SceneLoader`](crate::SceneLoader) and [`SceneSaver`](crate::SceneSaver).
+///
+/// Migrations are usually added while building the app, before any scene is loaded:
+///
+/// ```
+/// # use bevy_app::App;
+/// # use bevy_scene::SceneMigrationsArc;
+/// # let mut app = App::new();
+/// # app.init_resource::<SceneMigrationsArc>();
+/// app.world
+///     .resource::<SceneMigrationsArc>()
+///     .write()
+///     .rename_type(1, "game::Hp", "game::Health");
+/// ```
+#[derive(Clone, Default)]
+pub struct SceneMigrationsArc {
+    pub internal: Arc<RwLock<SceneMigrations>>,
+}
+
+impl SceneMigrationsArc {
+    pub fn read(&self) -> RwLockReadGuard<'_, SceneMigrations> {
+        self.internal.read()
+    }
+
+    pub fn write(&self) -> RwLockWriteGuard<'_, SceneMigrations> {
+        self.internal.write()
+    }
+}
+
+impl Debug for SceneMigrationsArc {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SceneMigrationsArc")
+            .field("current_version", &self.read().current_version())
+            .finish()
+    }
+}
+
+/// Upgrades the scene file at `path` to the current version of `migrations`, rewriting it in
+/// place, in RON or in the binary scene format depending on its extension.
+///
+/// This is meant to be called by a command-line tool of the application, with the type registry
+/// and migrations the application would load scenes with.
+pub fn upgrade_scene_file(
+    path: impl AsRef<Path>,
+    type_registry: &TypeRegistryArc,
+    migrations: &SceneMigrations,
+) -> Result<()> {
+    let path = path.as_ref();
+    let bytes = std::fs::read(path)?;
+    let scene_deserializer = SceneDeserializer {
+        type_registry: &type_registry.read(),
+        migrations: Some(migrations),
+    };
+    let binary = is_binary_scene_path(path);
+    let scene = if binary {
+        deserialize_binary(&bytes, scene_deserializer)?
+    } else {
+        let mut deserializer = ron::de::Deserializer::from_bytes(&bytes)?;
+        scene_deserializer.deserialize(&mut deserializer)?
+    };
+    let serializer =
+        SceneSerializer::new(&scene, type_registry).with_version(migrations.current_version());
+    if binary {
+        std::fs::write(path, serialize_binary(&serializer)?)?;
+    } else {
+        std::fs::write(path, crate::serialize_ron(serializer)?)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SceneMigrations;
+    use crate::{deserialize_binary, serde::SceneDeserializer, DynamicEntity, DynamicScene};
+    use bevy_reflect::{
+        DynamicStruct, FromReflect, GetField, GetPath, Reflect, ReflectDeserialize,
+        ReflectSerialize, Struct, TypeRegistry, TypeRegistryArc,
+    };
+    use serde::{de::DeserializeSeed, Deserialize, Serialize};
+    use std::any::type_name;
+
+    #[derive(Reflect, FromReflect, Clone, Serialize, Deserialize, PartialEq, Debug)]
+    #[reflect_value(Serialize, Deserialize, PartialEq)]
+    struct Label(String);
+
+    /// The value type saved in the scenes before it was renamed to [`Label`].
+    #[derive(Reflect, FromReflect, Clone, Serialize, Deserialize, PartialEq, Debug)]
+    #[reflect_value(Serialize, Deserialize, PartialEq)]
+    struct OldLabel(String);
+
+    #[derive(Reflect, FromReflect, PartialEq, Debug)]
+    struct Health {
+        current: f32,
+        max: f32,
+        label: Label,
+    }
+
+    const SCENE_V0: &str = r#"(
+  version: 0,
+  entities: [
+    (
+      entity: 0,
+      components: [
+        {
+          "type": "game::Hp",
+          "struct": {
+            "value": {
+              "type": "f32",
+              "value": 5.0,
+            },
+            "label": {
+              "type": "game::OldLabel",
+              "value": ("hero"),
+            },
+          },
+        },
+      ],
+    ),
+  ],
+)"#;
+
+    fn registry() -> TypeRegistry {
+        let mut registry = TypeRegistry::default();
+        registry.register::<f32>();
+        registry.register::<Label>();
+        registry.register::<Health>();
+        registry
+    }
+
+    /// Migrations referring to the types and fields by their names at each point of the sequence.
+    fn migrations() -> SceneMigrations {
+        let mut migrations = SceneMigrations::default();
+        migrations
+            .default_field(1, "game::Hp", "max", 10.0f32)
+            .rename_type(1, "game::Hp", type_name::<Health>())
+            .rename_type(1, "game::OldLabel", type_name::<Label>())
+            .rename_field(2, type_name::<Health>(), "value", "current")
+            .transform(3, type_name::<Health>(), |value| {
+                let current = value.path_mut("current").unwrap();
+                *current.downcast_mut::<f32>().unwrap() *= 2.0;
+            });
+        migrations
+    }
+
+    fn deserialize_ron(scene: &str, migrations: &SceneMigrations) -> DynamicScene {
+        let registry = registry();
+        let mut deserializer = ron::de::Deserializer::from_str(scene).unwrap();
+        SceneDeserializer {
+            type_registry: &registry,
+            migrations: Some(migrations),
+        }
+        .deserialize(&mut deserializer)
+        .unwrap()
+    }
+
+    fn health(scene: &DynamicScene) -> Health {
+        Health::from_reflect(&*scene.entities[0].components[0]).unwrap()
+    }
+
+    #[test]
+    fn migrations_are_applied_when_deserializing() {
+        let scene = deserialize_ron(SCENE_V0, &migrations());
+        assert_eq!(
+            scene.entities[0].components[0].type_name(),
+            type_name::<Health>()
+        );
+        assert_eq!(
+            health(&scene),
+            Health {
+                current: 10.0,
+                max: 10.0,
+                label: Label("hero".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn value_type_renames_are_required() {
+        let registry = registry();
+        let mut deserializer = ron::de::Deserializer::from_str(SCENE_V0).unwrap();
+        let result = SceneDeserializer {
+            type_registry: &registry,
+            migrations: None,
+        }
+        .deserialize(&mut deserializer);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn only_newer_migrations_are_applied() {
+        // a scene saved at version 2 already has the new names, and only gets transformed
+        let scene = SCENE_V0
+            .replace("version: 0", "version: 2")
+            .replace("game::Hp", type_name::<Health>())
+            .replace("game::OldLabel", type_name::<Label>())
+            .replace("\"value\": {", "\"current\": {");
+        let scene = deserialize_ron(&scene, &migrations());
+        let component = scene.entities[0].components[0]
+            .downcast_ref::<DynamicStruct>()
+            .unwrap();
+        assert!(component.field("max").is_none());
+        assert_eq!(component.get_field::<f32>("current").copied(), Some(10.0));
+    }
+
+    #[test]
+    fn default_fields_are_not_overwritten() {
+        let scene = SCENE_V0.replace(
+            "\"label\": {",
+            "\"max\": {\n              \"type\": \"f32\",\n              \"value\": 7.0,\n            },\n            \"label\": {",
+        );
+        let scene = deserialize_ron(&scene, &migrations());
+        assert_eq!(health(&scene).max, 7.0);
+    }
+
+    #[test]
+    fn migrations_are_applied_to_binary_scenes() {
+        // a scene saved while the label was an `OldLabel`
+        let mut old_registry = TypeRegistry::default();
+        old_registry.register::<f32>();
+        old_registry.register::<OldLabel>();
+        let old_registry = TypeRegistryArc {
+            internal: std::sync::Arc::new(parking_lot::RwLock::new(old_registry)),
+        };
+        let mut hp = DynamicStruct::default();
+        hp.set_name("game::Hp".to_string());
+        hp.insert("value", 5.0f32);
+        hp.insert("label", OldLabel("hero".to_string()));
+        let scene = DynamicScene {
+            entities: vec![DynamicEntity {
+                entity: 0,
+                components: vec![Box::new(hp)],
+            }],
+            ..Default::default()
+        };
+        let bytes = scene.serialize_binary(&old_registry).unwrap();
+
+        let mut migrations = migrations();
+        migrations.rename_type(4, type_name::<OldLabel>(), type_name::<Label>());
+        let registry = registry();
+        let scene = deserialize_binary(
+            &bytes,
+            SceneDeserializer {
+                type_registry: &registry,
+                migrations: Some(&migrations),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            health(&scene),
+            Health {
+                current: 10.0,
+                max: 10.0,
+                label: Label("hero".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn migrate_renames_dynamic_values() {
+        let mut hp = DynamicStruct::default();
+        hp.set_name("game::Hp".to_string());
+        hp.insert("value", 5.0f32);
+        hp.insert("label", Label("hero".to_string()));
+        let mut scene = DynamicScene {
+            entities: vec![DynamicEntity {
+                entity: 0,
+                components: vec![Box::new(hp)],
+            }],
+            ..Default::default()
+        };
+        migrations().migrate(&mut scene, 0);
+        assert_eq!(
+            health(&scene),
+            Health {
+                current: 10.0,
+                max: 10.0,
+                label: Label("hero".to_string()),
+            }
+        );
+    }
+}
@@ -0,0 +1,424 @@
+use crate::DynamicScene;
+use bevy_asset::{AssetPath, Handle};
+use bevy_core::Name;
+use bevy_ecs::{entity::Entity, reflect::ReflectComponent, world::World};
+use bevy_hierarchy::Children;
+use bevy_reflect::{DiffChange, GetPath, Reflect, ReflectDiff, TypeRegistryArc};
+use thiserror::Error;
+
+/// An instance of another [`DynamicScene`], the prefab, nested in a scene
+///
+/// The prefab is spawned by the [`SceneSpawner`](crate::SceneSpawner) as children of the
+/// `entity` of the scene, then its [`PrefabOverride`]s are applied. Prefabs can be nested in
+/// prefabs.
+pub struct PrefabInstance {
+    /// The scene entity the prefab is spawned as children of.
+    pub entity: u32,
+    /// The path of the prefab scene.
+    pub path: AssetPath<'static>,
+    /// The handle to the prefab scene at `path`.
+    ///
+    /// It is strong once the scene is loaded by the [`SceneLoader`](crate::SceneLoader).
+    pub handle: Handle<DynamicScene>,
+    /// The changes made to the prefab by this instance.
+    pub overrides: Vec<PrefabOverride>,
+}
+
+impl PrefabInstance {
+    pub fn new(entity: u32, path: AssetPath<'static>, handle: Handle<DynamicScene>) -> Self {
+        PrefabInstance {
+            entity,
+            path,
+            handle,
+            overrides: Vec::new(),
+        }
+    }
+
+    /// Returns a copy of this instance, with its overrides cloned with
+    /// [`PrefabOverride::clone_dynamic`].
+    pub fn clone_dynamic(&self) -> Self {
+        PrefabInstance {
+            entity: self.entity,
+            path: self.path.clone(),
+            handle: self.handle.clone(),
+            overrides: self
+                .overrides
+                .iter()
+                .map(PrefabOverride::clone_dynamic)
+                .collect(),
+        }
+    }
+}
+
+/// A change made to a component of an entity of a prefab by one of its [`PrefabInstance`]s
+///
+/// Only the changed value is stored, so that the other fields of the component keep following
+/// the prefab when it is modified.
+pub struct PrefabOverride {
+    /// The path of the entity in the prefab: the [`Name`]s of the entity and of its ancestors,
+    /// starting from a root entity of the prefab and separated by `/`, such as `"Door/Lock"`.
+    pub entity_path: String,
+    /// The type name of the component.
+    pub component: String,
+    /// The path of the overridden value in the component, as used by [`GetPath`], or an empty
+    /// string to override the whole component.
+    pub field_path: String,
+    /// The value of the override.
+    pub value: Box<dyn Reflect>,
+}
+
+impl PrefabOverride {
+    /// Overrides the field at `field_path` of the component `C` of the entity at `entity_path`.
+    pub fn new<C: Reflect>(
+        entity_path: impl Into<String>,
+        field_path: impl Into<String>,
+        value: impl Reflect,
+    ) -> Self {
+        PrefabOverride {
+            entity_path: entity_path.into(),
+            component: std::any::type_name::<C>().to_string(),
+            field_path: field_path.into(),
+            value: Box::new(value),
+        }
+    }
+
+    pub fn clone_dynamic(&self) -> Self {
+        PrefabOverride {
+            entity_path: self.entity_path.clone(),
+            component: self.component.clone(),
+            field_path: self.field_path.clone(),
+            value: self.value.clone_value(),
+        }
+    }
+
+    /// Returns the overrides turning the component `base` of the entity at `entity_path` of a
+    /// prefab into `edited`, one for each [`Modified`](DiffChange::Modified) value of their
+    /// [`ReflectDiff`].
+    ///
+    /// Overrides only replace values, so lists and maps with inserted or removed elements are
+    /// overridden as a whole.
+    pub fn diff(entity_path: &str, base: &dyn Reflect, edited: &dyn Reflect) -> Vec<Self> {
+        let mut overrides: Vec<Self> = Vec::new();
+        for change in ReflectDiff::new(base, edited).into_changes() {
+            let (field_path, value) = match change {
+                DiffChange::Modified { path, value } => (path, value),
+                change => {
+                    let path = change.path();
+                    if overrides
+                        .last()
+                        .map_or(false, |last| last.field_path == path)
+                    {
+                        continue;
+                    }
+                    // the changes of the elements of the collection are part of its override
+                    overrides.retain(|prefab_override| {
+                        !is_nested_path(&prefab_override.field_path, path)
+                    });
+                    let value = match edited.path(path) {
+                        Ok(value) => value.clone_value(),
+                        Err(_) => continue,
+                    };
+                    (path.to_string(), value)
+                }
+            };
+            overrides.push(PrefabOverride {
+                entity_path: entity_path.to_string(),
+                component: edited.type_name().to_string(),
+                field_path,
+                value,
+            });
+        }
+        overrides
+    }
+
+    /// Applies this override to the entities of a prefab instance, whose root entities are
+    /// `roots`.
+    ///
+    /// Values equal to the override are left untouched, so that they aren't marked as changed.
+    pub fn apply(&self, world: &mut World, roots: &[Entity]) -> Result<(), PrefabOverrideError> {
+        let entity = find_entity_by_path(world, roots, &self.entity_path).ok_or_else(|| {
+            PrefabOverrideError::EntityNotFound {
+                entity_path: self.entity_path.clone(),
+            }
+        })?;
+
+        let registry = world.resource::<TypeRegistryArc>().clone();
+        let registry = registry.read();
+        let registration = registry.get_with_name(&self.component).ok_or_else(|| {
+            PrefabOverrideError::UnregisteredType {
+                type_name: self.component.clone(),
+            }
+        })?;
+        let reflect_component = registration.data::<ReflectComponent>().ok_or_else(|| {
+            PrefabOverrideError::UnregisteredComponent {
+                type_name: self.component.clone(),
+            }
+        })?;
+
+        if self.field_path.is_empty() {
+            match reflect_component.reflect_component(world, entity) {
+                Some(component) => {
+                    if component.reflect_partial_eq(&*self.value) != Some(true) {
+                        reflect_component.apply_component(world, entity, &*self.value);
+                    }
+                }
+                None => reflect_component.add_component(world, entity, &*self.value),
+            }
+            return Ok(());
+        }
+
+        let missing_component = || PrefabOverrideError::MissingComponent {
+            entity_path: self.entity_path.clone(),
+            type_name: self.component.clone(),
+        };
+        let invalid_field_path =
+            |error: bevy_reflect::ReflectPathError| PrefabOverrideError::InvalidFieldPath {
+                field_path: self.field_path.clone(),
+                error: error.to_string(),
+            };
+        let component = reflect_component
+            .reflect_component(world, entity)
+            .ok_or_else(missing_component)?;
+        let field = component
+            .path(&self.field_path)
+            .map_err(invalid_field_path)?;
+        if field.reflect_partial_eq(&*self.value) == Some(true) {
+            return Ok(());
+        }
+        let mut component = reflect_component
+            .reflect_component_mut(world, entity)
+            .ok_or_else(missing_component)?;
+        component
+            .path_mut(&self.field_path)
+            .map_err(invalid_field_path)?
+            .apply(&*self.value);
+        Ok(())
+    }
+}
+
+/// Returns `true` if `path` is the path of a value nested in the value at `parent`.
+fn is_nested_path(path: &str, parent: &str) -> bool {
+    if parent.is_empty() {
+        return !path.is_empty();
+    }
+    path.strip_prefix(parent)
+        .map_or(false, |rest| rest.starts_with(['.', '[']))
+}
+
+/// Finds the entity at `path` from the entities `roots`: `path` is made of the [`Name`]s of the
+/// entity and of its ancestors separated by `/`, starting with the name of one of the `roots`.
+pub fn find_entity_by_path(world: &World, roots: &[Entity], path: &str) -> Option<Entity> {
+    let has_name = |entity: Entity, name: &str| {
+        world
+            .get::<Name>(entity)
+            .map_or(false, |entity_name| entity_name.as_str() == name)
+    };
+    let mut names = path.split('/');
+    let root_name = names.next()?;
+    let mut entity = roots
+        .iter()
+        .copied()
+        .find(|root| has_name(*root, root_name))?;
+    for name in names {
+        entity = world
+            .get::<Children>(entity)?
+            .iter()
+            .copied()
+            .find(|child| has_name(*child, name))?;
+    }
+    Some(entity)
+}
+
+#[derive(Error, Debug)]
+pub enum PrefabOverrideError {
+    #[error("the prefab has no entity at the path `{entity_path}`")]
+    EntityNotFound { entity_path: String },
+    #[error("the prefab override has the unregistered type `{type_name}`. consider registering the type using `app.register_type::<T>()`")]
+    UnregisteredType { type_name: String },
+    #[error("the prefab override has the unregistered component `{type_name}`. consider adding `#[reflect(Component)]` to your type")]
+    UnregisteredComponent { type_name: String },
+    #[error("the prefab entity `{entity_path}` has no component `{type_name}` to override")]
+    MissingComponent {
+        entity_path: String,
+        type_name: String,
+    },
+    #[error("invalid prefab override field path `{field_path}`: {error}")]
+    InvalidFieldPath { field_path: String, error: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PrefabOverride, PrefabOverrideError};
+    use crate::{
+        serde::{SceneDeserializer, SceneSerializer},
+        serialize_ron, DynamicScene, PrefabInstance,
+    };
+    use bevy_asset::{AssetPath, Handle, HandleId};
+    use bevy_core::Name;
+    use bevy_ecs::{
+        component::Component,
+        entity::Entity,
+        reflect::ReflectComponent,
+        world::{EntityMut, World},
+    };
+    use bevy_hierarchy::BuildWorldChildren;
+    use bevy_reflect::{Reflect, TypeRegistryArc};
+    use serde::de::DeserializeSeed;
+
+    #[derive(Component, Reflect, Default, Clone, PartialEq, Debug)]
+    #[reflect(Component)]
+    struct Door {
+        open: bool,
+        code: u32,
+        keys: Vec<u32>,
+    }
+
+    fn registry() -> TypeRegistryArc {
+        let registry = TypeRegistryArc::default();
+        {
+            let mut registry = registry.write();
+            registry.register::<bool>();
+            registry.register::<u32>();
+            registry.register::<Vec<u32>>();
+            registry.register::<String>();
+            registry.register::<Door>();
+            registry.register::<Name>();
+        }
+        registry
+    }
+
+    /// Spawns a `House` root entity with a `Door` child.
+    fn spawn_house(world: &mut World) -> Entity {
+        world
+            .spawn()
+            .insert(Name::new("House"))
+            .with_children(|house| {
+                house
+                    .spawn()
+                    .insert(Name::new("Door"))
+                    .insert(Door::default());
+            })
+            .id()
+    }
+
+    fn door(world: &mut World) -> Door {
+        world.query::<&Door>().single(world).clone()
+    }
+
+    fn door_entity(world: &mut World) -> EntityMut<'_> {
+        let entity = world.query::<(Entity, &Door)>().single(world).0;
+        world.entity_mut(entity)
+    }
+
+    #[test]
+    fn diff_overrides_changed_fields() {
+        let base = Door::default();
+        let edited = Door {
+            open: true,
+            ..Default::default()
+        };
+        let overrides = PrefabOverride::diff("House/Door", &base, &edited);
+        assert_eq!(overrides.len(), 1);
+        assert_eq!(overrides[0].entity_path, "House/Door");
+        assert_eq!(overrides[0].component, std::any::type_name::<Door>());
+        assert_eq!(overrides[0].field_path, "open");
+        assert_eq!(overrides[0].value.downcast_ref::<bool>(), Some(&true));
+
+        assert!(PrefabOverride::diff("House/Door", &base, &base.clone()).is_empty());
+    }
+
+    #[test]
+    fn diff_overrides_resized_lists_as_a_whole() {
+        let base = Door {
+            keys: vec![1, 2],
+            ..Default::default()
+        };
+        let edited = Door {
+            code: 7,
+            keys: vec![3, 2, 4],
+            ..Default::default()
+        };
+        let overrides = PrefabOverride::diff("House/Door", &base, &edited);
+        let field_paths: Vec<_> = overrides
+            .iter()
+            .map(|prefab_override| prefab_override.field_path.as_str())
+            .collect();
+        assert_eq!(field_paths, vec!["code", "keys"]);
+
+        let mut world = World::new();
+        world.insert_resource(registry());
+        let house = spawn_house(&mut world);
+        door_entity(&mut world).insert(base);
+        for prefab_override in &overrides {
+            prefab_override.apply(&mut world, &[house]).unwrap();
+        }
+        assert_eq!(door(&mut world), edited);
+    }
+
+    #[test]
+    fn apply_overrides() {
+        let mut world = World::new();
+        world.insert_resource(registry());
+        let house = spawn_house(&mut world);
+
+        PrefabOverride::new::<Door>("House/Door", "code", 42u32)
+            .apply(&mut world, &[house])
+            .unwrap();
+        assert_eq!(door(&mut world).code, 42);
+
+        // whole components are added to the entities missing them
+        let open_door = Door {
+            open: true,
+            ..Default::default()
+        };
+        PrefabOverride::new::<Door>("House", "", open_door.clone())
+            .apply(&mut world, &[house])
+            .unwrap();
+        assert_eq!(world.get::<Door>(house), Some(&open_door));
+
+        assert!(matches!(
+            PrefabOverride::new::<Door>("House/Window", "code", 1u32).apply(&mut world, &[house]),
+            Err(PrefabOverrideError::EntityNotFound { .. })
+        ));
+        assert!(matches!(
+            PrefabOverride::new::<Door>("House/Door", "lock", 1u32).apply(&mut world, &[house]),
+            Err(PrefabOverrideError::InvalidFieldPath { .. })
+        ));
+    }
+
+    #[test]
+    fn prefab_serde_round_trip() {
+        let registry = registry();
+        let path = AssetPath::from("house.scn.ron").to_owned();
+        let mut prefab =
+            PrefabInstance::new(3, path.clone(), Handle::weak(HandleId::from(path.clone())));
+        prefab
+            .overrides
+            .push(PrefabOverride::new::<Door>("House/Door", "code", 42u32));
+        let scene = DynamicScene {
+            prefabs: vec![prefab],
+            ..Default::default()
+        };
+
+        let ron = serialize_ron(SceneSerializer::new(&scene, &registry)).unwrap();
+        let mut deserializer = ron::de::Deserializer::from_str(&ron).unwrap();
+        let scene = SceneDeserializer {
+            type_registry: &registry.read(),
+            migrations: None,
+        }
+        .deserialize(&mut deserializer)
+        .unwrap();
+
+        assert_eq!(scene.prefabs.len(), 1);
+        let prefab = &scene.prefabs[0];
+        assert_eq!(prefab.entity, 3);
+        assert_eq!(prefab.path.to_string(), path.to_string());
+        assert_eq!(prefab.handle.id, HandleId::from(path));
+        assert_eq!(prefab.overrides.len(), 1);
+        let prefab_override = &prefab.overrides[0];
+        assert_eq!(prefab_override.entity_path, "House/Door");
+        assert_eq!(prefab_override.component, std::any::type_name::<Door>());
+        assert_eq!(prefab_override.field_path, "code");
+        assert_eq!(prefab_override.value.downcast_ref::<u32>(), Some(&42));
+    }
+}
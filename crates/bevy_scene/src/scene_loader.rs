@@ -1,6 +1,10 @@
-use crate::serde::SceneDeserializer;
+use crate::{
+    deserialize_binary, is_binary_scene_path,
+    serde::{asset_paths_to_handles, SceneDeserializer},
+    SceneMigrationsArc, BINARY_SCENE_EXTENSION,
+};
 use anyhow::Result;
-use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy_asset::{AssetLoader, AssetPath, LoadContext, LoadedAsset};
 use bevy_ecs::world::{FromWorld, World};
 use bevy_reflect::TypeRegistryArc;
 use bevy_utils::BoxedFuture;
@@ -9,6 +13,7 @@ use serde::de::DeserializeSeed;
 #[derive(Debug)]
 pub struct SceneLoader {
     type_registry: TypeRegistryArc,
+    migrations: SceneMigrationsArc,
 }
 
 impl FromWorld for SceneLoader {
@@ -16,6 +21,9 @@ impl FromWorld for SceneLoader {
         let type_registry = world.resource::<TypeRegistryArc>();
         SceneLoader {
             type_registry: (*type_registry).clone(),
+            migrations: world
+                .get_resource_or_insert_with(SceneMigrationsArc::default)
+                .clone(),
         }
     }
 }
@@ -27,17 +35,254 @@ impl AssetLoader for SceneLoader {
         load_context: &'a mut LoadContext,
     ) -> BoxedFuture<'a, Result<()>> {
         Box::pin(async move {
-            let mut deserializer = ron::de::Deserializer::from_bytes(bytes)?;
+            let type_registry = self.type_registry.read();
+            let migrations = self.migrations.read();
             let scene_deserializer = SceneDeserializer {
-                type_registry: &*self.type_registry.read(),
+                type_registry: &type_registry,
+                migrations: Some(&migrations),
             };
-            let scene = scene_deserializer.deserialize(&mut deserializer)?;
-            load_context.set_default_asset(LoadedAsset::new(scene));
+            let mut scene = if is_binary_scene_path(load_context.path()) {
+                deserialize_binary(bytes, scene_deserializer)?
+            } else {
+                let mut deserializer = ron::de::Deserializer::from_bytes(bytes)?;
+                scene_deserializer.deserialize(&mut deserializer)?
+            };
+
+            // re-acquire the assets the scene refers to by path
+            let mut asset_paths: Vec<AssetPath<'static>> = Vec::new();
+            let mut add_asset_path = |asset_path: AssetPath<'static>| {
+                let asset_path = load_context.resolve_path(asset_path);
+                if !asset_paths
+                    .iter()
+                    .any(|path| path.get_id() == asset_path.get_id())
+                {
+                    asset_paths.push(asset_path.clone());
+                }
+                asset_path
+            };
+            let prefab_values = scene.prefabs.iter_mut().flat_map(|prefab| {
+                prefab
+                    .overrides
+                    .iter_mut()
+                    .map(|prefab_override| &mut prefab_override.value)
+            });
+            for component in scene
+                .entities
+                .iter_mut()
+                .flat_map(|entity| entity.components.iter_mut())
+                .chain(scene.resources.iter_mut())
+                .chain(prefab_values)
+            {
+                asset_paths_to_handles(&mut **component, &type_registry, &mut add_asset_path);
+            }
+            drop(type_registry);
+            drop(migrations);
+            scene.asset_handles = asset_paths
+                .iter()
+                .map(|asset_path| load_context.get_handle_untyped(asset_path.clone()))
+                .collect();
+
+            // load the prefab scenes along with the scene
+            for prefab in &mut scene.prefabs {
+                prefab.path = load_context.resolve_path(prefab.path.clone());
+                prefab.handle = load_context.get_handle(prefab.path.clone());
+                if !asset_paths
+                    .iter()
+                    .any(|path| path.get_id() == prefab.path.get_id())
+                {
+                    asset_paths.push(prefab.path.clone());
+                }
+            }
+            load_context.set_default_asset(LoadedAsset::new(scene).with_dependencies(asset_paths));
             Ok(())
         })
     }
 
     fn extensions(&self) -> &[&str] {
-        &["scn", "scn.ron"]
+        &["scn", "scn.ron", BINARY_SCENE_EXTENSION]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{serde::SceneSerializer, serialize_ron, DynamicEntity, DynamicScene, ScenePlugin};
+    use anyhow::Result;
+    use bevy_app::App;
+    use bevy_asset::{
+        AddAsset, AssetLoader, AssetPath, AssetPlugin, AssetServer, Assets, Handle, HandleId,
+        LoadContext, LoadState, LoadedAsset, MemoryAssetIo,
+    };
+    use bevy_core::CorePlugin;
+    use bevy_ecs::{component::Component, reflect::ReflectComponent};
+    use bevy_reflect::{DynamicStruct, Reflect, Struct, TypeRegistryArc, TypeUuid};
+    use bevy_utils::BoxedFuture;
+    use futures_lite::future::block_on;
+    use std::path::Path;
+
+    #[derive(Debug, TypeUuid)]
+    #[uuid = "3c6a0f0e-8d3e-4c55-9d5f-2b1c1c7e4a10"]
+    struct Text(String);
+
+    struct TextLoader;
+
+    impl AssetLoader for TextLoader {
+        fn load<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            load_context: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<()>> {
+            Box::pin(async move {
+                let text = String::from_utf8(bytes.to_vec())?;
+                load_context.set_default_asset(LoadedAsset::new(Text(text)));
+                Ok(())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["txt"]
+        }
+    }
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Label {
+        text: Handle<Text>,
+    }
+
+    fn test_app(default_source: MemoryAssetIo) -> App {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .insert_resource(AssetServer::new(default_source))
+            .add_plugin(AssetPlugin)
+            .add_plugin(ScenePlugin)
+            .add_asset::<Text>()
+            .add_asset_loader(TextLoader)
+            .register_type::<Label>();
+        app
+    }
+
+    /// Serializes a scene with a [`Label`] referring to `text` with the asset server of `app`.
+    fn serialize_label_scene(app: &App, text: Handle<Text>) -> String {
+        let scene = DynamicScene {
+            entities: vec![DynamicEntity {
+                entity: 0,
+                components: vec![Label { text }.clone_value()],
+            }],
+            ..Default::default()
+        };
+        let registry = app.world.resource::<TypeRegistryArc>();
+        let asset_server = app.world.resource::<AssetServer>();
+        serialize_ron(SceneSerializer::new(&scene, registry).with_asset_server(asset_server))
+            .unwrap()
+    }
+
+    fn update_until_loaded(app: &mut App, handle: impl Into<HandleId>) {
+        let handle = handle.into();
+        for _ in 0..100 {
+            app.update();
+            if app.world.resource::<AssetServer>().get_load_state(handle) == LoadState::Loaded {
+                return;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        panic!("the asset {:?} wasn't loaded", handle);
+    }
+
+    /// Returns the id of the handle of the [`Label`] of the first entity of `scene`.
+    fn label_handle_id(scene: &DynamicScene) -> HandleId {
+        let label = scene.entities[0].components[0]
+            .downcast_ref::<DynamicStruct>()
+            .unwrap();
+        *label
+            .field("text")
+            .and_then(|text| text.downcast_ref::<DynamicStruct>())
+            .and_then(|text| text.field("id"))
+            .and_then(|id| id.downcast_ref::<HandleId>())
+            .unwrap()
+    }
+
+    #[test]
+    fn handle_paths_are_resolved_in_the_source_of_the_scene() {
+        let default_source = MemoryAssetIo::new();
+        default_source.insert("a.txt", b"default".as_slice());
+        let mut app = test_app(default_source);
+        let asset_server = app.world.resource::<AssetServer>().clone();
+        let scene = serialize_label_scene(&app, asset_server.load("a.txt"));
+
+        let mods = MemoryAssetIo::new();
+        mods.insert("a.txt", b"mods".as_slice());
+        mods.insert("level.scn.ron", scene.into_bytes());
+        asset_server.add_source("mods", mods);
+
+        let scene_handle: Handle<DynamicScene> = asset_server.load("mods://level.scn.ron");
+        update_until_loaded(&mut app, &scene_handle);
+        let mods_text = HandleId::from(AssetPath::from("mods://a.txt"));
+        update_until_loaded(&mut app, mods_text);
+
+        // the handle of the component is the one that was loaded as a dependency of the scene
+        let scenes = app.world.resource::<Assets<DynamicScene>>();
+        let scene = scenes.get(&scene_handle).unwrap();
+        assert_eq!(label_handle_id(scene), mods_text);
+        assert!(scene
+            .asset_handles
+            .iter()
+            .any(|handle| handle.id == mods_text));
+        let texts = app.world.resource::<Assets<Text>>();
+        assert_eq!(texts.get(&Handle::weak(mods_text)).unwrap().0, "mods");
+    }
+
+    #[test]
+    fn handles_are_saved_by_path_and_reacquired_when_loading() {
+        let default_source = MemoryAssetIo::new();
+        default_source.insert("a.txt", b"a".as_slice());
+        let mut app = test_app(default_source);
+        let asset_server = app.world.resource::<AssetServer>().clone();
+        let text: Handle<Text> = asset_server.load("a.txt");
+        let text_id = text.id;
+        let scene = DynamicScene {
+            entities: vec![DynamicEntity {
+                entity: 0,
+                components: vec![Label { text }.clone_value()],
+            }],
+            ..Default::default()
+        };
+        let scene = app.world.resource_mut::<Assets<DynamicScene>>().add(scene);
+
+        // the SceneSaver writes the path of the asset instead of its id
+        asset_server.save(&scene, "saved.scn.ron").unwrap();
+        app.update();
+        let saved = block_on(
+            asset_server
+                .asset_io()
+                .load_path(Path::new("saved.scn.ron")),
+        )
+        .unwrap();
+        let saved_text = std::str::from_utf8(&saved).unwrap();
+        assert!(saved_text.contains(r#""path""#), "{}", saved_text);
+        assert!(saved_text.contains(r#""a.txt""#), "{}", saved_text);
+        assert!(!saved_text.contains("HandleId"), "{}", saved_text);
+
+        // loading a copy re-acquires a strong handle to the asset, and depends on it
+        block_on(
+            asset_server
+                .asset_io()
+                .save_path(Path::new("copy.scn.ron"), &saved),
+        )
+        .unwrap();
+        let copy: Handle<DynamicScene> = asset_server.load("copy.scn.ron");
+        update_until_loaded(&mut app, &copy);
+        let scenes = app.world.resource::<Assets<DynamicScene>>();
+        let copy_scene = scenes.get(&copy).unwrap();
+        assert_eq!(label_handle_id(copy_scene), text_id);
+        let handle = copy_scene
+            .asset_handles
+            .iter()
+            .find(|handle| handle.id == text_id)
+            .unwrap();
+        assert!(handle.is_strong());
+        assert!(asset_server
+            .get_dependency_load_states(&copy)
+            .iter()
+            .any(|(path, _)| path.get_id() == AssetPath::from("a.txt").get_id()));
     }
 }
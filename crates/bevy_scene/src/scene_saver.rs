@@ -0,0 +1,47 @@
+use crate::{
+    is_binary_scene_path, serde::SceneSerializer, serialize_binary, DynamicScene,
+    SceneMigrationsArc, BINARY_SCENE_EXTENSION,
+};
+use anyhow::Result;
+use bevy_asset::{AssetSaver, SaveContext};
+use bevy_ecs::world::{FromWorld, World};
+use bevy_reflect::TypeRegistryArc;
+
+/// Saves [`DynamicScene`]s in the format read by the [`SceneLoader`](crate::SceneLoader): RON, or
+/// the binary scene format for paths ending with [`BINARY_SCENE_EXTENSION`]
+#[derive(Debug)]
+pub struct SceneSaver {
+    type_registry: TypeRegistryArc,
+    migrations: SceneMigrationsArc,
+}
+
+impl FromWorld for SceneSaver {
+    fn from_world(world: &mut World) -> Self {
+        let type_registry = world.resource::<TypeRegistryArc>();
+        SceneSaver {
+            type_registry: (*type_registry).clone(),
+            migrations: world
+                .get_resource_or_insert_with(SceneMigrationsArc::default)
+                .clone(),
+        }
+    }
+}
+
+impl AssetSaver for SceneSaver {
+    type Asset = DynamicScene;
+
+    fn save(&self, scene: &DynamicScene, save_context: &SaveContext) -> Result<Vec<u8>> {
+        let serializer = SceneSerializer::new(scene, &self.type_registry)
+            .with_asset_server(save_context.asset_server())
+            .with_version(self.migrations.read().current_version());
+        if is_binary_scene_path(save_context.path()) {
+            Ok(serialize_binary(&serializer)?)
+        } else {
+            Ok(crate::serialize_ron(serializer)?.into_bytes())
+        }
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["scn", "scn.ron", BINARY_SCENE_EXTENSION]
+    }
+}
@@ -1,7 +1,9 @@
-use crate::{DynamicScene, Scene};
+use crate::{
+    find_entity_by_path, DynamicEntity, DynamicScene, PrefabOverride, PrefabOverrideError, Scene,
+};
 use bevy_asset::{AssetEvent, Assets, Handle};
 use bevy_ecs::{
-    entity::{Entity, EntityMap},
+    entity::{Entity, EntityMap, MapEntitiesError},
     event::{Events, ManualEventReader},
     reflect::{ReflectComponent, ReflectMapEntities},
     system::Command,
@@ -9,7 +11,10 @@ use bevy_ecs::{
 };
 use bevy_hierarchy::{AddChild, Parent};
 use bevy_reflect::TypeRegistryArc;
-use bevy_utils::{tracing::error, HashMap};
+use bevy_utils::{
+    tracing::{error, warn},
+    HashMap, HashSet,
+};
 use thiserror::Error;
 use uuid::Uuid;
 
@@ -18,26 +23,74 @@ struct InstanceInfo {
     entity_map: EntityMap,
 }
 
+/// A prefab spawned as part of an instance of a dynamic scene
+struct PrefabInstanceInfo {
+    /// The instance of the scene the prefab is nested in.
+    owner: InstanceId,
+    /// The entity of the owner scene the prefab is spawned as children of.
+    scene_entity: u32,
+    parent: Entity,
+    handle: Handle<DynamicScene>,
+    overrides: Vec<PrefabOverride>,
+}
+
+/// How deep prefabs can be nested, to stop prefabs nested in themselves.
+const MAX_PREFAB_DEPTH: usize = 32;
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub struct InstanceId(Uuid);
 
+/// Event sent by the [`SceneSpawner`] once an instance spawned with one of its `spawn*` methods is
+/// spawned, parented, and its prefabs that were loaded are spawned too
+#[derive(Debug, Clone)]
+pub struct SceneInstanceReady {
+    /// The entity the instance was spawned as children of, if any.
+    pub parent: Option<Entity>,
+    pub instance_id: InstanceId,
+}
+
 impl InstanceId {
     fn new() -> Self {
         InstanceId(Uuid::new_v4())
     }
 }
 
+/// How the [`SceneSpawner`] updates the instances of a [`DynamicScene`] when it is modified, for
+/// example when it is hot reloaded
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum SceneReloadMode {
+    /// Writes every component of the scene again with [`DynamicScene::write_to_world`].
+    Reapply,
+    /// Only applies the changes between the previous and the new version of the scene, with
+    /// [`DynamicScene::patch_world`]: components that didn't change keep their runtime state,
+    /// and entities removed from the scene are despawned.
+    Patch,
+}
+
+impl Default for SceneReloadMode {
+    fn default() -> Self {
+        SceneReloadMode::Reapply
+    }
+}
+
 #[derive(Default)]
 pub struct SceneSpawner {
     spawned_scenes: HashMap<Handle<Scene>, Vec<InstanceId>>,
     spawned_dynamic_scenes: HashMap<Handle<DynamicScene>, Vec<InstanceId>>,
+    reload_mode: SceneReloadMode,
+    /// The entities of the spawned dynamic scenes, as they were last written to their instances,
+    /// to find the changes of the scenes in [`SceneReloadMode::Patch`].
+    dynamic_scene_snapshots: HashMap<Handle<DynamicScene>, Vec<DynamicEntity>>,
     spawned_instances: HashMap<InstanceId, InstanceInfo>,
+    prefab_instances: HashMap<InstanceId, PrefabInstanceInfo>,
+    prefab_overrides_to_apply: HashSet<InstanceId>,
     scene_asset_event_reader: ManualEventReader<AssetEvent<DynamicScene>>,
     dynamic_scenes_to_spawn: Vec<(Handle<DynamicScene>, InstanceId)>,
     scenes_to_spawn: Vec<(Handle<Scene>, InstanceId)>,
     scenes_to_despawn: Vec<Handle<DynamicScene>>,
     instances_to_despawn: Vec<InstanceId>,
     scenes_with_parent: Vec<(InstanceId, Entity)>,
+    instances_ready: Vec<SceneInstanceReady>,
 }
 
 #[derive(Error, Debug)]
@@ -46,13 +99,26 @@ pub enum SceneSpawnError {
     UnregisteredComponent { type_name: String },
     #[error("scene contains the unregistered type `{type_name}`. consider registering the type using `app.register_type::<T>()`")]
     UnregisteredType { type_name: String },
+    #[error("scene contains the unregistered resource `{type_name}`. consider adding `#[reflect(Resource)]` to your type")]
+    UnregisteredResource { type_name: String },
     #[error("scene does not exist")]
     NonExistentScene { handle: Handle<DynamicScene> },
     #[error("scene does not exist")]
     NonExistentRealScene { handle: Handle<Scene> },
+    #[error("scene contains an entity reference that can't be mapped: {0}")]
+    MapEntities(#[from] MapEntitiesError),
 }
 
 impl SceneSpawner {
+    pub fn reload_mode(&self) -> SceneReloadMode {
+        self.reload_mode
+    }
+
+    /// Sets how the instances of modified dynamic scenes are updated.
+    pub fn set_reload_mode(&mut self, reload_mode: SceneReloadMode) {
+        self.reload_mode = reload_mode;
+    }
+
     pub fn spawn_dynamic(&mut self, scene_handle: Handle<DynamicScene>) {
         let instance_id = InstanceId::new();
         self.dynamic_scenes_to_spawn
@@ -102,6 +168,7 @@ impl SceneSpawner {
                 self.despawn_instance_sync(world, &instance_id);
             }
         }
+        self.dynamic_scene_snapshots.remove(&scene_handle);
         Ok(())
     }
 
@@ -111,15 +178,33 @@ impl SceneSpawner {
                 let _ = world.despawn(entity);
             }
         }
+        self.prefab_instances.remove(instance_id);
+
+        let nested_prefabs: Vec<_> = self
+            .prefab_instances
+            .iter()
+            .filter(|(_, prefab)| prefab.owner == *instance_id)
+            .map(|(nested_id, _)| *nested_id)
+            .collect();
+        for nested_id in nested_prefabs {
+            self.dynamic_scenes_to_spawn
+                .retain(|(_, queued_id)| *queued_id != nested_id);
+            self.despawn_instance_sync(world, &nested_id);
+        }
     }
 
+    /// Spawns an instance of the scene right away.
+    ///
+    /// Its [`SceneInstanceReady`] event is sent by the next [`scene_spawner_system`], once its
+    /// prefabs are spawned.
     pub fn spawn_dynamic_sync(
         &mut self,
         world: &mut World,
         scene_handle: &Handle<DynamicScene>,
-    ) -> Result<(), SceneSpawnError> {
+    ) -> Result<InstanceId, SceneSpawnError> {
         let mut entity_map = EntityMap::default();
         Self::spawn_dynamic_internal(world, scene_handle, &mut entity_map)?;
+        self.snapshot_dynamic_scene(world, scene_handle);
         let instance_id = InstanceId::new();
         self.spawned_instances
             .insert(instance_id, InstanceInfo { entity_map });
@@ -128,7 +213,9 @@ impl SceneSpawner {
             .entry(scene_handle.clone())
             .or_insert_with(Vec::new);
         spawned.push(instance_id);
-        Ok(())
+        self.sync_prefabs(world, instance_id, scene_handle);
+        self.queue_instance_ready(instance_id);
+        Ok(instance_id)
     }
 
     fn spawn_dynamic_internal(
@@ -147,12 +234,231 @@ impl SceneSpawner {
         })
     }
 
+    /// Keeps a copy of the entities of the dynamic scene, if it is not kept yet, to patch its
+    /// instances when it is modified.
+    ///
+    /// The copy is kept whatever the reload mode, so that the reload mode can be changed after
+    /// spawning scenes.
+    fn snapshot_dynamic_scene(&mut self, world: &World, scene_handle: &Handle<DynamicScene>) {
+        if self.dynamic_scene_snapshots.contains_key(scene_handle) {
+            return;
+        }
+        if let Some(scene) = world.resource::<Assets<DynamicScene>>().get(scene_handle) {
+            self.replace_snapshot(scene_handle, scene);
+        }
+    }
+
+    fn replace_snapshot(&mut self, scene_handle: &Handle<DynamicScene>, scene: &DynamicScene) {
+        self.dynamic_scene_snapshots.insert(
+            scene_handle.clone_weak(),
+            scene
+                .entities
+                .iter()
+                .map(DynamicEntity::clone_dynamic)
+                .collect(),
+        );
+    }
+
+    /// Queues the spawn of the prefabs of a spawned instance of a dynamic scene, and the despawn
+    /// of the prefabs that are not part of the scene anymore.
+    ///
+    /// The overrides of the prefabs that are already spawned are replaced by those of the scene.
+    fn sync_prefabs(
+        &mut self,
+        world: &mut World,
+        instance_id: InstanceId,
+        scene_handle: &Handle<DynamicScene>,
+    ) {
+        let (scene, instance) = match (
+            world.resource::<Assets<DynamicScene>>().get(scene_handle),
+            self.spawned_instances.get(&instance_id),
+        ) {
+            (Some(scene), Some(instance)) => (scene, instance),
+            _ => return,
+        };
+
+        let mut removed_prefabs: Vec<_> = self
+            .prefab_instances
+            .iter()
+            .filter(|(_, prefab)| prefab.owner == instance_id)
+            .map(|(prefab_id, _)| *prefab_id)
+            .collect();
+        let mut new_prefabs = Vec::new();
+        for prefab in &scene.prefabs {
+            let parent = match instance.entity_map.get(Entity::from_raw(prefab.entity)) {
+                Ok(parent) => parent,
+                Err(_) => continue,
+            };
+            let overrides = prefab
+                .overrides
+                .iter()
+                .map(PrefabOverride::clone_dynamic)
+                .collect();
+            let spawned_prefab = removed_prefabs.iter().position(|prefab_id| {
+                let spawned_prefab = &self.prefab_instances[prefab_id];
+                spawned_prefab.scene_entity == prefab.entity
+                    && spawned_prefab.handle == prefab.handle
+            });
+            if let Some(index) = spawned_prefab {
+                let prefab_id = removed_prefabs.swap_remove(index);
+                let spawned_prefab = self.prefab_instances.get_mut(&prefab_id).unwrap();
+                spawned_prefab.parent = parent;
+                spawned_prefab.overrides = overrides;
+                self.prefab_overrides_to_apply.insert(prefab_id);
+            } else {
+                new_prefabs.push(PrefabInstanceInfo {
+                    owner: instance_id,
+                    scene_entity: prefab.entity,
+                    parent,
+                    handle: prefab.handle.clone(),
+                    overrides,
+                });
+            }
+        }
+
+        for prefab_id in removed_prefabs {
+            self.despawn_instance_sync(world, &prefab_id);
+        }
+        if !new_prefabs.is_empty() && self.prefab_depth(instance_id) >= MAX_PREFAB_DEPTH {
+            error!(
+                "prefabs are nested more than {} times, is a prefab nested in itself?",
+                MAX_PREFAB_DEPTH
+            );
+            return;
+        }
+        for prefab in new_prefabs {
+            let prefab_id = self.spawn_dynamic_as_child(prefab.handle.clone(), prefab.parent);
+            self.prefab_instances.insert(prefab_id, prefab);
+        }
+    }
+
+    /// The number of prefabs the instance is nested in.
+    fn prefab_depth(&self, mut instance_id: InstanceId) -> usize {
+        let mut depth = 0;
+        while let Some(prefab) = self.prefab_instances.get(&instance_id) {
+            depth += 1;
+            instance_id = prefab.owner;
+        }
+        depth
+    }
+
+    /// Queues the overrides of the prefabs the instance is part of to be applied again, since
+    /// they may refer to its entities.
+    fn reapply_prefab_overrides(&mut self, mut instance_id: InstanceId) {
+        while let Some(prefab) = self.prefab_instances.get(&instance_id) {
+            self.prefab_overrides_to_apply.insert(instance_id);
+            instance_id = prefab.owner;
+        }
+    }
+
+    /// Applies the overrides of the prefabs spawned or modified since the last call.
+    pub fn apply_prefab_overrides(&mut self, world: &mut World) {
+        let prefab_ids = std::mem::take(&mut self.prefab_overrides_to_apply);
+
+        for prefab_id in prefab_ids {
+            // the overrides of prefabs not spawned yet are applied once they are
+            let (prefab, instance) = match (
+                self.prefab_instances.get(&prefab_id),
+                self.spawned_instances.get(&prefab_id),
+            ) {
+                (Some(prefab), Some(instance)) => (prefab, instance),
+                _ => continue,
+            };
+            let entities: Vec<_> = instance.entity_map.values().collect();
+            let roots: Vec<_> = entities
+                .iter()
+                .copied()
+                .filter(|entity| {
+                    world
+                        .get::<Parent>(*entity)
+                        .map_or(true, |parent| !entities.contains(&parent.0))
+                })
+                .collect();
+            for prefab_override in &prefab.overrides {
+                match prefab_override.apply(world, &roots) {
+                    Ok(()) => {}
+                    // the entity can be part of a nested prefab that isn't spawned yet
+                    Err(PrefabOverrideError::EntityNotFound { .. })
+                        if self.has_queued_prefabs(prefab_id) => {}
+                    Err(err) => warn!("{}", err),
+                }
+            }
+        }
+    }
+
+    /// Returns `true` if a prefab nested in the instance is waiting for its scene to be loaded.
+    fn has_queued_prefabs(&self, instance_id: InstanceId) -> bool {
+        self.dynamic_scenes_to_spawn
+            .iter()
+            .any(|(_, mut queued_id)| {
+                while let Some(prefab) = self.prefab_instances.get(&queued_id) {
+                    if prefab.owner == instance_id {
+                        return true;
+                    }
+                    queued_id = prefab.owner;
+                }
+                false
+            })
+    }
+
+    /// Returns the instances of the prefabs nested in the instance, with the entity they are
+    /// spawned as children of.
+    pub fn iter_prefab_instances(
+        &'_ self,
+        instance_id: InstanceId,
+    ) -> impl Iterator<Item = (Entity, InstanceId)> + '_ {
+        self.prefab_instances
+            .iter()
+            .filter(move |(_, prefab)| prefab.owner == instance_id)
+            .map(|(prefab_id, prefab)| (prefab.parent, *prefab_id))
+    }
+
+    /// Returns the overrides of a prefab instance, or `None` if it isn't a prefab instance.
+    pub fn prefab_overrides(&self, instance_id: InstanceId) -> Option<&[PrefabOverride]> {
+        self.prefab_instances
+            .get(&instance_id)
+            .map(|prefab| &prefab.overrides[..])
+    }
+
+    /// Adds an override to a prefab instance, replacing its override of the same entity,
+    /// component and field if any. Returns `false` if it isn't a prefab instance.
+    ///
+    /// The override is kept when the prefab scene is modified, but is replaced by the overrides
+    /// of the scene the prefab is nested in when that scene is modified: save it in that scene to
+    /// keep it.
+    pub fn set_prefab_override(
+        &mut self,
+        instance_id: InstanceId,
+        prefab_override: PrefabOverride,
+    ) -> bool {
+        let prefab = match self.prefab_instances.get_mut(&instance_id) {
+            Some(prefab) => prefab,
+            None => return false,
+        };
+        let existing = prefab.overrides.iter_mut().find(|existing| {
+            existing.entity_path == prefab_override.entity_path
+                && existing.component == prefab_override.component
+                && existing.field_path == prefab_override.field_path
+        });
+        match existing {
+            Some(existing) => *existing = prefab_override,
+            None => prefab.overrides.push(prefab_override),
+        }
+        self.prefab_overrides_to_apply.insert(instance_id);
+        true
+    }
+
+    /// Spawns an instance of the scene right away.
+    ///
+    /// Its [`SceneInstanceReady`] event is sent by the next [`scene_spawner_system`].
     pub fn spawn_sync(
         &mut self,
         world: &mut World,
         scene_handle: Handle<Scene>,
     ) -> Result<InstanceId, SceneSpawnError> {
-        self.spawn_sync_internal(world, scene_handle, InstanceId::new())
+        let instance_id = self.spawn_sync_internal(world, scene_handle, InstanceId::new())?;
+        self.queue_instance_ready(instance_id);
+        Ok(instance_id)
     }
 
     fn spawn_sync_internal(
@@ -230,22 +536,76 @@ impl SceneSpawner {
         world: &mut World,
         scene_handles: &[Handle<DynamicScene>],
     ) -> Result<(), SceneSpawnError> {
-        for scene_handle in scene_handles {
-            if let Some(spawned_instances) = self.spawned_dynamic_scenes.get(scene_handle) {
-                for instance_id in spawned_instances.iter() {
-                    if let Some(instance_info) = self.spawned_instances.get_mut(instance_id) {
-                        Self::spawn_dynamic_internal(
-                            world,
-                            scene_handle,
-                            &mut instance_info.entity_map,
-                        )?;
+        if self.reload_mode == SceneReloadMode::Patch {
+            self.patch_spawned_scenes(world, scene_handles)?;
+        } else {
+            for scene_handle in scene_handles {
+                if let Some(spawned_instances) = self.spawned_dynamic_scenes.get(scene_handle) {
+                    for instance_id in spawned_instances.iter() {
+                        if let Some(instance_info) = self.spawned_instances.get_mut(instance_id) {
+                            Self::spawn_dynamic_internal(
+                                world,
+                                scene_handle,
+                                &mut instance_info.entity_map,
+                            )?;
+                        }
                     }
                 }
+                if let Some(scene) = world.resource::<Assets<DynamicScene>>().get(scene_handle) {
+                    self.replace_snapshot(scene_handle, scene);
+                }
+            }
+        }
+
+        // the prefabs of the scenes may have changed, and their overrides may have been
+        // overwritten
+        for scene_handle in scene_handles {
+            let instance_ids = self
+                .spawned_dynamic_scenes
+                .get(scene_handle)
+                .cloned()
+                .unwrap_or_default();
+            for instance_id in instance_ids {
+                self.sync_prefabs(world, instance_id, scene_handle);
+                self.reapply_prefab_overrides(instance_id);
             }
         }
         Ok(())
     }
 
+    fn patch_spawned_scenes(
+        &mut self,
+        world: &mut World,
+        scene_handles: &[Handle<DynamicScene>],
+    ) -> Result<(), SceneSpawnError> {
+        world.resource_scope(|world, scenes: Mut<Assets<DynamicScene>>| {
+            for scene_handle in scene_handles {
+                let scene =
+                    scenes
+                        .get(scene_handle)
+                        .ok_or_else(|| SceneSpawnError::NonExistentScene {
+                            handle: scene_handle.clone_weak(),
+                        })?;
+                // the snapshot is only replaced once every instance is patched, so that a failed
+                // patch is retried from the same version of the scene
+                let previous = self
+                    .dynamic_scene_snapshots
+                    .get(scene_handle)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                if let Some(spawned_instances) = self.spawned_dynamic_scenes.get(scene_handle) {
+                    for instance_id in spawned_instances.iter() {
+                        if let Some(instance_info) = self.spawned_instances.get_mut(instance_id) {
+                            scene.patch_world(previous, world, &mut instance_info.entity_map)?;
+                        }
+                    }
+                }
+                self.replace_snapshot(scene_handle, scene);
+            }
+            Ok(())
+        })
+    }
+
     pub fn despawn_queued_scenes(&mut self, world: &mut World) -> Result<(), SceneSpawnError> {
         let scenes_to_despawn = std::mem::take(&mut self.scenes_to_despawn);
 
@@ -264,26 +624,40 @@ impl SceneSpawner {
     }
 
     pub fn spawn_queued_scenes(&mut self, world: &mut World) -> Result<(), SceneSpawnError> {
-        let scenes_to_spawn = std::mem::take(&mut self.dynamic_scenes_to_spawn);
+        // spawning a scene queues the spawn of its prefabs, which are spawned right away if
+        // they are loaded
+        loop {
+            let scenes_to_spawn = std::mem::take(&mut self.dynamic_scenes_to_spawn);
+            let mut spawned_any = false;
 
-        for (scene_handle, instance_id) in scenes_to_spawn {
-            let mut entity_map = EntityMap::default();
-
-            match Self::spawn_dynamic_internal(world, &scene_handle, &mut entity_map) {
-                Ok(_) => {
-                    self.spawned_instances
-                        .insert(instance_id, InstanceInfo { entity_map });
-                    let spawned = self
-                        .spawned_dynamic_scenes
-                        .entry(scene_handle.clone())
-                        .or_insert_with(Vec::new);
-                    spawned.push(instance_id);
-                }
-                Err(SceneSpawnError::NonExistentScene { .. }) => {
-                    self.dynamic_scenes_to_spawn
-                        .push((scene_handle, instance_id));
+            for (scene_handle, instance_id) in scenes_to_spawn {
+                let mut entity_map = EntityMap::default();
+
+                match Self::spawn_dynamic_internal(world, &scene_handle, &mut entity_map) {
+                    Ok(_) => {
+                        self.snapshot_dynamic_scene(world, &scene_handle);
+                        self.spawned_instances
+                            .insert(instance_id, InstanceInfo { entity_map });
+                        let spawned = self
+                            .spawned_dynamic_scenes
+                            .entry(scene_handle.clone())
+                            .or_insert_with(Vec::new);
+                        spawned.push(instance_id);
+                        self.sync_prefabs(world, instance_id, &scene_handle);
+                        self.reapply_prefab_overrides(instance_id);
+                        self.queue_instance_ready(instance_id);
+                        spawned_any = true;
+                    }
+                    Err(SceneSpawnError::NonExistentScene { .. }) => {
+                        self.dynamic_scenes_to_spawn
+                            .push((scene_handle, instance_id));
+                    }
+                    Err(err) => return Err(err),
                 }
-                Err(err) => return Err(err),
+            }
+
+            if !spawned_any {
+                break;
             }
         }
 
@@ -291,7 +665,7 @@ impl SceneSpawner {
 
         for (scene_handle, instance_id) in scenes_to_spawn {
             match self.spawn_sync_internal(world, scene_handle, instance_id) {
-                Ok(_) => {}
+                Ok(_) => self.queue_instance_ready(instance_id),
                 Err(SceneSpawnError::NonExistentRealScene { handle }) => {
                     self.scenes_to_spawn.push((handle, instance_id));
                 }
@@ -302,6 +676,33 @@ impl SceneSpawner {
         Ok(())
     }
 
+    fn queue_instance_ready(&mut self, instance_id: InstanceId) {
+        let parent = self
+            .scenes_with_parent
+            .iter()
+            .find(|(parent_instance_id, _)| *parent_instance_id == instance_id)
+            .map(|(_, parent)| *parent);
+        self.instances_ready.push(SceneInstanceReady {
+            parent,
+            instance_id,
+        });
+    }
+
+    /// Sends a [`SceneInstanceReady`] event for each instance spawned since the last call.
+    pub fn send_instance_ready_events(&mut self, world: &mut World) {
+        let instances_ready = std::mem::take(&mut self.instances_ready);
+        let mut events = world.resource_mut::<Events<SceneInstanceReady>>();
+        for instance_ready in instances_ready {
+            // the instance may have been despawned by then
+            if self
+                .spawned_instances
+                .contains_key(&instance_ready.instance_id)
+            {
+                events.send(instance_ready);
+            }
+        }
+    }
+
     pub(crate) fn set_scene_instance_parent_sync(&mut self, world: &mut World) {
         let scenes_with_parent = std::mem::take(&mut self.scenes_with_parent);
 
@@ -346,6 +747,32 @@ impl SceneSpawner {
             .get(&instance_id)
             .map(|instance| instance.entity_map.values())
     }
+
+    /// Finds the entity of a spawned instance at `path`, such as `"Armature/Hand.L"`: the
+    /// [`Name`](bevy_core::Name)s of the entity and of its ancestors separated by `/`, starting
+    /// with the name of a root entity of the instance.
+    ///
+    /// The entities of the prefabs nested in the instance can be found too, since they are
+    /// spawned as children of its entities.
+    pub fn instance_entity_by_path(
+        &self,
+        world: &World,
+        instance_id: InstanceId,
+        path: &str,
+    ) -> Option<Entity> {
+        let instance = self.spawned_instances.get(&instance_id)?;
+        let entities: HashSet<Entity> = instance.entity_map.values().collect();
+        let roots: Vec<Entity> = entities
+            .iter()
+            .copied()
+            .filter(|entity| {
+                world
+                    .get::<Parent>(*entity)
+                    .map_or(true, |parent| !entities.contains(&parent.0))
+            })
+            .collect();
+        find_entity_by_path(world, &roots, path)
+    }
 }
 
 pub fn scene_spawner_system(world: &mut World) {
@@ -374,5 +801,370 @@ pub fn scene_spawner_system(world: &mut World) {
             .update_spawned_scenes(world, &updated_spawned_scenes)
             .unwrap();
         scene_spawner.set_scene_instance_parent_sync(world);
+        scene_spawner.apply_prefab_overrides(world);
+        scene_spawner.send_instance_ready_events(world);
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{scene_spawner_system, InstanceId, SceneInstanceReady, MAX_PREFAB_DEPTH};
+    use crate::{
+        DynamicEntity, DynamicScene, PrefabInstance, PrefabOverride, Scene, ScenePlugin,
+        SceneReloadMode, SceneSpawner,
+    };
+    use bevy_app::App;
+    use bevy_asset::{AssetPlugin, Assets, Handle, HandleId};
+    use bevy_core::Name;
+    use bevy_ecs::{
+        component::Component,
+        entity::Entity,
+        event::Events,
+        reflect::ReflectComponent,
+        world::{Mut, World},
+    };
+    use bevy_hierarchy::{BuildWorldChildren, Children, Parent};
+    use bevy_reflect::{Reflect, TypeRegistryArc};
+    use bevy_utils::HashMap;
+
+    /// The id of the scene entity an entity was spawned from.
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Id(u32);
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Value(u32);
+
+    /// A component that isn't registered.
+    #[derive(Reflect, Default)]
+    struct Unregistered;
+
+    fn app(reload_mode: SceneReloadMode) -> App {
+        let mut app = App::new();
+        app.add_plugin(AssetPlugin)
+            .add_plugin(ScenePlugin)
+            .register_type::<Id>()
+            .register_type::<Value>()
+            .register_type::<Name>();
+        app.world
+            .resource_mut::<SceneSpawner>()
+            .set_reload_mode(reload_mode);
+        app
+    }
+
+    /// A scene with an entity for each `(id, value)`.
+    fn scene(entities: &[(u32, u32)]) -> DynamicScene {
+        DynamicScene {
+            entities: entities
+                .iter()
+                .map(|(id, value)| DynamicEntity {
+                    entity: *id,
+                    components: vec![Box::new(Id(*id)), Box::new(Value(*value))],
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    /// The `(id, value)` of the spawned entities, sorted by id.
+    fn entities(world: &mut World) -> Vec<(u32, u32)> {
+        let mut entities: Vec<_> = world
+            .query::<(&Id, &Value)>()
+            .iter(world)
+            .map(|(id, value)| (id.0, value.0))
+            .collect();
+        entities.sort_unstable();
+        entities
+    }
+
+    fn set_value(world: &mut World, id: u32, value: u32) {
+        for (entity_id, mut entity_value) in world.query::<(&Id, &mut Value)>().iter_mut(world) {
+            if entity_id.0 == id {
+                entity_value.0 = value;
+            }
+        }
+    }
+
+    fn spawn(world: &mut World, scene: DynamicScene) -> Handle<DynamicScene> {
+        let handle = world.resource_mut::<Assets<DynamicScene>>().add(scene);
+        world.resource_scope(|world, mut spawner: Mut<SceneSpawner>| {
+            spawner.spawn_dynamic_sync(world, &handle).unwrap();
+        });
+        handle
+    }
+
+    fn update(
+        world: &mut World,
+        handle: &Handle<DynamicScene>,
+        scene: DynamicScene,
+    ) -> Result<(), crate::SceneSpawnError> {
+        world
+            .resource_mut::<Assets<DynamicScene>>()
+            .set_untracked(handle, scene);
+        world.resource_scope(|world, mut spawner: Mut<SceneSpawner>| {
+            spawner.update_spawned_scenes(world, &[handle.clone_weak()])
+        })
+    }
+
+    #[test]
+    fn patch_adds_changes_and_removes_entities() {
+        let mut app = app(SceneReloadMode::Patch);
+        let world = &mut app.world;
+        let handle = spawn(world, scene(&[(0, 0), (1, 1), (2, 2)]));
+        // runtime changes of the components the scene doesn't change are kept
+        set_value(world, 0, 10);
+        set_value(world, 1, 11);
+
+        update(world, &handle, scene(&[(0, 0), (1, 5), (3, 3)])).unwrap();
+        assert_eq!(entities(world), vec![(0, 10), (1, 5), (3, 3)]);
+    }
+
+    #[test]
+    fn reapply_writes_every_component() {
+        let mut app = app(SceneReloadMode::Reapply);
+        let world = &mut app.world;
+        let handle = spawn(world, scene(&[(0, 0), (1, 1), (2, 2)]));
+        set_value(world, 0, 10);
+        set_value(world, 1, 11);
+
+        update(world, &handle, scene(&[(0, 0), (1, 5), (3, 3)])).unwrap();
+        // entities removed from the scene are kept
+        assert_eq!(entities(world), vec![(0, 0), (1, 5), (2, 2), (3, 3)]);
+    }
+
+    #[test]
+    fn patch_after_changing_the_reload_mode() {
+        let mut app = app(SceneReloadMode::Reapply);
+        let world = &mut app.world;
+        let handle = spawn(world, scene(&[(0, 0), (1, 1)]));
+        world
+            .resource_mut::<SceneSpawner>()
+            .set_reload_mode(SceneReloadMode::Patch);
+
+        update(world, &handle, scene(&[(0, 2)])).unwrap();
+        assert_eq!(entities(world), vec![(0, 2)]);
+    }
+
+    #[test]
+    fn failed_patch_keeps_the_previous_scene() {
+        let mut app = app(SceneReloadMode::Patch);
+        let world = &mut app.world;
+        let handle = spawn(world, scene(&[(0, 0)]));
+        set_value(world, 0, 10);
+
+        let mut invalid = scene(&[(0, 0)]);
+        invalid.entities[0].components.push(Box::new(Unregistered));
+        assert!(update(world, &handle, invalid).is_err());
+
+        // the value is only kept if the patch is computed from the spawned version of the scene
+        update(world, &handle, scene(&[(0, 0)])).unwrap();
+        assert_eq!(entities(world), vec![(0, 10)]);
+    }
+
+    /// A scene with a parent entity of id `0`, and a child entity for each value, extracted from a
+    /// world with the types registered in `app`.
+    fn hierarchy_scene(app: &App, values: &[u32]) -> DynamicScene {
+        let mut world = World::new();
+        let parent = world.spawn().insert_bundle((Id(0), Value(0))).id();
+        for (i, value) in values.iter().enumerate() {
+            let child = world
+                .spawn()
+                .insert_bundle((Id(i as u32 + 1), Value(*value)))
+                .id();
+            world.entity_mut(parent).push_children(&[child]);
+        }
+        DynamicScene::from_world(&world, app.world.resource::<TypeRegistryArc>())
+    }
+
+    /// The ids of the children of the entity of id `0`, checking they have it as their parent.
+    fn children_ids(world: &mut World) -> Vec<u32> {
+        let (parent, children) = world
+            .query::<(Entity, &Id, &Children)>()
+            .iter(world)
+            .find(|(_, id, _)| id.0 == 0)
+            .map(|(parent, _, children)| (parent, children.to_vec()))
+            .unwrap();
+        children
+            .iter()
+            .map(|child| {
+                assert_eq!(world.get::<Parent>(*child).unwrap().0, parent);
+                world.get::<Id>(*child).unwrap().0
+            })
+            .collect()
+    }
+
+    #[test]
+    fn patch_maps_the_entities_of_the_written_components_only() {
+        let mut app = app(SceneReloadMode::Patch);
+        app.register_type::<Parent>().register_type::<Children>();
+        // the spawned entities don't have the ids of the scene entities
+        for _ in 0..5 {
+            app.world.spawn();
+        }
+        let original = hierarchy_scene(&app, &[1]);
+        let changed_value = hierarchy_scene(&app, &[2]);
+        let added_child = hierarchy_scene(&app, &[2, 3]);
+        let world = &mut app.world;
+        let handle = spawn(world, original);
+        assert_eq!(children_ids(world), vec![1]);
+
+        // the hierarchy is unchanged, so it keeps referring to the spawned entities
+        update(world, &handle, changed_value).unwrap();
+        assert_eq!(entities(world), vec![(0, 0), (1, 2)]);
+        assert_eq!(children_ids(world), vec![1]);
+
+        // the changed children are mapped to the spawned entities
+        update(world, &handle, added_child).unwrap();
+        assert_eq!(entities(world), vec![(0, 0), (1, 2), (2, 3)]);
+        assert_eq!(children_ids(world), vec![1, 2]);
+    }
+
+    /// A scene with a single entity named `name`, with the prefabs `prefabs` as its children.
+    fn named_scene(name: &str, prefabs: &[&Handle<DynamicScene>]) -> DynamicScene {
+        DynamicScene {
+            entities: vec![DynamicEntity {
+                entity: 0,
+                components: vec![
+                    Box::new(Name::new(name.to_string())),
+                    Box::new(Id(0)),
+                    Box::new(Value(0)),
+                ],
+            }],
+            prefabs: prefabs
+                .iter()
+                .map(|handle| {
+                    PrefabInstance::new(0, "prefab.scn.ron".into(), (*handle).clone_weak())
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn entities_named(world: &mut World, name: &str) -> Vec<Entity> {
+        world
+            .query::<(Entity, &Name)>()
+            .iter(world)
+            .filter(|(_, entity_name)| entity_name.as_str() == name)
+            .map(|(entity, _)| entity)
+            .collect()
+    }
+
+    #[test]
+    fn nested_prefabs_are_spawned_with_their_overrides() {
+        let mut app = app(SceneReloadMode::Reapply);
+        let world = &mut app.world;
+        let mut scenes = world.resource_mut::<Assets<DynamicScene>>();
+        let lock = scenes.add(named_scene("Lock", &[]));
+        let door = scenes.add(named_scene("Door", &[&lock]));
+        let mut house = named_scene("House", &[&door]);
+        // the override of the house reaches the lock, nested in the door prefab
+        house.prefabs[0]
+            .overrides
+            .push(PrefabOverride::new::<Value>("Door/Lock", "0", 7u32));
+        let house = scenes.add(house);
+        world.resource_mut::<SceneSpawner>().spawn_dynamic(house);
+        for _ in 0..4 {
+            scene_spawner_system(world);
+        }
+
+        let house = entities_named(world, "House")[0];
+        let door = entities_named(world, "Door")[0];
+        let lock = entities_named(world, "Lock")[0];
+        assert_eq!(world.get::<Parent>(door).unwrap().0, house);
+        assert_eq!(world.get::<Parent>(lock).unwrap().0, door);
+        assert_eq!(world.get::<Value>(lock).unwrap().0, 7);
+        assert_eq!(world.get::<Value>(door).unwrap().0, 0);
+    }
+
+    #[test]
+    fn prefab_cycles_stop_at_the_depth_limit() {
+        let mut app = app(SceneReloadMode::Reapply);
+        let world = &mut app.world;
+        let mut scenes = world.resource_mut::<Assets<DynamicScene>>();
+        // a scene nesting itself as a prefab
+        let handle = scenes.get_handle(HandleId::random::<DynamicScene>());
+        scenes.set_untracked(&handle, named_scene("Loop", &[&handle]));
+        world.resource_mut::<SceneSpawner>().spawn_dynamic(handle);
+        for _ in 0..MAX_PREFAB_DEPTH + 8 {
+            scene_spawner_system(world);
+        }
+
+        assert_eq!(entities_named(world, "Loop").len(), MAX_PREFAB_DEPTH + 1);
+    }
+
+    #[test]
+    fn instance_ready_events_are_sent_for_every_spawn() {
+        let mut app = app(SceneReloadMode::Reapply);
+        let world = &mut app.world;
+        let dynamic_scene = world
+            .resource_mut::<Assets<DynamicScene>>()
+            .add(scene(&[(0, 0)]));
+        let mut scene_world = World::new();
+        scene_world.spawn().insert(Id(1)).insert(Value(1));
+        let scene = world
+            .resource_mut::<Assets<Scene>>()
+            .add(Scene::new(scene_world));
+        let parent = world.spawn().id();
+
+        let (dynamic_sync, sync, dynamic_child, child) =
+            world.resource_scope(|world, mut spawner: Mut<SceneSpawner>| {
+                (
+                    spawner.spawn_dynamic_sync(world, &dynamic_scene).unwrap(),
+                    spawner.spawn_sync(world, scene.clone()).unwrap(),
+                    spawner.spawn_dynamic_as_child(dynamic_scene.clone(), parent),
+                    spawner.spawn_as_child(scene.clone(), parent),
+                )
+            });
+        scene_spawner_system(world);
+
+        let events = world.resource::<Events<SceneInstanceReady>>();
+        let ready: HashMap<InstanceId, Option<Entity>> = events
+            .get_reader()
+            .iter(events)
+            .map(|event| (event.instance_id, event.parent))
+            .collect();
+        assert_eq!(ready.len(), 4);
+        assert_eq!(ready[&dynamic_sync], None);
+        assert_eq!(ready[&sync], None);
+        assert_eq!(ready[&dynamic_child], Some(parent));
+        assert_eq!(ready[&child], Some(parent));
+        assert_eq!(
+            world.get::<Children>(parent).map(|children| children.len()),
+            Some(2)
+        );
+
+        // the events are only sent once
+        scene_spawner_system(world);
+        let events = world.resource::<Events<SceneInstanceReady>>();
+        assert_eq!(events.get_reader_current().iter(events).count(), 0);
+    }
+
+    #[test]
+    fn instance_entities_are_found_by_path() {
+        let mut app = app(SceneReloadMode::Reapply);
+        let world = &mut app.world;
+        let mut scenes = world.resource_mut::<Assets<DynamicScene>>();
+        let lock = scenes.add(named_scene("Lock", &[]));
+        let door = scenes.add(named_scene("Door", &[&lock]));
+        let house = scenes.add(named_scene("House", &[&door]));
+        let instance_id = world.resource_scope(|world, mut spawner: Mut<SceneSpawner>| {
+            spawner.spawn_dynamic_sync(world, &house).unwrap()
+        });
+        for _ in 0..3 {
+            scene_spawner_system(world);
+        }
+
+        let spawner = world.resource::<SceneSpawner>();
+        let by_path = |path| spawner.instance_entity_by_path(world, instance_id, path);
+        let house = by_path("House");
+        let lock = by_path("House/Door/Lock");
+        assert!(house.is_some());
+        assert!(lock.is_some());
+        assert_eq!(world.get::<Name>(lock.unwrap()).unwrap().as_str(), "Lock");
+        // paths start from a root of the instance
+        assert_eq!(by_path("Door/Lock"), None);
+        assert_eq!(by_path("House/Window"), None);
+        assert_eq!(by_path(""), None);
+    }
+}
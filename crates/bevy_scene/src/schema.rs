@@ -0,0 +1,23 @@
+use bevy_ecs::reflect::{ReflectComponent, ReflectResource};
+use bevy_reflect::{SchemaExporter, TypeRegistry};
+use serde_json::Value;
+
+/// The schema keyword marking types which can be added to entities as components.
+pub const COMPONENT_SCHEMA_KEYWORD: &str = "x-bevy-component";
+/// The schema keyword marking types which can be inserted into the world as resources.
+pub const RESOURCE_SCHEMA_KEYWORD: &str = "x-bevy-resource";
+
+/// Exports the registered types of `registry` as a JSON Schema document.
+///
+/// The schema of each type is stored under its full type name in `$defs`, and describes the
+/// output of [`ReflectSerializer`], which is also how components and resources are stored in
+/// scene files. Types registering [`ReflectComponent`] or [`ReflectResource`] are marked with
+/// [`COMPONENT_SCHEMA_KEYWORD`] and [`RESOURCE_SCHEMA_KEYWORD`] respectively.
+///
+/// [`ReflectSerializer`]: bevy_reflect::serde::ReflectSerializer
+pub fn registry_schema(registry: &TypeRegistry) -> Value {
+    SchemaExporter::new(registry)
+        .mark_type_data::<ReflectComponent>(COMPONENT_SCHEMA_KEYWORD)
+        .mark_type_data::<ReflectResource>(RESOURCE_SCHEMA_KEYWORD)
+        .export()
+}
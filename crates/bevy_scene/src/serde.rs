@@ -1,8 +1,9 @@
-use crate::{DynamicEntity, DynamicScene};
+use crate::{DynamicEntity, DynamicScene, PrefabInstance, PrefabOverride, SceneMigrations};
 use anyhow::Result;
+use bevy_asset::{AssetPath, AssetServer, Handle, HandleId, ReflectHandle};
 use bevy_reflect::{
-    serde::{ReflectDeserializer, ReflectSerializer},
-    Reflect, TypeRegistry, TypeRegistryArc,
+    serde::{DeserializeRenames, ReflectDeserializer, ReflectSerializer},
+    DynamicStruct, Reflect, ReflectMut, Struct, TypeRegistry, TypeRegistryArc,
 };
 use serde::{
     de::{DeserializeSeed, Error, MapAccess, SeqAccess, Visitor},
@@ -13,11 +14,36 @@ use serde::{
 pub struct SceneSerializer<'a> {
     pub scene: &'a DynamicScene,
     pub registry: &'a TypeRegistryArc,
+    /// The [`AssetServer`] to serialize the handles of the assets it knows the path of by their
+    /// [`AssetPath`], see [`HANDLE_FIELD_PATH`].
+    pub asset_server: Option<&'a AssetServer>,
+    /// The version of the scene, see [`SceneMigrations`].
+    pub version: u32,
 }
 
 impl<'a> SceneSerializer<'a> {
     pub fn new(scene: &'a DynamicScene, registry: &'a TypeRegistryArc) -> Self {
-        SceneSerializer { scene, registry }
+        SceneSerializer {
+            scene,
+            registry,
+            asset_server: None,
+            version: 0,
+        }
+    }
+
+    /// Sets the version written in the scene, usually
+    /// [`SceneMigrations::current_version`].
+    #[must_use]
+    pub fn with_version(mut self, version: u32) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Serializes the handles of the assets loaded by `asset_server` by their [`AssetPath`].
+    #[must_use]
+    pub fn with_asset_server(mut self, asset_server: &'a AssetServer) -> Self {
+        self.asset_server = Some(asset_server);
+        self
     }
 }
 
@@ -26,11 +52,53 @@ impl<'a> Serialize for SceneSerializer<'a> {
     where
         S: serde::Serializer,
     {
-        let mut state = serializer.serialize_seq(Some(self.scene.entities.len()))?;
-        for entity in &self.scene.entities {
+        let mut state = serializer.serialize_struct(SCENE_STRUCT, 4)?;
+        state.serialize_field(SCENE_FIELD_VERSION, &self.version)?;
+        state.serialize_field(
+            SCENE_FIELD_ENTITIES,
+            &EntitiesSerializer {
+                entities: &self.scene.entities,
+                registry: self.registry,
+                asset_server: self.asset_server,
+            },
+        )?;
+        state.serialize_field(
+            SCENE_FIELD_RESOURCES,
+            &ComponentsSerializer {
+                components: &self.scene.resources,
+                registry: self.registry,
+                asset_server: self.asset_server,
+            },
+        )?;
+        state.serialize_field(
+            SCENE_FIELD_PREFABS,
+            &PrefabsSerializer {
+                prefabs: &self.scene.prefabs,
+                registry: self.registry,
+                asset_server: self.asset_server,
+            },
+        )?;
+        state.end()
+    }
+}
+
+pub struct EntitiesSerializer<'a> {
+    pub entities: &'a [DynamicEntity],
+    pub registry: &'a TypeRegistryArc,
+    pub asset_server: Option<&'a AssetServer>,
+}
+
+impl<'a> Serialize for EntitiesSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.entities.len()))?;
+        for entity in self.entities {
             state.serialize_element(&EntitySerializer {
                 entity,
                 registry: self.registry,
+                asset_server: self.asset_server,
             })?;
         }
         state.end()
@@ -40,6 +108,7 @@ impl<'a> Serialize for SceneSerializer<'a> {
 pub struct EntitySerializer<'a> {
     pub entity: &'a DynamicEntity,
     pub registry: &'a TypeRegistryArc,
+    pub asset_server: Option<&'a AssetServer>,
 }
 
 impl<'a> Serialize for EntitySerializer<'a> {
@@ -54,6 +123,7 @@ impl<'a> Serialize for EntitySerializer<'a> {
             &ComponentsSerializer {
                 components: &self.entity.components,
                 registry: self.registry,
+                asset_server: self.asset_server,
             },
         )?;
         state.end()
@@ -63,6 +133,7 @@ impl<'a> Serialize for EntitySerializer<'a> {
 pub struct ComponentsSerializer<'a> {
     pub components: &'a [Box<dyn Reflect>],
     pub registry: &'a TypeRegistryArc,
+    pub asset_server: Option<&'a AssetServer>,
 }
 
 impl<'a> Serialize for ComponentsSerializer<'a> {
@@ -70,19 +141,127 @@ impl<'a> Serialize for ComponentsSerializer<'a> {
     where
         S: serde::Serializer,
     {
+        let registry = self.registry.read();
         let mut state = serializer.serialize_seq(Some(self.components.len()))?;
         for component in self.components.iter() {
-            state.serialize_element(&ReflectSerializer::new(
-                &**component,
-                &*self.registry.read(),
-            ))?;
+            let with_asset_paths = self
+                .asset_server
+                .map(|asset_server| handles_to_asset_paths(&**component, &registry, asset_server));
+            let component = with_asset_paths.as_ref().unwrap_or(component);
+            state.serialize_element(&ReflectSerializer::new(&**component, &registry))?;
+        }
+        state.end()
+    }
+}
+
+pub struct PrefabsSerializer<'a> {
+    pub prefabs: &'a [PrefabInstance],
+    pub registry: &'a TypeRegistryArc,
+    pub asset_server: Option<&'a AssetServer>,
+}
+
+impl<'a> Serialize for PrefabsSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.prefabs.len()))?;
+        for prefab in self.prefabs {
+            state.serialize_element(&PrefabSerializer {
+                prefab,
+                registry: self.registry,
+                asset_server: self.asset_server,
+            })?;
         }
         state.end()
     }
 }
 
+pub struct PrefabSerializer<'a> {
+    pub prefab: &'a PrefabInstance,
+    pub registry: &'a TypeRegistryArc,
+    pub asset_server: Option<&'a AssetServer>,
+}
+
+impl<'a> Serialize for PrefabSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct(PREFAB_STRUCT, 3)?;
+        state.serialize_field(PREFAB_FIELD_ENTITY, &self.prefab.entity)?;
+        state.serialize_field(PREFAB_FIELD_SCENE, &self.prefab.path.to_string())?;
+        state.serialize_field(
+            PREFAB_FIELD_OVERRIDES,
+            &PrefabOverridesSerializer {
+                overrides: &self.prefab.overrides,
+                registry: self.registry,
+                asset_server: self.asset_server,
+            },
+        )?;
+        state.end()
+    }
+}
+
+pub struct PrefabOverridesSerializer<'a> {
+    pub overrides: &'a [PrefabOverride],
+    pub registry: &'a TypeRegistryArc,
+    pub asset_server: Option<&'a AssetServer>,
+}
+
+impl<'a> Serialize for PrefabOverridesSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let registry = self.registry.read();
+        let mut state = serializer.serialize_seq(Some(self.overrides.len()))?;
+        for prefab_override in self.overrides {
+            let with_asset_paths = self.asset_server.map(|asset_server| {
+                handles_to_asset_paths(&*prefab_override.value, &registry, asset_server)
+            });
+            let value = with_asset_paths.as_ref().unwrap_or(&prefab_override.value);
+            state.serialize_element(&PrefabOverrideSerializer {
+                prefab_override,
+                value: ReflectSerializer::new(&**value, &registry),
+            })?;
+        }
+        state.end()
+    }
+}
+
+struct PrefabOverrideSerializer<'a> {
+    prefab_override: &'a PrefabOverride,
+    value: ReflectSerializer<'a>,
+}
+
+impl<'a> Serialize for PrefabOverrideSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct(PREFAB_OVERRIDE_STRUCT, 4)?;
+        state.serialize_field(
+            PREFAB_OVERRIDE_FIELD_ENTITY,
+            &self.prefab_override.entity_path,
+        )?;
+        state.serialize_field(
+            PREFAB_OVERRIDE_FIELD_COMPONENT,
+            &self.prefab_override.component,
+        )?;
+        state.serialize_field(PREFAB_OVERRIDE_FIELD_PATH, &self.prefab_override.field_path)?;
+        state.serialize_field(PREFAB_OVERRIDE_FIELD_VALUE, &self.value)?;
+        state.end()
+    }
+}
+
+/// Deserializes a [`DynamicScene`], upgrading its components with the [`SceneMigrations`]
+///
+/// Scenes saved before the scene format was versioned, as a list of entities, have the version
+/// `0`.
 pub struct SceneDeserializer<'a> {
     pub type_registry: &'a TypeRegistry,
+    pub migrations: Option<&'a SceneMigrations>,
 }
 
 impl<'a, 'de> DeserializeSeed<'de> for SceneDeserializer<'a> {
@@ -92,16 +271,173 @@ impl<'a, 'de> DeserializeSeed<'de> for SceneDeserializer<'a> {
     where
         D: serde::Deserializer<'de>,
     {
-        Ok(DynamicScene {
-            entities: deserializer.deserialize_seq(SceneEntitySeqVisitor {
-                type_registry: self.type_registry,
-            })?,
+        let content = deserializer.deserialize_any(SceneVisitor {
+            type_registry: self.type_registry,
+            migrations: self.migrations,
+        })?;
+        let mut scene = DynamicScene {
+            entities: content.entities,
+            resources: content.resources,
+            asset_handles: Vec::new(),
+            prefabs: content.prefabs,
+        };
+        if let Some(migrations) = self.migrations {
+            migrations.migrate_renamed(&mut scene, content.version);
+        }
+        Ok(scene)
+    }
+}
+
+#[derive(Deserialize, PartialEq)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum SceneField {
+    Version,
+    Entities,
+    Resources,
+    Prefabs,
+}
+
+pub const SCENE_STRUCT: &str = "Scene";
+pub const SCENE_FIELD_VERSION: &str = "version";
+pub const SCENE_FIELD_ENTITIES: &str = "entities";
+pub const SCENE_FIELD_RESOURCES: &str = "resources";
+pub const SCENE_FIELD_PREFABS: &str = "prefabs";
+
+/// The content of a deserialized scene, before it is migrated
+struct SceneContent {
+    version: u32,
+    entities: Vec<DynamicEntity>,
+    resources: Vec<Box<dyn Reflect>>,
+    prefabs: Vec<PrefabInstance>,
+}
+
+struct SceneVisitor<'a> {
+    pub type_registry: &'a TypeRegistry,
+    pub migrations: Option<&'a SceneMigrations>,
+}
+
+impl<'a, 'de> Visitor<'de> for SceneVisitor<'a> {
+    type Value = SceneContent;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("scene")
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let renames = self.migrations.map(|migrations| migrations.renames(0));
+        let entities = SceneEntitySeqVisitor {
+            type_registry: self.type_registry,
+            renames: renames
+                .as_ref()
+                .map(|renames| renames as &dyn DeserializeRenames),
+        }
+        .visit_seq(seq)?;
+        Ok(SceneContent {
+            version: 0,
+            entities,
+            resources: Vec::new(),
+            prefabs: Vec::new(),
+        })
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut version = None;
+        let mut migration_renames = None;
+        let mut entities = None;
+        let mut resources = None;
+        let mut prefabs = None;
+        while let Some(key) = map.next_key()? {
+            // the values are renamed while they are deserialized, which requires their version
+            if self.migrations.is_some() && version.is_none() && key != SceneField::Version {
+                return Err(Error::custom(format_args!(
+                    "the `{}` of a migrated scene must come first",
+                    SCENE_FIELD_VERSION
+                )));
+            }
+            let renames = migration_renames
+                .as_ref()
+                .map(|renames| renames as &dyn DeserializeRenames);
+            match key {
+                SceneField::Version => {
+                    if version.is_some() {
+                        return Err(Error::duplicate_field(SCENE_FIELD_VERSION));
+                    }
+                    let scene_version = map.next_value::<u32>()?;
+                    version = Some(scene_version);
+                    migration_renames = self
+                        .migrations
+                        .map(|migrations| migrations.renames(scene_version));
+                }
+                SceneField::Entities => {
+                    if entities.is_some() {
+                        return Err(Error::duplicate_field(SCENE_FIELD_ENTITIES));
+                    }
+                    entities = Some(map.next_value_seed(SceneEntitiesDeserializer {
+                        type_registry: self.type_registry,
+                        renames,
+                    })?);
+                }
+                SceneField::Resources => {
+                    if resources.is_some() {
+                        return Err(Error::duplicate_field(SCENE_FIELD_RESOURCES));
+                    }
+                    resources = Some(map.next_value_seed(ComponentVecDeserializer {
+                        registry: self.type_registry,
+                        renames,
+                    })?);
+                }
+                SceneField::Prefabs => {
+                    if prefabs.is_some() {
+                        return Err(Error::duplicate_field(SCENE_FIELD_PREFABS));
+                    }
+                    prefabs = Some(map.next_value_seed(PrefabsDeserializer {
+                        type_registry: self.type_registry,
+                        renames,
+                    })?);
+                }
+            }
+        }
+
+        let version = version.ok_or_else(|| Error::missing_field(SCENE_FIELD_VERSION))?;
+        let entities = entities.ok_or_else(|| Error::missing_field(SCENE_FIELD_ENTITIES))?;
+        // scenes saved before resources and prefabs were supported don't have them
+        Ok(SceneContent {
+            version,
+            entities,
+            resources: resources.unwrap_or_default(),
+            prefabs: prefabs.unwrap_or_default(),
+        })
+    }
+}
+
+struct SceneEntitiesDeserializer<'a> {
+    pub type_registry: &'a TypeRegistry,
+    pub renames: Option<&'a dyn DeserializeRenames>,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for SceneEntitiesDeserializer<'a> {
+    type Value = Vec<DynamicEntity>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(SceneEntitySeqVisitor {
+            type_registry: self.type_registry,
+            renames: self.renames,
         })
     }
 }
 
 struct SceneEntitySeqVisitor<'a> {
     pub type_registry: &'a TypeRegistry,
+    pub renames: Option<&'a dyn DeserializeRenames>,
 }
 
 impl<'a, 'de> Visitor<'de> for SceneEntitySeqVisitor<'a> {
@@ -118,6 +454,7 @@ impl<'a, 'de> Visitor<'de> for SceneEntitySeqVisitor<'a> {
         let mut entities = Vec::new();
         while let Some(entity) = seq.next_element_seed(SceneEntityDeserializer {
             type_registry: self.type_registry,
+            renames: self.renames,
         })? {
             entities.push(entity);
         }
@@ -128,6 +465,7 @@ impl<'a, 'de> Visitor<'de> for SceneEntitySeqVisitor<'a> {
 
 pub struct SceneEntityDeserializer<'a> {
     pub type_registry: &'a TypeRegistry,
+    pub renames: Option<&'a dyn DeserializeRenames>,
 }
 
 impl<'a, 'de> DeserializeSeed<'de> for SceneEntityDeserializer<'a> {
@@ -142,6 +480,7 @@ impl<'a, 'de> DeserializeSeed<'de> for SceneEntityDeserializer<'a> {
             &[ENTITY_FIELD_ENTITY, ENTITY_FIELD_COMPONENTS],
             SceneEntityVisitor {
                 registry: self.type_registry,
+                renames: self.renames,
             },
         )
     }
@@ -160,6 +499,7 @@ pub const ENTITY_FIELD_COMPONENTS: &str = "components";
 
 struct SceneEntityVisitor<'a> {
     pub registry: &'a TypeRegistry,
+    pub renames: Option<&'a dyn DeserializeRenames>,
 }
 
 impl<'a, 'de> Visitor<'de> for SceneEntityVisitor<'a> {
@@ -190,6 +530,7 @@ impl<'a, 'de> Visitor<'de> for SceneEntityVisitor<'a> {
 
                     components = Some(map.next_value_seed(ComponentVecDeserializer {
                         registry: self.registry,
+                        renames: self.renames,
                     })?);
                 }
             }
@@ -211,6 +552,7 @@ impl<'a, 'de> Visitor<'de> for SceneEntityVisitor<'a> {
 
 pub struct ComponentVecDeserializer<'a> {
     pub registry: &'a TypeRegistry,
+    pub renames: Option<&'a dyn DeserializeRenames>,
 }
 
 impl<'a, 'de> DeserializeSeed<'de> for ComponentVecDeserializer<'a> {
@@ -222,12 +564,14 @@ impl<'a, 'de> DeserializeSeed<'de> for ComponentVecDeserializer<'a> {
     {
         deserializer.deserialize_seq(ComponentSeqVisitor {
             registry: self.registry,
+            renames: self.renames,
         })
     }
 }
 
 struct ComponentSeqVisitor<'a> {
     pub registry: &'a TypeRegistry,
+    pub renames: Option<&'a dyn DeserializeRenames>,
 }
 
 impl<'a, 'de> Visitor<'de> for ComponentSeqVisitor<'a> {
@@ -242,10 +586,451 @@ impl<'a, 'de> Visitor<'de> for ComponentSeqVisitor<'a> {
         A: SeqAccess<'de>,
     {
         let mut dynamic_properties = Vec::new();
-        while let Some(entity) = seq.next_element_seed(ReflectDeserializer::new(self.registry))? {
+        while let Some(entity) =
+            seq.next_element_seed(reflect_deserializer(self.registry, self.renames))?
+        {
             dynamic_properties.push(entity);
         }
 
         Ok(dynamic_properties)
     }
 }
+
+struct PrefabsDeserializer<'a> {
+    pub type_registry: &'a TypeRegistry,
+    pub renames: Option<&'a dyn DeserializeRenames>,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for PrefabsDeserializer<'a> {
+    type Value = Vec<PrefabInstance>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(PrefabSeqVisitor {
+            type_registry: self.type_registry,
+            renames: self.renames,
+        })
+    }
+}
+
+struct PrefabSeqVisitor<'a> {
+    pub type_registry: &'a TypeRegistry,
+    pub renames: Option<&'a dyn DeserializeRenames>,
+}
+
+impl<'a, 'de> Visitor<'de> for PrefabSeqVisitor<'a> {
+    type Value = Vec<PrefabInstance>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("list of prefabs")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut prefabs = Vec::new();
+        while let Some(prefab) = seq.next_element_seed(PrefabDeserializer {
+            type_registry: self.type_registry,
+            renames: self.renames,
+        })? {
+            prefabs.push(prefab);
+        }
+
+        Ok(prefabs)
+    }
+}
+
+/// Deserializes a [`PrefabInstance`], whose handle is weak until its scene is loaded.
+pub struct PrefabDeserializer<'a> {
+    pub type_registry: &'a TypeRegistry,
+    pub renames: Option<&'a dyn DeserializeRenames>,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for PrefabDeserializer<'a> {
+    type Value = PrefabInstance;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            PREFAB_STRUCT,
+            &[
+                PREFAB_FIELD_ENTITY,
+                PREFAB_FIELD_SCENE,
+                PREFAB_FIELD_OVERRIDES,
+            ],
+            PrefabVisitor {
+                type_registry: self.type_registry,
+                renames: self.renames,
+            },
+        )
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum PrefabField {
+    Entity,
+    Scene,
+    Overrides,
+}
+
+pub const PREFAB_STRUCT: &str = "Prefab";
+pub const PREFAB_FIELD_ENTITY: &str = "entity";
+pub const PREFAB_FIELD_SCENE: &str = "scene";
+pub const PREFAB_FIELD_OVERRIDES: &str = "overrides";
+
+struct PrefabVisitor<'a> {
+    pub type_registry: &'a TypeRegistry,
+    pub renames: Option<&'a dyn DeserializeRenames>,
+}
+
+impl<'a, 'de> Visitor<'de> for PrefabVisitor<'a> {
+    type Value = PrefabInstance;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("prefab")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut entity = None;
+        let mut scene = None;
+        let mut overrides = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                PrefabField::Entity => {
+                    if entity.is_some() {
+                        return Err(Error::duplicate_field(PREFAB_FIELD_ENTITY));
+                    }
+                    entity = Some(map.next_value::<u32>()?);
+                }
+                PrefabField::Scene => {
+                    if scene.is_some() {
+                        return Err(Error::duplicate_field(PREFAB_FIELD_SCENE));
+                    }
+                    scene = Some(map.next_value::<String>()?);
+                }
+                PrefabField::Overrides => {
+                    if overrides.is_some() {
+                        return Err(Error::duplicate_field(PREFAB_FIELD_OVERRIDES));
+                    }
+                    overrides = Some(map.next_value_seed(PrefabOverridesDeserializer {
+                        type_registry: self.type_registry,
+                        renames: self.renames,
+                    })?);
+                }
+            }
+        }
+
+        let entity = entity.ok_or_else(|| Error::missing_field(PREFAB_FIELD_ENTITY))?;
+        let scene = scene.ok_or_else(|| Error::missing_field(PREFAB_FIELD_SCENE))?;
+        let path = AssetPath::from(scene.as_str()).to_owned();
+        Ok(PrefabInstance {
+            entity,
+            handle: Handle::weak(HandleId::from(path.clone())),
+            path,
+            overrides: overrides.unwrap_or_default(),
+        })
+    }
+}
+
+struct PrefabOverridesDeserializer<'a> {
+    pub type_registry: &'a TypeRegistry,
+    pub renames: Option<&'a dyn DeserializeRenames>,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for PrefabOverridesDeserializer<'a> {
+    type Value = Vec<PrefabOverride>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(PrefabOverrideSeqVisitor {
+            type_registry: self.type_registry,
+            renames: self.renames,
+        })
+    }
+}
+
+struct PrefabOverrideSeqVisitor<'a> {
+    pub type_registry: &'a TypeRegistry,
+    pub renames: Option<&'a dyn DeserializeRenames>,
+}
+
+impl<'a, 'de> Visitor<'de> for PrefabOverrideSeqVisitor<'a> {
+    type Value = Vec<PrefabOverride>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("list of prefab overrides")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut overrides = Vec::new();
+        while let Some(prefab_override) = seq.next_element_seed(PrefabOverrideDeserializer {
+            type_registry: self.type_registry,
+            renames: self.renames,
+        })? {
+            overrides.push(prefab_override);
+        }
+
+        Ok(overrides)
+    }
+}
+
+pub struct PrefabOverrideDeserializer<'a> {
+    pub type_registry: &'a TypeRegistry,
+    pub renames: Option<&'a dyn DeserializeRenames>,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for PrefabOverrideDeserializer<'a> {
+    type Value = PrefabOverride;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            PREFAB_OVERRIDE_STRUCT,
+            &[
+                PREFAB_OVERRIDE_FIELD_ENTITY,
+                PREFAB_OVERRIDE_FIELD_COMPONENT,
+                PREFAB_OVERRIDE_FIELD_PATH,
+                PREFAB_OVERRIDE_FIELD_VALUE,
+            ],
+            PrefabOverrideVisitor {
+                type_registry: self.type_registry,
+                renames: self.renames,
+            },
+        )
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum PrefabOverrideField {
+    Entity,
+    Component,
+    Path,
+    Value,
+}
+
+pub const PREFAB_OVERRIDE_STRUCT: &str = "PrefabOverride";
+pub const PREFAB_OVERRIDE_FIELD_ENTITY: &str = "entity";
+pub const PREFAB_OVERRIDE_FIELD_COMPONENT: &str = "component";
+pub const PREFAB_OVERRIDE_FIELD_PATH: &str = "path";
+pub const PREFAB_OVERRIDE_FIELD_VALUE: &str = "value";
+
+struct PrefabOverrideVisitor<'a> {
+    pub type_registry: &'a TypeRegistry,
+    pub renames: Option<&'a dyn DeserializeRenames>,
+}
+
+impl<'a, 'de> Visitor<'de> for PrefabOverrideVisitor<'a> {
+    type Value = PrefabOverride;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("prefab override")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut entity_path = None;
+        let mut component = None;
+        let mut field_path = None;
+        let mut value = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                PrefabOverrideField::Entity => {
+                    if entity_path.is_some() {
+                        return Err(Error::duplicate_field(PREFAB_OVERRIDE_FIELD_ENTITY));
+                    }
+                    entity_path = Some(map.next_value::<String>()?);
+                }
+                PrefabOverrideField::Component => {
+                    if component.is_some() {
+                        return Err(Error::duplicate_field(PREFAB_OVERRIDE_FIELD_COMPONENT));
+                    }
+                    component = Some(map.next_value::<String>()?);
+                }
+                PrefabOverrideField::Path => {
+                    if field_path.is_some() {
+                        return Err(Error::duplicate_field(PREFAB_OVERRIDE_FIELD_PATH));
+                    }
+                    field_path = Some(map.next_value::<String>()?);
+                }
+                PrefabOverrideField::Value => {
+                    if value.is_some() {
+                        return Err(Error::duplicate_field(PREFAB_OVERRIDE_FIELD_VALUE));
+                    }
+                    value =
+                        Some(map.next_value_seed(reflect_deserializer(
+                            self.type_registry,
+                            self.renames,
+                        ))?);
+                }
+            }
+        }
+
+        Ok(PrefabOverride {
+            entity_path: entity_path
+                .ok_or_else(|| Error::missing_field(PREFAB_OVERRIDE_FIELD_ENTITY))?,
+            component: component
+                .ok_or_else(|| Error::missing_field(PREFAB_OVERRIDE_FIELD_COMPONENT))?,
+            field_path: field_path.unwrap_or_default(),
+            value: value.ok_or_else(|| Error::missing_field(PREFAB_OVERRIDE_FIELD_VALUE))?,
+        })
+    }
+}
+
+/// Returns a [`ReflectDeserializer`] renaming the values it deserializes with `renames`.
+fn reflect_deserializer<'a>(
+    registry: &'a TypeRegistry,
+    renames: Option<&'a dyn DeserializeRenames>,
+) -> ReflectDeserializer<'a> {
+    let deserializer = ReflectDeserializer::new(registry);
+    match renames {
+        Some(renames) => deserializer.with_renames(renames),
+        None => deserializer,
+    }
+}
+
+/// The field of the serialized [`Handle`](bevy_asset::Handle)s holding the [`AssetPath`] of their
+/// asset, in place of their `id` field.
+///
+/// Handles are serialized this way by a [`SceneSerializer`] with an [`AssetServer`] when the
+/// asset was loaded from a path, as the ids of the other assets aren't stable across runs.
+pub const HANDLE_FIELD_PATH: &str = "path";
+
+/// Returns a copy of `component` where the handles to the assets `asset_server` knows the path of
+/// have a [`HANDLE_FIELD_PATH`] field instead of their id.
+pub fn handles_to_asset_paths(
+    component: &dyn Reflect,
+    registry: &TypeRegistry,
+    asset_server: &AssetServer,
+) -> Box<dyn Reflect> {
+    let mut component = component.clone_value();
+    visit_handles_mut(&mut *component, registry, &mut |handle| {
+        let asset_path = handle
+            .field("id")
+            .and_then(|id| id.downcast_ref::<HandleId>())
+            .and_then(|id| asset_server.get_handle_path(*id));
+        if let Some(asset_path) = asset_path {
+            let mut handle_with_path = DynamicStruct::default();
+            handle_with_path.set_name(handle.name().to_string());
+            handle_with_path.insert(HANDLE_FIELD_PATH, asset_path.to_string());
+            *handle = handle_with_path;
+        }
+    });
+    component
+}
+
+/// Replaces the [`HANDLE_FIELD_PATH`] field of the deserialized handles of `component` by the id
+/// of the asset at that path.
+///
+/// `f` is called with each path and returns the path the asset is loaded from, for example
+/// resolved with [`LoadContext::resolve_path`](bevy_asset::LoadContext::resolve_path). The handle
+/// gets the id of the returned path, so `f` usually acquires a strong handle to that same path,
+/// for example with
+/// [`LoadContext::get_handle_untyped`](bevy_asset::LoadContext::get_handle_untyped).
+pub fn asset_paths_to_handles(
+    component: &mut dyn Reflect,
+    registry: &TypeRegistry,
+    f: &mut dyn FnMut(AssetPath<'static>) -> AssetPath<'static>,
+) {
+    visit_handles_mut(component, registry, &mut |handle| {
+        let asset_path = handle
+            .field(HANDLE_FIELD_PATH)
+            .and_then(|path| path.downcast_ref::<String>())
+            .map(|path| AssetPath::from(path.as_str()).to_owned());
+        if let Some(asset_path) = asset_path {
+            let mut handle_with_id = DynamicStruct::default();
+            handle_with_id.set_name(handle.name().to_string());
+            handle_with_id.insert("id", HandleId::from(f(asset_path)));
+            *handle = handle_with_id;
+        }
+    });
+}
+
+/// Calls `f` with every dynamic [`Handle`](bevy_asset::Handle) nested in `value`, recognized by
+/// their [`ReflectHandle`] type data.
+fn visit_handles_mut(
+    value: &mut dyn Reflect,
+    registry: &TypeRegistry,
+    f: &mut dyn FnMut(&mut DynamicStruct),
+) {
+    visit_reflect_mut(value, &mut |value| {
+        if let Some(dynamic_struct) = value.downcast_mut::<DynamicStruct>() {
+            let is_handle = registry
+                .get_with_name(dynamic_struct.name())
+                .map_or(false, |registration| {
+                    registration.data::<ReflectHandle>().is_some()
+                });
+            if is_handle {
+                f(dynamic_struct);
+                return false;
+            }
+        }
+        true
+    });
+}
+
+/// Calls `f` with `value` then with every value nested in it, unless `f` returns `false`.
+///
+/// The values nested in `value` can be replaced by `f` if they are dynamic, as made by
+/// [`Reflect::clone_value`] or by deserialization.
+pub(crate) fn visit_reflect_mut(
+    value: &mut dyn Reflect,
+    f: &mut dyn FnMut(&mut dyn Reflect) -> bool,
+) {
+    if !f(value) {
+        return;
+    }
+
+    match value.reflect_mut() {
+        ReflectMut::Struct(value) => {
+            for i in 0..value.field_len() {
+                visit_reflect_mut(value.field_at_mut(i).unwrap(), f);
+            }
+        }
+        ReflectMut::TupleStruct(value) => {
+            for i in 0..value.field_len() {
+                visit_reflect_mut(value.field_mut(i).unwrap(), f);
+            }
+        }
+        ReflectMut::Tuple(value) => {
+            for i in 0..value.field_len() {
+                visit_reflect_mut(value.field_mut(i).unwrap(), f);
+            }
+        }
+        ReflectMut::List(value) => {
+            for i in 0..value.len() {
+                visit_reflect_mut(value.get_mut(i).unwrap(), f);
+            }
+        }
+        ReflectMut::Array(value) => {
+            for i in 0..value.len() {
+                visit_reflect_mut(value.get_mut(i).unwrap(), f);
+            }
+        }
+        ReflectMut::Map(value) => {
+            let keys: Vec<_> = value.iter().map(|(key, _)| key.clone_value()).collect();
+            for key in keys {
+                visit_reflect_mut(value.get_mut(&*key).unwrap(), f);
+            }
+        }
+        ReflectMut::Value(_) => {}
+    }
+}
@@ -0,0 +1,514 @@
+//! App configuration loaded from a settings file.
+//!
+//! [`SettingsPlugin`] patches the reflected resources of an app with the values of a RON or JSON
+//! settings file, at startup and optionally whenever the file changes.
+
+#[cfg(all(
+    feature = "filesystem_watcher",
+    all(not(target_arch = "wasm32"), not(target_os = "android"))
+))]
+mod settings_watcher;
+
+use bevy_app::{App, Plugin, StartupStage};
+use bevy_asset::AssetServerSettings;
+use bevy_ecs::{
+    event::Events, reflect::ReflectResource, system::IntoExclusiveSystem, world::World,
+};
+use bevy_log::warn;
+use bevy_reflect::{
+    serde::ReflectDeserializer, std_traits::ReflectDefault, Reflect, ReflectMut, ReflectRef,
+    TypeRegistry, TypeRegistryArc,
+};
+use serde::de::{DeserializeSeed, SeqAccess, Visitor};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Errors that occur while reading a settings file
+#[derive(Error, Debug)]
+pub enum SettingsError {
+    #[error("encountered an io error while reading settings: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("failed to parse settings as ron: {0}")]
+    Ron(#[from] ron::Error),
+    #[error("failed to parse settings as json: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("settings files can't be read on this platform")]
+    Unsupported,
+}
+
+/// Patches [`Resource`](bevy_ecs::system::Resource)s with values read from a settings file, using reflection.
+///
+/// The settings file is a list of reflected values, in the format produced by
+/// [`ReflectSerializer`](bevy_reflect::serde::ReflectSerializer). It is parsed as JSON if its
+/// extension is `json`, and as RON otherwise:
+///
+/// ```ron
+/// [
+///   {
+///     "type": "bevy_asset::AssetServerSettings",
+///     "struct": {
+///       "watch_for_changes": {
+///         "type": "bool",
+///         "value": true,
+///       },
+///     },
+///   },
+/// ]
+/// ```
+///
+/// Each value is applied to the resource of the same type through its [`ReflectResource`]
+/// type data, so the type must be registered with `#[reflect(Resource)]`. Fields present in the
+/// file replace the current values of the resource while the other fields are left untouched.
+/// If the resource doesn't exist yet, it is inserted.
+///
+/// Entries of maps are patched the same way. Entries missing from a map are inserted if their
+/// key is a reflected value and the type of their value is registered with
+/// `#[reflect(Default)]`: the entry starts from the default value, then the fields present in the
+/// file are applied to it.
+///
+/// Settings are applied when the plugin is built, so this plugin should be added before the
+/// plugins reading these resources while they are built, and the types of those resources (and
+/// of their fields) must be registered beforehand. Values whose type isn't registered yet are
+/// applied again during [`StartupStage::PreStartup`].
+///
+/// When [`watch_for_changes`](SettingsPlugin::watch_for_changes) is enabled, the file is read
+/// again whenever it is modified, and a [`SettingsChanged`] event is sent for each value that was
+/// applied.
+pub struct SettingsPlugin {
+    /// The path of the settings file, relative to the root directory of the app.
+    ///
+    /// See [`FileAssetIo::get_root_path`](bevy_asset::FileAssetIo::get_root_path).
+    pub path: PathBuf,
+    /// Whether to watch the settings file for changes. Requires the `filesystem_watcher` feature,
+    /// and cannot be supported on the wasm32 arch nor android os.
+    pub watch_for_changes: bool,
+}
+
+impl Default for SettingsPlugin {
+    fn default() -> Self {
+        Self {
+            path: PathBuf::from("settings.ron"),
+            watch_for_changes: false,
+        }
+    }
+}
+
+/// Sent when a resource has been patched after its settings file changed
+#[derive(Debug, Clone)]
+pub struct SettingsChanged {
+    /// The type name of the patched resource
+    pub type_name: String,
+}
+
+/// The state of the settings file loaded by the [`SettingsPlugin`]
+pub struct Settings {
+    path: PathBuf,
+    pending: Vec<Box<dyn Reflect>>,
+}
+
+impl Settings {
+    /// The full path of the settings file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        {
+            let registry = app.world.resource::<TypeRegistryArc>();
+            let mut registry = registry.write();
+            if registry
+                .get(std::any::TypeId::of::<AssetServerSettings>())
+                .is_none()
+            {
+                registry.register::<AssetServerSettings>();
+            }
+        }
+
+        let path = settings_root_path().join(&self.path);
+        let type_registry = app.world.resource::<TypeRegistryArc>().clone();
+        let values = read_settings(&path, &type_registry.read());
+        let pending = match values {
+            Ok(values) => apply_settings(&mut app.world, values, false),
+            Err(err) => {
+                warn!("failed to load settings from {:?}: {}", path, err);
+                Vec::new()
+            }
+        };
+
+        if self.watch_for_changes {
+            #[cfg(all(
+                feature = "filesystem_watcher",
+                all(not(target_arch = "wasm32"), not(target_os = "android"))
+            ))]
+            match settings_watcher::SettingsWatcher::new(&path) {
+                Ok(watcher) => {
+                    app.insert_resource(watcher).add_system_to_stage(
+                        bevy_app::CoreStage::PreUpdate,
+                        settings_watcher::settings_watcher_system.exclusive_system(),
+                    );
+                }
+                Err(err) => warn!("failed to watch settings file {:?}: {}", path, err),
+            }
+            #[cfg(not(all(
+                feature = "filesystem_watcher",
+                all(not(target_arch = "wasm32"), not(target_os = "android"))
+            )))]
+            warn!("Watching settings for changes requires the filesystem_watcher feature and cannot be used on wasm32 / android targets");
+        }
+
+        app.add_event::<SettingsChanged>()
+            .insert_resource(Settings { path, pending })
+            .add_startup_system_to_stage(
+                StartupStage::PreStartup,
+                apply_pending_settings_system.exclusive_system(),
+            );
+    }
+}
+
+#[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+fn settings_root_path() -> PathBuf {
+    bevy_asset::FileAssetIo::get_root_path()
+}
+
+#[cfg(any(target_arch = "wasm32", target_os = "android"))]
+fn settings_root_path() -> PathBuf {
+    PathBuf::new()
+}
+
+/// Reads the reflected values of a settings file.
+///
+/// The file is parsed as JSON if its extension is `json`, and as RON otherwise.
+pub fn read_settings(
+    path: &Path,
+    type_registry: &TypeRegistry,
+) -> Result<Vec<Box<dyn Reflect>>, SettingsError> {
+    #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+    {
+        let bytes = std::fs::read(path)?;
+        deserialize_settings(&bytes, path, type_registry)
+    }
+    #[cfg(any(target_arch = "wasm32", target_os = "android"))]
+    {
+        let _ = (path, type_registry);
+        Err(SettingsError::Unsupported)
+    }
+}
+
+fn deserialize_settings(
+    bytes: &[u8],
+    path: &Path,
+    type_registry: &TypeRegistry,
+) -> Result<Vec<Box<dyn Reflect>>, SettingsError> {
+    let settings_deserializer = SettingsDeserializer { type_registry };
+    if path.extension() == Some("json".as_ref()) {
+        let mut deserializer = serde_json::Deserializer::from_slice(bytes);
+        Ok(settings_deserializer.deserialize(&mut deserializer)?)
+    } else {
+        let mut deserializer = ron::de::Deserializer::from_bytes(bytes)?;
+        Ok(settings_deserializer.deserialize(&mut deserializer)?)
+    }
+}
+
+/// Applies each value to the resource of the same type, returning the values whose type isn't
+/// registered with [`ReflectResource`].
+fn apply_settings(
+    world: &mut World,
+    values: Vec<Box<dyn Reflect>>,
+    send_events: bool,
+) -> Vec<Box<dyn Reflect>> {
+    let type_registry = world.resource::<TypeRegistryArc>().clone();
+    let type_registry = type_registry.read();
+    let mut pending = Vec::new();
+    for value in values {
+        let reflect_resource = type_registry
+            .get_with_name(value.type_name())
+            .and_then(|registration| registration.data::<ReflectResource>());
+        if let Some(reflect_resource) = reflect_resource {
+            if reflect_resource.reflect_resource(world).is_none() {
+                reflect_resource.insert_resource(world, &*value);
+            }
+            // applied again after an insertion to add the map entries `apply` skips
+            let mut resource = reflect_resource.reflect_resource_mut(world).unwrap();
+            apply_settings_value(&mut *resource, &*value, &type_registry);
+            if send_events {
+                world
+                    .resource_mut::<Events<SettingsChanged>>()
+                    .send(SettingsChanged {
+                        type_name: value.type_name().to_string(),
+                    });
+            }
+        } else {
+            pending.push(value);
+        }
+    }
+    pending
+}
+
+fn apply_pending_settings_system(world: &mut World) {
+    let pending = std::mem::take(&mut world.resource_mut::<Settings>().pending);
+    for value in apply_settings(world, pending, false) {
+        warn!(
+            "settings contain the type `{}`, which isn't registered as a reflected resource. consider adding `#[reflect(Resource)]` to your type and registering it",
+            value.type_name()
+        );
+    }
+}
+
+/// Applies `value` to `target` like [`Reflect::apply`], also inserting the entries of maps that
+/// are missing from `target`.
+fn apply_settings_value(target: &mut dyn Reflect, value: &dyn Reflect, registry: &TypeRegistry) {
+    match (target.reflect_mut(), value.reflect_ref()) {
+        (ReflectMut::Struct(target), ReflectRef::Struct(value)) => {
+            for (i, field_value) in value.iter_fields().enumerate() {
+                let name = value.name_at(i).unwrap();
+                if let Some(field) = target.field_mut(name) {
+                    apply_settings_value(field, field_value, registry);
+                }
+            }
+        }
+        (ReflectMut::Map(target), ReflectRef::Map(value)) => {
+            for (key, entry_value) in value.iter() {
+                if let Some(entry) = target.get_mut(key) {
+                    apply_settings_value(entry, entry_value, registry);
+                    continue;
+                }
+                let default = registry
+                    .get_with_name(entry_value.type_name())
+                    .and_then(|registration| registration.data::<ReflectDefault>());
+                let result = match default {
+                    Some(default) => {
+                        let mut entry = default.default();
+                        apply_settings_value(&mut *entry, entry_value, registry);
+                        target.insert_boxed(key.clone_value(), entry)
+                    }
+                    None => target.insert_boxed(key.clone_value(), entry_value.clone_value()),
+                };
+                if let Err(err) = result {
+                    warn!(
+                        "settings contain a map entry of type `{}` that can't be inserted in `{}`. consider adding `#[reflect(Default)]` to the type of its value and registering it",
+                        err.value.type_name(),
+                        target.type_name()
+                    );
+                }
+            }
+        }
+        _ => target.apply(value),
+    }
+}
+
+struct SettingsDeserializer<'a> {
+    type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for SettingsDeserializer<'a> {
+    type Value = Vec<Box<dyn Reflect>>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(SettingsSeqVisitor {
+            type_registry: self.type_registry,
+        })
+    }
+}
+
+struct SettingsSeqVisitor<'a> {
+    type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for SettingsSeqVisitor<'a> {
+    type Value = Vec<Box<dyn Reflect>>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("list of reflected resources")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut values = Vec::new();
+        while let Some(value) =
+            seq.next_element_seed(ReflectDeserializer::new(self.type_registry))?
+        {
+            values.push(value);
+        }
+        Ok(values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::reflect::ReflectResource;
+
+    #[derive(Reflect, Default)]
+    #[reflect(Resource)]
+    struct TestSettings {
+        samples: u32,
+        scale: f32,
+    }
+
+    fn setup(file_name: &str, contents: &str) -> (App, tempfile::TempDir) {
+        setup_with(file_name, contents, |_| {})
+    }
+
+    fn setup_with(
+        file_name: &str,
+        contents: &str,
+        build: impl FnOnce(&mut App),
+    ) -> (App, tempfile::TempDir) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(file_name);
+        std::fs::write(&path, contents).unwrap();
+
+        let mut app = App::new();
+        app.register_type::<TestSettings>();
+        build(&mut app);
+        app.insert_resource(TestSettings {
+            samples: 1,
+            scale: 2.0,
+        });
+        app.add_plugin(SettingsPlugin {
+            path,
+            watch_for_changes: false,
+        });
+        (app, dir)
+    }
+
+    #[test]
+    fn patches_existing_resource() {
+        let (app, _dir) = setup(
+            "settings.ron",
+            r#"[
+                {
+                    "type": "bevy_settings::tests::TestSettings",
+                    "struct": {
+                        "samples": {
+                            "type": "u32",
+                            "value": 4,
+                        },
+                    },
+                },
+            ]"#,
+        );
+
+        let settings = app.world.resource::<TestSettings>();
+        assert_eq!(settings.samples, 4);
+        assert_eq!(settings.scale, 2.0);
+    }
+
+    #[test]
+    fn patches_log_level() {
+        let (app, _dir) = setup_with(
+            "settings.ron",
+            r#"[
+                {
+                    "type": "bevy_log::LogSettings",
+                    "struct": {
+                        "level": {
+                            "type": "tracing_core::metadata::Level",
+                            "value": "DEBUG",
+                        },
+                    },
+                },
+            ]"#,
+            |app| {
+                app.register_type::<bevy_log::LogSettings>()
+                    .register_type::<bevy_log::Level>();
+            },
+        );
+
+        let settings = app.world.resource::<bevy_log::LogSettings>();
+        assert_eq!(settings.level, bevy_log::Level::DEBUG);
+        assert_eq!(settings.filter, bevy_log::LogSettings::default().filter);
+    }
+
+    #[test]
+    fn patches_and_inserts_map_entries() {
+        use bevy_input::gamepad::{Gamepad, GamepadButton, GamepadButtonType, GamepadSettings};
+
+        let (app, _dir) = setup_with(
+            "settings.ron",
+            r#"[
+                {
+                    "type": "bevy_input::gamepad::GamepadSettings",
+                    "struct": {
+                        "button_settings": {
+                            "type": "bevy_utils::hashbrown::HashMap<bevy_input::gamepad::GamepadButton, bevy_input::gamepad::ButtonSettings>",
+                            "map": {
+                                {
+                                    "type": "bevy_input::gamepad::GamepadButton",
+                                    "value": (gamepad: (id: 0), button_type: South),
+                                }: {
+                                    "type": "bevy_input::gamepad::ButtonSettings",
+                                    "struct": {
+                                        "press": {
+                                            "type": "f32",
+                                            "value": 0.5,
+                                        },
+                                    },
+                                },
+                                {
+                                    "type": "bevy_input::gamepad::GamepadButton",
+                                    "value": (gamepad: (id: 0), button_type: East),
+                                }: {
+                                    "type": "bevy_input::gamepad::ButtonSettings",
+                                    "struct": {
+                                        "release": {
+                                            "type": "f32",
+                                            "value": 0.1,
+                                        },
+                                    },
+                                },
+                            },
+                        },
+                    },
+                },
+            ]"#,
+            |app| {
+                app.add_plugin(bevy_input::InputPlugin);
+            },
+        );
+
+        let settings = app.world.resource::<GamepadSettings>();
+        let south = settings.get_button_settings(GamepadButton::new(
+            Gamepad::new(0),
+            GamepadButtonType::South,
+        ));
+        assert_eq!(south.press, 0.5);
+        assert_eq!(south.release, 0.65);
+        let east = settings
+            .get_button_settings(GamepadButton::new(Gamepad::new(0), GamepadButtonType::East));
+        assert_eq!(east.press, 0.75);
+        assert_eq!(east.release, 0.1);
+    }
+
+    #[test]
+    fn inserts_missing_resource_from_json() {
+        let (mut app, _dir) = setup(
+            "settings.json",
+            r#"[
+                {
+                    "type": "bevy_asset::AssetServerSettings",
+                    "struct": {
+                        "watch_for_changes": {
+                            "type": "bool",
+                            "value": true
+                        }
+                    }
+                }
+            ]"#,
+        );
+
+        let settings = app.world.resource::<AssetServerSettings>();
+        assert!(settings.watch_for_changes);
+        assert_eq!(settings.asset_folder, "assets");
+
+        app.update();
+        assert!(app.world.resource::<Settings>().pending.is_empty());
+    }
+}
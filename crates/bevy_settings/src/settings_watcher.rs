@@ -0,0 +1,84 @@
+use crate::{apply_settings, read_settings, Settings};
+use bevy_ecs::world::World;
+use bevy_log::warn;
+use bevy_reflect::TypeRegistryArc;
+use crossbeam_channel::Receiver;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Result, Watcher};
+use std::path::{Path, PathBuf};
+
+/// Watches the directory of the settings file, so that the file is watched even if it doesn't
+/// exist yet or is replaced by an editor.
+pub(crate) struct SettingsWatcher {
+    _watcher: RecommendedWatcher,
+    receiver: Receiver<Result<Event>>,
+    path: PathBuf,
+}
+
+impl SettingsWatcher {
+    pub(crate) fn new(path: &Path) -> Result<Self> {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        let mut watcher = RecommendedWatcher::new(move |res| {
+            sender.send(res).expect("Watch event send failure.");
+        })?;
+        let directory = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        watcher.watch(directory, RecursiveMode::NonRecursive)?;
+        Ok(SettingsWatcher {
+            _watcher: watcher,
+            receiver,
+            path: path.to_path_buf(),
+        })
+    }
+
+    /// Returns `true` if the settings file was created or modified since the last call.
+    fn changed(&self) -> bool {
+        let mut changed = false;
+        loop {
+            let event = match self.receiver.try_recv() {
+                Ok(Ok(event)) => event,
+                Ok(Err(err)) => {
+                    warn!("failed to watch settings file {:?}: {}", self.path, err);
+                    continue;
+                }
+                Err(crossbeam_channel::TryRecvError::Empty) => break,
+                Err(crossbeam_channel::TryRecvError::Disconnected) => {
+                    panic!("FilesystemWatcher disconnected.")
+                }
+            };
+            if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_))
+                && event
+                    .paths
+                    .iter()
+                    .any(|path| path.file_name() == self.path.file_name())
+            {
+                changed = true;
+            }
+        }
+        changed
+    }
+}
+
+pub(crate) fn settings_watcher_system(world: &mut World) {
+    if !world.resource::<SettingsWatcher>().changed() {
+        return;
+    }
+
+    let path = world.resource::<Settings>().path.clone();
+    let type_registry = world.resource::<TypeRegistryArc>().clone();
+    let values = read_settings(&path, &type_registry.read());
+    let values = match values {
+        Ok(values) => values,
+        Err(err) => {
+            warn!("failed to reload settings from {:?}: {}", path, err);
+            return;
+        }
+    };
+    for value in apply_settings(world, values, true) {
+        warn!(
+            "settings contain the type `{}`, which isn't registered as a reflected resource",
+            value.type_name()
+        );
+    }
+}
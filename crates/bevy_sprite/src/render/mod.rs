@@ -217,6 +217,9 @@ pub fn extract_sprite_events(
             AssetEvent::Removed { handle } => AssetEvent::Removed {
                 handle: handle.clone_weak(),
             },
+            AssetEvent::LoadedWithDependencies { handle } => AssetEvent::LoadedWithDependencies {
+                handle: handle.clone_weak(),
+            },
         });
     }
 }
@@ -353,7 +356,7 @@ pub fn queue_sprites(
     // If an image has changed, the GpuImage has (probably) changed
     for event in &events.images {
         match event {
-            AssetEvent::Created { .. } => None,
+            AssetEvent::Created { .. } | AssetEvent::LoadedWithDependencies { .. } => None,
             AssetEvent::Modified { handle } | AssetEvent::Removed { handle } => {
                 image_bind_groups.values.remove(handle)
             }
@@ -76,7 +76,13 @@ impl Plugin for WindowPlugin {
             .add_event::<WindowBackendScaleFactorChanged>()
             .add_event::<FileDragAndDrop>()
             .add_event::<WindowMoved>()
-            .init_resource::<Windows>();
+            .init_resource::<Windows>()
+            .register_type::<WindowDescriptor>()
+            .register_type::<WindowResizeConstraints>()
+            .register_type::<PresentMode>()
+            .register_type::<WindowMode>()
+            .register_type::<Option<bevy_math::Vec2>>()
+            .register_type::<Option<f64>>();
 
         if self.add_primary_window {
             let window_descriptor = app